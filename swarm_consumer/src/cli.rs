@@ -0,0 +1,505 @@
+//! Non-interactive command line, without a subcommand the consumer starts the interactive menus
+//!
+//! Every command prints its result to stdout as a table or, with `--output json`, as JSON,
+//! messages go to stderr and any failure exits non-zero, so the commands can be piped and scripted.
+//! `submit` and `apply` print the created job IDs one per line in table mode.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::bail;
+use clap::{Parser, Subcommand};
+use dialoguer::{Input, Password};
+
+use crate::client::Session;
+use crate::commands;
+use crate::models::{join_present, Job, JobStateEnum};
+use crate::output::{cell, print_json, print_table, OutputFormat};
+use crate::spec::{self, JobSpec};
+use crate::views::{auth, connect};
+
+/// Swarm-Sync consumer
+#[derive(Debug, Parser)]
+#[command(name = "consumer", version, about)]
+pub struct Cli {
+    /// Output format of the non-interactive commands
+    #[arg(short, long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Log in once and keep the password in the OS keyring for later commands
+    Login {
+        /// Username, defaults to SWARM_USERNAME or a prompt
+        #[arg(short, long)]
+        username: Option<String>,
+        /// Core URL, defaults to CORE_API_URL or the saved config
+        #[arg(long)]
+        core: Option<String>,
+    },
+    /// Submit the job described by a YAML or TOML spec file
+    Submit {
+        /// Spec file (.yaml, .yml or .toml)
+        #[arg(short = 'f', long = "file")]
+        file: PathBuf,
+        /// Only validate the spec, submit nothing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Submit every spec file in a directory, all files are validated before anything is submitted
+    Apply {
+        /// Directory containing spec files
+        #[arg(short = 'f', long = "file")]
+        dir: PathBuf,
+        /// Only validate the specs, submit nothing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Inspect and control your jobs
+    #[command(subcommand)]
+    Jobs(JobsCommand),
+    /// Inspect your workers
+    #[command(subcommand)]
+    Workers(WorkersCommand),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum JobsCommand {
+    /// List your jobs
+    List {
+        /// Only jobs in this state, e.g. Running
+        #[arg(long)]
+        state: Option<String>,
+    },
+    /// Show one job
+    Get { id: i32 },
+    /// Show the results of a job
+    Results { id: i32 },
+    /// Cancel a job that has not finished
+    Cancel { id: i32 },
+    /// Follow a job until it finishes, exits non-zero if it fails
+    Watch {
+        id: i32,
+        /// Seconds between polls
+        #[arg(long, default_value_t = 2)]
+        interval: u64,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum WorkersCommand {
+    /// Status of your workers
+    Status,
+}
+
+/// Run a non-interactive command
+/// # Arguments
+/// * command: The parsed subcommand
+/// * output: Table or JSON
+/// # Returns
+/// * `anyhow::Result<()>` - an error makes the process exit non-zero
+/// # Panics
+/// Does not panic.
+pub async fn run(command: Command, output: OutputFormat) -> anyhow::Result<()> {
+    match command {
+        Command::Login { username, core } => login(username, core, output).await,
+        Command::Submit { file, dry_run } => {
+            let specs = vec![(file.clone(), spec::load_spec(&file)?)];
+            submit_specs(specs, dry_run, output).await
+        }
+        Command::Apply { dir, dry_run } => submit_specs(load_dir(&dir)?, dry_run, output).await,
+        Command::Jobs(jobs) => {
+            let session = session().await?;
+            run_jobs(&session, jobs, output).await
+        }
+        Command::Workers(WorkersCommand::Status) => {
+            let session = session().await?;
+            let workers = commands::list_workers(&session).await?;
+            if output == OutputFormat::Json {
+                return print_json(&workers);
+            }
+            let rows: Vec<Vec<String>> = workers
+                .iter()
+                .map(|w| {
+                    let status = w.status.as_ref();
+                    vec![
+                        w.worker.id.to_string(),
+                        w.worker.label.clone(),
+                        w.worker.hostname.clone(),
+                        cell(status.map(|s| s.status)),
+                        cell(status.and_then(|s| s.active_job_id)),
+                        cell(status.and_then(|s| s.last_heartbeat)),
+                    ]
+                })
+                .collect();
+            print_table(
+                &[
+                    "ID",
+                    "LABEL",
+                    "HOST",
+                    "STATUS",
+                    "ACTIVE JOB",
+                    "LAST HEARTBEAT",
+                ],
+                &rows,
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Log in with the stored credentials
+async fn session() -> anyhow::Result<Session> {
+    let base_url = connect::saved_core_location()?;
+    auth::scripted_session(&base_url).await
+}
+
+async fn login(
+    username: Option<String>,
+    core: Option<String>,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let base_url = match core {
+        Some(url) => url,
+        None => connect::saved_core_location()?,
+    };
+    let username = match username.or_else(|| std::env::var("SWARM_USERNAME").ok()) {
+        Some(username) => username,
+        None => Input::new().with_prompt("Username").interact_text()?,
+    };
+    let password = match std::env::var("SWARM_PASSWORD") {
+        Ok(password) => password,
+        Err(_) => Password::new().with_prompt("Password").interact()?,
+    };
+
+    let user = auth::login_and_store(&base_url, &username, &password).await?;
+    match output {
+        OutputFormat::Json => print_json(&user),
+        OutputFormat::Table => {
+            println!("✅ Logged in as {} at {}", user.username, base_url);
+            Ok(())
+        }
+    }
+}
+
+async fn submit_specs(
+    specs: Vec<(PathBuf, JobSpec)>,
+    dry_run: bool,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    if dry_run {
+        for (path, spec) in &specs {
+            eprintln!("✅ {} is valid ({})", path.display(), spec.name);
+        }
+        return Ok(());
+    }
+
+    let session = session().await?;
+    let mut created = Vec::with_capacity(specs.len());
+    for (path, spec) in &specs {
+        let job = commands::submit_spec(&session, spec)
+            .await
+            .map_err(|e| e.context(format!("submitting {}", path.display())))?;
+        eprintln!(
+            "✅ Job \"{}\" submitted from {}",
+            job.job_name,
+            path.display()
+        );
+        if output == OutputFormat::Table {
+            println!("{}", job.id);
+        }
+        created.push(job);
+    }
+    if output == OutputFormat::Json {
+        print_json(&created)?;
+    }
+    Ok(())
+}
+
+async fn run_jobs(
+    session: &Session,
+    command: JobsCommand,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    match command {
+        JobsCommand::List { state } => {
+            let mut jobs = commands::list_jobs(session).await?;
+            if let Some(state) = state {
+                jobs.retain(|j| j.state.as_str().eq_ignore_ascii_case(&state));
+            }
+            match output {
+                OutputFormat::Json => print_json(&jobs),
+                OutputFormat::Table => {
+                    print_jobs(&jobs);
+                    Ok(())
+                }
+            }
+        }
+        JobsCommand::Get { id } => {
+            let job = commands::get_job(session, id).await?;
+            match output {
+                OutputFormat::Json => print_json(&job),
+                OutputFormat::Table => {
+                    print_job(&job);
+                    Ok(())
+                }
+            }
+        }
+        JobsCommand::Results { id } => {
+            // ownership check, results are fetched by job id only
+            commands::get_job(session, id).await?;
+            let results = commands::get_results_for_job(session, id).await?;
+            if output == OutputFormat::Json {
+                return print_json(&results);
+            }
+            let rows: Vec<Vec<String>> = results
+                .iter()
+                .map(|r| {
+                    vec![
+                        r.id.to_string(),
+                        r.saved_at.to_string(),
+                        cell(r.stdout.as_deref().and_then(|s| s.lines().next())),
+                        cell(r.files.as_ref().map(|f| join_present(f, ","))),
+                    ]
+                })
+                .collect();
+            print_table(&["ID", "SAVED", "STDOUT", "FILES"], &rows);
+            Ok(())
+        }
+        JobsCommand::Cancel { id } => {
+            let job = commands::cancel_job(session, id).await?;
+            match output {
+                OutputFormat::Json => print_json(&job),
+                OutputFormat::Table => {
+                    println!("🗑️ Job {} cancelled", job.id);
+                    Ok(())
+                }
+            }
+        }
+        JobsCommand::Watch { id, interval } => watch_job(session, id, interval, output).await,
+    }
+}
+
+/// Poll a job and print every state change until it is Completed or Failed
+async fn watch_job(
+    session: &Session,
+    id: i32,
+    interval: u64,
+    output: OutputFormat,
+) -> anyhow::Result<()> {
+    let mut last_state: Option<JobStateEnum> = None;
+    loop {
+        let job = commands::get_job(session, id).await?;
+        if last_state != Some(job.state) {
+            match output {
+                // one JSON document per line, easy to consume with jq
+                OutputFormat::Json => println!("{}", serde_json::to_string(&job)?),
+                OutputFormat::Table => {
+                    println!("{}  job {}  {}", job.updated_at, job.id, job.state)
+                }
+            }
+            last_state = Some(job.state);
+        }
+        if watch_finished(&job)? {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_secs(interval.max(1))).await;
+    }
+}
+
+/// Whether `jobs watch` is done with a job, a failed job ends it with an error so the exit code is non-zero
+fn watch_finished(job: &Job) -> anyhow::Result<bool> {
+    match job.state {
+        JobStateEnum::Completed => Ok(true),
+        JobStateEnum::Failed => bail!(
+            "Job {} failed: {}",
+            job.id,
+            job.error_message.as_deref().unwrap_or("no error message")
+        ),
+        _ => Ok(false),
+    }
+}
+
+fn print_jobs(jobs: &[Job]) {
+    let rows: Vec<Vec<String>> = jobs
+        .iter()
+        .map(|j| {
+            vec![
+                j.id.to_string(),
+                j.job_name.clone(),
+                j.state.to_string(),
+                j.schedule_type.to_string(),
+                j.priority.to_string(),
+                j.updated_at.to_string(),
+            ]
+        })
+        .collect();
+    print_table(
+        &["ID", "NAME", "STATE", "SCHEDULE", "PRIORITY", "UPDATED"],
+        &rows,
+    );
+}
+
+fn print_job(job: &Job) {
+    let rows = vec![
+        vec!["id".into(), job.id.to_string()],
+        vec!["name".into(), job.job_name.clone()],
+        vec!["state".into(), job.state.to_string()],
+        vec!["image".into(), job.image_url.clone()],
+        vec!["format".into(), job.image_format.to_string()],
+        vec!["output".into(), job.output_type.to_string()],
+        vec![
+            "output paths".into(),
+            cell(job.output_paths.as_ref().map(|p| join_present(p, ","))),
+        ],
+        vec!["schedule".into(), job.schedule_type.to_string()],
+        vec!["cron".into(), cell(job.cron_expression.clone())],
+        vec!["priority".into(), job.priority.to_string()],
+        vec!["error".into(), cell(job.error_message.clone())],
+        vec!["notes".into(), cell(job.notes.clone())],
+        vec!["created".into(), job.created_at.to_string()],
+        vec!["updated".into(), job.updated_at.to_string()],
+    ];
+    print_table(&["FIELD", "VALUE"], &rows);
+}
+
+/// Load and validate every spec in a directory, failing on the first bad file
+fn load_dir(dir: &Path) -> anyhow::Result<Vec<(PathBuf, JobSpec)>> {
+    let files = spec::collect_spec_files(dir)?;
+    if files.is_empty() {
+        bail!("No .yaml, .yml or .toml files in {}", dir.display());
+    }
+    files
+        .into_iter()
+        .map(|path| spec::load_spec(&path).map(|spec| (path, spec)))
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+    use clap::CommandFactory;
+
+    use crate::models::{ImageFormatEnum, OutputTypeEnum, ScheduleTypeEnum};
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::try_parse_from(std::iter::once("consumer").chain(args.iter().copied())).unwrap()
+    }
+
+    fn job(state: JobStateEnum) -> Job {
+        Job {
+            id: 7,
+            user_id: 1,
+            job_name: "nightly".to_string(),
+            image_url: "docker.io/library/alpine:3.20".to_string(),
+            image_format: ImageFormatEnum::DockerRegistry,
+            docker_flags: None,
+            output_type: OutputTypeEnum::Stdout,
+            output_paths: None,
+            schedule_type: ScheduleTypeEnum::Once,
+            cron_expression: None,
+            notes: None,
+            state,
+            error_message: None,
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn command_definition_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn no_subcommand_starts_the_menus() {
+        let cli = parse(&[]);
+        assert!(cli.command.is_none());
+        assert_eq!(cli.output, OutputFormat::Table);
+    }
+
+    #[test]
+    fn output_is_global() {
+        let cli = parse(&["--output", "json", "jobs", "list", "--state", "Running"]);
+        assert_eq!(cli.output, OutputFormat::Json);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Jobs(JobsCommand::List { state: Some(ref s) })) if s == "Running"
+        ));
+
+        let cli = parse(&["jobs", "get", "3", "-o", "json"]);
+        assert_eq!(cli.output, OutputFormat::Json);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Jobs(JobsCommand::Get { id: 3 }))
+        ));
+    }
+
+    #[test]
+    fn subcommand_arguments() {
+        let cli = parse(&["submit", "-f", "job.yaml", "--dry-run"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Submit { ref file, dry_run: true }) if file == Path::new("job.yaml")
+        ));
+        let cli = parse(&["apply", "--file", "specs"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Apply { dry_run: false, .. })
+        ));
+        let cli = parse(&["jobs", "watch", "5"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Jobs(JobsCommand::Watch { id: 5, interval: 2 }))
+        ));
+        let cli = parse(&["login", "-u", "alice", "--core", "http://core:8000"]);
+        assert!(matches!(
+            cli.command,
+            Some(Command::Login { username: Some(ref u), core: Some(_) }) if u == "alice"
+        ));
+        assert!(matches!(
+            parse(&["workers", "status"]).command,
+            Some(Command::Workers(WorkersCommand::Status))
+        ));
+    }
+
+    #[test]
+    fn bad_arguments_are_rejected() {
+        for args in [
+            vec!["consumer", "--output", "yaml", "jobs", "list"],
+            vec!["consumer", "jobs", "get"],
+            vec!["consumer", "jobs", "get", "seven"],
+            vec!["consumer", "submit"],
+            vec!["consumer", "nope"],
+        ] {
+            assert!(Cli::try_parse_from(&args).is_err(), "{args:?} parsed");
+        }
+    }
+
+    #[test]
+    fn watch_ends_with_the_job() {
+        assert!(!watch_finished(&job(JobStateEnum::Running)).unwrap());
+        assert!(!watch_finished(&job(JobStateEnum::Queued)).unwrap());
+        assert!(watch_finished(&job(JobStateEnum::Completed)).unwrap());
+
+        // a failed job is an error, the process exits non-zero
+        let mut failed = job(JobStateEnum::Failed);
+        failed.error_message = Some("exit code 1".to_string());
+        let err = watch_finished(&failed).unwrap_err();
+        assert_eq!(err.to_string(), "Job 7 failed: exit code 1");
+    }
+
+    #[test]
+    fn json_output_keeps_the_wire_names() {
+        let printed = serde_json::to_value(job(JobStateEnum::Queued)).unwrap();
+        assert_eq!(printed["id"], 7);
+        assert_eq!(printed["state"], "Queued");
+        assert_eq!(printed["image_format"], "DockerRegistry");
+    }
+}
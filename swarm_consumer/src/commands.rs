@@ -1,14 +1,23 @@
-use anyhow::bail;
-use reqwest::{Client, StatusCode};
-use serde_json::json;
+use std::collections::HashMap;
 
-use crate::client::{self, Session};
-use crate::models::{Job, JobResult, UserResponse};
+use anyhow::bail;
+use reqwest::StatusCode;
+use swarm_client::models::{
+    CreateTemplateRequest, Credentials, NewJobTemplate, NewUserRequest, RunTemplateRequest,
+    UpdateUserRequest,
+};
+use swarm_client::{ClientError, SwarmClient};
+
+use crate::client::Session;
+use crate::models::{
+    ImageFormatEnum, Job, JobResult, NewJob, OutputTypeEnum, QuotaStatus, ScheduleTypeEnum,
+    TemplateParameterSpec, TemplateWithParameters, UserResponse, WorkerOverview,
+};
+use crate::spec::JobSpec;
 
 /// Register a new user via `/users` rocket endpoint
 /// # Arguments
-/// * client: An authenticated client, can be retrieved with `client::build_authed_client()`
-/// * base_url: The base url of the swarm server, excluding the port number e.g. http://127.0.0.1
+/// * client: An anonymous `SwarmClient` pointed at the swarm server, e.g. `SwarmClient::new("http://127.0.0.1:8000")`
 /// * username: The desired username
 /// * email: The desired email
 /// * password: The desired password, (hashed and salted during procedure)
@@ -22,126 +31,89 @@ use crate::models::{Job, JobResult, UserResponse};
 /// # Examples
 /// Examples
 /// ```
-/// let client = client::build_authed_client("token").unwrap();
-/// let user = commands::register_user(&client, "http://127.0.0.1", "username", "email", "password").await.unwrap();
+/// let client = SwarmClient::new("http://127.0.0.1:8000").unwrap();
+/// let user = commands::register_user(&client, "username", "email", "password").await.unwrap();
 /// ```
 pub async fn register_user(
-    client: &Client,
-    base_url: &str,
+    client: &SwarmClient,
     username: &str,
     email: &str,
     password: &str,
 ) -> anyhow::Result<UserResponse> {
-    let res = client
-        .post(&format!("{}/users", base_url))
-        .json(&json!({
-            "username": username,
-            "email": email,
-            "password": password
-        }))
-        .send()
-        .await?;
-
-    // If the response had unsuccessful status code, bail with correct message
-    if !res.status().is_success() {
-        // we interpret the status code and message
-        let status = res.status();
-        let body_text = res.text().await.unwrap_or_default();
+    let request = NewUserRequest {
+        username: username.to_string(),
+        email: email.to_string(),
+        password: password.to_string(),
+    };
 
+    match client.create_user(&request).await {
+        Ok(user) => Ok(user),
         // The reason for the bad request is that the password doesn't meet requirements
-        if status == StatusCode::BAD_REQUEST && body_text.contains("Password must") {
-            bail!(
-                "Registration failed: {}",
-                body_text.trim_matches(|c: char| c == '"' || c.is_whitespace())
-            );
-        }
-
+        Err(ClientError::Api {
+            status: StatusCode::BAD_REQUEST,
+            message,
+            ..
+        }) if message.contains("Password must") => bail!(
+            "Registration failed: {}",
+            message.trim_matches(|c: char| c == '"' || c.is_whitespace())
+        ),
         // The reason for the conflict is that the username or email already exists
-        if status == StatusCode::CONFLICT {
-            bail!("Registration failed: username or email already exists.");
+        Err(err) if err.status() == Some(StatusCode::CONFLICT) => {
+            bail!("Registration failed: username or email already exists.")
         }
-
         // Otherwise, something else went wrong
-        bail!("Registration failed (status={}): {}", status, body_text);
+        Err(err) => bail!("Registration failed: {}", err),
     }
-
-    // If the response was successful, return the user
-    Ok(res.json().await?)
 }
 
 /// Login via `/login` and then fetch the user via `/users/username/<name>`
 /// # Arguments
-/// * client: An authenticated client, can be retrieved with `client::build_authed_client()`
-/// * base_url: The base url of the swarm server, excluding the port number e.g. http://127.0.0.1
+/// * client: An anonymous `SwarmClient` pointed at the swarm server, e.g. `SwarmClient::new("http://127.0.0.1:8000")`
 /// * username: The desired username
 /// * password: The desired password (hashed and salted during procedure)
 /// # Returns
-/// result: A tuple of the token and UserResponse
+/// result: A tuple of the authenticated client and UserResponse
 /// # Panics
 /// doesn't panic but returns error if
 /// * username or password invalid
 /// * migrations not ran and/or server not running
 /// # Examples
 /// ```
-/// let client = client::build_authed_client("token").unwrap();
-/// let (token, user) = commands::login_user(&client, "http://127.0.0.1", "username", "password").await.unwrap();
+/// let client = SwarmClient::new("http://127.0.0.1:8000").unwrap();
+/// let (authed_client, user) = commands::login_user(&client, "username", "password").await.unwrap();
 /// ```
 pub async fn login_user(
-    client: &Client,
-    base_url: &str,
+    client: &SwarmClient,
     username: &str,
     password: &str,
-) -> anyhow::Result<(String, UserResponse)> {
-    // Post to /login
-    let res = client
-        .post(&format!("{}/login", base_url))
-        .json(&json!({ "username": username, "password": password }))
-        .send()
-        .await?;
-
-    // If authgaurd failed meaning the username or password was incorrect
-    if res.status() == StatusCode::UNAUTHORIZED {
-        bail!("Login failed: invalid username or password.");
-    }
-
-    // If the response had unsuccessful status code, bail with correct message
-    if !res.status().is_success() {
-        let status = res.status();
-        let body = res.text().await.unwrap_or_default();
-        bail!("Login request failed (status={}): {}", status, body);
-    }
-
-    // Extract the token
-    let resp_json: serde_json::Value = res.json().await?;
-    let token = resp_json["token"]
-        .as_str()
-        .ok_or_else(|| anyhow::anyhow!("No token in response"))?
-        .to_string();
-
-    // Build authed client
-    let authed_client = client::build_authed_client(&token)?;
-
-    // Fetch the user
-    let user_resp = authed_client
-        .get(format!("{}/users/username/{}", base_url, username))
-        .send()
-        .await?;
-
-    // If the response had unsuccessful status code, bail with correct message
-    if !user_resp.status().is_success() {
-        bail!("Failed to fetch user info (status={})", user_resp.status());
-    }
+) -> anyhow::Result<(SwarmClient, UserResponse)> {
+    let credentials = Credentials {
+        username: username.to_string(),
+        password: password.to_string(),
+    };
+    let session = match client.login(&credentials).await {
+        Ok(session) => session,
+        // If authgaurd failed meaning the username or password was incorrect
+        Err(err) if err.status() == Some(StatusCode::UNAUTHORIZED) => {
+            bail!("Login failed: invalid username or password.")
+        }
+        Err(err) => bail!("Login request failed: {}", err),
+    };
 
-    // endpoint returns Option<UserResponse>
-    let user_opt: Option<UserResponse> = user_resp.json().await?;
-    let user = user_opt.ok_or_else(|| anyhow::anyhow!("User lookup returned None"))?;
+    // Fetch the user with the authenticated client
+    let authed_client = client.authenticated(&session.token)?;
+    let user = authed_client
+        .find_user_by_username(username)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to fetch user info: {}", e))?
+        .ok_or_else(|| anyhow::anyhow!("User lookup returned None"))?;
 
-    Ok((token, user))
+    Ok((authed_client, user))
 }
 
 /// PUT /users/<id>  ➜ update username / email / password
 /// # Arguments
-/// * session: An authenticated session, can be retrieved with `client::build_authed_client()`
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
 /// * new_username: The desired username
 /// * new_email: The desired email
 /// * new_password: The desired password (hashed and salted during procedure)
@@ -153,8 +125,7 @@ pub async fn login_user(
 /// * migrations not ran and/or server not running
 /// # Examples
 /// ```
-/// let client = client::build_authed_client("token").unwrap();
-/// let user = commands::update_user(&client, "http://127.0.0.1", "username", "email", "password").await.unwrap();
+/// let user = commands::update_user(&session, "username", "email", Some("password")).await.unwrap();
 /// ```
 pub async fn update_user(
     session: &Session,
@@ -162,38 +133,22 @@ pub async fn update_user(
     new_email: &str,
     new_password: Option<&str>,
 ) -> anyhow::Result<UserResponse> {
-    // Construct payload
-    let mut payload = json!({
-        "username": new_username,
-        "email": new_email,
-    });
-
-    // Add password if provided
-    if let Some(pw) = new_password {
-        payload["password"] = json!(pw);
-    }
+    let update = UpdateUserRequest {
+        username: new_username.to_string(),
+        email: new_email.to_string(),
+        password: new_password.map(String::from),
+    };
 
-    // Send update http request
-    let res = session
+    session
         .client
-        .put(format!("{}/users/{}", session.app_host, session.user.id))
-        .json(&payload)
-        .send()
-        .await?;
-
-    // If the response had unsuccessful status code, bail with correct message
-    if !res.status().is_success() {
-        let status = res.status();
-        let body = res.text().await.unwrap_or_default();
-        anyhow::bail!("Update failed (status={}): {}", status, body);
-    }
-
-    Ok(res.json().await?)
+        .update_user(session.user.id, &update)
+        .await
+        .map_err(|e| anyhow::anyhow!("Update failed: {}", e))
 }
 
 /// Create a new job via `POST /jobs`
 /// # Arguments
-/// * session: An authenticated session, can be retrieved with `client::build_authed_client()`
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
 /// * job_name: The name of the job
 /// * image_url: The URL of the image
 /// * image_format: The format of the image
@@ -209,61 +164,83 @@ pub async fn update_user(
 /// * migrations not ran and/or server not running
 /// # Examples
 /// ```
-/// let client = client::build_authed_client("token").unwrap();
-/// let job = commands::submit_job(&client, "http://127.0.0.1", "job_name", "image_url", "image_format", "output_type", "output_paths", "schedule_type", "cron_expression").await.unwrap();
+/// let job = commands::submit_job(&session, "job_name", "image_url", ImageFormatEnum::DockerRegistry, OutputTypeEnum::Stdout, None, ScheduleTypeEnum::Once, None).await.unwrap();
 /// ```
 pub async fn submit_job(
     session: &Session,
     job_name: &str,
     image_url: &str,
-    image_format: &str,
-    output_type: &str,
+    image_format: ImageFormatEnum,
+    output_type: OutputTypeEnum,
     output_paths: Option<Vec<String>>,
-    schedule_type: &str,
+    schedule_type: ScheduleTypeEnum,
     cron_expression: Option<&str>,
 ) -> anyhow::Result<Job> {
-    // Determine initial state
-    let initial_state = if schedule_type == "Cron" {
-        "Submitted"
-    } else {
-        "Queued"
+    let job = NewJob {
+        user_id: session.user.id,
+        job_name: job_name.to_string(),
+        image_url: image_url.to_string(),
+        image_format,
+        docker_flags: None,
+        output_type,
+        output_paths: output_paths.map(|paths| paths.into_iter().map(Some).collect()),
+        schedule_type,
+        cron_expression: cron_expression.map(String::from),
+        notes: None,
+        priority: 0,
     };
 
-    let payload = json!({
-        "user_id": session.user.id,
-        "job_name": job_name,
-        "image_url": image_url,
-        "image_format": image_format,
-        "docker_flags": null,
-        "output_type": output_type,
-        "output_paths": output_paths,
-        "schedule_type": schedule_type,
-        "cron_expression": cron_expression,
-        "notes": null,
-        "state": initial_state
-    });
+    post_new_job(session, &job).await
+}
 
-    // Send POST request
-    let res = session
-        .client
-        .post(format!("{}/jobs", session.app_host))
-        .json(&payload)
-        .send()
-        .await?;
+/// Submit a job described by a spec file, see `spec::JobSpec`
+/// # Arguments
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
+/// * spec: A validated job spec
+/// # Returns
+/// result: The created job
+/// # Panics
+/// doesn't panic but returns error if
+/// * session not authenticated or malformed
+/// * quota exceeded
+/// * migrations not ran and/or server not running
+/// # Examples
+/// ```
+/// let spec = spec::load_spec(Path::new("job.yaml")).unwrap();
+/// let job = commands::submit_spec(&session, &spec).await.unwrap();
+/// ```
+pub async fn submit_spec(session: &Session, spec: &JobSpec) -> anyhow::Result<Job> {
+    post_new_job(session, &spec.to_new_job(session.user.id)?).await
+}
 
-    // If the response had unsuccessful status code, bail with correct message
-    if res.status() != StatusCode::CREATED {
-        let status = res.status();
-        let body = res.text().await.unwrap_or_default();
-        bail!("Job submission failed (status={}): {}", status, body);
+/// POST a NewJob to `/jobs`, shared by the interactive and spec based submits
+async fn post_new_job(session: &Session, job: &NewJob) -> anyhow::Result<Job> {
+    // Same checks core runs, reported without a round trip
+    if let Err(errors) = job.validate() {
+        bail!("Job is invalid: {}", errors);
+    }
+    match session.client.create_job(job).await {
+        Ok(job) => Ok(job),
+        // Over quota, tell the user when to try again
+        Err(err) if err.status() == Some(StatusCode::TOO_MANY_REQUESTS) => {
+            let retry_after = err.retry_after_secs().unwrap_or(60);
+            let reason = match &err {
+                ClientError::Api { message, .. } => message.as_str(),
+                _ => "quota exceeded",
+            };
+            bail!(
+                "Job submission rejected: {} (retry in {}s)",
+                reason,
+                retry_after
+            )
+        }
+        Err(err) => bail!("Job submission failed: {}", err),
     }
-
-    Ok(res.json().await?)
 }
 
 /// List all jobs for the current user
 /// # Arguments
-/// * session: An authenticated session, can be retrieved with `client::build_authed_client()`
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
 /// # Returns
 /// result: A list of jobs
 /// # Panics
@@ -272,24 +249,19 @@ pub async fn submit_job(
 /// * migrations not ran and/or server not running
 /// # Examples
 /// ```
-/// let client = client::build_authed_client("token").unwrap();
-/// let jobs = commands::list_jobs(&client).await.unwrap();
+/// let jobs = commands::list_jobs(&session).await.unwrap();
 /// ```
 pub async fn list_jobs(session: &Session) -> anyhow::Result<Vec<Job>> {
-    let url = format!(
-        "{}/jobs/by_admin?user_id={}",
-        session.app_host, session.user.id
-    );
-    let res = session.client.get(url).send().await?;
-    if !res.status().is_success() {
-        bail!("Failed to list jobs (status={})", res.status());
-    }
-    Ok(res.json().await?)
+    session
+        .client
+        .list_jobs_by_admin(session.user.id, None, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list jobs: {}", e))
 }
 
 /// Filter finished jobs (not Submitted / Queued / Running)
 /// # Arguments
-/// * session: An authenticated session, can be retrieved with `client::build_authed_client()`
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
 /// # Returns
 /// result: A list of finished jobs
 /// # Panics
@@ -298,23 +270,16 @@ pub async fn list_jobs(session: &Session) -> anyhow::Result<Vec<Job>> {
 /// * migrations not ran and/or server not running
 /// # Examples
 /// ```
-/// let client = client::build_authed_client("token").unwrap();
-/// let jobs = commands::get_finished_jobs(&client).await.unwrap();
+/// let jobs = commands::get_finished_jobs(&session).await.unwrap();
 /// ```
 pub async fn get_finished_jobs(session: &Session) -> anyhow::Result<Vec<Job>> {
     let jobs = list_jobs(session).await?;
-    Ok(jobs
-        .into_iter()
-        .filter(|j| {
-            let s = j.state.as_str();
-            s != "Submitted" && s != "Queued" && s != "Running"
-        })
-        .collect())
+    Ok(jobs.into_iter().filter(|j| j.state.is_finished()).collect())
 }
 
 /// Fetch `/results/job/<id>`
 /// # Arguments
-/// * session: An authenticated session, can be retrieved with `client::build_authed_client()`
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
 /// * job_id: The id of the job
 /// # Returns
 /// result: A list of job results
@@ -325,27 +290,268 @@ pub async fn get_finished_jobs(session: &Session) -> anyhow::Result<Vec<Job>> {
 /// * job_id not found
 /// # Examples
 /// ```
-/// let client = client::build_authed_client("token").unwrap();
-/// let results = commands::get_results_for_job(&client, 1).await.unwrap();
+/// let results = commands::get_results_for_job(&session, 1).await.unwrap();
 /// ```
 pub async fn get_results_for_job(session: &Session, job_id: i32) -> anyhow::Result<Vec<JobResult>> {
-    let url = format!("{}/results/job/{}", session.app_host, job_id);
-    let res = session.client.get(url).send().await?;
+    match session.client.results_for_job(job_id).await {
+        Ok(results) => Ok(results),
+        // If not found, no results where fetched -> job not finished yet
+        Err(err) if err.is_not_found() => Ok(vec![]),
+        Err(err) => bail!("Failed to fetch results for job {}: {}", job_id, err),
+    }
+}
+
+/// Fetch quota limits and current usage for the logged in user via `/quotas/<user_id>`
+/// # Arguments
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
+/// # Returns
+/// result: The quota in effect and the usage counted against it
+/// # Panics
+/// doesn't panic but returns error if
+/// * session not authenticated or malformed
+/// * migrations not ran and/or server not running
+/// # Examples
+/// ```
+/// let status = commands::get_quota_status(&session).await.unwrap();
+/// ```
+pub async fn get_quota_status(session: &Session) -> anyhow::Result<QuotaStatus> {
+    session
+        .client
+        .quota_status(session.user.id)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to fetch quota: {}", e))
+}
 
-    // If not found, no results where fetched -> job not finished yet
-    if res.status() == StatusCode::NOT_FOUND {
-        return Ok(vec![]); // none yet
+/// Re-run a job via `/jobs/<id>/rerun`, the job is cloned into a new submission
+/// # Arguments
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
+/// * job_id: The id of the job to clone
+/// # Returns
+/// result: The newly created job
+/// # Panics
+/// doesn't panic but returns error if
+/// * session not authenticated or malformed
+/// * job not found or not owned by the user
+/// * quota exceeded
+/// # Examples
+/// ```
+/// let job = commands::rerun_job(&session, 1).await.unwrap();
+/// ```
+pub async fn rerun_job(session: &Session, job_id: i32) -> anyhow::Result<Job> {
+    session
+        .client
+        .rerun_job(job_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("Re-run of job {} failed: {}", job_id, e))
+}
+
+/// List the templates of the current user via `/templates/user/<user_id>`
+/// # Arguments
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
+/// # Returns
+/// result: Templates with their parameters
+/// # Panics
+/// doesn't panic but returns error if
+/// * session not authenticated or malformed
+/// * migrations not ran and/or server not running
+/// # Examples
+/// ```
+/// let templates = commands::list_templates(&session).await.unwrap();
+/// ```
+pub async fn list_templates(session: &Session) -> anyhow::Result<Vec<TemplateWithParameters>> {
+    session
+        .client
+        .templates_for_user(session.user.id)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list templates: {}", e))
+}
+
+/// Save an existing job as a template via `/templates`
+/// # Arguments
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
+/// * job: The job to copy the settings from
+/// * name: The template name, unique per user
+/// * parameters: Typed parameters of the template
+/// # Returns
+/// result: The stored template
+/// # Panics
+/// doesn't panic but returns error if
+/// * session not authenticated or malformed
+/// * name already taken or a parameter is invalid
+/// # Examples
+/// ```
+/// let template = commands::create_template_from_job(&session, &job, "nightly", vec![]).await.unwrap();
+/// ```
+pub async fn create_template_from_job(
+    session: &Session,
+    job: &Job,
+    name: &str,
+    parameters: Vec<TemplateParameterSpec>,
+) -> anyhow::Result<TemplateWithParameters> {
+    let request = CreateTemplateRequest {
+        template: NewJobTemplate {
+            user_id: session.user.id,
+            name: name.to_string(),
+            image_url: job.image_url.clone(),
+            image_format: job.image_format,
+            docker_flags: job.docker_flags.clone(),
+            output_type: job.output_type,
+            output_paths: job.output_paths.clone(),
+            schedule_type: job.schedule_type,
+            cron_expression: job.cron_expression.clone(),
+            notes: job.notes.clone(),
+            priority: job.priority,
+        },
+        parameters,
+    };
+
+    session
+        .client
+        .create_template(&request)
+        .await
+        .map_err(|e| anyhow::anyhow!("Saving template failed: {}", e))
+}
+
+/// Run a template via `/templates/<id>/run`
+/// # Arguments
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
+/// * template_id: The id of the template
+/// * job_name: Optional name of the created job
+/// * overrides: Parameter name → value, replaces the defaults
+/// # Returns
+/// result: The created job
+/// # Panics
+/// doesn't panic but returns error if
+/// * session not authenticated or malformed
+/// * a required parameter is missing or a value is invalid
+/// * quota exceeded
+/// # Examples
+/// ```
+/// let job = commands::run_template(&session, 1, None, HashMap::new()).await.unwrap();
+/// ```
+pub async fn run_template(
+    session: &Session,
+    template_id: i32,
+    job_name: Option<&str>,
+    overrides: HashMap<String, String>,
+) -> anyhow::Result<Job> {
+    let run = RunTemplateRequest {
+        job_name: job_name.map(String::from),
+        overrides,
+    };
+    session
+        .client
+        .run_template(template_id, &run)
+        .await
+        .map_err(|e| anyhow::anyhow!("Running template failed: {}", e))
+}
+
+/// Delete a template via `/templates/<id>`
+/// # Arguments
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
+/// * template_id: The id of the template
+/// # Returns
+/// result: Unit on success
+/// # Panics
+/// doesn't panic but returns error if
+/// * session not authenticated or malformed
+/// # Examples
+/// ```
+/// commands::delete_template(&session, 1).await.unwrap();
+/// ```
+pub async fn delete_template(session: &Session, template_id: i32) -> anyhow::Result<()> {
+    session
+        .client
+        .delete_template(template_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to delete template: {}", e))
+}
+
+/// Fetch a single job via `/jobs/<id>`
+/// # Arguments
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
+/// * job_id: The id of the job
+/// # Returns
+/// result: The job
+/// # Panics
+/// doesn't panic but returns error if
+/// * session not authenticated or malformed
+/// * job not found or not owned by the user
+/// # Examples
+/// ```
+/// let job = commands::get_job(&session, 1).await.unwrap();
+/// ```
+pub async fn get_job(session: &Session, job_id: i32) -> anyhow::Result<Job> {
+    let job = session
+        .client
+        .get_job(job_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to fetch job {}: {}", job_id, e))?;
+    if job.user_id != session.user.id {
+        bail!("Job {} is not owned by {}", job_id, session.user.username);
+    }
+    Ok(job)
+}
+
+/// Cancel a job that has not finished yet, it is marked Failed with a cancel message
+/// # Arguments
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
+/// * job_id: The id of the job
+/// # Returns
+/// result: The cancelled job
+/// # Panics
+/// doesn't panic but returns error if
+/// * session not authenticated or malformed
+/// * job not found, not owned by the user or already finished
+/// # Examples
+/// ```
+/// let job = commands::cancel_job(&session, 1).await.unwrap();
+/// ```
+pub async fn cancel_job(session: &Session, job_id: i32) -> anyhow::Result<Job> {
+    let job = get_job(session, job_id).await?;
+    if job.state.is_finished() {
+        bail!("Job {} already finished ({})", job_id, job.state);
     }
+    let message = format!("Cancelled by {}", session.user.username);
+    session
+        .client
+        .mark_job_failed(job_id, Some(&message))
+        .await
+        .map_err(|e| anyhow::anyhow!("Cancel of job {} failed: {}", job_id, e))
+}
 
-    // If the response had unsuccessful status code, bail with correct message
-    if !res.status().is_success() {
-        bail!(
-            "Failed to fetch results for job {} (status={})",
-            job_id,
-            res.status()
-        );
+/// List the workers of the current user together with their live status
+/// # Arguments
+/// * session: An authenticated session, can be retrieved with `auth::auth_flow()`
+/// # Returns
+/// result: Every worker with its status, status is None for workers that never reported in
+/// # Panics
+/// doesn't panic but returns error if
+/// * session not authenticated or malformed
+/// * migrations not ran and/or server not running
+/// # Examples
+/// ```
+/// let workers = commands::list_workers(&session).await.unwrap();
+/// ```
+pub async fn list_workers(session: &Session) -> anyhow::Result<Vec<WorkerOverview>> {
+    let workers = session
+        .client
+        .workers_for_admin(session.user.id)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to list workers: {}", e))?;
+
+    let mut overview = Vec::with_capacity(workers.len());
+    for worker in workers {
+        let status = session
+            .client
+            .status_for_worker(worker.id)
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!("Failed to fetch status of worker {}: {}", worker.id, e)
+            })?;
+        overview.push(WorkerOverview { worker, status });
     }
-    Ok(res.json().await?)
+    Ok(overview)
 }
 
 // TODO: write tests here
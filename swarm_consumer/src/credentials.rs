@@ -0,0 +1,84 @@
+//! Credentials for the non-interactive commands
+//!
+//! Environment variables win (`SWARM_USERNAME`, `SWARM_PASSWORD`) so CI needs no state on disk.
+//! Otherwise the username saved by `consumer login` is used and the password is read from the OS keyring.
+
+use std::fs;
+
+use anyhow::Context;
+
+use crate::views::connect::{config_file_path, CoreConfig};
+
+/// Service name the password is stored under in the OS keyring
+const KEYRING_SERVICE: &str = "swarm-consumer";
+
+/// Username and password used to log in to Core
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// Resolve credentials from the environment, falling back to the saved username and the keyring
+/// # Returns
+/// * `Credentials`
+/// * `anyhow::Error` - if no username is known or no password is stored
+/// # Panics
+/// Does not panic.
+pub fn load() -> anyhow::Result<Credentials> {
+    load_with(|name| std::env::var(name).ok())
+}
+
+/// `load` with the environment variables looked up through `env`
+fn load_with(env: impl Fn(&str) -> Option<String>) -> anyhow::Result<Credentials> {
+    let username = match env("SWARM_USERNAME") {
+        Some(username) => username,
+        None => fs::read_to_string(config_file_path())
+            .ok()
+            .and_then(|raw| serde_json::from_str::<CoreConfig>(&raw).ok())
+            .and_then(|cfg| cfg.last_username)
+            .context("Not logged in: run `consumer login` or set SWARM_USERNAME")?,
+    };
+    let password = match env("SWARM_PASSWORD") {
+        Some(password) => password,
+        None => keyring::Entry::new(KEYRING_SERVICE, &username)?
+            .get_password()
+            .with_context(|| {
+                format!(
+                    "No stored password for {}: run `consumer login` or set SWARM_PASSWORD",
+                    username
+                )
+            })?,
+    };
+    Ok(Credentials { username, password })
+}
+
+/// Store the password of a user in the OS keyring
+/// # Arguments
+/// * username: The user the password belongs to
+/// * password: The password to store
+/// # Returns
+/// * `anyhow::Result<()>`
+/// # Panics
+/// Does not panic.
+pub fn store(username: &str, password: &str) -> anyhow::Result<()> {
+    keyring::Entry::new(KEYRING_SERVICE, username)?
+        .set_password(password)
+        .context("Could not store the password in the OS keyring")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn environment_wins_over_config_and_keyring() {
+        // the keyring is never asked
+        let env = HashMap::from([("SWARM_USERNAME", "ci-bot"), ("SWARM_PASSWORD", "s3cret")]);
+        let credentials = load_with(|name| env.get(name).map(|v| v.to_string())).unwrap();
+        assert_eq!(credentials.username, "ci-bot");
+        assert_eq!(credentials.password, "s3cret");
+    }
+}
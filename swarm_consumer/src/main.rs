@@ -3,16 +3,27 @@
 #![doc = include_str!("../docs/overview.md")]
 //! The main entry point for Swarm Consumer TUI application
 
+use clap::Parser;
+
 use crate::views::{auth, connect, menu};
 
+mod cli;
 mod client;
 mod commands;
+mod credentials;
 mod models;
+mod output;
+mod spec;
 mod state;
 mod views;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    // Scripted use, e.g. `consumer submit -f job.yaml`, skips the menus entirely
+    let cli = cli::Cli::parse();
+    if let Some(command) = cli.command {
+        return cli::run(command, cli.output).await;
+    }
     // Determine backend core location (local or remote) and get base URL
     let base_url = connect::choose_core_location().await?;
     // Perform login or registration to obtain an authenticated session
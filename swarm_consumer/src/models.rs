@@ -1,63 +1,32 @@
-use chrono::NaiveDateTime;
-use serde::Deserialize;
+//! Wire models of core, shared with core-api and the worker through `swarm-client`
+use serde::Serialize;
 
-/// User information returned by the backend (sanitized, without password).
-#[allow(dead_code)]
-#[derive(Debug, Clone, Deserialize)]
-pub struct UserResponse {
-    // id: primary key
-    pub id: i32,
-    // username: unique, non-null
-    pub username: String,
-    // email: unique, non-null
-    pub email: String,
-    // created_at: non-null
-    pub created_at: NaiveDateTime,
-}
+pub use swarm_client::enums::{
+    ImageFormatEnum, JobStateEnum, OutputTypeEnum, ScheduleTypeEnum, TemplateParamKindEnum,
+};
+pub use swarm_client::models::{
+    Job, JobResult, NewJob, QuotaStatus, TemplateParameterSpec, TemplateWithParameters,
+    UserResponse, Worker, WorkerStatus,
+};
 
-/// Job model returned from the backend.
-#[allow(dead_code)]
-#[derive(Debug, Clone, Deserialize)]
-pub struct Job {
-    // id: primary key
-    pub id: i32,
-    // user_id: foreign key
-    pub user_id: i32,
-    // job_name: non-null
-    pub job_name: String,
-    // image_url: non-null
-    pub image_url: String,
-    // image_format: non-null
-    pub image_format: String,
-    // docker_flags
-    pub docker_flags: Option<String>,
-    // output_type: non-null
-    pub output_type: String,
-    // output_paths
-    pub output_paths: Option<Vec<String>>,
-    // schedule_type: non-null
-    pub schedule_type: String,
-    // cron_expression
-    pub cron_expression: Option<String>,
-    // notes
-    pub notes: Option<String>,
-    // state : non-null
-    pub state: String,
-    // created_at: non-null
-    pub created_at: NaiveDateTime,
-    // updated_at: non-null
-    pub updated_at: NaiveDateTime,
+/// Worker together with its status, None if the worker never reported in.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkerOverview {
+    pub worker: Worker,
+    pub status: Option<WorkerStatus>,
 }
 
-/// Job result model returned from the backend.
-#[allow(dead_code)]
-#[derive(Debug, Clone, Deserialize)]
-pub struct JobResult {
-    // id: primary key
-    pub id: i32,
-    // job_id: foreign key
-    pub job_id: i32,
-    pub stdout: Option<String>,
-    pub files: Option<Vec<String>>,
-    pub created_at: NaiveDateTime,
+/// Comma separated list of the present values, array columns of core may hold nulls
+/// # Arguments
+/// * values: e.g. `Job::output_paths` or `JobResult::files`
+/// * separator: placed between two values
+/// # Returns
+/// * `String` - the joined values, empty if there are none
+pub fn join_present(values: &[Option<String>], separator: &str) -> String {
+    values
+        .iter()
+        .flatten()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(separator)
 }
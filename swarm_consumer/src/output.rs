@@ -0,0 +1,100 @@
+//! Output of the non-interactive commands, either JSON for scripts or a plain table for people
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// Selected with `--output`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    #[default]
+    Table,
+}
+
+/// Print a value as pretty JSON
+/// # Arguments
+/// * value: Anything serializable
+/// # Returns
+/// * `anyhow::Result<()>`
+/// # Panics
+/// Does not panic.
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+/// Print rows as a left aligned table, column width follows the widest cell
+/// # Arguments
+/// * headers: Column titles
+/// * rows: One `Vec<String>` per row, same length as `headers`
+/// # Panics
+/// Does not panic.
+pub fn print_table(headers: &[&str], rows: &[Vec<String>]) {
+    print!("{}", render_table(headers, rows));
+}
+
+/// The table printed by `print_table`, one line per row with trailing blanks trimmed
+fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = *width))
+            .collect();
+        format!("{}\n", padded.join("  ").trim_end())
+    };
+    let mut table = line(headers.to_vec());
+    for row in rows {
+        table.push_str(&line(row.iter().map(String::as_str).collect()));
+    }
+    table
+}
+
+/// Render an optional value for a table cell, `-` when missing
+pub fn cell<T: ToString>(value: Option<T>) -> String {
+    value
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_columns_follow_the_widest_cell() {
+        let rows = vec![
+            vec![
+                "1".to_string(),
+                "nightly-backup".to_string(),
+                "-".to_string(),
+            ],
+            vec!["12".to_string(), "ö".to_string(), "Running".to_string()],
+        ];
+        assert_eq!(
+            render_table(&["ID", "NAME", "STATE"], &rows),
+            "ID  NAME            STATE\n\
+             1   nightly-backup  -\n\
+             12  ö               Running\n"
+        );
+    }
+
+    #[test]
+    fn table_without_rows_prints_the_headers() {
+        assert_eq!(render_table(&["ID", "NAME"], &[]), "ID  NAME\n");
+    }
+
+    #[test]
+    fn missing_cells_render_as_dash() {
+        assert_eq!(cell(Some(3)), "3");
+        assert_eq!(cell(None::<i32>), "-");
+    }
+}
@@ -0,0 +1,306 @@
+//! Declarative job specs, YAML or TOML files describing a job for `consumer submit` / `consumer apply`
+//!
+//! ```yaml
+//! name: nightly-report
+//! image: docker.io/library/alpine:3.20
+//! format: DockerRegistry
+//! flags: ["--env=MODE=fast"]
+//! output:
+//!   type: Files
+//!   paths: ["/out/report.csv"]
+//! schedule:
+//!   type: Cron
+//!   cron: "0 5 * * *"
+//! notes: Generated nightly
+//! priority: 2
+//! ```
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{bail, Context};
+use serde::Deserialize;
+
+use swarm_client::validation::parse_cron;
+
+use crate::models::{ImageFormatEnum, NewJob, OutputTypeEnum, ScheduleTypeEnum};
+
+/// File extensions picked up by `consumer apply`
+const SPEC_EXTENSIONS: [&str; 3] = ["yaml", "yml", "toml"];
+
+/// A job described in a spec file, maps onto the `NewJob` body of `POST /jobs`
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct JobSpec {
+    // name: job name, non-empty
+    pub name: String,
+    // image: image url or tarball location
+    pub image: String,
+    // format: DockerRegistry or Tarball
+    #[serde(default = "default_format")]
+    pub format: String,
+    // flags: extra docker flags
+    #[serde(default)]
+    pub flags: Vec<String>,
+    // output: where the job writes its result, defaults to stdout
+    #[serde(default)]
+    pub output: OutputSpec,
+    // schedule: run once or on a cron schedule, defaults to once
+    #[serde(default)]
+    pub schedule: ScheduleSpec,
+    // notes
+    #[serde(default)]
+    pub notes: Option<String>,
+    // priority: higher is admitted first
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// Output section of a spec
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum OutputSpec {
+    #[default]
+    Stdout,
+    Files {
+        paths: Vec<String>,
+    },
+}
+
+/// Schedule section of a spec
+#[derive(Debug, Clone, Default, Deserialize, PartialEq)]
+#[serde(tag = "type")]
+pub enum ScheduleSpec {
+    #[default]
+    Once,
+    Cron {
+        cron: String,
+    },
+}
+
+/// Every problem found in a spec, reported together so a file can be fixed in one go
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpecErrors(pub Vec<String>);
+
+impl fmt::Display for SpecErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.join("; "))
+    }
+}
+
+impl std::error::Error for SpecErrors {}
+
+fn default_format() -> String {
+    "DockerRegistry".to_string()
+}
+
+impl JobSpec {
+    /// Check the spec locally, before anything is sent to Core
+    /// # Returns
+    /// * `Ok(())` if the spec is valid
+    /// * `SpecErrors` - one message per invalid field
+    /// # Panics
+    /// Does not panic.
+    pub fn validate(&self) -> Result<(), SpecErrors> {
+        let mut errors = Vec::new();
+
+        if self.name.trim().is_empty() {
+            errors.push("name: must not be empty".to_string());
+        }
+        if self.image.trim().is_empty() {
+            errors.push("image: must not be empty".to_string());
+        } else if self.image.chars().any(char::is_whitespace) {
+            errors.push("image: must not contain whitespace".to_string());
+        }
+        if ImageFormatEnum::from_str(&self.format).is_err() {
+            errors.push(format!(
+                "format: expected DockerRegistry or Tarball, got \"{}\"",
+                self.format
+            ));
+        }
+        if let OutputSpec::Files { paths } = &self.output {
+            if paths.is_empty() {
+                errors.push("output.paths: at least one path is required for Files".to_string());
+            }
+            if paths.iter().any(|p| p.trim().is_empty()) {
+                errors.push("output.paths: paths must not be empty".to_string());
+            }
+        }
+        if let ScheduleSpec::Cron { cron } = &self.schedule {
+            if let Err(e) = parse_cron(cron) {
+                errors.push(format!(
+                    "schedule.cron: invalid expression \"{}\": {}",
+                    cron, e
+                ));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SpecErrors(errors))
+        }
+    }
+
+    /// Build the `POST /jobs` body for this spec
+    /// # Arguments
+    /// * user_id: The id of the submitting user
+    /// # Returns
+    /// * `NewJob` - The job to submit
+    /// # Panics
+    /// Does not panic, returns an error if the format is not a known image format.
+    pub fn to_new_job(&self, user_id: i32) -> anyhow::Result<NewJob> {
+        let image_format = ImageFormatEnum::from_str(&self.format).map_err(anyhow::Error::msg)?;
+        let (output_type, output_paths) = match &self.output {
+            OutputSpec::Stdout => (OutputTypeEnum::Stdout, None),
+            OutputSpec::Files { paths } => (
+                OutputTypeEnum::Files,
+                Some(paths.iter().cloned().map(Some).collect()),
+            ),
+        };
+        let (schedule_type, cron_expression) = match &self.schedule {
+            ScheduleSpec::Once => (ScheduleTypeEnum::Once, None),
+            ScheduleSpec::Cron { cron } => (ScheduleTypeEnum::Cron, Some(cron.clone())),
+        };
+        Ok(NewJob {
+            user_id,
+            job_name: self.name.trim().to_string(),
+            image_url: self.image.trim().to_string(),
+            image_format,
+            docker_flags: (!self.flags.is_empty())
+                .then(|| self.flags.iter().cloned().map(Some).collect()),
+            output_type,
+            output_paths,
+            schedule_type,
+            cron_expression,
+            notes: self.notes.clone(),
+            priority: self.priority,
+        })
+    }
+}
+
+/// Parse spec file contents, the format is chosen by file extension
+/// # Arguments
+/// * raw: The file contents
+/// * extension: `yaml`, `yml` or `toml`
+/// # Returns
+/// * `JobSpec` - The parsed, not yet validated, spec
+/// # Panics
+/// Does not panic, returns an error on unknown extensions or malformed files.
+pub fn parse_spec(raw: &str, extension: &str) -> anyhow::Result<JobSpec> {
+    match extension {
+        "yaml" | "yml" => Ok(serde_yaml::from_str(raw)?),
+        "toml" => Ok(toml::from_str(raw)?),
+        other => bail!(
+            "unsupported spec format \"{}\", use .yaml, .yml or .toml",
+            other
+        ),
+    }
+}
+
+/// Read, parse and validate one spec file
+/// # Arguments
+/// * path: Path to a `.yaml`, `.yml` or `.toml` spec
+/// # Returns
+/// * `JobSpec` - A valid spec
+/// # Panics
+/// Does not panic, returns an error naming the file if it can't be read, parsed or validated.
+pub fn load_spec(path: &Path) -> anyhow::Result<JobSpec> {
+    let raw = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let spec =
+        parse_spec(&raw, &extension).with_context(|| format!("parsing {}", path.display()))?;
+    spec.validate()
+        .with_context(|| format!("validating {}", path.display()))?;
+    Ok(spec)
+}
+
+/// Collect the spec files of a directory, sorted by name so `apply` runs in a stable order
+/// # Arguments
+/// * dir: Directory to scan, not recursive
+/// # Returns
+/// * `Vec<PathBuf>` - Paths of every `.yaml`, `.yml` and `.toml` file
+/// # Panics
+/// Does not panic.
+pub fn collect_spec_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("reading {}", dir.display()))? {
+        let path = entry?.path();
+        let is_spec = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| SPEC_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()));
+        if path.is_file() && is_spec {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yaml_and_toml_specs_parse_the_same() {
+        let yaml = r#"
+name: nightly
+image: docker.io/library/alpine:3.20
+output:
+  type: Files
+  paths: ["/out/a.csv"]
+schedule:
+  type: Cron
+  cron: "0 5 * * *"
+"#;
+        let toml = r#"
+name = "nightly"
+image = "docker.io/library/alpine:3.20"
+
+[output]
+type = "Files"
+paths = ["/out/a.csv"]
+
+[schedule]
+type = "Cron"
+cron = "0 5 * * *"
+"#;
+        let a = parse_spec(yaml, "yaml").unwrap();
+        let b = parse_spec(toml, "toml").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.format, "DockerRegistry");
+        assert!(a.validate().is_ok());
+
+        let job = a.to_new_job(7).unwrap();
+        assert_eq!(job.schedule_type, ScheduleTypeEnum::Cron);
+        assert_eq!(job.cron_expression.as_deref(), Some("0 5 * * *"));
+        assert!(job.validate().is_ok());
+        assert_eq!(job.docker_flags, None);
+    }
+
+    #[test]
+    fn invalid_specs_report_every_field() {
+        let spec = parse_spec(
+            "name: ''\nimage: alpine\nformat: Zip\noutput:\n  type: Files\n  paths: []\nschedule:\n  type: Cron\n  cron: every day\n",
+            "yaml",
+        )
+        .unwrap();
+        let errors = spec.validate().unwrap_err().0;
+        assert_eq!(errors.len(), 4);
+        assert!(errors.iter().any(|e| e.starts_with("schedule.cron")));
+    }
+
+    #[test]
+    fn unknown_fields_and_extensions_are_rejected() {
+        assert!(parse_spec("name: a\nimage: b\nimgae: c\n", "yaml").is_err());
+        assert!(parse_spec("{}", "json").is_err());
+    }
+}
@@ -2,8 +2,11 @@ use std::fs;
 
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 
-use crate::client::{build_authed_client, Session};
+use swarm_client::SwarmClient;
+
+use crate::client::Session;
 use crate::commands;
+use crate::credentials;
 use crate::models::UserResponse;
 use crate::views::connect::{config_file_path, CoreConfig};
 
@@ -11,7 +14,7 @@ use crate::views::connect::{config_file_path, CoreConfig};
 /// # Args
 /// * `base_url` - The base URL of the Core instance, no port, e.g. `http://127.0.0.1`
 /// # Returns
-/// * `Session` - A session struct with the client, user, and app host
+/// * `Session` - A session struct with the client and user
 /// # Example
 /// ```
 /// let session = auth_flow("http://127.0.0.1").await.unwrap();
@@ -50,16 +53,16 @@ pub async fn auth_flow(base_url: &str) -> anyhow::Result<Session> {
     let password: String = Input::new().with_prompt("Password").interact_text()?;
 
     // Login or register flow
-    let client = reqwest::Client::new();
-    let (token, user): (String, UserResponse) = if mode == 0 {
+    let client = SwarmClient::new(base_url)?;
+    let (authed_client, user): (SwarmClient, UserResponse) = if mode == 0 {
         // Login
-        commands::login_user(&client, base_url, &username, &password).await?
+        commands::login_user(&client, &username, &password).await?
     } else {
         // Register
         let email: String = Input::new().with_prompt("Email").interact_text()?;
-        let user = commands::register_user(&client, base_url, &username, &email, &password).await?;
-        let (token, _) = commands::login_user(&client, base_url, &username, &password).await?;
-        (token, user)
+        let user = commands::register_user(&client, &username, &email, &password).await?;
+        let (authed_client, _) = commands::login_user(&client, &username, &password).await?;
+        (authed_client, user)
     };
 
     // Save last username
@@ -73,11 +76,55 @@ pub async fn auth_flow(base_url: &str) -> anyhow::Result<Session> {
     cfg.last_username = Some(user.username.clone());
     fs::write(config_file_path(), serde_json::to_string_pretty(&cfg)?)?;
 
-    // Build authed client, return session
-    let authed_client = build_authed_client(&token)?;
+    // Return session with the authed client
     Ok(Session {
         client: authed_client,
         user,
-        app_host: base_url.to_string(),
     })
 }
+
+/// Authenticate without prompts, for scripted use such as `consumer submit`
+/// Credentials are resolved by `credentials::load`, environment first, then the keyring.
+/// # Args
+/// * `base_url` - The base URL of the Core instance, no port, e.g. `http://127.0.0.1`
+/// # Returns
+/// * `Session` - A session struct with the client and user
+/// # Panics
+/// * Does not panic, returns an error if no credentials are found or login fails
+pub async fn scripted_session(base_url: &str) -> anyhow::Result<Session> {
+    let creds = credentials::load()?;
+    let client = SwarmClient::new(base_url)?;
+    let (authed_client, user) =
+        commands::login_user(&client, &creds.username, &creds.password).await?;
+    Ok(Session {
+        client: authed_client,
+        user,
+    })
+}
+
+/// Verify credentials against Core, then remember them for later scripted runs
+/// The password goes to the OS keyring, the username and Core location to `consumer_config.json`.
+/// # Args
+/// * `base_url` - The base URL of the Core instance
+/// * `username` - The username to log in with
+/// * `password` - The password to log in with
+/// # Returns
+/// * `UserResponse` - The logged in user
+/// # Panics
+/// * Does not panic, returns an error if login fails or the keyring is unavailable
+pub async fn login_and_store(
+    base_url: &str,
+    username: &str,
+    password: &str,
+) -> anyhow::Result<UserResponse> {
+    let client = SwarmClient::new(base_url)?;
+    let (_, user) = commands::login_user(&client, username, password).await?;
+    credentials::store(&user.username, password)?;
+
+    let cfg = CoreConfig {
+        base_url: base_url.to_string(),
+        last_username: Some(user.username.clone()),
+    };
+    fs::write(config_file_path(), serde_json::to_string_pretty(&cfg)?)?;
+    Ok(user)
+}
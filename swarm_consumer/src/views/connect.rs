@@ -136,3 +136,24 @@ async fn is_reachable(url: &str) -> bool {
         Ok(true)
     )
 }
+
+/// Core location for non-interactive runs, `CORE_API_URL` wins over the saved config
+/// # Returns
+/// * `String` - The base URL of the Core instance
+/// * `anyhow::Error` - if neither is set
+/// # Panics
+/// * Does not panic
+pub fn saved_core_location() -> anyhow::Result<String> {
+    if let Ok(url) = std::env::var("CORE_API_URL") {
+        return Ok(url);
+    }
+    fs::read_to_string(config_file_path())
+        .ok()
+        .and_then(|raw| serde_json::from_str::<CoreConfig>(&raw).ok())
+        .map(|cfg| cfg.base_url)
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No Core location: set CORE_API_URL or run the consumer interactively once"
+            )
+        })
+}
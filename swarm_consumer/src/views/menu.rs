@@ -1,5 +1,6 @@
 use dialoguer::{theme::ColorfulTheme, Select};
 
+use crate::models::{join_present, ImageFormatEnum, OutputTypeEnum, ScheduleTypeEnum};
 use crate::{commands, state};
 
 /// Main menu loop presenting user actions and handling navigation.
@@ -15,6 +16,8 @@ pub async fn main_menu() -> anyhow::Result<()> {
             "Submit Job",
             "List Jobs",
             "Finished Jobs",
+            "Re-run Job",
+            "Job Templates",
             "Logout",
             "Quit",
         ];
@@ -27,7 +30,12 @@ pub async fn main_menu() -> anyhow::Result<()> {
         match choice {
             0 => {
                 // ====== USER SETTINGS ======
-                let sub_opts = vec!["Change Core / IP", "Update Profile", "Back"];
+                let sub_opts = vec![
+                    "Change Core / IP",
+                    "Update Profile",
+                    "Usage & Quotas",
+                    "Back",
+                ];
                 let sel = Select::with_theme(&ColorfulTheme::default())
                     .with_prompt("User Settings")
                     .items(&sub_opts)
@@ -92,6 +100,28 @@ pub async fn main_menu() -> anyhow::Result<()> {
                             Err(err) => println!("❌ {}", err),
                         }
                     }
+                    2 => {
+                        // show quota limits next to current usage
+                        let sess = state::get_session();
+                        match commands::get_quota_status(&sess).await {
+                            Ok(status) => {
+                                println!(
+                                    "📊 Queued jobs:        {}/{}",
+                                    status.usage.queued_jobs, status.quota.max_queued_jobs
+                                );
+                                println!(
+                                    "📊 Running jobs:       {}/{}",
+                                    status.usage.running_jobs, status.quota.max_running_jobs
+                                );
+                                println!(
+                                    "📊 Submissions/minute: {}/{}",
+                                    status.usage.submissions_last_minute,
+                                    status.quota.max_submissions_per_minute
+                                );
+                            }
+                            Err(err) => println!("❌ {}", err),
+                        }
+                    }
                     _ => {}
                 }
                 continue; // back to main loop
@@ -107,7 +137,7 @@ pub async fn main_menu() -> anyhow::Result<()> {
                     .with_prompt("Docker image (e.g. user/repo:tag or image tarball path)")
                     .interact_text()?;
                 // Image format selection
-                let img_formats = vec!["DockerRegistry", "Tarball"];
+                let img_formats = [ImageFormatEnum::DockerRegistry, ImageFormatEnum::Tarball];
                 let img_choice = Select::with_theme(&ColorfulTheme::default())
                     .with_prompt("Image format")
                     .items(&img_formats)
@@ -115,7 +145,7 @@ pub async fn main_menu() -> anyhow::Result<()> {
                     .interact()?;
                 let image_format = img_formats[img_choice];
                 // Output type selection
-                let output_types = vec!["Stdout", "Files"];
+                let output_types = [OutputTypeEnum::Stdout, OutputTypeEnum::Files];
                 let out_choice = Select::with_theme(&ColorfulTheme::default())
                     .with_prompt("Output type")
                     .items(&output_types)
@@ -123,7 +153,7 @@ pub async fn main_menu() -> anyhow::Result<()> {
                     .interact()?;
                 let output_type = output_types[out_choice];
                 // If output type is Files, ask for output paths
-                let output_paths = if output_type == "Files" {
+                let output_paths = if output_type == OutputTypeEnum::Files {
                     let paths_input: String = dialoguer::Input::new()
                         .with_prompt("Enter output file paths (comma-separated)")
                         .interact_text()?;
@@ -142,7 +172,7 @@ pub async fn main_menu() -> anyhow::Result<()> {
                     None
                 };
                 // Schedule type selection
-                let sched_types = vec!["Once", "Cron"];
+                let sched_types = [ScheduleTypeEnum::Once, ScheduleTypeEnum::Cron];
                 let sched_choice = Select::with_theme(&ColorfulTheme::default())
                     .with_prompt("Schedule type")
                     .items(&sched_types)
@@ -150,7 +180,7 @@ pub async fn main_menu() -> anyhow::Result<()> {
                     .interact()?;
                 let schedule_type = sched_types[sched_choice];
                 // If Cron schedule, prompt for cron expression
-                let cron_expression = if schedule_type == "Cron" {
+                let cron_expression = if schedule_type == ScheduleTypeEnum::Cron {
                     let mut expr: String;
                     loop {
                         expr = dialoguer::Input::new()
@@ -237,7 +267,10 @@ pub async fn main_menu() -> anyhow::Result<()> {
                                                     println!("      Stdout: {}", output);
                                                 }
                                                 if let Some(files) = &result.files {
-                                                    println!("      Files: {}", files.join(", "));
+                                                    println!(
+                                                        "      Files: {}",
+                                                        join_present(files, ", ")
+                                                    );
                                                 }
                                             }
                                         }
@@ -255,6 +288,18 @@ pub async fn main_menu() -> anyhow::Result<()> {
                 }
             }
             4 => {
+                // Clone one of the users jobs into a new submission
+                if let Err(err) = crate::views::templates::rerun_job().await {
+                    println!("❌ {}", err);
+                }
+            }
+            5 => {
+                // Saved templates, run with parameter overrides
+                if let Err(err) = crate::views::templates::templates_menu().await {
+                    println!("❌ {}", err);
+                }
+            }
+            6 => {
                 // Logout: terminate the application (could also implement returning to auth, but here we exit)
                 println!(
                     "🔒 Logging out. Goodbye, {}!",
@@ -262,7 +307,7 @@ pub async fn main_menu() -> anyhow::Result<()> {
                 );
                 std::process::exit(0);
             }
-            5 => {
+            7 => {
                 // Quit the application
                 println!("👋 Exiting application. Goodbye!");
                 std::process::exit(0);
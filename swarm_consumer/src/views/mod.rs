@@ -3,3 +3,4 @@
 pub mod auth;
 pub mod connect;
 pub mod menu;
+pub mod templates;
@@ -0,0 +1,209 @@
+//! Job templates and re-runs, shortcuts around the long `Submit Job` flow
+
+use std::collections::HashMap;
+
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+
+use crate::models::{Job, TemplateParamKindEnum, TemplateParameterSpec};
+use crate::{commands, state};
+
+/// Template sub menu, run, save or delete templates
+/// # Returns
+/// * `anyhow::Result<()>`
+/// # Panics
+/// Does not panic.
+pub async fn templates_menu() -> anyhow::Result<()> {
+    let sub_opts = vec![
+        "Run Template",
+        "Save Job as Template",
+        "Delete Template",
+        "Back",
+    ];
+    let sel = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Job Templates")
+        .items(&sub_opts)
+        .default(0)
+        .interact()?;
+
+    let session = state::get_session();
+    match sel {
+        0 => {
+            let templates = commands::list_templates(&session).await?;
+            if templates.is_empty() {
+                println!("(No templates saved yet)");
+                return Ok(());
+            }
+            let labels: Vec<String> = templates
+                .iter()
+                .map(|t| {
+                    format!(
+                        "[{}] {} ({})",
+                        t.template.id, t.template.name, t.template.image_url
+                    )
+                })
+                .collect();
+            let idx = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Template to run")
+                .items(&labels)
+                .default(0)
+                .interact()?;
+            let chosen = &templates[idx];
+
+            // Ask for every parameter, blank keeps the default
+            let mut overrides = HashMap::new();
+            for param in &chosen.parameters {
+                let prompt = match &param.default_value {
+                    Some(d) => format!("{} ({}) [default: {}]", param.name, param.kind, d),
+                    None => format!("{} ({})", param.name, param.kind),
+                };
+                let value: String = Input::new()
+                    .with_prompt(prompt)
+                    .allow_empty(!param.required || param.default_value.is_some())
+                    .interact_text()?;
+                if !value.trim().is_empty() {
+                    overrides.insert(param.name.clone(), value.trim().to_string());
+                }
+            }
+            let job_name: String = Input::new()
+                .with_prompt("Job name (leave blank for generated)")
+                .allow_empty(true)
+                .interact_text()?;
+            let job_name = (!job_name.trim().is_empty()).then_some(job_name.trim());
+
+            match commands::run_template(&session, chosen.template.id, job_name, overrides).await {
+                Ok(job) => println!(
+                    "✅ Job \"{}\" submitted from template (ID: {}).",
+                    job.job_name, job.id
+                ),
+                Err(err) => println!("❌ {}", err),
+            }
+        }
+        1 => {
+            let Some(job) = select_job("Job to save as template").await? else {
+                return Ok(());
+            };
+            let name: String = Input::new().with_prompt("Template name").interact_text()?;
+            let parameters = prompt_parameters(&job)?;
+            match commands::create_template_from_job(&session, &job, name.trim(), parameters).await
+            {
+                Ok(t) => println!(
+                    "✅ Template \"{}\" saved (ID: {}, {} parameters).",
+                    t.template.name,
+                    t.template.id,
+                    t.parameters.len()
+                ),
+                Err(err) => println!("❌ {}", err),
+            }
+        }
+        2 => {
+            let templates = commands::list_templates(&session).await?;
+            if templates.is_empty() {
+                println!("(No templates saved yet)");
+                return Ok(());
+            }
+            let labels: Vec<String> = templates
+                .iter()
+                .map(|t| format!("[{}] {}", t.template.id, t.template.name))
+                .collect();
+            let idx = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Template to delete")
+                .items(&labels)
+                .default(0)
+                .interact()?;
+            match commands::delete_template(&session, templates[idx].template.id).await {
+                Ok(()) => println!("🗑️ Template deleted."),
+                Err(err) => println!("❌ {}", err),
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Pick one of the users jobs and submit a copy of it
+/// # Returns
+/// * `anyhow::Result<()>`
+/// # Panics
+/// Does not panic.
+pub async fn rerun_job() -> anyhow::Result<()> {
+    let Some(job) = select_job("Job to re-run").await? else {
+        return Ok(());
+    };
+    let session = state::get_session();
+    match commands::rerun_job(&session, job.id).await {
+        Ok(new_job) => println!(
+            "✅ Job \"{}\" re-submitted (new ID: {}).",
+            new_job.job_name, new_job.id
+        ),
+        Err(err) => println!("❌ {}", err),
+    }
+    Ok(())
+}
+
+/// Let the user choose one of their jobs, None if there are no jobs
+async fn select_job(prompt: &str) -> anyhow::Result<Option<Job>> {
+    let session = state::get_session();
+    let mut jobs = commands::list_jobs(&session).await?;
+    if jobs.is_empty() {
+        println!("(No jobs found for user {})", session.user.username);
+        return Ok(None);
+    }
+    let labels: Vec<String> = jobs
+        .iter()
+        .map(|j| format!("[{}] {} (State: {})", j.id, j.job_name, j.state))
+        .collect();
+    let idx = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .items(&labels)
+        .default(0)
+        .interact()?;
+    Ok(Some(jobs.swap_remove(idx)))
+}
+
+/// Ask which parts of the job should become parameters
+fn prompt_parameters(job: &Job) -> anyhow::Result<Vec<TemplateParameterSpec>> {
+    let mut parameters = Vec::new();
+
+    if Confirm::new()
+        .with_prompt(format!(
+            "Make the image tag of {} a parameter?",
+            job.image_url
+        ))
+        .default(true)
+        .interact()?
+    {
+        // current tag becomes the default, colon after the last slash is the tag
+        let name_start = job.image_url.rfind('/').map(|i| i + 1).unwrap_or(0);
+        let current = job.image_url[name_start..]
+            .split_once(':')
+            .map(|(_, tag)| tag.to_string());
+        parameters.push(TemplateParameterSpec {
+            name: "tag".into(),
+            kind: TemplateParamKindEnum::ImageTag,
+            default_value: current,
+            required: true,
+        });
+    }
+
+    let env_names: String = Input::new()
+        .with_prompt("Env variables to parameterize (comma-separated, blank for none)")
+        .allow_empty(true)
+        .interact_text()?;
+    for name in env_names
+        .split(',')
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+    {
+        let default: String = Input::new()
+            .with_prompt(format!("Default for {} (blank for required)", name))
+            .allow_empty(true)
+            .interact_text()?;
+        parameters.push(TemplateParameterSpec {
+            name: name.to_string(),
+            kind: TemplateParamKindEnum::Env,
+            default_value: (!default.is_empty()).then_some(default),
+            required: true,
+        });
+    }
+    Ok(parameters)
+}
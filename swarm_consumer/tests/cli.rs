@@ -0,0 +1,69 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// Run the consumer binary in an empty directory, without any saved config or credentials
+fn consumer(dir: &str, args: &[&str]) -> Output {
+    let cwd = std::env::temp_dir().join(format!("consumer-cli-{}-{}", dir, std::process::id()));
+    fs::create_dir_all(&cwd).unwrap();
+    Command::new(env!("CARGO_BIN_EXE_consumer"))
+        .args(args)
+        .current_dir(&cwd)
+        .env_remove("CORE_API_URL")
+        .env_remove("SWARM_USERNAME")
+        .env_remove("SWARM_PASSWORD")
+        .output()
+        .unwrap()
+}
+
+fn spec_file(name: &str, content: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("{}-{}", std::process::id(), name));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn dry_run_of_a_valid_spec_exits_zero() {
+    let path = spec_file(
+        "valid.yaml",
+        "name: nightly\nimage: docker.io/library/alpine:3.20\n",
+    );
+    let out = consumer(
+        "valid",
+        &["submit", "-f", path.to_str().unwrap(), "--dry-run"],
+    );
+    assert!(out.status.success(), "{out:?}");
+    // messages go to stderr, stdout stays clean for scripts
+    assert!(out.stdout.is_empty());
+}
+
+#[test]
+fn invalid_or_missing_spec_exits_non_zero() {
+    let path = spec_file("invalid.yaml", "name: ''\nimage: alpine\nformat: Zip\n");
+    let out = consumer(
+        "invalid",
+        &["submit", "-f", path.to_str().unwrap(), "--dry-run"],
+    );
+    assert_eq!(out.status.code(), Some(1));
+
+    let out = consumer(
+        "missing",
+        &["submit", "-f", "does-not-exist.yaml", "--dry-run"],
+    );
+    assert_eq!(out.status.code(), Some(1));
+}
+
+#[test]
+fn commands_without_a_core_exit_non_zero() {
+    let out = consumer("no-core", &["--output", "json", "jobs", "list"]);
+    assert_eq!(out.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&out.stderr).contains("CORE_API_URL"));
+}
+
+#[test]
+fn usage_errors_exit_with_two() {
+    let out = consumer("usage", &["--output", "yaml", "jobs", "list"]);
+    assert_eq!(out.status.code(), Some(2));
+    let out = consumer("usage", &["jobs", "get"]);
+    assert_eq!(out.status.code(), Some(2));
+}
@@ -0,0 +1,35 @@
+use crate::models::{ControlAck, CoreState, SchedulerState};
+use crate::{Result, SwarmClient};
+
+/// Only for users listed in `CORE_ADMIN_USERS` of core, anyone else gets a 403 `ClientError::Api`
+impl SwarmClient {
+    /// `GET /admin/state`, module health, the scheduler pause and the dispatcher and logger state
+    pub async fn core_state(&self) -> Result<CoreState> {
+        Self::send(self.http.get(self.url(&["admin", "state"]))).await
+    }
+
+    /// `POST /admin/shutdown`, core shuts down gracefully after answering
+    pub async fn shutdown_core(&self) -> Result<ControlAck> {
+        Self::send(self.http.post(self.url(&["admin", "shutdown"]))).await
+    }
+
+    /// `POST /admin/restart`, every module but the HTTP server is built again
+    pub async fn restart_core(&self) -> Result<ControlAck> {
+        Self::send(self.http.post(self.url(&["admin", "restart"]))).await
+    }
+
+    /// `POST /admin/modules/{module}/restart`, `module` as in `ModuleHealth::module`, e.g. `scheduler`
+    pub async fn restart_module(&self, module: &str) -> Result<ControlAck> {
+        Self::send(self.http.post(self.url(&["admin", "modules", module, "restart"]))).await
+    }
+
+    /// `POST /admin/scheduler/pause`, no job is admitted or claimed until resumed
+    pub async fn pause_scheduler(&self) -> Result<SchedulerState> {
+        Self::send(self.http.post(self.url(&["admin", "scheduler", "pause"]))).await
+    }
+
+    /// `POST /admin/scheduler/resume`
+    pub async fn resume_scheduler(&self) -> Result<SchedulerState> {
+        Self::send(self.http.post(self.url(&["admin", "scheduler", "resume"]))).await
+    }
+}
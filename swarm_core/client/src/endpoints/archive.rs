@@ -0,0 +1,21 @@
+use crate::models::{ArchivedJob, Job, JobArchive, ListParams, Page};
+use crate::{Result, SwarmClient};
+
+impl SwarmClient {
+    /// `GET /archive/jobs`, one page of archived jobs
+    pub async fn list_archived_jobs(&self, params: &ListParams) -> Result<Page<JobArchive>> {
+        Self::send(self.http.get(self.url(&["archive", "jobs"])).query(params)).await
+    }
+
+    /// `GET /archive/jobs/<id>`
+    pub async fn get_archived_job(&self, id: i32) -> Result<ArchivedJob> {
+        let url = self.url(&["archive", "jobs", &id.to_string()]);
+        Self::send(self.http.get(url)).await
+    }
+
+    /// `POST /archive/jobs/<id>/restore`, moves the job back into `/jobs`, owner or admin
+    pub async fn restore_archived_job(&self, id: i32) -> Result<Job> {
+        let url = self.url(&["archive", "jobs", &id.to_string(), "restore"]);
+        Self::send(self.http.post(url)).await
+    }
+}
@@ -0,0 +1,9 @@
+use crate::models::{Credentials, SessionToken};
+use crate::{Result, SwarmClient};
+
+impl SwarmClient {
+    /// `POST /login`, pass the token to `authenticated` for a logged in client
+    pub async fn login(&self, credentials: &Credentials) -> Result<SessionToken> {
+        Self::send(self.http.post(self.url(&["login"])).json(credentials)).await
+    }
+}
@@ -0,0 +1,174 @@
+use crate::enums::JobStateEnum;
+use crate::models::{FailJobPayload, Job, ListParams, NewJob, Page};
+use crate::{Result, SwarmClient};
+
+impl SwarmClient {
+    /// `POST /jobs`, a 429 carries `retry_after_secs`, see `ClientError::retry_after_secs`
+    pub async fn create_job(&self, job: &NewJob) -> Result<Job> {
+        Self::send(self.http.post(self.url(&["jobs"])).json(job)).await
+    }
+
+    /// `POST /jobs/<id>/rerun`, submits a copy of the job
+    pub async fn rerun_job(&self, id: i32) -> Result<Job> {
+        Self::send(self.http.post(self.url(&["jobs", &id.to_string(), "rerun"]))).await
+    }
+
+    /// `GET /jobs`, one page of jobs
+    pub async fn list_jobs(&self, params: &ListParams) -> Result<Page<Job>> {
+        Self::send(self.http.get(self.url(&["jobs"])).query(params)).await
+    }
+
+    /// `GET /jobs/<id>`
+    pub async fn get_job(&self, id: i32) -> Result<Job> {
+        Self::send(self.http.get(self.url(&["jobs", &id.to_string()]))).await
+    }
+
+    /// `PATCH /jobs/<id>`, replaces the stored job with `job`
+    pub async fn update_job(&self, id: i32, job: &Job) -> Result<Job> {
+        Self::send(
+            self.http
+                .patch(self.url(&["jobs", &id.to_string()]))
+                .json(job),
+        )
+        .await
+    }
+
+    /// `DELETE /jobs/<id>`
+    pub async fn delete_job(&self, id: i32) -> Result<()> {
+        Self::send_empty(self.http.delete(self.url(&["jobs", &id.to_string()]))).await
+    }
+
+    /// `GET /jobs/search?user_id=&query=`, jobs of the user whose name contains `query`
+    pub async fn search_jobs(&self, user_id: i32, query: &str) -> Result<Vec<Job>> {
+        let request = self
+            .http
+            .get(self.url(&["jobs", "search"]))
+            .query(&[("user_id", user_id.to_string().as_str()), ("query", query)]);
+        Self::send(request).await
+    }
+
+    /// `GET /jobs/name/<user_id>?name=`
+    pub async fn find_jobs_by_name(&self, user_id: i32, name: &str) -> Result<Vec<Job>> {
+        let request = self
+            .http
+            .get(self.url(&["jobs", "name", &user_id.to_string()]))
+            .query(&[("name", name)]);
+        Self::send(request).await
+    }
+
+    /// `GET /jobs/by_admin?user_id=&limit=&offset=`, jobs owned by `user_id`
+    pub async fn list_jobs_by_admin(
+        &self,
+        user_id: i32,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<Vec<Job>> {
+        let request = self.http.get(self.url(&["jobs", "by_admin"])).query(&[
+            ("user_id", Some(user_id as i64)),
+            ("limit", limit),
+            ("offset", offset),
+        ]);
+        Self::send(request).await
+    }
+
+    /// `GET /jobs/state/<state>`
+    pub async fn list_jobs_by_state(&self, state: JobStateEnum) -> Result<Vec<Job>> {
+        Self::send(self.http.get(self.url(&["jobs", "state", state.as_str()]))).await
+    }
+
+    /// `GET /jobs/recent?limit=`
+    pub async fn recent_jobs(&self, limit: Option<i64>) -> Result<Vec<Job>> {
+        let request = self
+            .http
+            .get(self.url(&["jobs", "recent"]))
+            .query(&[("limit", limit)]);
+        Self::send(request).await
+    }
+
+    /// `GET /jobs/failed?limit=`
+    pub async fn failed_jobs(&self, limit: Option<i64>) -> Result<Vec<Job>> {
+        let request = self
+            .http
+            .get(self.url(&["jobs", "failed"]))
+            .query(&[("limit", limit)]);
+        Self::send(request).await
+    }
+
+    /// `PATCH /jobs/<id>/running`
+    pub async fn mark_job_running(&self, id: i32) -> Result<Job> {
+        Self::send(
+            self.http
+                .patch(self.url(&["jobs", &id.to_string(), "running"])),
+        )
+        .await
+    }
+
+    /// `PATCH /jobs/<id>/succeeded`
+    pub async fn mark_job_succeeded(&self, id: i32) -> Result<Job> {
+        Self::send(
+            self.http
+                .patch(self.url(&["jobs", &id.to_string(), "succeeded"])),
+        )
+        .await
+    }
+
+    /// `PATCH /jobs/<id>/failed`, `message` ends up in `error_message`
+    pub async fn mark_job_failed(&self, id: i32, message: Option<&str>) -> Result<Job> {
+        let body = FailJobPayload {
+            message: message.map(String::from),
+        };
+        Self::send(
+            self.http
+                .patch(self.url(&["jobs", &id.to_string(), "failed"]))
+                .json(&body),
+        )
+        .await
+    }
+
+    /// `GET /jobs/scheduled`, cron jobs
+    pub async fn scheduled_jobs(&self) -> Result<Vec<Job>> {
+        Self::send(self.http.get(self.url(&["jobs", "scheduled"]))).await
+    }
+
+    /// `GET /jobs/cron_due?date=&time=`, `date` is parsed with the chrono format `time`
+    pub async fn cron_due_jobs(&self, date: &str, time: &str) -> Result<Vec<Job>> {
+        let request = self
+            .http
+            .get(self.url(&["jobs", "cron_due"]))
+            .query(&[("date", date), ("time", time)]);
+        Self::send(request).await
+    }
+
+    /// `GET /jobs/one-time-ready`, queued one-time jobs
+    pub async fn ready_jobs(&self) -> Result<Vec<Job>> {
+        Self::send(self.http.get(self.url(&["jobs", "one-time-ready"]))).await
+    }
+
+    /// `GET /jobs/stats/admins`, `(user_id, job count)` per user
+    pub async fn admin_job_counts(&self) -> Result<Vec<(i32, i64)>> {
+        Self::send(self.http.get(self.url(&["jobs", "stats", "admins"]))).await
+    }
+
+    /// `GET /jobs/active/<worker_id>`, running jobs of the worker
+    pub async fn active_jobs_for_worker(&self, worker_id: i32) -> Result<Vec<Job>> {
+        Self::send(
+            self.http
+                .get(self.url(&["jobs", "active", &worker_id.to_string()])),
+        )
+        .await
+    }
+
+    /// `GET /jobs/assigned/<worker_id>`
+    pub async fn jobs_assigned_to_worker(&self, worker_id: i32) -> Result<Vec<Job>> {
+        Self::send(
+            self.http
+                .get(self.url(&["jobs", "assigned", &worker_id.to_string()])),
+        )
+        .await
+    }
+
+    /// `GET /jobs/unassigned`
+    pub async fn unassigned_jobs(&self) -> Result<Vec<Job>> {
+        Self::send(self.http.get(self.url(&["jobs", "unassigned"]))).await
+    }
+}
@@ -0,0 +1,105 @@
+use chrono::NaiveDateTime;
+
+use crate::models::{FinishedAtPayload, JobAssignment, NewJobAssignment, StartedAtPayload};
+use crate::{Result, SwarmClient};
+
+/// Format core parses the range bounds with
+const RANGE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+impl SwarmClient {
+    /// `POST /assignments`
+    pub async fn create_assignment(&self, assignment: &NewJobAssignment) -> Result<JobAssignment> {
+        Self::send(self.http.post(self.url(&["assignments"])).json(assignment)).await
+    }
+
+    /// `POST /assignments/claim/<worker_id>`, the next queued job of the workers owner,
+    /// moved to Running and assigned to the worker. None if nothing is queued
+    pub async fn claim_job(&self, worker_id: i32) -> Result<Option<JobAssignment>> {
+        let url = self.url(&["assignments", "claim", &worker_id.to_string()]);
+        Self::send(self.http.post(url)).await
+    }
+
+    /// `GET /assignments/<id>`
+    pub async fn get_assignment(&self, id: i32) -> Result<JobAssignment> {
+        Self::send(self.http.get(self.url(&["assignments", &id.to_string()]))).await
+    }
+
+    /// `DELETE /assignments/<id>`
+    pub async fn delete_assignment(&self, id: i32) -> Result<()> {
+        Self::send_empty(
+            self.http
+                .delete(self.url(&["assignments", &id.to_string()])),
+        )
+        .await
+    }
+
+    /// `GET /assignments/lookup/<job_id>/<worker_id>`
+    pub async fn lookup_assignment(
+        &self,
+        job_id: i32,
+        worker_id: i32,
+    ) -> Result<Option<JobAssignment>> {
+        let url = self.url(&[
+            "assignments",
+            "lookup",
+            &job_id.to_string(),
+            &worker_id.to_string(),
+        ]);
+        Self::send(self.http.get(url)).await
+    }
+
+    /// `GET /assignments/by_job/<job_id>`
+    pub async fn assignments_for_job(&self, job_id: i32) -> Result<Vec<JobAssignment>> {
+        let url = self.url(&["assignments", "by_job", &job_id.to_string()]);
+        Self::send(self.http.get(url)).await
+    }
+
+    /// `GET /assignments/by_worker/<worker_id>`
+    pub async fn assignments_for_worker(&self, worker_id: i32) -> Result<Vec<JobAssignment>> {
+        let url = self.url(&["assignments", "by_worker", &worker_id.to_string()]);
+        Self::send(self.http.get(url)).await
+    }
+
+    /// `GET /assignments/by_worker/range?worker_id=&start=&end=`, assigned between the bounds
+    pub async fn assignments_for_worker_in_range(
+        &self,
+        worker_id: i32,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> Result<Vec<JobAssignment>> {
+        let request = self
+            .http
+            .get(self.url(&["assignments", "by_worker", "range"]))
+            .query(&[
+                ("worker_id", worker_id.to_string()),
+                ("start", start.format(RANGE_FORMAT).to_string()),
+                ("end", end.format(RANGE_FORMAT).to_string()),
+            ]);
+        Self::send(request).await
+    }
+
+    /// `GET /assignments/active`, started but not finished
+    pub async fn active_assignments(&self) -> Result<Vec<JobAssignment>> {
+        Self::send(self.http.get(self.url(&["assignments", "active"]))).await
+    }
+
+    /// `PATCH /assignments/<id>/started`
+    pub async fn mark_assignment_started(
+        &self,
+        id: i32,
+        started_at: NaiveDateTime,
+    ) -> Result<JobAssignment> {
+        let url = self.url(&["assignments", &id.to_string(), "started"]);
+        Self::send(self.http.patch(url).json(&StartedAtPayload { started_at })).await
+    }
+
+    /// `PATCH /assignments/<id>/finished`
+    pub async fn mark_assignment_finished(
+        &self,
+        id: i32,
+        finished_at: NaiveDateTime,
+    ) -> Result<JobAssignment> {
+        let url = self.url(&["assignments", &id.to_string(), "finished"]);
+        Self::send(self.http.patch(url).json(&FinishedAtPayload { finished_at })).await
+    }
+}
@@ -0,0 +1,37 @@
+use crate::models::{JobMetric, NewJobMetric};
+use crate::{Result, SwarmClient};
+
+impl SwarmClient {
+    /// `POST /metrics`
+    pub async fn create_metric(&self, metric: &NewJobMetric) -> Result<JobMetric> {
+        Self::send(self.http.post(self.url(&["metrics"])).json(metric)).await
+    }
+
+    /// `GET /metrics/<id>`
+    pub async fn get_metric(&self, id: i32) -> Result<JobMetric> {
+        Self::send(self.http.get(self.url(&["metrics", &id.to_string()]))).await
+    }
+
+    /// `DELETE /metrics/<id>`
+    pub async fn delete_metric(&self, id: i32) -> Result<()> {
+        Self::send_empty(self.http.delete(self.url(&["metrics", &id.to_string()]))).await
+    }
+
+    /// `GET /metrics/by_job/<job_id>`
+    pub async fn metric_for_job(&self, job_id: i32) -> Result<JobMetric> {
+        let url = self.url(&["metrics", "by_job", &job_id.to_string()]);
+        Self::send(self.http.get(url)).await
+    }
+
+    /// `GET /metrics/by_worker/<worker_id>`
+    pub async fn metrics_for_worker(&self, worker_id: i32) -> Result<Vec<JobMetric>> {
+        let url = self.url(&["metrics", "by_worker", &worker_id.to_string()]);
+        Self::send(self.http.get(url)).await
+    }
+
+    /// `GET /metrics/recent/<job_id>`
+    pub async fn recent_metric_for_job(&self, job_id: i32) -> Result<Option<JobMetric>> {
+        let url = self.url(&["metrics", "recent", &job_id.to_string()]);
+        Self::send(self.http.get(url)).await
+    }
+}
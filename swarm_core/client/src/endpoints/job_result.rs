@@ -0,0 +1,63 @@
+use crate::models::{
+    JobResult, ListParams, NewJobResult, Page, UpdateFilesPayload, UpdateStdoutPayload,
+};
+use crate::{Result, SwarmClient};
+
+impl SwarmClient {
+    /// `POST /results`
+    pub async fn create_result(&self, result: &NewJobResult) -> Result<JobResult> {
+        Self::send(self.http.post(self.url(&["results"])).json(result)).await
+    }
+
+    /// `GET /results`, one page of results
+    pub async fn list_results(&self, params: &ListParams) -> Result<Page<JobResult>> {
+        Self::send(self.http.get(self.url(&["results"])).query(params)).await
+    }
+
+    /// `GET /results/<id>`
+    pub async fn get_result(&self, id: i32) -> Result<JobResult> {
+        Self::send(self.http.get(self.url(&["results", &id.to_string()]))).await
+    }
+
+    /// `DELETE /results/<id>`
+    pub async fn delete_result(&self, id: i32) -> Result<()> {
+        Self::send_empty(self.http.delete(self.url(&["results", &id.to_string()]))).await
+    }
+
+    /// `GET /results/job/<job_id>`
+    pub async fn results_for_job(&self, job_id: i32) -> Result<Vec<JobResult>> {
+        let url = self.url(&["results", "job", &job_id.to_string()]);
+        Self::send(self.http.get(url)).await
+    }
+
+    /// `GET /results/list/<job_id>`
+    pub async fn list_results_for_job(&self, job_id: i32) -> Result<Vec<JobResult>> {
+        let url = self.url(&["results", "list", &job_id.to_string()]);
+        Self::send(self.http.get(url)).await
+    }
+
+    /// `GET /results/recent/<job_id>`
+    pub async fn recent_result_for_job(&self, job_id: i32) -> Result<Option<JobResult>> {
+        let url = self.url(&["results", "recent", &job_id.to_string()]);
+        Self::send(self.http.get(url)).await
+    }
+
+    /// `PATCH /results/<id>/stdout`
+    pub async fn update_result_stdout(&self, id: i32, stdout: Option<&str>) -> Result<JobResult> {
+        let body = UpdateStdoutPayload {
+            stdout: stdout.map(String::from),
+        };
+        let url = self.url(&["results", &id.to_string(), "stdout"]);
+        Self::send(self.http.patch(url).json(&body)).await
+    }
+
+    /// `PATCH /results/<id>/files`
+    pub async fn update_result_files(
+        &self,
+        id: i32,
+        files: Option<Vec<String>>,
+    ) -> Result<JobResult> {
+        let url = self.url(&["results", &id.to_string(), "files"]);
+        Self::send(self.http.patch(url).json(&UpdateFilesPayload { files })).await
+    }
+}
@@ -0,0 +1,28 @@
+use crate::models::{ListParams, LogEntry, LogRetentionPolicy, NewLogRetentionPolicy, Page};
+use crate::{Result, SwarmClient};
+
+impl SwarmClient {
+    /// `GET /logs`, one page of the system log
+    pub async fn list_logs(&self, params: &ListParams) -> Result<Page<LogEntry>> {
+        Self::send(self.http.get(self.url(&["logs"])).query(params)).await
+    }
+
+    /// `GET /logs/retention`
+    pub async fn log_retention_policies(&self) -> Result<Vec<LogRetentionPolicy>> {
+        Self::send(self.http.get(self.url(&["logs", "retention"]))).await
+    }
+
+    /// `PUT /logs/retention`, replaces the policy with the same level, module and action, admins only
+    pub async fn set_log_retention(
+        &self,
+        policy: &NewLogRetentionPolicy,
+    ) -> Result<LogRetentionPolicy> {
+        Self::send(self.http.put(self.url(&["logs", "retention"])).json(policy)).await
+    }
+
+    /// `DELETE /logs/retention/<id>`, admins only
+    pub async fn delete_log_retention(&self, id: i32) -> Result<()> {
+        let url = self.url(&["logs", "retention", &id.to_string()]);
+        Self::send_empty(self.http.delete(url)).await
+    }
+}
@@ -0,0 +1,160 @@
+//! One file per routes file of core-api, each adding its methods to `SwarmClient`
+mod admin;
+mod archive;
+mod auth;
+mod job;
+mod job_assignment;
+mod job_metric;
+mod job_result;
+mod log;
+mod quota;
+mod system;
+mod template;
+mod user;
+mod webhook;
+mod worker;
+mod worker_status;
+
+/// An operation of the OpenAPI document, path in OpenAPI form e.g. `/jobs/{id}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Endpoint {
+    pub method: &'static str,
+    pub path: &'static str,
+}
+
+const fn op(method: &'static str, path: &'static str) -> Endpoint {
+    Endpoint { method, path }
+}
+
+/// Every operation `SwarmClient` has a method for
+pub const ENDPOINTS: &[Endpoint] = &[
+    // auth
+    op("POST", "/login"),
+    // users
+    op("GET", "/users/{id}"),
+    op("POST", "/users"),
+    op("DELETE", "/users/{id}"),
+    op("PUT", "/users/{id}"),
+    op("GET", "/users/email/{email}"),
+    op("GET", "/users/username/{username}"),
+    op("GET", "/users/search/username"),
+    op("GET", "/users/search/email"),
+    op("GET", "/users"),
+    op("HEAD", "/users/exists/email/{email}"),
+    op("HEAD", "/users/exists/username/{username}"),
+    op("GET", "/users/with-jobs"),
+    op("GET", "/users/job-counts"),
+    // jobs
+    op("POST", "/jobs"),
+    op("POST", "/jobs/{id}/rerun"),
+    op("GET", "/jobs"),
+    op("GET", "/jobs/{id}"),
+    op("PATCH", "/jobs/{id}"),
+    op("DELETE", "/jobs/{id}"),
+    op("GET", "/jobs/search"),
+    op("GET", "/jobs/name/{user_id}"),
+    op("GET", "/jobs/by_admin"),
+    op("GET", "/jobs/state/{state}"),
+    op("GET", "/jobs/recent"),
+    op("GET", "/jobs/failed"),
+    op("PATCH", "/jobs/{id}/running"),
+    op("PATCH", "/jobs/{id}/succeeded"),
+    op("PATCH", "/jobs/{id}/failed"),
+    op("GET", "/jobs/scheduled"),
+    op("GET", "/jobs/cron_due"),
+    op("GET", "/jobs/one-time-ready"),
+    op("GET", "/jobs/stats/admins"),
+    op("GET", "/jobs/active/{worker_id}"),
+    op("GET", "/jobs/assigned/{worker_id}"),
+    op("GET", "/jobs/unassigned"),
+    // assignments
+    op("POST", "/assignments"),
+    op("POST", "/assignments/claim/{worker_id}"),
+    op("GET", "/assignments/{id}"),
+    op("DELETE", "/assignments/{id}"),
+    op("GET", "/assignments/lookup/{job_id}/{worker_id}"),
+    op("GET", "/assignments/by_job/{job_id}"),
+    op("GET", "/assignments/by_worker/{worker_id}"),
+    op("GET", "/assignments/by_worker/range"),
+    op("GET", "/assignments/active"),
+    op("PATCH", "/assignments/{id}/started"),
+    op("PATCH", "/assignments/{id}/finished"),
+    // results
+    op("POST", "/results"),
+    op("GET", "/results"),
+    op("GET", "/results/{id}"),
+    op("DELETE", "/results/{id}"),
+    op("GET", "/results/job/{job_id}"),
+    op("GET", "/results/list/{job_id}"),
+    op("GET", "/results/recent/{job_id}"),
+    op("PATCH", "/results/{id}/stdout"),
+    op("PATCH", "/results/{id}/files"),
+    // metrics
+    op("POST", "/metrics"),
+    op("GET", "/metrics/{id}"),
+    op("DELETE", "/metrics/{id}"),
+    op("GET", "/metrics/by_job/{job_id}"),
+    op("GET", "/metrics/by_worker/{worker_id}"),
+    op("GET", "/metrics/recent/{job_id}"),
+    // archive
+    op("GET", "/archive/jobs"),
+    op("GET", "/archive/jobs/{id}"),
+    op("POST", "/archive/jobs/{id}/restore"),
+    // templates
+    op("POST", "/templates"),
+    op("GET", "/templates/{id}"),
+    op("DELETE", "/templates/{id}"),
+    op("GET", "/templates/user/{user_id}"),
+    op("POST", "/templates/{id}/run"),
+    // quotas
+    op("GET", "/quotas/{user_id}"),
+    op("PUT", "/quotas/{user_id}"),
+    op("DELETE", "/quotas/{user_id}"),
+    // workers
+    op("POST", "/workers"),
+    op("PATCH", "/workers/{id}"),
+    op("GET", "/workers"),
+    op("GET", "/workers/{id}"),
+    op("DELETE", "/workers/{id}"),
+    op("GET", "/workers/admin/{admin_id}"),
+    op("GET", "/workers/label/{label}"),
+    op("GET", "/workers/ip/{ip_address}"),
+    op("GET", "/workers/admin/{admin_id}/list"),
+    op("PUT", "/workers/{id}/last-seen"),
+    // worker status
+    op("POST", "/worker-status"),
+    op("GET", "/worker-status/{id}"),
+    op("DELETE", "/worker-status/{id}"),
+    op("GET", "/worker-status/worker/{worker_id}"),
+    op("PUT", "/worker-status/{id}/status"),
+    op("PUT", "/worker-status/{id}/last-heartbeat"),
+    op("PUT", "/worker-status/{id}/active-job-id"),
+    op("PUT", "/worker-status/{id}/uptime"),
+    op("PUT", "/worker-status/{id}/load-avg"),
+    op("PUT", "/worker-status/{id}/last-error"),
+    // logs
+    op("GET", "/logs"),
+    op("GET", "/logs/retention"),
+    op("PUT", "/logs/retention"),
+    op("DELETE", "/logs/retention/{id}"),
+    // webhooks
+    op("GET", "/webhooks"),
+    op("POST", "/webhooks"),
+    op("PATCH", "/webhooks/{id}"),
+    op("DELETE", "/webhooks/{id}"),
+    op("GET", "/webhooks/{id}/deliveries"),
+    op("POST", "/webhooks/{id}/deliveries/{delivery_id}/redeliver"),
+    // system
+    op("GET", "/metrics"),
+    op("GET", "/openapi.json"),
+    op("GET", "/health"),
+    op("GET", "/health/live"),
+    op("GET", "/health/ready"),
+    // admin
+    op("GET", "/admin/state"),
+    op("POST", "/admin/shutdown"),
+    op("POST", "/admin/restart"),
+    op("POST", "/admin/modules/{module}/restart"),
+    op("POST", "/admin/scheduler/pause"),
+    op("POST", "/admin/scheduler/resume"),
+];
@@ -0,0 +1,24 @@
+use crate::models::{QuotaStatus, UpdateQuota, UserQuota};
+use crate::{Result, SwarmClient};
+
+impl SwarmClient {
+    /// `GET /quotas/<user_id>`, limits in effect and the usage counted against them, own or as admin
+    pub async fn quota_status(&self, user_id: i32) -> Result<QuotaStatus> {
+        Self::send(self.http.get(self.url(&["quotas", &user_id.to_string()]))).await
+    }
+
+    /// `PUT /quotas/<user_id>`, admins only
+    pub async fn set_quota(&self, user_id: i32, update: &UpdateQuota) -> Result<UserQuota> {
+        let url = self.url(&["quotas", &user_id.to_string()]);
+        Self::send(self.http.put(url).json(update)).await
+    }
+
+    /// `DELETE /quotas/<user_id>`, back to the defaults, admins only
+    pub async fn reset_quota(&self, user_id: i32) -> Result<()> {
+        Self::send_empty(
+            self.http
+                .delete(self.url(&["quotas", &user_id.to_string()])),
+        )
+        .await
+    }
+}
@@ -0,0 +1,31 @@
+use serde_json::Value;
+
+use crate::models::{Health, Liveness, Readiness};
+use crate::{Result, SwarmClient};
+
+impl SwarmClient {
+    /// `GET /metrics`, Prometheus text format
+    pub async fn metrics_text(&self) -> Result<String> {
+        Self::send_text(self.http.get(self.url(&["metrics"]))).await
+    }
+
+    /// `GET /openapi.json`, the document this client is checked against
+    pub async fn openapi(&self) -> Result<Value> {
+        Self::send(self.http.get(self.url(&["openapi.json"]))).await
+    }
+
+    /// `GET /health`, schema version of the database next to the one core was built for
+    pub async fn health(&self) -> Result<Health> {
+        Self::send(self.http.get(self.url(&["health"]))).await
+    }
+
+    /// `GET /health/live`, a 503 comes back as `ClientError::Api` with the `Liveness` as body
+    pub async fn health_live(&self) -> Result<Liveness> {
+        Self::send(self.http.get(self.url(&["health", "live"]))).await
+    }
+
+    /// `GET /health/ready`, a 503 comes back as `ClientError::Api` with the `Readiness` as body
+    pub async fn health_ready(&self) -> Result<Readiness> {
+        Self::send(self.http.get(self.url(&["health", "ready"]))).await
+    }
+}
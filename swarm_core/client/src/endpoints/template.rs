@@ -0,0 +1,34 @@
+use crate::models::{CreateTemplateRequest, Job, RunTemplateRequest, TemplateWithParameters};
+use crate::{Result, SwarmClient};
+
+impl SwarmClient {
+    /// `POST /templates`
+    pub async fn create_template(
+        &self,
+        template: &CreateTemplateRequest,
+    ) -> Result<TemplateWithParameters> {
+        Self::send(self.http.post(self.url(&["templates"])).json(template)).await
+    }
+
+    /// `GET /templates/<id>`
+    pub async fn get_template(&self, id: i32) -> Result<TemplateWithParameters> {
+        Self::send(self.http.get(self.url(&["templates", &id.to_string()]))).await
+    }
+
+    /// `DELETE /templates/<id>`
+    pub async fn delete_template(&self, id: i32) -> Result<()> {
+        Self::send_empty(self.http.delete(self.url(&["templates", &id.to_string()]))).await
+    }
+
+    /// `GET /templates/user/<user_id>`
+    pub async fn templates_for_user(&self, user_id: i32) -> Result<Vec<TemplateWithParameters>> {
+        let url = self.url(&["templates", "user", &user_id.to_string()]);
+        Self::send(self.http.get(url)).await
+    }
+
+    /// `POST /templates/<id>/run`, submits the rendered job, quota rules as `create_job`
+    pub async fn run_template(&self, id: i32, run: &RunTemplateRequest) -> Result<Job> {
+        let url = self.url(&["templates", &id.to_string(), "run"]);
+        Self::send(self.http.post(url).json(run)).await
+    }
+}
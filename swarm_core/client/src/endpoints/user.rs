@@ -0,0 +1,100 @@
+use reqwest::StatusCode;
+
+use crate::models::{NewUserRequest, UpdateUserRequest, UserResponse};
+use crate::{ClientError, Result, SwarmClient};
+
+impl SwarmClient {
+    /// `GET /users/<id>`
+    pub async fn get_user(&self, id: i32) -> Result<UserResponse> {
+        Self::send(self.http.get(self.url(&["users", &id.to_string()]))).await
+    }
+
+    /// `POST /users`, does not need a session
+    pub async fn create_user(&self, user: &NewUserRequest) -> Result<UserResponse> {
+        Self::send(self.http.post(self.url(&["users"])).json(user)).await
+    }
+
+    /// `DELETE /users/<id>`
+    pub async fn delete_user(&self, id: i32) -> Result<()> {
+        Self::send_empty(self.http.delete(self.url(&["users", &id.to_string()]))).await
+    }
+
+    /// `PUT /users/<id>`
+    pub async fn update_user(&self, id: i32, update: &UpdateUserRequest) -> Result<UserResponse> {
+        Self::send(
+            self.http
+                .put(self.url(&["users", &id.to_string()]))
+                .json(update),
+        )
+        .await
+    }
+
+    /// `GET /users/email/<email>`
+    pub async fn find_user_by_email(&self, email: &str) -> Result<UserResponse> {
+        Self::send(self.http.get(self.url(&["users", "email", email]))).await
+    }
+
+    /// `GET /users/username/<username>`, None if no such user
+    pub async fn find_user_by_username(&self, username: &str) -> Result<Option<UserResponse>> {
+        Self::send(self.http.get(self.url(&["users", "username", username]))).await
+    }
+
+    /// `GET /users/search/username?q=`, partial match
+    pub async fn search_users_by_username(&self, q: &str) -> Result<Vec<UserResponse>> {
+        let url = self.url(&["users", "search", "username"]);
+        Self::send(self.http.get(url).query(&[("q", q)])).await
+    }
+
+    /// `GET /users/search/email?q=`, partial match
+    pub async fn search_users_by_email(&self, q: &str) -> Result<Vec<UserResponse>> {
+        let url = self.url(&["users", "search", "email"]);
+        Self::send(self.http.get(url).query(&[("q", q)])).await
+    }
+
+    /// `GET /users?page=&limit=`
+    pub async fn list_users(
+        &self,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<Vec<UserResponse>> {
+        let request = self
+            .http
+            .get(self.url(&["users"]))
+            .query(&[("page", page), ("limit", limit)]);
+        Self::send(request).await
+    }
+
+    /// `HEAD /users/exists/email/<email>`
+    pub async fn user_email_exists(&self, email: &str) -> Result<bool> {
+        self.exists(&["users", "exists", "email", email]).await
+    }
+
+    /// `HEAD /users/exists/username/<username>`
+    pub async fn username_exists(&self, username: &str) -> Result<bool> {
+        self.exists(&["users", "exists", "username", username]).await
+    }
+
+    /// `GET /users/with-jobs`, users owning at least one job
+    pub async fn users_with_jobs(&self) -> Result<Vec<UserResponse>> {
+        Self::send(self.http.get(self.url(&["users", "with-jobs"]))).await
+    }
+
+    /// `GET /users/job-counts`, every user with the number of jobs they own
+    pub async fn user_job_counts(&self) -> Result<Vec<(UserResponse, i64)>> {
+        Self::send(self.http.get(self.url(&["users", "job-counts"]))).await
+    }
+
+    /// 204 is true, 404 false, anything else an error
+    async fn exists(&self, segments: &[&str]) -> Result<bool> {
+        let res = self.http.head(self.url(segments)).send().await?;
+        match res.status() {
+            status if status.is_success() => Ok(true),
+            StatusCode::NOT_FOUND => Ok(false),
+            status => Err(ClientError::Api {
+                status,
+                message: status.canonical_reason().unwrap_or("no reason").to_string(),
+                body: serde_json::Value::Null,
+            }),
+        }
+    }
+}
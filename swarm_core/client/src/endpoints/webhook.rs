@@ -0,0 +1,51 @@
+use crate::models::{CreatedWebhook, UpdateWebhook, Webhook, WebhookDelivery, WebhookRequest};
+use crate::{Result, SwarmClient};
+
+impl SwarmClient {
+    /// `GET /webhooks`, webhooks of the logged in user
+    pub async fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        Self::send(self.http.get(self.url(&["webhooks"]))).await
+    }
+
+    /// `POST /webhooks`, the answer is the only time the signing secret is returned
+    pub async fn create_webhook(&self, request: &WebhookRequest) -> Result<CreatedWebhook> {
+        Self::send(self.http.post(self.url(&["webhooks"])).json(request)).await
+    }
+
+    /// `PATCH /webhooks/<id>`
+    pub async fn update_webhook(&self, id: i32, changes: &UpdateWebhook) -> Result<Webhook> {
+        let url = self.url(&["webhooks", &id.to_string()]);
+        Self::send(self.http.patch(url).json(changes)).await
+    }
+
+    /// `DELETE /webhooks/<id>`
+    pub async fn delete_webhook(&self, id: i32) -> Result<()> {
+        Self::send_empty(self.http.delete(self.url(&["webhooks", &id.to_string()]))).await
+    }
+
+    /// `GET /webhooks/<id>/deliveries?limit=&offset=`, newest first
+    pub async fn webhook_deliveries(
+        &self,
+        id: i32,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<Vec<WebhookDelivery>> {
+        let request = self
+            .http
+            .get(self.url(&["webhooks", &id.to_string(), "deliveries"]))
+            .query(&[("limit", limit), ("offset", offset)]);
+        Self::send(request).await
+    }
+
+    /// `POST /webhooks/<id>/deliveries/<delivery_id>/redeliver`, queues the delivery again
+    pub async fn redeliver(&self, id: i32, delivery_id: i32) -> Result<WebhookDelivery> {
+        let url = self.url(&[
+            "webhooks",
+            &id.to_string(),
+            "deliveries",
+            &delivery_id.to_string(),
+            "redeliver",
+        ]);
+        Self::send(self.http.post(url)).await
+    }
+}
@@ -0,0 +1,72 @@
+use chrono::NaiveDateTime;
+
+use crate::models::{LastSeenPayload, ListParams, NewWorker, Page, Worker};
+use crate::{Result, SwarmClient};
+
+impl SwarmClient {
+    /// `POST /workers`
+    pub async fn create_worker(&self, worker: &NewWorker) -> Result<Worker> {
+        Self::send(self.http.post(self.url(&["workers"])).json(worker)).await
+    }
+
+    /// `PATCH /workers/<id>`, replaces the stored worker with `worker`
+    pub async fn update_worker(&self, id: i32, worker: &Worker) -> Result<Worker> {
+        let url = self.url(&["workers", &id.to_string()]);
+        Self::send(self.http.patch(url).json(worker)).await
+    }
+
+    /// `GET /workers`, one page of workers
+    pub async fn list_workers(&self, params: &ListParams) -> Result<Page<Worker>> {
+        Self::send(self.http.get(self.url(&["workers"])).query(params)).await
+    }
+
+    /// `GET /workers/<id>`
+    pub async fn get_worker(&self, id: i32) -> Result<Worker> {
+        Self::send(self.http.get(self.url(&["workers", &id.to_string()]))).await
+    }
+
+    /// `DELETE /workers/<id>`
+    pub async fn delete_worker(&self, id: i32) -> Result<()> {
+        Self::send_empty(self.http.delete(self.url(&["workers", &id.to_string()]))).await
+    }
+
+    /// `GET /workers/admin/<admin_id>`, workers owned by the user
+    pub async fn workers_for_admin(&self, admin_id: i32) -> Result<Vec<Worker>> {
+        let url = self.url(&["workers", "admin", &admin_id.to_string()]);
+        Self::send(self.http.get(url)).await
+    }
+
+    /// `GET /workers/label/<label>`
+    pub async fn find_worker_by_label(&self, label: &str) -> Result<Option<Worker>> {
+        Self::send(self.http.get(self.url(&["workers", "label", label]))).await
+    }
+
+    /// `GET /workers/ip/<ip_address>`
+    pub async fn find_worker_by_ip(&self, ip_address: &str) -> Result<Option<Worker>> {
+        Self::send(self.http.get(self.url(&["workers", "ip", ip_address]))).await
+    }
+
+    /// `GET /workers/admin/<admin_id>/list?page=&limit=`
+    pub async fn list_workers_for_admin(
+        &self,
+        admin_id: i32,
+        page: Option<u32>,
+        limit: Option<u32>,
+    ) -> Result<Vec<Worker>> {
+        let request = self
+            .http
+            .get(self.url(&["workers", "admin", &admin_id.to_string(), "list"]))
+            .query(&[("page", page), ("limit", limit)]);
+        Self::send(request).await
+    }
+
+    /// `PUT /workers/<id>/last-seen`
+    pub async fn update_worker_last_seen(
+        &self,
+        id: i32,
+        last_seen_at: NaiveDateTime,
+    ) -> Result<Worker> {
+        let url = self.url(&["workers", &id.to_string(), "last-seen"]);
+        Self::send(self.http.put(url).json(&LastSeenPayload { last_seen_at })).await
+    }
+}
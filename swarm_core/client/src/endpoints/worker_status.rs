@@ -0,0 +1,88 @@
+use crate::enums::WorkerStatusEnum;
+use crate::models::{
+    LastErrorPayload, LoadAvgPayload, NewWorkerStatus, UpdateActiveJobId, UptimePayload,
+    WorkerStatus,
+};
+use crate::{Result, SwarmClient};
+
+impl SwarmClient {
+    /// `POST /worker-status`
+    pub async fn create_worker_status(&self, status: &NewWorkerStatus) -> Result<WorkerStatus> {
+        Self::send(self.http.post(self.url(&["worker-status"])).json(status)).await
+    }
+
+    /// `GET /worker-status/<id>`
+    pub async fn get_worker_status(&self, id: i32) -> Result<WorkerStatus> {
+        Self::send(self.http.get(self.url(&["worker-status", &id.to_string()]))).await
+    }
+
+    /// `DELETE /worker-status/<id>`
+    pub async fn delete_worker_status(&self, id: i32) -> Result<()> {
+        Self::send_empty(
+            self.http
+                .delete(self.url(&["worker-status", &id.to_string()])),
+        )
+        .await
+    }
+
+    /// `GET /worker-status/worker/<worker_id>`, None if the worker never reported in
+    pub async fn status_for_worker(&self, worker_id: i32) -> Result<Option<WorkerStatus>> {
+        let url = self.url(&["worker-status", "worker", &worker_id.to_string()]);
+        Self::send(self.http.get(url)).await
+    }
+
+    /// `PUT /worker-status/<id>/status`
+    pub async fn update_worker_status(
+        &self,
+        id: i32,
+        status: WorkerStatusEnum,
+    ) -> Result<WorkerStatus> {
+        let url = self.url(&["worker-status", &id.to_string(), "status"]);
+        Self::send(self.http.put(url).json(&status)).await
+    }
+
+    /// `PUT /worker-status/<id>/last-heartbeat`, sets the heartbeat to now
+    pub async fn touch_worker_heartbeat(&self, id: i32) -> Result<WorkerStatus> {
+        let url = self.url(&["worker-status", &id.to_string(), "last-heartbeat"]);
+        Self::send(self.http.put(url)).await
+    }
+
+    /// `PUT /worker-status/<id>/active-job-id`
+    pub async fn update_active_job(
+        &self,
+        id: i32,
+        active_job_id: Option<i32>,
+    ) -> Result<WorkerStatus> {
+        let url = self.url(&["worker-status", &id.to_string(), "active-job-id"]);
+        Self::send(self.http.put(url).json(&UpdateActiveJobId { active_job_id })).await
+    }
+
+    /// `PUT /worker-status/<id>/uptime`
+    pub async fn update_uptime(&self, id: i32, uptime: Option<i32>) -> Result<WorkerStatus> {
+        let url = self.url(&["worker-status", &id.to_string(), "uptime"]);
+        Self::send(self.http.put(url).json(&UptimePayload { uptime })).await
+    }
+
+    /// `PUT /worker-status/<id>/load-avg`
+    pub async fn update_load_avg(
+        &self,
+        id: i32,
+        load_avg: Option<Vec<f32>>,
+    ) -> Result<WorkerStatus> {
+        let url = self.url(&["worker-status", &id.to_string(), "load-avg"]);
+        Self::send(self.http.put(url).json(&LoadAvgPayload { load_avg })).await
+    }
+
+    /// `PUT /worker-status/<id>/last-error`
+    pub async fn update_last_error(
+        &self,
+        id: i32,
+        last_error: Option<&str>,
+    ) -> Result<WorkerStatus> {
+        let body = LastErrorPayload {
+            last_error: last_error.map(String::from),
+        };
+        let url = self.url(&["worker-status", &id.to_string(), "last-error"]);
+        Self::send(self.http.put(url).json(&body)).await
+    }
+}
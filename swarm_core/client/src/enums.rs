@@ -0,0 +1,154 @@
+//! Enums as they travel over the wire, one per `ToSchema` enum of `common::enums`
+//!
+//! Variants are serialized by name, so these only need to agree with core on the names,
+//! `core-api` checks that against the OpenAPI document in its tests.
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Declares a unit enum with `VARIANTS`, `as_str`, `Display` and `FromStr`
+macro_rules! wire_enum {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        pub enum $name {
+            $($variant),+
+        }
+
+        impl $name {
+            /// Every variant name, in declaration order
+            pub const VARIANTS: &'static [&'static str] = &[$(stringify!($variant)),+];
+
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => stringify!($variant)),+
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $(stringify!($variant) => Ok($name::$variant),)+
+                    other => Err(format!("unknown {} '{}'", stringify!($name), other)),
+                }
+            }
+        }
+    };
+}
+
+wire_enum!(
+    /// How the image of a job is fetched
+    ImageFormatEnum { Tarball, DockerRegistry }
+);
+
+wire_enum!(
+    /// Lifecycle state of a job
+    JobStateEnum { Submitted, Queued, Running, Completed, Failed }
+);
+
+impl JobStateEnum {
+    /// Completed or Failed, the job will not change anymore
+    pub fn is_finished(&self) -> bool {
+        matches!(self, JobStateEnum::Completed | JobStateEnum::Failed)
+    }
+}
+
+wire_enum!(
+    /// Where the output of a job ends up
+    OutputTypeEnum { Stdout, Files }
+);
+
+wire_enum!(
+    /// Run once or on a cron expression
+    ScheduleTypeEnum { Once, Cron }
+);
+
+wire_enum!(
+    /// Where the value of a template parameter ends up in the rendered job
+    TemplateParamKindEnum { ImageTag, Env, Text }
+);
+
+wire_enum!(
+    /// Liveness of a worker as tracked by the dispatcher
+    WorkerStatusEnum { Idle, Busy, Offline, Unreachable }
+);
+
+wire_enum!(
+    /// Operating system a worker runs on
+    OSEnum { Linux, Windows, MacOSEnum, Any }
+);
+
+wire_enum!(
+    /// Severity of a log entry
+    LogLevelEnum { Info, Success, Warning, Error, Fatal }
+);
+
+wire_enum!(
+    /// What a log entry records
+    LogActionEnum {
+        ClientConnected,
+        JobSubmitted,
+        JobCompleted,
+        JobPreempted,
+        SystemStarted,
+        SystemShutdown,
+        Custom,
+    }
+);
+
+wire_enum!(
+    /// Core module that wrote a log entry
+    SystemModuleEnum {
+        Dispatcher,
+        Harvester,
+        Hibernator,
+        Receiver,
+        Scheduler,
+        TaskArchive,
+        Notifier,
+        Supervisor,
+    }
+);
+
+wire_enum!(
+    /// Lifecycle event a webhook can subscribe to
+    WebhookEventEnum {
+        JobSubmitted,
+        JobRunning,
+        JobCompleted,
+        JobFailed,
+        JobRequeued,
+        WorkerUnreachable,
+    }
+);
+
+wire_enum!(
+    /// Where a webhook delivery stands
+    DeliveryStatusEnum { Pending, Delivered, Failed }
+);
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for name in JobStateEnum::VARIANTS {
+            let state: JobStateEnum = name.parse().unwrap();
+            assert_eq!(state.as_str(), *name);
+            assert_eq!(serde_json::to_value(state).unwrap(), *name);
+        }
+        assert!("Paused".parse::<JobStateEnum>().is_err());
+    }
+}
@@ -0,0 +1,234 @@
+//! Typed HTTP client of core-api, shared by the consumer, the worker and the core-api tests
+//!
+//! Every route core mounts has one method on `SwarmClient`, grouped by the routes file it
+//! lives in, and one entry in `ENDPOINTS`. core-api compares `ENDPOINTS` against its OpenAPI
+//! document in its tests, so a route added, moved or removed on the server fails there first.
+//! Requests go to `<base url>/api/v1/...`, failed ones decode core's `{error, code, details}` envelope.
+//!
+//! ```no_run
+//! # async fn run() -> Result<(), swarm_client::ClientError> {
+//! use swarm_client::{models::Credentials, SwarmClient};
+//!
+//! let anonymous = SwarmClient::new("http://127.0.0.1:8000")?;
+//! let session = anonymous
+//!     .login(&Credentials { username: "admin".into(), password: "secret".into() })
+//!     .await?;
+//! let client = anonymous.authenticated(&session.token)?;
+//! let job = client.get_job(1).await?;
+//! # Ok(())
+//! # }
+//! ```
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use reqwest::{Client, RequestBuilder, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use thiserror::Error;
+
+mod endpoints;
+pub mod enums;
+pub mod models;
+pub mod validation;
+
+pub use endpoints::{Endpoint, ENDPOINTS};
+
+/// Version prefix core mounts every route below, `ENDPOINTS` are relative to it
+pub const API_BASE: &str = "/api/v1";
+
+/// Everything that can go wrong talking to core
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("invalid base url '{0}'")]
+    BaseUrl(String),
+    #[error("invalid session token")]
+    Token,
+    // connection refused, timeout or a body that did not decode
+    #[error("request failed: {0}")]
+    Transport(#[from] reqwest::Error),
+    // core answered with a non 2xx status, `body` is the error envelope if it was JSON
+    #[error("{status}: {message}")]
+    Api {
+        status: StatusCode,
+        message: String,
+        body: Value,
+    },
+}
+
+impl ClientError {
+    /// Status core answered with, None if the request never got an answer
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ClientError::Api { status, .. } => Some(*status),
+            ClientError::Transport(e) => e.status(),
+            _ => None,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(StatusCode::NOT_FOUND)
+    }
+
+    /// Core refused a state change the job state machine does not allow
+    pub fn is_conflict(&self) -> bool {
+        self.status() == Some(StatusCode::CONFLICT)
+    }
+
+    /// Machine readable `code` of the envelope, e.g. `not_found` or `quota_exceeded`
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            ClientError::Api { body, .. } => body.get("code").and_then(Value::as_str),
+            _ => None,
+        }
+    }
+
+    /// Fields core rejected, set on `invalid_input` answers of validated bodies
+    pub fn invalid_fields(&self) -> Option<validation::ValidationErrors> {
+        match self {
+            ClientError::Api { body, .. } => body
+                .get("details")
+                .and_then(|details| serde_json::from_value(details.clone()).ok()),
+            _ => None,
+        }
+    }
+
+    /// Seconds to wait before submitting again, set when a quota was exceeded
+    pub fn retry_after_secs(&self) -> Option<i64> {
+        match self {
+            ClientError::Api { body, .. } => body
+                .pointer("/details/retry_after_secs")
+                .and_then(Value::as_i64),
+            _ => None,
+        }
+    }
+}
+
+pub type Result<T, E = ClientError> = std::result::Result<T, E>;
+
+/// Client of one core instance, cheap to clone
+#[derive(Debug, Clone)]
+pub struct SwarmClient {
+    http: Client,
+    base_url: Url,
+}
+
+impl SwarmClient {
+    /// Anonymous client, enough for `login`, `create_user`, `metrics` and `openapi`
+    /// # Arguments
+    /// * `base_url` - Address of core including the port, e.g. `http://127.0.0.1:8000`
+    pub fn new(base_url: &str) -> Result<Self> {
+        Self::from_http(Client::new(), base_url)
+    }
+
+    /// Client sending `token` as bearer on every request
+    /// # Arguments
+    /// * `base_url` - Address of core including the port
+    /// * `token` - Session token returned by `login`
+    pub fn with_token(base_url: &str, token: &str) -> Result<Self> {
+        Self::from_http(authed_http(token)?, base_url)
+    }
+
+    /// Wraps an already configured reqwest client, e.g. with custom timeouts
+    pub fn from_http(http: Client, base_url: &str) -> Result<Self> {
+        let base_url = Url::parse(base_url.trim_end_matches('/'))
+            .map_err(|_| ClientError::BaseUrl(base_url.to_string()))?;
+        if base_url.cannot_be_a_base() {
+            return Err(ClientError::BaseUrl(base_url.to_string()));
+        }
+        Ok(SwarmClient { http, base_url })
+    }
+
+    /// Same core, authenticated with `token`
+    pub fn authenticated(&self, token: &str) -> Result<Self> {
+        Ok(SwarmClient {
+            http: authed_http(token)?,
+            base_url: self.base_url.clone(),
+        })
+    }
+
+    /// The underlying reqwest client, for calls the typed methods do not cover
+    pub fn http(&self) -> &Client {
+        &self.http
+    }
+
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// Url of an API route, `segments` are appended below `API_BASE` and percent-encoded
+    pub fn url(&self, segments: &[&str]) -> Url {
+        let mut url = self.base_url.clone();
+        if let Ok(mut path) = url.path_segments_mut() {
+            path.pop_if_empty()
+                .extend(API_BASE.split('/').filter(|s| !s.is_empty()))
+                .extend(segments);
+        }
+        url
+    }
+
+    /// Sends the request and decodes a JSON answer
+    pub(crate) async fn send<T: DeserializeOwned>(request: RequestBuilder) -> Result<T> {
+        Ok(check(request.send().await?).await?.json().await?)
+    }
+
+    /// Sends the request and ignores the body of a 2xx answer
+    pub(crate) async fn send_empty(request: RequestBuilder) -> Result<()> {
+        check(request.send().await?).await?;
+        Ok(())
+    }
+
+    /// Sends the request and returns the body of a 2xx answer as text
+    pub(crate) async fn send_text(request: RequestBuilder) -> Result<String> {
+        Ok(check(request.send().await?).await?.text().await?)
+    }
+}
+
+fn authed_http(token: &str) -> Result<Client> {
+    let mut headers = HeaderMap::new();
+    let value =
+        HeaderValue::from_str(&format!("Bearer {}", token)).map_err(|_| ClientError::Token)?;
+    headers.insert(header::AUTHORIZATION, value);
+    Ok(Client::builder().default_headers(headers).build()?)
+}
+
+/// Turns a non 2xx answer into `ClientError::Api`, the message is the `error` field if any
+async fn check(res: reqwest::Response) -> Result<reqwest::Response> {
+    let status = res.status();
+    if status.is_success() {
+        return Ok(res);
+    }
+    let text = res.text().await.unwrap_or_default();
+    let body: Value = serde_json::from_str(&text).unwrap_or(Value::Null);
+    let message = match body.get("error").and_then(Value::as_str) {
+        Some(error) => error.to_string(),
+        None if !text.trim().is_empty() => text.trim().to_string(),
+        None => status.canonical_reason().unwrap_or("no reason").to_string(),
+    };
+    Err(ClientError::Api {
+        status,
+        message,
+        body,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_encodes_segments() {
+        let client = SwarmClient::new("http://core:8000/").unwrap();
+        assert_eq!(
+            client.url(&["users", "email", "a b@x.io"]).as_str(),
+            "http://core:8000/api/v1/users/email/a%20b@x.io"
+        );
+        assert_eq!(client.url(&["jobs", "7"]).path(), "/api/v1/jobs/7");
+    }
+
+    #[test]
+    fn base_url_must_be_absolute() {
+        assert!(matches!(
+            SwarmClient::new("core"),
+            Err(ClientError::BaseUrl(_))
+        ));
+    }
+}
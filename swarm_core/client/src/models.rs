@@ -0,0 +1,654 @@
+//! Request and response bodies of core-api, field for field what the routes (de)serialize
+//!
+//! These mirror the `ToSchema` models of `common` without pulling in diesel and rocket,
+//! the names match the schema names of the OpenAPI document. core-api's tests decode a body with
+//! every documented property into each of them, a field missing on either side fails there.
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::enums::{
+    DeliveryStatusEnum, ImageFormatEnum, JobStateEnum, LogActionEnum, LogLevelEnum, OSEnum,
+    OutputTypeEnum, ScheduleTypeEnum, SystemModuleEnum, TemplateParamKindEnum, WebhookEventEnum,
+    WorkerStatusEnum,
+};
+
+// ========== Listing ==========
+
+/// One page of `GET /jobs`, `/workers`, `/results`, `/logs` or `/archive/jobs`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    // pass as `cursor` to get the next page, None on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Query of the cursor paginated list endpoints, every field is optional
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ListParams {
+    // comma separated clauses, e.g. `state:Failed,created>2024-01-01`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+    // one field, `-` prefix for descending
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    // `next_cursor` of the previous page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+// ========== Users ==========
+
+/// `POST /login`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Credentials {
+    pub username: String,
+    pub password: String,
+}
+
+/// Answer of `POST /login`, sent back as bearer token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionToken {
+    pub token: String,
+}
+
+/// A user without its password hash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserResponse {
+    pub id: i32,
+    pub username: String,
+    pub email: String,
+    pub created_at: NaiveDateTime,
+}
+
+/// `POST /users`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewUserRequest {
+    pub username: String,
+    pub email: String,
+    pub password: String,
+}
+
+/// `PUT /users/<id>`, the password is only changed when given
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateUserRequest {
+    pub username: String,
+    pub email: String,
+    pub password: Option<String>,
+}
+
+// ========== Jobs ==========
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: i32,
+    pub user_id: i32,
+    pub job_name: String,
+    pub image_url: String,
+    pub image_format: ImageFormatEnum,
+    pub docker_flags: Option<Vec<Option<String>>>,
+    pub output_type: OutputTypeEnum,
+    pub output_paths: Option<Vec<Option<String>>>,
+    pub schedule_type: ScheduleTypeEnum,
+    pub cron_expression: Option<String>,
+    pub notes: Option<String>,
+    pub state: JobStateEnum,
+    // set when the job failed
+    pub error_message: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    // higher is admitted first
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// `POST /jobs`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewJob {
+    pub user_id: i32,
+    pub job_name: String,
+    pub image_url: String,
+    pub image_format: ImageFormatEnum,
+    pub docker_flags: Option<Vec<Option<String>>>,
+    pub output_type: OutputTypeEnum,
+    pub output_paths: Option<Vec<Option<String>>>,
+    pub schedule_type: ScheduleTypeEnum,
+    pub cron_expression: Option<String>,
+    pub notes: Option<String>,
+    // no `state`, core starts every job `Submitted`
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// `PATCH /jobs/<id>/failed`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FailJobPayload {
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobAssignment {
+    pub id: i32,
+    pub job_id: i32,
+    pub worker_id: i32,
+    pub assigned_at: NaiveDateTime,
+    pub started_at: Option<NaiveDateTime>,
+    pub finished_at: Option<NaiveDateTime>,
+}
+
+/// `POST /assignments`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewJobAssignment {
+    pub job_id: i32,
+    pub worker_id: i32,
+}
+
+/// `PATCH /assignments/<id>/started`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartedAtPayload {
+    pub started_at: NaiveDateTime,
+}
+
+/// `PATCH /assignments/<id>/finished`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FinishedAtPayload {
+    pub finished_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobResult {
+    pub id: i32,
+    pub job_id: i32,
+    pub stdout: Option<String>,
+    pub files: Option<Vec<Option<String>>>,
+    pub saved_at: NaiveDateTime,
+}
+
+/// `POST /results`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewJobResult {
+    pub job_id: i32,
+    pub stdout: Option<String>,
+    pub files: Option<Vec<Option<String>>>,
+}
+
+/// `PATCH /results/<id>/stdout`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateStdoutPayload {
+    pub stdout: Option<String>,
+}
+
+/// `PATCH /results/<id>/files`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateFilesPayload {
+    pub files: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobMetric {
+    pub id: i32,
+    pub job_id: i32,
+    pub worker_id: i32,
+    pub duration_sec: Option<i32>,
+    pub cpu_usage_pct: Option<f32>,
+    pub mem_usage_mb: Option<f32>,
+    pub exit_code: Option<i32>,
+    pub timestamp: NaiveDateTime,
+}
+
+/// `POST /metrics`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewJobMetric {
+    pub job_id: i32,
+    pub worker_id: i32,
+    pub duration_sec: Option<i32>,
+    pub cpu_usage_pct: Option<f32>,
+    pub mem_usage_mb: Option<f32>,
+    pub exit_code: Option<i32>,
+}
+
+// ========== Archive ==========
+
+/// Summary of a finished job moved out of `/jobs`, `id` is the id the job had
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobArchive {
+    pub id: i32,
+    pub user_id: i32,
+    pub job_name: String,
+    pub image_url: String,
+    pub state: JobStateEnum,
+    pub error_message: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub finished_at: NaiveDateTime,
+    pub result_count: i32,
+    pub archived_at: NaiveDateTime,
+}
+
+/// Answer of `GET /archive/jobs/<id>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedJob {
+    #[serde(flatten)]
+    pub summary: JobArchive,
+    // {"job", "results", "metrics", "assignments", "logs"}, rows as they were in their tables
+    pub data: Value,
+}
+
+// ========== Templates ==========
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobTemplate {
+    pub id: i32,
+    pub user_id: i32,
+    pub name: String,
+    // may hold `{{param}}` placeholders
+    pub image_url: String,
+    pub image_format: ImageFormatEnum,
+    pub docker_flags: Option<Vec<Option<String>>>,
+    pub output_type: OutputTypeEnum,
+    pub output_paths: Option<Vec<Option<String>>>,
+    pub schedule_type: ScheduleTypeEnum,
+    pub cron_expression: Option<String>,
+    pub notes: Option<String>,
+    pub priority: i32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Template part of `POST /templates`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewJobTemplate {
+    pub user_id: i32,
+    pub name: String,
+    pub image_url: String,
+    pub image_format: ImageFormatEnum,
+    pub docker_flags: Option<Vec<Option<String>>>,
+    pub output_type: OutputTypeEnum,
+    pub output_paths: Option<Vec<Option<String>>>,
+    pub schedule_type: ScheduleTypeEnum,
+    pub cron_expression: Option<String>,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateParameter {
+    pub id: i32,
+    pub template_id: i32,
+    // Env variable name or `{{name}}` placeholder
+    pub name: String,
+    pub kind: TemplateParamKindEnum,
+    // used when no override is given
+    pub default_value: Option<String>,
+    // run fails without a value
+    pub required: bool,
+}
+
+/// Parameter as declared when creating a template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateParameterSpec {
+    pub name: String,
+    pub kind: TemplateParamKindEnum,
+    #[serde(default)]
+    pub default_value: Option<String>,
+    pub required: bool,
+}
+
+impl From<TemplateParameter> for TemplateParameterSpec {
+    fn from(p: TemplateParameter) -> Self {
+        TemplateParameterSpec {
+            name: p.name,
+            kind: p.kind,
+            default_value: p.default_value,
+            required: p.required,
+        }
+    }
+}
+
+/// `POST /templates`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateTemplateRequest {
+    #[serde(flatten)]
+    pub template: NewJobTemplate,
+    #[serde(default)]
+    pub parameters: Vec<TemplateParameterSpec>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateWithParameters {
+    pub template: JobTemplate,
+    pub parameters: Vec<TemplateParameter>,
+}
+
+/// `POST /templates/<id>/run`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RunTemplateRequest {
+    #[serde(default)]
+    pub job_name: Option<String>,
+    // parameter name → value, replaces the defaults
+    #[serde(default)]
+    pub overrides: HashMap<String, String>,
+}
+
+// ========== Quotas ==========
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserQuota {
+    pub id: i32,
+    pub user_id: i32,
+    // Submitted + Queued jobs allowed at once
+    pub max_queued_jobs: i32,
+    // jobs allowed to run concurrently
+    pub max_running_jobs: i32,
+    // job submissions allowed per minute
+    pub max_submissions_per_minute: i32,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaUsage {
+    pub queued_jobs: i64,
+    pub running_jobs: i64,
+    pub submissions_last_minute: i64,
+    pub oldest_submission_in_window: Option<NaiveDateTime>,
+}
+
+/// Answer of `GET /quotas/<user_id>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaStatus {
+    pub quota: UserQuota,
+    pub usage: QuotaUsage,
+}
+
+/// `PUT /quotas/<user_id>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateQuota {
+    pub max_queued_jobs: i32,
+    pub max_running_jobs: i32,
+    pub max_submissions_per_minute: i32,
+}
+
+// ========== Workers ==========
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Worker {
+    pub id: i32,
+    // owning user
+    pub user_id: i32,
+    pub label: String,
+    pub ip_address: String,
+    pub hostname: String,
+    pub ssh_user: String,
+    pub ssh_key: String,
+    pub docker_version: String,
+    pub arch: String,
+    pub os: OSEnum,
+    pub tags: Option<Vec<Option<String>>>,
+    pub created_at: NaiveDateTime,
+    pub last_seen_at: Option<NaiveDateTime>,
+}
+
+/// `POST /workers`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewWorker {
+    pub user_id: i32,
+    pub label: String,
+    pub ip_address: String,
+    pub hostname: String,
+    pub ssh_user: String,
+    pub ssh_key: String,
+    pub docker_version: String,
+    pub arch: String,
+    pub os: OSEnum,
+    pub tags: Option<Vec<Option<String>>>,
+}
+
+/// `PUT /workers/<id>/last-seen`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LastSeenPayload {
+    pub last_seen_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerStatus {
+    pub id: i32,
+    pub worker_id: i32,
+    pub status: WorkerStatusEnum,
+    pub last_heartbeat: Option<NaiveDateTime>,
+    // job currently running on the worker
+    pub active_job_id: Option<i32>,
+    pub uptime_sec: Option<i32>,
+    pub load_avg: Option<Vec<Option<f32>>>,
+    pub last_error: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+/// `POST /worker-status`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewWorkerStatus {
+    pub worker_id: i32,
+    pub status: WorkerStatusEnum,
+    pub last_heartbeat: Option<NaiveDateTime>,
+    pub active_job_id: Option<i32>,
+    pub uptime_sec: Option<i32>,
+    pub load_avg: Option<Vec<Option<f32>>>,
+    pub last_error: Option<String>,
+}
+
+/// `PUT /worker-status/<id>/active-job-id`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateActiveJobId {
+    pub active_job_id: Option<i32>,
+}
+
+/// `PUT /worker-status/<id>/uptime`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UptimePayload {
+    pub uptime: Option<i32>,
+}
+
+/// `PUT /worker-status/<id>/load-avg`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadAvgPayload {
+    pub load_avg: Option<Vec<f32>>,
+}
+
+/// `PUT /worker-status/<id>/last-error`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LastErrorPayload {
+    pub last_error: Option<String>,
+}
+
+// ========== Logs ==========
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub id: i32,
+    pub created_at: NaiveDateTime,
+    pub level: LogLevelEnum,
+    pub module: SystemModuleEnum,
+    pub action: LogActionEnum,
+    pub expires_at: NaiveDateTime,
+    pub client_connected_payload: Option<ClientConnectedPayload>,
+    pub job_submitted_payload: Option<JobSubmittedPayload>,
+    pub job_completed_payload: Option<JobCompletedPayload>,
+    pub custom_msg: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientConnectedPayload {
+    pub ip: String,
+    pub username: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobSubmittedPayload {
+    pub job_id: i32,
+    pub from_module: SystemModuleEnum,
+    pub to_module: SystemModuleEnum,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobCompletedPayload {
+    pub job_id: i32,
+    pub success: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogRetentionPolicy {
+    pub id: i32,
+    // None matches every level, module or action
+    pub level: Option<LogLevelEnum>,
+    pub module: Option<SystemModuleEnum>,
+    pub action: Option<LogActionEnum>,
+    pub retention_minutes: i64,
+    pub updated_at: NaiveDateTime,
+}
+
+/// `PUT /logs/retention`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewLogRetentionPolicy {
+    pub level: Option<LogLevelEnum>,
+    pub module: Option<SystemModuleEnum>,
+    pub action: Option<LogActionEnum>,
+    pub retention_minutes: i64,
+}
+
+// ========== Webhooks ==========
+
+/// A webhook, the signing secret is only ever returned on creation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: i32,
+    pub user_id: i32,
+    pub url: String,
+    pub events: Vec<WebhookEventEnum>,
+    pub active: bool,
+    pub created_at: NaiveDateTime,
+}
+
+/// Answer of `POST /webhooks`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedWebhook {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    pub secret: String,
+}
+
+/// `POST /webhooks`, a secret is generated when none is given
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookRequest {
+    pub url: String,
+    pub events: Vec<WebhookEventEnum>,
+    pub secret: Option<String>,
+}
+
+/// `PATCH /webhooks/<id>`, only the given fields change
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateWebhook {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<WebhookEventEnum>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: i32,
+    pub webhook_id: i32,
+    pub event: WebhookEventEnum,
+    pub payload: Value,
+    pub status: DeliveryStatusEnum,
+    pub attempts: i32,
+    pub response_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub next_attempt_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+    pub delivered_at: Option<NaiveDateTime>,
+}
+
+// ========== System ==========
+
+/// Answer of `GET /health`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Health {
+    pub schema_version: Option<String>,
+    pub expected_schema_version: String,
+    pub schema_up_to_date: bool,
+}
+
+/// One module task of core, as reported by the health routes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleHealth {
+    // dispatcher, harvester, hibernator, logger, notifier, reciever, scheduler, task_archive, rocket
+    pub module: String,
+    // running, stopped or failed
+    pub status: String,
+    pub last_pulse: Option<NaiveDateTime>,
+    pub stalled: bool,
+    pub restarts: u32,
+    pub last_error: Option<String>,
+}
+
+/// Answer of `GET /health/live`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Liveness {
+    pub live: bool,
+    pub modules: Vec<ModuleHealth>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DependencyCheck {
+    pub name: String,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+/// Answer of `GET /health/ready`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Readiness {
+    pub ready: bool,
+    pub dependencies: Vec<DependencyCheck>,
+    pub modules: Vec<ModuleHealth>,
+}
+
+// ========== Admin ==========
+
+/// Answer of `GET /admin/state`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoreState {
+    pub scheduler_paused: bool,
+    pub modules: Vec<ModuleHealth>,
+    // module name -> its state, dispatcher and logger, missing if they did not answer in time
+    pub dumps: HashMap<String, Value>,
+}
+
+/// Answer of the admin lifecycle routes, the command runs after it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlAck {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchedulerState {
+    pub paused: bool,
+}
+
+// ========== Errors ==========
+
+/// Body next to most 4xx and 5xx answers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorBody {
+    pub error: String,
+    // machine readable, e.g. `not_found` or `quota_exceeded`
+    pub code: String,
+    // extra fields of some codes, e.g. the invalid `fields` of `invalid_input`
+    #[serde(default)]
+    pub details: Option<Value>,
+}
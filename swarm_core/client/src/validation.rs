@@ -0,0 +1,44 @@
+//! The checks `POST /jobs` and `POST /workers` run, for callers that want to report problems before submitting
+//!
+//! The rules come from `swarm-validation`, the same crate `common::validation` runs them from,
+//! so both sides agree on every field and message. core still validates every request itself.
+use swarm_validation::{validate_job, validate_worker, JobFields, WorkerFields};
+
+use crate::enums::{ImageFormatEnum, OutputTypeEnum, ScheduleTypeEnum};
+use crate::models::{NewJob, NewWorker};
+
+pub use swarm_validation::{
+    is_hostname, is_image_reference, is_image_tag, parse_cron, FieldError, ValidationErrors,
+    MAX_NAME_LEN,
+};
+
+impl NewJob {
+    /// Check the body like core does on `POST /jobs`
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        validate_job(&JobFields {
+            job_name: &self.job_name,
+            image_url: &self.image_url,
+            from_registry: self.image_format == ImageFormatEnum::DockerRegistry,
+            docker_flags: &self.docker_flags,
+            output_files: self.output_type == OutputTypeEnum::Files,
+            output_paths: &self.output_paths,
+            cron_schedule: self.schedule_type == ScheduleTypeEnum::Cron,
+            cron_expression: self.cron_expression.as_deref(),
+        })
+    }
+}
+
+impl NewWorker {
+    /// Check the body like core does on `POST /workers`
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        validate_worker(&WorkerFields {
+            label: &self.label,
+            ip_address: &self.ip_address,
+            hostname: &self.hostname,
+            ssh_user: &self.ssh_user,
+            docker_version: &self.docker_version,
+            arch: &self.arch,
+            tags: &self.tags,
+        })
+    }
+}
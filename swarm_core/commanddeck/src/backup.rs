@@ -0,0 +1,38 @@
+//! `commanddeck backup` and `commanddeck restore`, the file format lives in `common::backup`
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use common::backup::{create_backup, default_backup_path, restore_backup, RestoreScope};
+
+/// Directory of backups when no file is given, shared with the `db_backup` compose service
+const DEFAULT_BACKUP_DIR: &str = "backups";
+
+/// Write a backup and print what it holds
+pub async fn backup(output: Option<PathBuf>) -> anyhow::Result<()> {
+    let path = output.unwrap_or_else(|| default_backup_path(Path::new(DEFAULT_BACKUP_DIR)));
+    let counts = create_backup(&path).await?;
+    println!("Backup written to {}", path.display());
+    print_counts(&counts);
+    Ok(())
+}
+
+/// Restore a backup, everything or one user's jobs, and print what was inserted
+pub async fn restore(file: &Path, user: Option<i32>, replace: bool) -> anyhow::Result<()> {
+    let scope = match user {
+        Some(user_id) => RestoreScope::User(user_id),
+        None => RestoreScope::All { replace },
+    };
+    let counts = restore_backup(file, scope).await?;
+    match user {
+        Some(user_id) => println!("Restored user {} from {}", user_id, file.display()),
+        None => println!("Restored {}", file.display()),
+    }
+    print_counts(&counts);
+    Ok(())
+}
+
+fn print_counts(counts: &BTreeMap<String, usize>) {
+    for (table, count) in counts {
+        println!("  {:<24} {:>8}", table, count);
+    }
+}
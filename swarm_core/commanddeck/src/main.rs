@@ -2,22 +2,66 @@
 //!
 //! This is the main entry point for the CommandDeck CLI.
 //! A TUI allowing for the user to upload and fetch jobs.
+//! Without a subcommand the interactive menu starts, `backup` and `restore` run without prompts.
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
 
 use crate::menu::main_menu;
 
+pub mod backup;
 pub mod menu;
 pub mod views;
 
+/// Command line of CommandDeck
+#[derive(Debug, Parser)]
+#[command(name = "commanddeck", about = "Manage a SwarmSync core")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Write a snapshot of users, jobs, workers, results, metrics and logs
+    Backup {
+        /// Backup file, defaults to `backups/swarm-<timestamp>.jsonl.gz`
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Restore a snapshot written by `backup`
+    Restore {
+        /// Backup file
+        file: PathBuf,
+        /// Only restore this user and its jobs, keeping everything else
+        #[arg(long, conflicts_with = "replace")]
+        user: Option<i32>,
+        /// Drop the current data if the database is not empty
+        #[arg(long)]
+        replace: bool,
+    },
+}
+
 /// Tokio runtime
 #[tokio::main]
 async fn main() {
     // run the CLI
-    if let Err(e) = run_cli().await {
+    if let Err(e) = run_cli(Cli::parse()).await {
         eprintln!("CLI error:\n{:#}", e);
+        std::process::exit(1);
     }
 }
 
-pub async fn run_cli() -> anyhow::Result<()> {
-    // run the main menu
-    main_menu().await
+pub async fn run_cli(cli: Cli) -> anyhow::Result<()> {
+    match cli.command {
+        // run the main menu
+        None => main_menu().await,
+        Some(Command::Backup { output }) => backup::backup(output).await,
+        Some(Command::Restore {
+            file,
+            user,
+            replace,
+        }) => backup::restore(&file, user, replace).await,
+    }
 }
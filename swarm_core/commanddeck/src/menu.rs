@@ -1,6 +1,6 @@
 use dialoguer::{theme::ColorfulTheme, Select};
 
-use crate::views::{core_inspect, job_inspect, jobs, logs, users};
+use crate::views::{core_control, core_inspect, job_inspect, jobs, logs, users};
 
 /// Entry point for the main menu
 pub async fn main_menu() -> anyhow::Result<()> {
@@ -13,6 +13,7 @@ pub async fn main_menu() -> anyhow::Result<()> {
             "Manage Logs",
             "JobInspect",
             "CoreInspect",
+            "Core Control",
         ];
 
         // Ask user for a choice
@@ -29,6 +30,7 @@ pub async fn main_menu() -> anyhow::Result<()> {
             3 => logs::menu().await?,
             4 => job_inspect::inspect().await?,
             5 => core_inspect::inspect().await?,
+            6 => core_control::menu().await?,
             _ => unreachable!(),
         }
     }
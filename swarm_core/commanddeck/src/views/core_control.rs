@@ -0,0 +1,157 @@
+use std::env;
+
+use anyhow::{Context, Result};
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Password, Select};
+use swarm_client::models::Credentials;
+use swarm_client::SwarmClient;
+
+/// Core-api asked for when `CORE_API_URL` is not set
+const DEFAULT_CORE_URL: &str = "http://localhost:8000";
+
+/// Modules that can be restarted on their own, rocket serves the admin API itself
+const RESTARTABLE_MODULES: [&str; 8] = [
+    "dispatcher",
+    "harvester",
+    "hibernator",
+    "logger",
+    "notifier",
+    "reciever",
+    "scheduler",
+    "task_archive",
+];
+
+/// Entry point for the Core Control view
+/// Unlike the other views it talks to a running core over its admin API,
+/// the login needs a user listed in `CORE_ADMIN_USERS` of that core.
+pub async fn menu() -> Result<()> {
+    let client = match login().await {
+        Ok(client) => client,
+        Err(e) => {
+            println!("Login failed: {e:#}");
+            return Ok(());
+        }
+    };
+
+    loop {
+        let options = vec![
+            "Back",
+            "Dump State",
+            "Pause Scheduler",
+            "Resume Scheduler",
+            "Restart Module",
+            "Restart All Modules",
+            "Shutdown Core",
+        ];
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Core Control")
+            .default(0)
+            .items(&options)
+            .interact()?;
+
+        // API errors, e.g. a 403 for non admins, are shown and the menu stays open
+        let result = match selection {
+            0 => break,
+            1 => dump_state(&client).await,
+            2 => client
+                .pause_scheduler()
+                .await
+                .map(|s| println!("Scheduler paused: {}", s.paused))
+                .map_err(Into::into),
+            3 => client
+                .resume_scheduler()
+                .await
+                .map(|s| println!("Scheduler paused: {}", s.paused))
+                .map_err(Into::into),
+            4 => restart_module(&client).await,
+            5 if confirm("Restart every module of core?")? => client
+                .restart_core()
+                .await
+                .map(|ack| println!("{}", ack.message))
+                .map_err(Into::into),
+            6 if confirm("Shut core down?")? => match client.shutdown_core().await {
+                // nothing left to control
+                Ok(ack) => {
+                    println!("{}", ack.message);
+                    break;
+                }
+                Err(e) => Err(e.into()),
+            },
+            5 | 6 => Ok(()),
+            _ => unreachable!(),
+        };
+        if let Err(e) = result {
+            println!("Error: {e:#}");
+        }
+    }
+    Ok(())
+}
+
+/// Ask for the core url and admin credentials, returns a logged in client
+async fn login() -> Result<SwarmClient> {
+    let url: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Core URL")
+        .default(env::var("CORE_API_URL").unwrap_or_else(|_| DEFAULT_CORE_URL.to_string()))
+        .interact_text()?;
+    let username: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Username")
+        .interact_text()?;
+    let password: String = Password::with_theme(&ColorfulTheme::default())
+        .with_prompt("Password")
+        .interact()?;
+
+    let client = SwarmClient::new(&url)?;
+    let session = client
+        .login(&Credentials { username, password })
+        .await
+        .context("POST /login")?;
+    Ok(client.authenticated(&session.token)?)
+}
+
+/// Print module health, the scheduler pause and every module dump
+async fn dump_state(client: &SwarmClient) -> Result<()> {
+    let state = client.core_state().await?;
+    println!("Scheduler paused: {}", state.scheduler_paused);
+    for m in &state.modules {
+        println!(
+            "{:<13} {:<8} restarts: {:<3} stalled: {:<5} {}",
+            m.module,
+            m.status,
+            m.restarts,
+            m.stalled,
+            m.last_error.as_deref().unwrap_or("")
+        );
+    }
+    let mut dumps: Vec<_> = state.dumps.iter().collect();
+    dumps.sort_by(|a, b| a.0.cmp(b.0));
+    for (module, dump) in dumps {
+        println!("--- {module} ---");
+        println!("{}", serde_json::to_string_pretty(dump)?);
+    }
+    Ok(())
+}
+
+async fn restart_module(client: &SwarmClient) -> Result<()> {
+    let mut options = vec!["Back"];
+    options.extend(RESTARTABLE_MODULES);
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Module to restart")
+        .default(0)
+        .items(&options)
+        .interact()?;
+    if selection == 0 {
+        return Ok(());
+    }
+    println!(
+        "{}",
+        client.restart_module(options[selection]).await?.message
+    );
+    Ok(())
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    Ok(Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .default(false)
+        .interact()?)
+}
@@ -46,7 +46,7 @@ pub async fn inspect() -> Result<()> {
     loop {
         // fetch logs for module before drawing (avoid block_on)
         let module = SystemModuleEnum::from(sel);
-        let logs = fetch_logs_by_module(module.clone(), limit, log_offset)
+        let logs = fetch_logs_by_module(module, limit, log_offset)
             .await
             .unwrap_or_default();
 
@@ -86,6 +86,8 @@ pub async fn inspect() -> Result<()> {
                 SystemModuleEnum::Receiver => "Receiver: accepts new jobs.",
                 SystemModuleEnum::Scheduler => "Scheduler: orders tasks in queue.",
                 SystemModuleEnum::TaskArchive => "TaskArchive: stores completed results.",
+                SystemModuleEnum::Notifier => "Notifier: delivers webhooks on job events.",
+                SystemModuleEnum::Supervisor => "Supervisor: restarts failed modules.",
             };
             let info_para =
                 Paragraph::new(info).block(Block::default().title("Info").borders(Borders::ALL));
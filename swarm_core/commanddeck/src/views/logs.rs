@@ -4,17 +4,26 @@ use dialoguer::{theme::ColorfulTheme, Input, Select};
 
 use common::{
     commands::{
-        create_log_entry, delete_log_entry, fetch_log_entry, fetch_logs, fetch_logs_by_action,
-        fetch_logs_by_level, fetch_logs_by_module, update_log_entry,
+        create_log_entry, delete_log_entry, delete_retention_policy, fetch_audit_logs,
+        fetch_log_entry, fetch_logs, fetch_logs_by_action, fetch_logs_by_level,
+        fetch_logs_by_module, fetch_retention_policies, set_retention_policy, update_log_entry,
     },
+    database::models::audit::{AuditLog, AuditLogFilter},
     database::models::log::DBLogEntry,
+    database::models::retention::NewLogRetentionPolicy,
     enums::{log::LogActionEnum, log::LogLevelEnum, system::SystemModuleEnum},
 };
 
 // Logs main menu
 pub async fn menu() -> Result<()> {
     loop {
-        let items = vec!["Back", "Create Log", "Browse Logs"];
+        let items = vec![
+            "Back",
+            "Create Log",
+            "Browse Logs",
+            "Audit Trail",
+            "Retention Policies",
+        ];
         let choice = Select::with_theme(&ColorfulTheme::default())
             .with_prompt("Logs Menu")
             .items(&items)
@@ -25,6 +34,8 @@ pub async fn menu() -> Result<()> {
             0 => break,
             1 => create_flow().await?,
             2 => browse_flow().await?,
+            3 => audit_flow().await?,
+            4 => retention_flow().await?,
             _ => unreachable!(),
         }
     }
@@ -159,7 +170,7 @@ async fn browse_action() -> Result<()> {
     let mut offset = 0;
     let limit = 10;
     loop {
-        let logs = fetch_logs_by_action(action.clone(), limit, offset).await?;
+        let logs = fetch_logs_by_action(action, limit, offset).await?;
         if logs.is_empty() {
             println!("📭 No logs for action `{}`.", action);
             break;
@@ -183,7 +194,7 @@ async fn browse_level() -> Result<()> {
     let mut offset = 0;
     let limit = 10;
     loop {
-        let logs = fetch_logs_by_level(level.clone(), limit, offset).await?;
+        let logs = fetch_logs_by_level(level, limit, offset).await?;
         if logs.is_empty() {
             println!("📭 No logs for level `{}`.", level);
             break;
@@ -207,7 +218,7 @@ async fn browse_module() -> Result<()> {
     let mut offset = 0;
     let limit = 10;
     loop {
-        let logs = fetch_logs_by_module(module.clone(), limit, offset).await?;
+        let logs = fetch_logs_by_module(module, limit, offset).await?;
         if logs.is_empty() {
             println!("📭 No logs for module {:?}.", module);
             break;
@@ -372,3 +383,188 @@ async fn manage_single_log(id: i32) -> Result<(), anyhow::Error> {
 
     Ok(())
 }
+
+/// Read a prompt, blank = None
+fn optional_input(prompt: &str) -> Result<Option<String>, anyhow::Error> {
+    let raw: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .allow_empty(true)
+        .interact_text()?;
+    Ok(Some(raw.trim().to_string()).filter(|s| !s.is_empty()))
+}
+
+/// Search the audit trail, every blank filter matches everything
+async fn audit_flow() -> Result<()> {
+    let username = optional_input("Username (or blank)")?;
+    let resource = optional_input("Resource, e.g. jobs, workers, users (or blank)")?;
+    let resource_id = match optional_input("Resource ID (or blank)")? {
+        Some(raw) => match raw.parse::<i32>() {
+            Ok(id) => Some(id),
+            Err(_) => {
+                println!("⚠️ `{}` is not a number, ignoring it", raw);
+                None
+            }
+        },
+        None => None,
+    };
+    let methods = ["Any", "POST", "PATCH", "PUT", "DELETE"];
+    let method = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Method")
+        .items(&methods)
+        .default(0)
+        .interact()?;
+    let filter = AuditLogFilter {
+        username,
+        resource,
+        resource_id,
+        method: (method > 0).then(|| methods[method].to_string()),
+        ..Default::default()
+    };
+
+    let mut offset = 0;
+    let limit = 10;
+    loop {
+        let entries = fetch_audit_logs(&filter, limit, offset).await?;
+        if entries.is_empty() {
+            println!("📭 No audit entries.");
+            break;
+        }
+        if select_audit_entry(&entries)? {
+            break;
+        }
+        offset = paginate_offset(offset, limit)?;
+    }
+    Ok(())
+}
+
+/// List a page of audit entries and show the selected one
+/// # Arguments
+/// * entries - One page of the audit trail
+fn select_audit_entry(entries: &[AuditLog]) -> Result<bool, anyhow::Error> {
+    for entry in entries {
+        println!(
+            "({}) {} {} {} -> {} by {}",
+            entry.id,
+            entry.created_at.format("%Y-%m-%d %H:%M:%S"),
+            entry.method,
+            entry.path,
+            entry.status,
+            entry.username.as_deref().unwrap_or("anonymous")
+        );
+    }
+    let mut items: Vec<String> = entries.iter().map(|e| e.id.to_string()).collect();
+    items.extend_from_slice(&["Next Page".into(), "Prev Page".into(), "Back".into()]);
+    let choice = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Select ID or navigate")
+        .items(&items)
+        .interact()?;
+    let n = entries.len();
+    if choice < n {
+        print_audit_entry(&entries[choice]);
+        return Ok(true);
+    }
+    Ok(choice - n == 2)
+}
+
+/// Print one audit entry with the fields the call changed
+fn print_audit_entry(entry: &AuditLog) {
+    println!("🧾 Audit entry {}", entry.id);
+    println!("  When:     {}", entry.created_at);
+    println!(
+        "  Who:      {} (user {:?}, token {}…)",
+        entry.username.as_deref().unwrap_or("anonymous"),
+        entry.user_id,
+        entry.token_prefix.as_deref().unwrap_or("-")
+    );
+    println!("  From:     {}", entry.client_ip.as_deref().unwrap_or("-"));
+    println!(
+        "  Call:     {} {} ({})",
+        entry.method, entry.path, entry.route
+    );
+    println!("  Status:   {}", entry.status);
+    println!("  Expires:  {}", entry.expires_at);
+
+    let changes = entry.diff();
+    if changes.is_empty() {
+        println!("  No recorded changes.");
+    }
+    for change in changes {
+        println!("  {}: {} → {}", change.field, change.before, change.after);
+    }
+}
+
+/// Manage log retention policies, the Logger applies changes on its next slow pulse
+async fn retention_flow() -> Result<()> {
+    loop {
+        let policies = fetch_retention_policies().await?;
+        println!("🗄️ Retention policies (most specific match wins: action > module > level)");
+        for p in &policies {
+            println!(
+                "({}) level={} module={} action={} → {} min",
+                p.id,
+                p.level.as_ref().map_or("*".to_string(), |l| l.to_string()),
+                p.module.as_ref().map_or("*".to_string(), |m| m.to_string()),
+                p.action.as_ref().map_or("*".to_string(), |a| a.to_string()),
+                p.retention_minutes
+            );
+        }
+
+        let items = vec!["Back", "Set Policy", "Delete Policy"];
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Retention Policies")
+            .items(&items)
+            .default(0)
+            .interact()?;
+        match choice {
+            0 => break,
+            1 => set_retention_flow().await?,
+            2 => {
+                let id: i32 = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Policy ID")
+                    .interact_text()?;
+                delete_retention_policy(id).await?;
+            }
+            _ => unreachable!(),
+        }
+    }
+    Ok(())
+}
+
+/// Pick an enum variant or "Any", index into `variants` on a pick
+fn select_or_any(prompt: &str, variants: &[&str]) -> Result<Option<usize>, anyhow::Error> {
+    let mut items = vec!["Any"];
+    items.extend_from_slice(variants);
+    let choice = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .items(&items)
+        .default(0)
+        .interact()?;
+    Ok(choice.checked_sub(1))
+}
+
+/// Create or replace the policy for a level/module/action combination
+async fn set_retention_flow() -> Result<()> {
+    let level = select_or_any("Level", LogLevelEnum::variants())?.map(LogLevelEnum::from);
+    let module = select_or_any("Module", SystemModuleEnum::variants())?.map(SystemModuleEnum::from);
+    let action = select_or_any("Action", LogActionEnum::variants())?.map(LogActionEnum::from);
+    let retention_minutes: i64 = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Keep entries for (minutes)")
+        .validate_with(|m: &i64| {
+            if *m > 0 {
+                Ok(())
+            } else {
+                Err("must be positive")
+            }
+        })
+        .interact_text()?;
+
+    let policy = set_retention_policy(NewLogRetentionPolicy {
+        level,
+        module,
+        action,
+        retention_minutes,
+    })
+    .await?;
+    println!("✅ Saved retention policy {}", policy.id);
+    Ok(())
+}
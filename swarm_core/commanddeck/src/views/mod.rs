@@ -1,3 +1,4 @@
+pub mod core_control;
 pub mod core_inspect;
 pub mod job_inspect;
 pub mod jobs;
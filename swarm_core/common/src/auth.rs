@@ -10,7 +10,7 @@ use rand::Rng;
 use crate::database::models::user::User;
 
 /// Struct to hold username and password
-#[derive(serde::Deserialize)]
+#[derive(serde::Deserialize, utoipa::ToSchema)]
 pub struct Credentials {
     pub username: String,
     pub password: String,
@@ -0,0 +1,416 @@
+//! Backups of the core state, written by `commanddeck backup` and read by `commanddeck restore`
+//!
+//! A backup is a gzipped JSON-lines file:
+//! * a `header` line with the file format and the schema version (latest migration) of the source
+//! * one `row` line per row, table by table in `BACKUP_TABLES` order, rows as `row_to_json` gives them
+//! * an `end` line with the row count of every table, a file without it is incomplete
+//!
+//! The rows are read in one read-only `REPEATABLE READ` transaction, so the snapshot is consistent
+//! while core keeps running. A restore only accepts a backup of the same format and schema version
+//! and runs in one transaction, it either applies completely or not at all.
+use std::collections::{BTreeMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use chrono::{NaiveDateTime, Utc};
+use diesel_async::AsyncConnection;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::commands::load_db_connection;
+use crate::database::repositories::backup::backup_table;
+use crate::database::repositories::{BackupRepository, BACKUP_TABLES};
+
+/// Layout of the file, bumped on incompatible changes
+pub const BACKUP_FORMAT: u32 = 1;
+/// Rows read per query while writing a backup
+const DUMP_PAGE: i64 = 1000;
+/// Rows inserted per statement while restoring
+const INSERT_BATCH: usize = 500;
+
+/// One line of a backup file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BackupLine {
+    Header {
+        format: u32,
+        // latest migration of the source database
+        schema_version: String,
+        created_at: NaiveDateTime,
+    },
+    Row {
+        table: String,
+        row: Value,
+    },
+    End {
+        // rows written per table
+        counts: BTreeMap<String, usize>,
+    },
+}
+
+/// Why a backup file can not be restored
+#[derive(Debug, Error, PartialEq)]
+pub enum BackupError {
+    #[error("backup format {0} is not supported, expected {BACKUP_FORMAT}")]
+    Format(u32),
+    #[error("backup was taken at schema version {backup}, the database is at {database}")]
+    SchemaMismatch { backup: String, database: String },
+    #[error("the database already holds users, restore with `--replace` to overwrite them")]
+    NotEmpty,
+    #[error("backup is incomplete: {0}")]
+    Incomplete(String),
+    #[error("backup contains unknown table `{0}`")]
+    UnknownTable(String),
+}
+
+/// What a restore puts back
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RestoreScope {
+    /// Every table, the current contents are dropped. `replace` must be set if users exist.
+    All { replace: bool },
+    /// One user with its jobs, their results, metrics, assignments and archive entries.
+    /// Rows that still exist are kept as they are.
+    User(i32),
+}
+
+/// Rows of a backup that belong to one user, jobs come before their child rows in a backup
+#[derive(Debug)]
+pub struct UserSelection {
+    user_id: i32,
+    job_ids: HashSet<i64>,
+}
+
+impl UserSelection {
+    pub fn new(user_id: i32) -> Self {
+        UserSelection {
+            user_id,
+            job_ids: HashSet::new(),
+        }
+    }
+
+    /// Whether `row` of `table` is restored, remembers the ids of the users jobs
+    pub fn keeps(&mut self, table: &str, row: &Value) -> bool {
+        let field = |name: &str| row.get(name).and_then(Value::as_i64);
+        let user = Some(i64::from(self.user_id));
+        match table {
+            "users" => field("id") == user,
+            "jobs" if field("user_id") == user => {
+                self.job_ids.extend(field("id"));
+                true
+            }
+            "job_assignments" | "job_results" | "job_metrics" => {
+                field("job_id").is_some_and(|id| self.job_ids.contains(&id))
+            }
+            "job_archives" => field("user_id") == user,
+            _ => false,
+        }
+    }
+}
+
+/// Check the first line of a backup against the target database
+/// # Arguments
+/// * `line` - The first line of the file
+/// * `database_version` - Latest migration of the target database
+/// # Returns
+/// * `Result<(), BackupError>`
+pub fn check_header(line: &BackupLine, database_version: &str) -> Result<(), BackupError> {
+    match line {
+        BackupLine::Header { format, .. } if *format != BACKUP_FORMAT => {
+            Err(BackupError::Format(*format))
+        }
+        BackupLine::Header { schema_version, .. } if schema_version != database_version => {
+            Err(BackupError::SchemaMismatch {
+                backup: schema_version.clone(),
+                database: database_version.to_string(),
+            })
+        }
+        BackupLine::Header { .. } => Ok(()),
+        _ => Err(BackupError::Incomplete("missing header".to_string())),
+    }
+}
+
+/// Write one line
+pub fn write_line(out: &mut impl Write, line: &BackupLine) -> anyhow::Result<()> {
+    serde_json::to_writer(&mut *out, line)?;
+    out.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Lines of a backup file, in file order
+pub fn read_lines(input: impl BufRead) -> impl Iterator<Item = anyhow::Result<BackupLine>> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !matches!(line, Ok(l) if l.trim().is_empty()))
+        .map(|(n, line)| {
+            let line = line?;
+            serde_json::from_str(&line).with_context(|| format!("line {} is malformed", n + 1))
+        })
+}
+
+/// Default file name of a new backup, `swarm-<UTC timestamp>.jsonl.gz` inside `dir`
+pub fn default_backup_path(dir: &Path) -> PathBuf {
+    dir.join(format!(
+        "swarm-{}.jsonl.gz",
+        Utc::now().format("%Y%m%d_%H%M%S")
+    ))
+}
+
+/// Write a snapshot of every table in `BACKUP_TABLES` to `path`
+/// The file is written next to `path` first and only renamed once complete.
+/// # Arguments
+/// * `path` - The backup file to create
+/// # Returns
+/// * `anyhow::Result<BTreeMap<String, usize>>` - rows written per table
+pub async fn create_backup(path: &Path) -> anyhow::Result<BTreeMap<String, usize>> {
+    let mut conn = load_db_connection().await?;
+    let schema_version = BackupRepository::schema_version(&mut conn)
+        .await?
+        .context("The database has no migrations applied")?;
+
+    if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+    }
+    let partial = PathBuf::from(format!("{}.partial", path.display()));
+    let file = File::create(&partial).with_context(|| format!("creating {}", partial.display()))?;
+    let mut out = GzEncoder::new(file, Compression::default());
+
+    let counts = conn
+        .build_transaction()
+        .read_only()
+        .repeatable_read()
+        .run::<_, anyhow::Error, _>(|tx| {
+            Box::pin(async move {
+                write_line(
+                    &mut out,
+                    &BackupLine::Header {
+                        format: BACKUP_FORMAT,
+                        schema_version,
+                        created_at: Utc::now().naive_utc(),
+                    },
+                )?;
+                let mut counts = BTreeMap::new();
+                for table in BACKUP_TABLES {
+                    let mut after_id = 0;
+                    let mut count = 0;
+                    loop {
+                        let page =
+                            BackupRepository::dump_page(tx, table, after_id, DUMP_PAGE).await?;
+                        for row in &page {
+                            after_id =
+                                row.get("id").and_then(Value::as_i64).unwrap_or_default() as i32;
+                            write_line(
+                                &mut out,
+                                &BackupLine::Row {
+                                    table: table.to_string(),
+                                    row: row.clone(),
+                                },
+                            )?;
+                        }
+                        count += page.len();
+                        if (page.len() as i64) < DUMP_PAGE {
+                            break;
+                        }
+                    }
+                    counts.insert(table.to_string(), count);
+                }
+                write_line(
+                    &mut out,
+                    &BackupLine::End {
+                        counts: counts.clone(),
+                    },
+                )?;
+                out.finish()?.sync_all()?;
+                Ok(counts)
+            })
+        })
+        .await;
+
+    match counts {
+        Ok(counts) => {
+            std::fs::rename(&partial, path)
+                .with_context(|| format!("moving backup to {}", path.display()))?;
+            Ok(counts)
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&partial);
+            Err(e)
+        }
+    }
+}
+
+/// Restore a backup written by `create_backup`
+/// # Arguments
+/// * `path` - The backup file
+/// * `scope` - Everything, or the data of one user
+/// # Returns
+/// * `anyhow::Result<BTreeMap<String, usize>>` - rows inserted per table
+/// # Errors
+/// * `BackupError` when the file does not fit the database, nothing is changed then
+/// * DB connection or insert failed
+pub async fn restore_backup(
+    path: &Path,
+    scope: RestoreScope,
+) -> anyhow::Result<BTreeMap<String, usize>> {
+    let file = File::open(path).with_context(|| format!("opening {}", path.display()))?;
+    let mut lines = read_lines(BufReader::new(GzDecoder::new(file)));
+
+    let mut conn = load_db_connection().await?;
+    let database_version = BackupRepository::schema_version(&mut conn)
+        .await?
+        .context("The database has no migrations applied")?;
+    let header = lines
+        .next()
+        .transpose()?
+        .ok_or_else(|| BackupError::Incomplete("file is empty".to_string()))?;
+    check_header(&header, &database_version)?;
+
+    conn.transaction::<_, anyhow::Error, _>(|tx| {
+        Box::pin(async move {
+            let mut selection = match scope {
+                RestoreScope::All { replace } => {
+                    if !replace && !BackupRepository::is_empty(tx).await? {
+                        return Err(BackupError::NotEmpty.into());
+                    }
+                    // also drops rows seeded by migrations, the backup has its own
+                    BackupRepository::truncate_all(tx).await?;
+                    None
+                }
+                RestoreScope::User(user_id) => Some(UserSelection::new(user_id)),
+            };
+            let skip_existing = selection.is_some();
+
+            let mut read: BTreeMap<String, usize> = BTreeMap::new();
+            let mut inserted: BTreeMap<String, usize> = BTreeMap::new();
+            let mut batch: Vec<Value> = Vec::new();
+            let mut batch_table: &'static str = BACKUP_TABLES[0];
+            let mut expected = None;
+
+            for line in lines {
+                let (table, row) = match line? {
+                    BackupLine::Row { table, row } => (table, row),
+                    BackupLine::End { counts } => {
+                        expected = Some(counts);
+                        break;
+                    }
+                    BackupLine::Header { .. } => {
+                        return Err(BackupError::Incomplete("second header".to_string()).into())
+                    }
+                };
+                let table = backup_table(&table).ok_or(BackupError::UnknownTable(table))?;
+                *read.entry(table.to_string()).or_default() += 1;
+                if !selection.as_mut().is_none_or(|s| s.keeps(table, &row)) {
+                    continue;
+                }
+                if table != batch_table || batch.len() >= INSERT_BATCH {
+                    let rows = std::mem::take(&mut batch);
+                    *inserted.entry(batch_table.to_string()).or_default() +=
+                        BackupRepository::insert_rows(tx, batch_table, rows, skip_existing).await?;
+                    batch_table = table;
+                }
+                batch.push(row);
+            }
+            *inserted.entry(batch_table.to_string()).or_default() +=
+                BackupRepository::insert_rows(tx, batch_table, batch, skip_existing).await?;
+
+            let expected =
+                expected.ok_or_else(|| BackupError::Incomplete("no end marker".to_string()))?;
+            let expected: BTreeMap<String, usize> =
+                expected.into_iter().filter(|(_, n)| *n > 0).collect();
+            if read != expected {
+                return Err(BackupError::Incomplete(
+                    "row counts differ from the end marker".to_string(),
+                )
+                .into());
+            }
+
+            BackupRepository::reset_sequences(tx).await?;
+            inserted.retain(|_, n| *n > 0);
+            Ok(inserted)
+        })
+    })
+    .await
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn header(format: u32, schema_version: &str) -> BackupLine {
+        BackupLine::Header {
+            format,
+            schema_version: schema_version.to_string(),
+            created_at: NaiveDateTime::default(),
+        }
+    }
+
+    #[test]
+    fn lines_round_trip_through_gzip() {
+        let lines = vec![
+            header(BACKUP_FORMAT, "20250511090000"),
+            BackupLine::Row {
+                table: "users".to_string(),
+                row: json!({ "id": 1, "username": "ada" }),
+            },
+            BackupLine::End {
+                counts: BTreeMap::from([("users".to_string(), 1)]),
+            },
+        ];
+        let mut out = GzEncoder::new(Vec::new(), Compression::default());
+        for line in &lines {
+            write_line(&mut out, line).unwrap();
+        }
+        let compressed = out.finish().unwrap();
+
+        let read: Vec<BackupLine> = read_lines(BufReader::new(GzDecoder::new(&compressed[..])))
+            .collect::<anyhow::Result<_>>()
+            .unwrap();
+        assert_eq!(read, lines);
+    }
+
+    #[test]
+    fn header_must_match_format_and_schema() {
+        assert_eq!(check_header(&header(BACKUP_FORMAT, "2"), "2"), Ok(()));
+        assert_eq!(
+            check_header(&header(BACKUP_FORMAT + 1, "2"), "2"),
+            Err(BackupError::Format(BACKUP_FORMAT + 1))
+        );
+        assert_eq!(
+            check_header(&header(BACKUP_FORMAT, "1"), "2"),
+            Err(BackupError::SchemaMismatch {
+                backup: "1".to_string(),
+                database: "2".to_string()
+            })
+        );
+        let row = BackupLine::Row {
+            table: "users".to_string(),
+            row: json!({}),
+        };
+        assert!(matches!(
+            check_header(&row, "2"),
+            Err(BackupError::Incomplete(_))
+        ));
+    }
+
+    #[test]
+    fn user_selection_follows_the_jobs() {
+        let mut selection = UserSelection::new(7);
+        assert!(selection.keeps("users", &json!({ "id": 7 })));
+        assert!(!selection.keeps("users", &json!({ "id": 8 })));
+        assert!(!selection.keeps("workers", &json!({ "id": 1, "user_id": 7 })));
+        assert!(selection.keeps("jobs", &json!({ "id": 10, "user_id": 7 })));
+        assert!(!selection.keeps("jobs", &json!({ "id": 11, "user_id": 8 })));
+        assert!(selection.keeps("job_results", &json!({ "id": 1, "job_id": 10 })));
+        assert!(!selection.keeps("job_results", &json!({ "id": 2, "job_id": 11 })));
+        assert!(selection.keeps("job_archives", &json!({ "id": 3, "user_id": 7 })));
+        assert!(!selection.keeps("logs", &json!({ "id": 1, "job_submitted_job_id": 10 })));
+    }
+}
@@ -1,16 +1,24 @@
 ///! Mostly used within commanddeck, that's why some functions have a lot of prints, for the UI
+use std::sync::OnceLock;
+use std::time::Duration;
+
 use anyhow::Context;
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use deadpool::Runtime;
+use diesel_async::pooled_connection::deadpool::{Object, Pool};
+use diesel_async::pooled_connection::AsyncDieselConnectionManager;
 use diesel_async::{AsyncConnection, AsyncPgConnection};
 
 use crate::auth;
+use crate::database::models::audit::{AuditLog, AuditLogFilter};
 use crate::database::models::job::{Job, JobAssignment, NewJob, NewJobAssignment};
 use crate::database::models::log::{DBLogEntry, NewDBLogEntry};
+use crate::database::models::retention::{LogRetentionPolicy, NewLogRetentionPolicy};
 use crate::database::models::user::{NewUser, User};
 use crate::database::models::worker::{NewWorker, NewWorkerStatus, Worker};
 use crate::database::repositories::{
-    JobAssignmentRepository, JobRepository, LogEntryRepository, UserRepository, WorkerRepository,
-    WorkerStatusRepository,
+    AuditLogRepository, JobAssignmentRepository, JobRepository, LogEntryRepository,
+    LogRetentionRepository, UserRepository, WorkerRepository, WorkerStatusRepository,
 };
 use crate::enums::image_format::ImageFormatEnum;
 use crate::enums::job::JobStateEnum;
@@ -20,16 +28,79 @@ use crate::enums::schedule::ScheduleTypeEnum;
 use crate::enums::system::SystemModuleEnum;
 use crate::enums::workers::{OSEnum, WorkerStatusEnum};
 
-/// Load database connection,
+/// Connections kept open by the shared pool when `DATABASE_POOL_SIZE` is not set
+pub const DEFAULT_POOL_SIZE: usize = 16;
+
+/// Seconds a caller waits for a free connection when `DATABASE_POOL_WAIT_TIMEOUT_SECS` is not set
+pub const DEFAULT_POOL_WAIT_TIMEOUT_SECS: u64 = 10;
+
+/// Seconds to open a new connection when `DATABASE_POOL_CREATE_TIMEOUT_SECS` is not set
+pub const DEFAULT_POOL_CREATE_TIMEOUT_SECS: u64 = 5;
+
+/// A connection borrowed from the shared pool, goes back to the pool when dropped
+pub type DbConnection = Object<AsyncPgConnection>;
+
+/// The process wide pool behind `load_db_connection`, built on first use
+static DB_POOL: OnceLock<Pool<AsyncPgConnection>> = OnceLock::new();
+
+/// The shared connection pool of this process, sized by `DATABASE_POOL_SIZE`
+/// Cloning is cheap, every clone hands out connections from the same pool
+/// # Returns
+/// * `Pool<AsyncPgConnection>` - Error if `DATABASE_URL` is missing
+pub fn db_pool() -> anyhow::Result<Pool<AsyncPgConnection>, anyhow::Error> {
+    if let Some(pool) = DB_POOL.get() {
+        return Ok(pool.clone());
+    }
+    let max_size = std::env::var("DATABASE_POOL_SIZE")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|size| *size > 0)
+        .unwrap_or(DEFAULT_POOL_SIZE);
+    let pool = load_db_pool(max_size)?;
+    // a racing caller may have won, its pool is kept and ours dropped unused
+    Ok(DB_POOL.get_or_init(|| pool).clone())
+}
+
+/// Load database connection from the shared pool,
 /// Error if DB not up
-#[allow(clippy::expect_used)]
-pub async fn load_db_connection() -> anyhow::Result<AsyncPgConnection, anyhow::Error> {
+pub async fn load_db_connection() -> anyhow::Result<DbConnection, anyhow::Error> {
+    db_pool()?.get().await.context("Cannot connect to Postgres")
+}
+
+/// A timeout in whole seconds from the environment, `default` when unset, unparsable or zero
+fn pool_timeout(var: &str, default: u64) -> Duration {
+    let secs = std::env::var(var)
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .filter(|secs| *secs > 0)
+        .unwrap_or(default);
+    Duration::from_secs(secs)
+}
+
+/// Build a connection pool for long running modules, connections are opened lazily
+/// Waiting for a free connection is bounded by `DATABASE_POOL_WAIT_TIMEOUT_SECS`,
+/// opening one by `DATABASE_POOL_CREATE_TIMEOUT_SECS`, so a stuck Postgres surfaces as an error
+/// # Arguments
+/// * `max_size` - Maximum number of open connections
+/// # Returns
+/// * `Pool<AsyncPgConnection>` - Error if `DATABASE_URL` is missing
+pub fn load_db_pool(max_size: usize) -> anyhow::Result<Pool<AsyncPgConnection>, anyhow::Error> {
     let database_url =
         std::env::var("DATABASE_URL").context("Cannot load DB url from environment")?;
-    let conn = AsyncPgConnection::establish(&database_url)
-        .await
-        .context("Cannot connect to Postgres")?;
-    Ok(conn)
+    let manager = AsyncDieselConnectionManager::<AsyncPgConnection>::new(database_url);
+    Pool::builder(manager)
+        .max_size(max_size)
+        .runtime(Runtime::Tokio1)
+        .wait_timeout(Some(pool_timeout(
+            "DATABASE_POOL_WAIT_TIMEOUT_SECS",
+            DEFAULT_POOL_WAIT_TIMEOUT_SECS,
+        )))
+        .create_timeout(Some(pool_timeout(
+            "DATABASE_POOL_CREATE_TIMEOUT_SECS",
+            DEFAULT_POOL_CREATE_TIMEOUT_SECS,
+        )))
+        .build()
+        .context("Cannot build Postgres pool")
 }
 
 /// Create a new user
@@ -269,6 +340,7 @@ pub async fn create_full_job(
         cron_expression,
         notes: None,
         state: JobStateEnum::Submitted,
+        priority: 0,
     };
 
     match JobRepository::create(&mut c, new_job).await {
@@ -317,7 +389,7 @@ pub async fn get_job_by_id(id: i32) -> anyhow::Result<Job> {
 
 pub async fn create_worker(user_id: i32, label: String) -> anyhow::Result<()> {
     // ── 1. Open connection ─────────────────────────────────────────────
-    let mut conn: AsyncPgConnection = load_db_connection().await.context("DB connect failed")?;
+    let mut conn = load_db_connection().await.context("DB connect failed")?;
 
     // ── 2. Run both inserts in a single transaction ────────────────────
     conn.transaction::<_, anyhow::Error, _>(|tx| {
@@ -531,6 +603,7 @@ pub async fn create_log_entry(
 ) -> anyhow::Result<(), anyhow::Error> {
     let mut conn = load_db_connection().await?;
     let new_entry = NewDBLogEntry {
+        created_at: None,
         level,
         module,
         action,
@@ -605,6 +678,41 @@ pub async fn fetch_logs_by_module(
     Ok(LogEntryRepository::find_logs_by_module(&mut conn, module, limit, offset).await?)
 }
 
+/// Search a page of the audit trail, newest first
+pub async fn fetch_audit_logs(
+    filter: &AuditLogFilter,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<AuditLog>, anyhow::Error> {
+    let mut conn = load_db_connection().await?;
+    Ok(AuditLogRepository::search(&mut conn, filter, limit, offset).await?)
+}
+
+/// Fetch all log retention policies
+pub async fn fetch_retention_policies() -> Result<Vec<LogRetentionPolicy>, anyhow::Error> {
+    let mut conn = load_db_connection().await?;
+    Ok(LogRetentionRepository::list_all(&mut conn).await?)
+}
+
+/// Create or replace a log retention policy, the Logger picks it up on its next slow pulse
+pub async fn set_retention_policy(
+    policy: NewLogRetentionPolicy,
+) -> Result<LogRetentionPolicy, anyhow::Error> {
+    let mut conn = load_db_connection().await?;
+    Ok(LogRetentionRepository::upsert(&mut conn, policy).await?)
+}
+
+/// Delete a log retention policy by ID
+pub async fn delete_retention_policy(id: i32) -> anyhow::Result<(), anyhow::Error> {
+    let mut conn = load_db_connection().await?;
+    match LogRetentionRepository::delete(&mut conn, id).await {
+        Ok(n) if n > 0 => println!("🗑️ Deleted retention policy {}", id),
+        Ok(_) => println!("⚠️ No retention policy with ID {}", id),
+        Err(e) => eprintln!("❌ Error deleting retention policy {}: {}", id, e),
+    }
+    Ok(())
+}
+
 /// Update an existing log entry
 pub async fn update_log_entry(id: i32, updated: DBLogEntry) -> anyhow::Result<(), anyhow::Error> {
     let mut conn = load_db_connection().await?;
@@ -637,4 +745,27 @@ mod tests {
         // Replace this with real assertions as you go
         assert_eq!(2 + 2, 4);
     }
+
+    #[test]
+    fn pool_timeout_falls_back_to_the_default() {
+        std::env::set_var("TEST_POOL_TIMEOUT_SET", "3");
+        std::env::set_var("TEST_POOL_TIMEOUT_ZERO", "0");
+        std::env::set_var("TEST_POOL_TIMEOUT_BAD", "soon");
+        assert_eq!(
+            pool_timeout("TEST_POOL_TIMEOUT_SET", 10),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            pool_timeout("TEST_POOL_TIMEOUT_ZERO", 10),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            pool_timeout("TEST_POOL_TIMEOUT_BAD", 10),
+            Duration::from_secs(10)
+        );
+        assert_eq!(
+            pool_timeout("TEST_POOL_TIMEOUT_UNSET", 10),
+            Duration::from_secs(10)
+        );
+    }
 }
@@ -0,0 +1,37 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::database::schema::job_archives;
+use crate::enums::job::JobStateEnum;
+
+/// Summary of a finished job moved out of `jobs` by the TaskArchive
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Selectable, ToSchema)]
+#[diesel(table_name = job_archives)]
+pub struct JobArchive {
+    // id the job had, restoring keeps it
+    pub id: i32,
+    pub user_id: i32,
+    pub job_name: String,
+    pub image_url: String,
+    // Completed or Failed
+    pub state: JobStateEnum,
+    pub error_message: Option<String>,
+    pub created_at: NaiveDateTime,
+    // last update of the job, when it finished
+    pub finished_at: NaiveDateTime,
+    // results stored with the job
+    pub result_count: i32,
+    pub archived_at: NaiveDateTime,
+}
+
+/// An archived job with everything needed to restore it
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ArchivedJob {
+    #[serde(flatten)]
+    pub summary: JobArchive,
+    // {"job", "results", "metrics", "assignments", "logs"}, rows as they were in their tables
+    pub data: Value,
+}
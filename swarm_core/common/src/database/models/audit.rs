@@ -0,0 +1,204 @@
+use chrono::{Duration, NaiveDateTime};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::database::schema::audit_logs;
+
+/// Days audit entries are kept when `AUDIT_RETENTION_DAYS` is not set
+pub const DEFAULT_AUDIT_RETENTION_DAYS: i64 = 90;
+
+/// Fields never written to the audit trail
+const REDACTED_FIELDS: [&str; 5] = ["password", "password_hash", "ssh_key", "token", "secret"];
+
+/// Route prefixes that are audited with row snapshots: (first path segment, table, key column)
+/// Table and column names are only ever taken from this list when building snapshot queries.
+pub const AUDITED_RESOURCES: [(&str, &str, &str); 11] = [
+    ("jobs", "jobs", "id"),
+    ("archive", "job_archives", "id"),
+    ("assignments", "job_assignments", "id"),
+    ("metrics", "job_metrics", "id"),
+    ("results", "job_results", "id"),
+    ("quotas", "user_quotas", "user_id"),
+    ("templates", "job_templates", "id"),
+    ("users", "users", "id"),
+    ("webhooks", "webhooks", "id"),
+    ("workers", "workers", "id"),
+    ("worker-status", "worker_status", "id"),
+];
+
+/// One mutating API call
+#[derive(Debug, Clone, Queryable, Selectable, Serialize, Deserialize)]
+#[diesel(table_name = audit_logs)]
+pub struct AuditLog {
+    pub id: i32,
+    pub created_at: NaiveDateTime,
+    // entry may be deleted after this
+    pub expires_at: NaiveDateTime,
+    // caller, None for unauthenticated calls
+    pub user_id: Option<i32>,
+    pub username: Option<String>,
+    // first characters of the bearer token, tells sessions apart without storing them
+    pub token_prefix: Option<String>,
+    pub client_ip: Option<String>,
+    // POST, PATCH, PUT or DELETE
+    pub method: String,
+    // matched route template, e.g. /jobs/<id>
+    pub route: String,
+    // requested path, e.g. /jobs/12
+    pub path: String,
+    // first path segment, e.g. jobs
+    pub resource: Option<String>,
+    pub resource_id: Option<i32>,
+    // HTTP status of the response
+    pub status: i32,
+    // row before and after the call, secrets removed
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// Insertable audit entry
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = audit_logs)]
+pub struct NewAuditLog {
+    pub expires_at: NaiveDateTime,
+    pub user_id: Option<i32>,
+    pub username: Option<String>,
+    pub token_prefix: Option<String>,
+    pub client_ip: Option<String>,
+    pub method: String,
+    pub route: String,
+    pub path: String,
+    pub resource: Option<String>,
+    pub resource_id: Option<i32>,
+    pub status: i32,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// Search filter for the audit trail, every set field must match
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditLogFilter {
+    pub username: Option<String>,
+    pub resource: Option<String>,
+    pub resource_id: Option<i32>,
+    pub method: Option<String>,
+    pub since: Option<NaiveDateTime>,
+    pub until: Option<NaiveDateTime>,
+}
+
+/// A top level field that differs between `before` and `after`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+impl AuditLog {
+    /// Fields that changed with the call, a missing side is `null`
+    /// # Returns
+    /// * `Vec<FieldChange>` - sorted by field name, empty if nothing changed or no snapshots exist
+    pub fn diff(&self) -> Vec<FieldChange> {
+        diff_values(self.before.as_ref(), self.after.as_ref())
+    }
+}
+
+/// Compare two row snapshots field by field
+pub fn diff_values(before: Option<&Value>, after: Option<&Value>) -> Vec<FieldChange> {
+    let empty = serde_json::Map::new();
+    let before = before.and_then(Value::as_object).unwrap_or(&empty);
+    let after = after.and_then(Value::as_object).unwrap_or(&empty);
+
+    let mut fields: Vec<&String> = before.keys().chain(after.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let old = before.get(field).cloned().unwrap_or(Value::Null);
+            let new = after.get(field).cloned().unwrap_or(Value::Null);
+            (old != new).then(|| FieldChange {
+                field: field.clone(),
+                before: old,
+                after: new,
+            })
+        })
+        .collect()
+}
+
+/// Remove secrets from a snapshot, recursively
+/// # Arguments
+/// * `value` - a row or response body
+/// # Returns
+/// * `Value` - the same value without password, token and key fields
+pub fn redact(value: Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(k, _)| !REDACTED_FIELDS.contains(&k.as_str()))
+                .map(|(k, v)| (k, redact(v)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact).collect()),
+        other => other,
+    }
+}
+
+/// Table and key column audited for a path segment, None for unaudited prefixes
+pub fn audited_table(segment: &str) -> Option<(&'static str, &'static str)> {
+    AUDITED_RESOURCES
+        .iter()
+        .find(|(prefix, _, _)| *prefix == segment)
+        .map(|(_, table, key)| (*table, *key))
+}
+
+/// How long audit entries are kept, `AUDIT_RETENTION_DAYS` or 90 days
+pub fn audit_retention() -> Duration {
+    let days = std::env::var("AUDIT_RETENTION_DAYS")
+        .ok()
+        .and_then(|d| d.parse::<i64>().ok())
+        .filter(|d| *d > 0)
+        .unwrap_or(DEFAULT_AUDIT_RETENTION_DAYS);
+    Duration::days(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn redact_strips_secrets_at_any_depth() {
+        let row = json!({
+            "id": 1,
+            "password_hash": "x",
+            "nested": [{ "token": "t", "name": "n" }]
+        });
+        assert_eq!(redact(row), json!({ "id": 1, "nested": [{ "name": "n" }] }));
+    }
+
+    #[test]
+    fn diff_lists_changed_fields_only() {
+        let before = json!({ "id": 1, "state": "Queued", "notes": null });
+        let after = json!({ "id": 1, "state": "Running", "priority": 3, "notes": null });
+        let changes = diff_values(Some(&before), Some(&after));
+        assert_eq!(
+            changes,
+            vec![
+                FieldChange {
+                    field: "priority".into(),
+                    before: Value::Null,
+                    after: json!(3)
+                },
+                FieldChange {
+                    field: "state".into(),
+                    before: json!("Queued"),
+                    after: json!("Running")
+                },
+            ]
+        );
+        assert!(diff_values(None, None).is_empty());
+    }
+}
@@ -3,6 +3,7 @@ use std::fmt;
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::database::models::{user::User, worker::Worker};
 use crate::database::schema::*;
@@ -13,7 +14,7 @@ use crate::enums::{
 
 // A job bound to a user
 // Can be sent as JSON over websocket, and stored in postgres
-#[derive(Debug, Serialize, Deserialize, Queryable, Identifiable, Associations)]
+#[derive(Debug, Serialize, Deserialize, Queryable, Identifiable, Associations, ToSchema)]
 #[diesel(belongs_to(User))] // FK: user_id
 pub struct Job {
     pub id: i32,
@@ -45,6 +46,21 @@ pub struct Job {
     pub created_at: NaiveDateTime,
     // When job was last updated
     pub updated_at: NaiveDateTime,
+    // Scheduling priority, higher runs first
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// Seconds a job has to wait before its effective priority grows by one
+pub const PRIORITY_AGING_SECS: i64 = 30;
+
+impl Job {
+    /// Priority used by the Scheduler, aged by the time the job has been waiting
+    /// so low priority jobs can not starve behind a stream of high priority ones
+    pub fn effective_priority(&self, now: NaiveDateTime) -> i64 {
+        let waited = (now - self.created_at).num_seconds().max(0);
+        self.priority as i64 + waited / PRIORITY_AGING_SECS
+    }
 }
 
 // Display job
@@ -70,7 +86,7 @@ impl fmt::Display for Job {
 
 /// Insertable struct, some fields are created dynamically during insert
 /// Thus the need for a separate "new" struct
-#[derive(Debug, Insertable, Deserialize, Serialize)]
+#[derive(Debug, Insertable, Deserialize, Serialize, ToSchema)]
 #[diesel(table_name = jobs)]
 pub struct NewJob {
     // FK
@@ -93,12 +109,36 @@ pub struct NewJob {
     pub cron_expression: Option<String>,
     // Optional notes about job, for UI
     pub notes: Option<String>,
-    // Job state, job life cycle, submitted, queued, running, completed, ..
+    // Initial job state, decided by core on `POST /jobs`: any value sent is replaced by `Submitted`
+    #[serde(default)]
     pub state: JobStateEnum,
+    // Scheduling priority, higher runs first, defaults to 0
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// Re-run, clone a job into a fresh submission, run specific fields are left behind
+impl From<Job> for NewJob {
+    fn from(job: Job) -> Self {
+        NewJob {
+            user_id: job.user_id,
+            job_name: job.job_name,
+            image_url: job.image_url,
+            image_format: job.image_format,
+            docker_flags: job.docker_flags,
+            output_type: job.output_type,
+            output_paths: job.output_paths,
+            schedule_type: job.schedule_type,
+            cron_expression: job.cron_expression,
+            notes: job.notes,
+            state: JobStateEnum::Submitted,
+            priority: job.priority,
+        }
+    }
 }
 
 /// Assignment of a job to a worker, binds job to specific worker/runner
-#[derive(Debug, Serialize, Deserialize, Queryable, Identifiable, Associations)]
+#[derive(Debug, Serialize, Deserialize, Queryable, Identifiable, Associations, ToSchema)]
 #[diesel(belongs_to(Job))] // FK: job_id
 #[diesel(belongs_to(Worker))] // FK: worker_id
 pub struct JobAssignment {
@@ -116,7 +156,7 @@ pub struct JobAssignment {
 }
 
 /// Insertable struct, used since certain fields are generated post insert
-#[derive(Debug, Insertable, Deserialize)]
+#[derive(Debug, Insertable, Deserialize, ToSchema)]
 #[diesel(table_name = job_assignments)]
 pub struct NewJobAssignment {
     // FK
@@ -126,7 +166,7 @@ pub struct NewJobAssignment {
 }
 
 /// Holds information abuot a job result
-#[derive(Debug, Serialize, Deserialize, Queryable, Identifiable, Associations)]
+#[derive(Debug, Serialize, Deserialize, Queryable, Identifiable, Associations, ToSchema)]
 #[diesel(belongs_to(Job))] // FK: job_id
 pub struct JobResult {
     pub id: i32,
@@ -141,7 +181,7 @@ pub struct JobResult {
 }
 
 /// Insertable struct
-#[derive(Debug, Insertable, Deserialize)]
+#[derive(Debug, Insertable, Deserialize, ToSchema)]
 #[diesel(table_name = job_results)]
 pub struct NewJobResult {
     pub job_id: i32,
@@ -150,7 +190,7 @@ pub struct NewJobResult {
 }
 
 /// Holds information abuot a job metric, created after a job is assigned worker
-#[derive(Debug, Serialize, Deserialize, Queryable, Identifiable, Associations)]
+#[derive(Debug, Serialize, Deserialize, Queryable, Identifiable, Associations, ToSchema)]
 #[diesel(belongs_to(Job))] // FK: job_id
 #[diesel(belongs_to(Worker))] // FK: worker_id
 #[diesel(table_name = job_metrics)]
@@ -173,7 +213,7 @@ pub struct JobMetric {
 }
 
 /// Insertable struct
-#[derive(Debug, Insertable, Deserialize)]
+#[derive(Debug, Insertable, Deserialize, ToSchema)]
 #[diesel(table_name = job_metrics)]
 pub struct NewJobMetric {
     // FK
@@ -1,12 +1,14 @@
 use chrono::NaiveDateTime;
 use diesel::{prelude::*, Insertable};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::database::schema::*;
 use crate::enums::{log::LogActionEnum, log::LogLevelEnum, system::SystemModuleEnum};
 
 /// The LogEntry struct is the in-memory representation of the database model
-/// In-memory variant needed since the embedded payloads are not serializable?
+/// Payloads are embedded as typed structs, this is also the shape sent to external log sinks
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct LogEntry {
     pub id: i32,
     pub created_at: NaiveDateTime,
@@ -30,6 +32,7 @@ pub struct LogEntry {
 }
 
 /// Client connected payload
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ClientConnectedPayload {
     pub ip: String,
     // username of client connecting
@@ -37,6 +40,7 @@ pub struct ClientConnectedPayload {
 }
 
 /// Job submitted payload
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct JobSubmittedPayload {
     pub job_id: i32,
     // From and to module
@@ -45,6 +49,7 @@ pub struct JobSubmittedPayload {
 }
 
 /// Job completed payload
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct JobCompletedPayload {
     pub job_id: i32,
     // Whether job completed successfully
@@ -82,10 +87,13 @@ pub struct DBLogEntry {
     pub custom_msg: Option<String>, // Nullable custom message
 }
 
-// Insertable DBLogEntry
-#[derive(Debug, Insertable, Deserialize)]
+// Insertable DBLogEntry, serializable so the Logger can spill it to disk
+#[derive(Debug, Insertable, Serialize, Deserialize)]
 #[diesel(table_name = logs)]
 pub struct NewDBLogEntry {
+    // Time the entry was logged, None lets Postgres stamp it on insert
+    #[serde(default)]
+    pub created_at: Option<NaiveDateTime>,
     pub level: LogLevelEnum,
     // Module log created from
     pub module: SystemModuleEnum,
@@ -113,6 +121,7 @@ impl From<LogEntry> for NewDBLogEntry {
     fn from(log: LogEntry) -> Self {
         // Convert LogEntry (with embedded payloads) to DBLogEntry (raw database model)
         NewDBLogEntry {
+            created_at: Some(log.created_at),
             level: log.level,
             module: log.module,
             action: log.action,
@@ -123,14 +132,8 @@ impl From<LogEntry> for NewDBLogEntry {
                 .as_ref()
                 .map(|p| p.username.clone()),
             job_submitted_job_id: log.job_submitted_payload.as_ref().map(|p| p.job_id),
-            job_submitted_from_module: log
-                .job_submitted_payload
-                .as_ref()
-                .map(|p| p.from_module.clone()),
-            job_submitted_to_module: log
-                .job_submitted_payload
-                .as_ref()
-                .map(|p| p.to_module.clone()),
+            job_submitted_from_module: log.job_submitted_payload.as_ref().map(|p| p.from_module),
+            job_submitted_to_module: log.job_submitted_payload.as_ref().map(|p| p.to_module),
             job_completed_job_id: log.job_completed_payload.as_ref().map(|p| p.job_id),
             job_completed_success: log.job_completed_payload.as_ref().map(|p| p.success),
             custom_msg: log.custom_msg,
@@ -184,9 +187,10 @@ impl From<DBLogEntry> for LogEntry {
 impl From<&LogEntry> for NewDBLogEntry {
     fn from(log: &LogEntry) -> Self {
         NewDBLogEntry {
-            level: log.level.clone(),
-            module: log.module.clone(),
-            action: log.action.clone(),
+            created_at: Some(log.created_at),
+            level: log.level,
+            module: log.module,
+            action: log.action,
             expires_at: log.expires_at,
 
             client_connected_ip: log.client_connected_payload.as_ref().map(|p| p.ip.clone()),
@@ -196,14 +200,8 @@ impl From<&LogEntry> for NewDBLogEntry {
                 .map(|p| p.username.clone()),
 
             job_submitted_job_id: log.job_submitted_payload.as_ref().map(|p| p.job_id),
-            job_submitted_from_module: log
-                .job_submitted_payload
-                .as_ref()
-                .map(|p| p.from_module.clone()),
-            job_submitted_to_module: log
-                .job_submitted_payload
-                .as_ref()
-                .map(|p| p.to_module.clone()),
+            job_submitted_from_module: log.job_submitted_payload.as_ref().map(|p| p.from_module),
+            job_submitted_to_module: log.job_submitted_payload.as_ref().map(|p| p.to_module),
 
             job_completed_job_id: log.job_completed_payload.as_ref().map(|p| p.job_id),
             job_completed_success: log.job_completed_payload.as_ref().map(|p| p.success),
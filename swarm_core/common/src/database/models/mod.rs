@@ -1,4 +1,10 @@
+pub mod archive;
+pub mod audit;
 pub mod job;
 pub mod log;
+pub mod quota;
+pub mod retention;
+pub mod template;
 pub mod user;
+pub mod webhook;
 pub mod worker;
@@ -0,0 +1,208 @@
+use std::fmt;
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::database::models::user::User;
+use crate::database::schema::*;
+
+/// Limits applied to users without a row in `user_quotas`
+pub const DEFAULT_MAX_QUEUED_JOBS: i32 = 50;
+pub const DEFAULT_MAX_RUNNING_JOBS: i32 = 5;
+pub const DEFAULT_MAX_SUBMISSIONS_PER_MINUTE: i32 = 20;
+
+/// Retry hint handed out when the queue is full, a slot frees up once the Scheduler moves on
+pub const QUEUE_FULL_RETRY_SECS: i64 = 30;
+
+/// Length of the sliding window used for the submission rate limit
+pub const RATE_WINDOW_SECS: i64 = 60;
+
+/// Job quota bound to a user, limits how much of the swarm a single user can occupy
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Associations, ToSchema)]
+#[diesel(belongs_to(User))] // FK: user_id
+pub struct UserQuota {
+    pub id: i32,
+    // FK
+    pub user_id: i32,
+    // Max jobs waiting to run, Submitted + Queued
+    pub max_queued_jobs: i32,
+    // Max jobs running at the same time, enforced by the Scheduler
+    pub max_running_jobs: i32,
+    // Max POST /jobs per minute
+    pub max_submissions_per_minute: i32,
+    // When quota was last changed
+    pub updated_at: NaiveDateTime,
+}
+
+impl UserQuota {
+    /// Quota used when no explicit quota has been set for the user
+    pub fn default_for(user_id: i32) -> Self {
+        UserQuota {
+            id: 0,
+            user_id,
+            max_queued_jobs: DEFAULT_MAX_QUEUED_JOBS,
+            max_running_jobs: DEFAULT_MAX_RUNNING_JOBS,
+            max_submissions_per_minute: DEFAULT_MAX_SUBMISSIONS_PER_MINUTE,
+            updated_at: Utc::now().naive_utc(),
+        }
+    }
+
+    /// Check if the user may submit another job
+    /// # Arguments
+    /// * `usage` - The current usage of the user
+    /// * `now` - The current time, used for the rate limit retry hint
+    /// # Returns
+    /// * `Result<(), QuotaExceeded>` - Err holds the limit hit and when to retry
+    pub fn check_submission(
+        &self,
+        usage: &QuotaUsage,
+        now: NaiveDateTime,
+    ) -> Result<(), QuotaExceeded> {
+        if usage.submissions_last_minute >= self.max_submissions_per_minute as i64 {
+            // the window frees up a slot once the oldest submission in it ages out
+            let retry_after_secs = usage
+                .oldest_submission_in_window
+                .map(|oldest| RATE_WINDOW_SECS - (now - oldest).num_seconds())
+                .unwrap_or(RATE_WINDOW_SECS)
+                .clamp(1, RATE_WINDOW_SECS);
+            return Err(QuotaExceeded {
+                quota: "max_submissions_per_minute".to_string(),
+                limit: self.max_submissions_per_minute,
+                current: usage.submissions_last_minute,
+                retry_after_secs,
+            });
+        }
+        if usage.queued_jobs >= self.max_queued_jobs as i64 {
+            return Err(QuotaExceeded {
+                quota: "max_queued_jobs".to_string(),
+                limit: self.max_queued_jobs,
+                current: usage.queued_jobs,
+                retry_after_secs: QUEUE_FULL_RETRY_SECS,
+            });
+        }
+        Ok(())
+    }
+
+    /// Number of jobs the Scheduler may still admit for this user
+    /// # Arguments
+    /// * `admitted` - Jobs already admitted, Queued + Running
+    /// # Returns
+    /// * `i64` - Free slots, never negative
+    pub fn free_running_slots(&self, admitted: i64) -> i64 {
+        (self.max_running_jobs as i64 - admitted).max(0)
+    }
+}
+
+/// Insertable/updatable quota, sent as JSON when setting a quota for a user
+#[derive(Debug, Clone, Serialize, Deserialize, Insertable, AsChangeset, ToSchema)]
+#[diesel(table_name = user_quotas)]
+pub struct NewUserQuota {
+    // FK
+    pub user_id: i32,
+    pub max_queued_jobs: i32,
+    pub max_running_jobs: i32,
+    pub max_submissions_per_minute: i32,
+}
+
+/// Current usage of a user, counted from the jobs table
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct QuotaUsage {
+    // Jobs waiting to run, Submitted + Queued
+    pub queued_jobs: i64,
+    // Jobs running right now
+    pub running_jobs: i64,
+    // Jobs created during the last minute
+    pub submissions_last_minute: i64,
+    // Creation time of the oldest job inside the rate window
+    pub oldest_submission_in_window: Option<NaiveDateTime>,
+}
+
+/// Quota together with usage, returned by `GET /quotas/<user_id>`
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct QuotaStatus {
+    pub quota: UserQuota,
+    pub usage: QuotaUsage,
+}
+
+/// A quota that was hit, carries what to tell the client
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct QuotaExceeded {
+    // Name of the limit that was hit, matches the field on `UserQuota`
+    pub quota: String,
+    pub limit: i32,
+    pub current: i64,
+    // Seconds until a retry could succeed
+    pub retry_after_secs: i64,
+}
+
+// Display quota violation
+impl fmt::Display for QuotaExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Quota {} exceeded ({}/{}), retry in {}s",
+            self.quota, self.current, self.limit, self.retry_after_secs
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn quota() -> UserQuota {
+        UserQuota {
+            max_queued_jobs: 2,
+            max_running_jobs: 1,
+            max_submissions_per_minute: 3,
+            ..UserQuota::default_for(1)
+        }
+    }
+
+    #[test]
+    fn submission_within_limits_is_allowed() {
+        let usage = QuotaUsage {
+            queued_jobs: 1,
+            submissions_last_minute: 2,
+            ..Default::default()
+        };
+        assert!(quota()
+            .check_submission(&usage, Utc::now().naive_utc())
+            .is_ok());
+    }
+
+    #[test]
+    fn rate_limit_retry_hint_follows_oldest_submission() {
+        let now = Utc::now().naive_utc();
+        let usage = QuotaUsage {
+            submissions_last_minute: 3,
+            oldest_submission_in_window: Some(now - Duration::seconds(45)),
+            ..Default::default()
+        };
+        let err = quota().check_submission(&usage, now).unwrap_err();
+        assert_eq!(err.quota, "max_submissions_per_minute");
+        assert_eq!(err.retry_after_secs, 15);
+    }
+
+    #[test]
+    fn full_queue_is_rejected() {
+        let usage = QuotaUsage {
+            queued_jobs: 2,
+            ..Default::default()
+        };
+        let err = quota()
+            .check_submission(&usage, Utc::now().naive_utc())
+            .unwrap_err();
+        assert_eq!(err.quota, "max_queued_jobs");
+        assert_eq!(err.retry_after_secs, QUEUE_FULL_RETRY_SECS);
+    }
+
+    #[test]
+    fn free_running_slots_never_negative() {
+        assert_eq!(quota().free_running_slots(0), 1);
+        assert_eq!(quota().free_running_slots(4), 0);
+    }
+}
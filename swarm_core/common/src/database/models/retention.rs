@@ -0,0 +1,160 @@
+use chrono::{Duration, NaiveDateTime};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::database::schema::log_retention_policies;
+use crate::enums::{log::LogActionEnum, log::LogLevelEnum, system::SystemModuleEnum};
+
+/// How long log entries matching a level, module and action are kept
+/// An empty field matches everything, the most specific matching policy wins.
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, ToSchema)]
+#[diesel(table_name = log_retention_policies)]
+pub struct LogRetentionPolicy {
+    pub id: i32,
+    pub level: Option<LogLevelEnum>,
+    pub module: Option<SystemModuleEnum>,
+    pub action: Option<LogActionEnum>,
+    // Minutes an entry is kept before it expires
+    pub retention_minutes: i64,
+    // When the policy was last changed
+    pub updated_at: NaiveDateTime,
+}
+
+/// Insertable retention policy, (level, module, action) is unique
+#[derive(Debug, Clone, Serialize, Deserialize, Insertable, ToSchema)]
+#[diesel(table_name = log_retention_policies)]
+pub struct NewLogRetentionPolicy {
+    pub level: Option<LogLevelEnum>,
+    pub module: Option<SystemModuleEnum>,
+    pub action: Option<LogActionEnum>,
+    pub retention_minutes: i64,
+}
+
+impl LogRetentionPolicy {
+    /// Whether the policy applies to an entry
+    pub fn matches(
+        &self,
+        level: &LogLevelEnum,
+        module: &SystemModuleEnum,
+        action: &LogActionEnum,
+    ) -> bool {
+        self.level.as_ref().is_none_or(|l| l == level)
+            && self.module.as_ref().is_none_or(|m| m == module)
+            && self.action.as_ref().is_none_or(|a| a == action)
+    }
+
+    /// Higher is more specific, an action outweighs a module, a module outweighs a level
+    pub fn specificity(&self) -> u8 {
+        u8::from(self.action.is_some()) * 4
+            + u8::from(self.module.is_some()) * 2
+            + u8::from(self.level.is_some())
+    }
+
+    /// Retention as a duration
+    pub fn retention(&self) -> Duration {
+        Duration::minutes(self.retention_minutes)
+    }
+}
+
+/// Retention used when no policy matches, the expiry the Logger used before policies existed
+pub fn default_retention(level: &LogLevelEnum) -> Duration {
+    match level {
+        LogLevelEnum::Info => Duration::minutes(5),
+        LogLevelEnum::Success => Duration::days(1),
+        LogLevelEnum::Warning => Duration::days(3),
+        LogLevelEnum::Error | LogLevelEnum::Fatal => Duration::days(7),
+    }
+}
+
+/// Retention for an entry
+/// # Arguments
+/// * `policies` - All policies, in any order
+/// * `level`, `module`, `action` - The entry
+/// # Returns
+/// * `Duration` - retention of the most specific matching policy, or `default_retention(level)`
+pub fn resolve_retention(
+    policies: &[LogRetentionPolicy],
+    level: &LogLevelEnum,
+    module: &SystemModuleEnum,
+    action: &LogActionEnum,
+) -> Duration {
+    policies
+        .iter()
+        .filter(|p| p.matches(level, module, action))
+        .max_by_key(|p| p.specificity())
+        .map(LogRetentionPolicy::retention)
+        .unwrap_or_else(|| default_retention(level))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(
+        level: Option<LogLevelEnum>,
+        module: Option<SystemModuleEnum>,
+        action: Option<LogActionEnum>,
+        retention_minutes: i64,
+    ) -> LogRetentionPolicy {
+        LogRetentionPolicy {
+            id: 0,
+            level,
+            module,
+            action,
+            retention_minutes,
+            updated_at: NaiveDateTime::default(),
+        }
+    }
+
+    #[test]
+    fn most_specific_policy_wins() {
+        let policies = vec![
+            policy(Some(LogLevelEnum::Info), None, None, 5),
+            policy(None, Some(SystemModuleEnum::Scheduler), None, 60),
+            policy(
+                Some(LogLevelEnum::Info),
+                None,
+                Some(LogActionEnum::Custom),
+                120,
+            ),
+        ];
+        let resolve = |level, module, action| {
+            resolve_retention(&policies, &level, &module, &action).num_minutes()
+        };
+
+        assert_eq!(
+            resolve(
+                LogLevelEnum::Info,
+                SystemModuleEnum::Dispatcher,
+                LogActionEnum::Custom
+            ),
+            120
+        );
+        assert_eq!(
+            resolve(
+                LogLevelEnum::Info,
+                SystemModuleEnum::Scheduler,
+                LogActionEnum::JobSubmitted
+            ),
+            60
+        );
+        assert_eq!(
+            resolve(
+                LogLevelEnum::Info,
+                SystemModuleEnum::Dispatcher,
+                LogActionEnum::JobSubmitted
+            ),
+            5
+        );
+        // nothing matches: hard-coded default
+        assert_eq!(
+            resolve(
+                LogLevelEnum::Warning,
+                SystemModuleEnum::Dispatcher,
+                LogActionEnum::JobSubmitted
+            ),
+            3 * 24 * 60
+        );
+    }
+}
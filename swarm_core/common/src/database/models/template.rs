@@ -0,0 +1,395 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::database::models::job::NewJob;
+use crate::database::models::user::User;
+use crate::database::schema::*;
+use crate::enums::{
+    image_format::ImageFormatEnum, job::JobStateEnum, output::OutputTypeEnum,
+    schedule::ScheduleTypeEnum, template::TemplateParamKindEnum,
+};
+use crate::validation::is_image_tag;
+
+/// A saved job bound to a user, rendered into a `NewJob` with parameter values on run
+#[derive(Debug, Serialize, Deserialize, Queryable, Identifiable, Associations, ToSchema)]
+#[diesel(belongs_to(User))] // FK: user_id
+pub struct JobTemplate {
+    pub id: i32,
+    // FK
+    pub user_id: i32,
+    // Template name, unique per user
+    pub name: String,
+    // Docker image url, may hold `{{param}}` placeholders
+    pub image_url: String,
+    // Docker image format, either tarball or dockerfile
+    pub image_format: ImageFormatEnum,
+    // Docker flags, may hold `{{param}}` placeholders
+    pub docker_flags: Option<Vec<Option<String>>>,
+    // Output type, either stdout or files
+    pub output_type: OutputTypeEnum,
+    // If files, list files from where to collect
+    pub output_paths: Option<Vec<Option<String>>>,
+    // Schedule type, once or cron
+    pub schedule_type: ScheduleTypeEnum,
+    // If cron, cron expression i.e. * * * * *
+    pub cron_expression: Option<String>,
+    // Optional notes, may hold `{{param}}` placeholders
+    pub notes: Option<String>,
+    // Priority given to rendered jobs
+    pub priority: i32,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+/// Insertable template
+#[derive(Debug, Insertable, Deserialize, Serialize, ToSchema)]
+#[diesel(table_name = job_templates)]
+pub struct NewJobTemplate {
+    // FK
+    pub user_id: i32,
+    pub name: String,
+    pub image_url: String,
+    pub image_format: ImageFormatEnum,
+    pub docker_flags: Option<Vec<Option<String>>>,
+    pub output_type: OutputTypeEnum,
+    pub output_paths: Option<Vec<Option<String>>>,
+    pub schedule_type: ScheduleTypeEnum,
+    pub cron_expression: Option<String>,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// Typed parameter of a template
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Associations, ToSchema)]
+#[diesel(belongs_to(JobTemplate, foreign_key = template_id))] // FK: template_id
+#[diesel(table_name = job_template_parameters)]
+pub struct TemplateParameter {
+    pub id: i32,
+    // FK
+    pub template_id: i32,
+    // Name, for Env the variable name, for Text the `{{name}}` placeholder
+    pub name: String,
+    // Where the value ends up in the rendered job
+    pub kind: TemplateParamKindEnum,
+    // Used when no override is given
+    pub default_value: Option<String>,
+    // Run fails if no value can be found for a required parameter
+    pub required: bool,
+}
+
+/// Insertable parameter
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = job_template_parameters)]
+pub struct NewTemplateParameter {
+    // FK
+    pub template_id: i32,
+    pub name: String,
+    pub kind: TemplateParamKindEnum,
+    pub default_value: Option<String>,
+    pub required: bool,
+}
+
+/// Parameter definition sent when creating a template, template id is not known yet
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TemplateParameterSpec {
+    pub name: String,
+    pub kind: TemplateParamKindEnum,
+    #[serde(default)]
+    pub default_value: Option<String>,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+impl TemplateParameterSpec {
+    /// Check the parameter name and default value against its kind
+    pub fn validate(&self) -> Result<(), TemplateError> {
+        if self.kind == TemplateParamKindEnum::Env && !is_env_name(&self.name) {
+            return Err(TemplateError::InvalidName(self.name.clone()));
+        }
+        if self.name.trim().is_empty() {
+            return Err(TemplateError::InvalidName(self.name.clone()));
+        }
+        match &self.default_value {
+            Some(value) => validate_value(&self.name, &self.kind, value),
+            None => Ok(()),
+        }
+    }
+
+    /// Bind the spec to a stored template
+    pub fn into_new(self, template_id: i32) -> NewTemplateParameter {
+        NewTemplateParameter {
+            template_id,
+            name: self.name,
+            kind: self.kind,
+            default_value: self.default_value,
+            required: self.required,
+        }
+    }
+}
+
+/// Body of `POST /templates`
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateTemplateRequest {
+    #[serde(flatten)]
+    pub template: NewJobTemplate,
+    #[serde(default)]
+    pub parameters: Vec<TemplateParameterSpec>,
+}
+
+/// Template together with its parameters
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TemplateWithParameters {
+    pub template: JobTemplate,
+    pub parameters: Vec<TemplateParameter>,
+}
+
+/// Body of `POST /templates/<id>/run`
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct RunTemplateRequest {
+    // Name of the created job, defaults to the template name with a timestamp
+    #[serde(default)]
+    pub job_name: Option<String>,
+    // Parameter name → value, overrides the defaults
+    #[serde(default)]
+    pub overrides: HashMap<String, String>,
+}
+
+/// Errors while validating or rendering a template
+#[derive(Debug, Error, PartialEq)]
+pub enum TemplateError {
+    #[error("missing value for required parameter '{0}'")]
+    MissingParameter(String),
+    #[error("unknown parameter '{0}'")]
+    UnknownParameter(String),
+    #[error("invalid parameter name '{0}'")]
+    InvalidName(String),
+    #[error("invalid value for parameter '{name}': {reason}")]
+    InvalidValue { name: String, reason: String },
+}
+
+impl TemplateWithParameters {
+    /// Render the template into a job for `user_id`, applying overrides on top of defaults
+    /// # Arguments
+    /// * `run` - Job name and parameter overrides
+    /// * `user_id` - Owner of the rendered job
+    /// # Returns
+    /// * `Result<NewJob, TemplateError>`
+    pub fn render(self, run: RunTemplateRequest, user_id: i32) -> Result<NewJob, TemplateError> {
+        let TemplateWithParameters {
+            template,
+            parameters,
+        } = self;
+
+        if let Some(unknown) = run
+            .overrides
+            .keys()
+            .find(|k| !parameters.iter().any(|p| &p.name == *k))
+        {
+            return Err(TemplateError::UnknownParameter(unknown.clone()));
+        }
+
+        let mut image_url = template.image_url;
+        let mut docker_flags: Vec<Option<String>> = template.docker_flags.unwrap_or_default();
+        let mut notes = template.notes;
+
+        for param in &parameters {
+            let value = match run
+                .overrides
+                .get(&param.name)
+                .or(param.default_value.as_ref())
+            {
+                Some(v) => v.clone(),
+                None if param.required => {
+                    return Err(TemplateError::MissingParameter(param.name.clone()))
+                }
+                None => continue,
+            };
+            validate_value(&param.name, &param.kind, &value)?;
+
+            match param.kind {
+                TemplateParamKindEnum::ImageTag => image_url = with_image_tag(&image_url, &value),
+                TemplateParamKindEnum::Env => {
+                    docker_flags.push(Some(format!("--env={}={}", param.name, value)))
+                }
+                TemplateParamKindEnum::Text => {
+                    let placeholder = format!("{{{{{}}}}}", param.name);
+                    image_url = image_url.replace(&placeholder, &value);
+                    notes = notes.map(|n| n.replace(&placeholder, &value));
+                    for flag in docker_flags.iter_mut().flatten() {
+                        *flag = flag.replace(&placeholder, &value);
+                    }
+                }
+            }
+        }
+
+        let job_name = run.job_name.unwrap_or_else(|| {
+            format!(
+                "{}-{}",
+                template.name,
+                Utc::now().naive_utc().format("%Y%m%d%H%M%S")
+            )
+        });
+
+        Ok(NewJob {
+            user_id,
+            job_name,
+            image_url,
+            image_format: template.image_format,
+            docker_flags: if docker_flags.is_empty() {
+                None
+            } else {
+                Some(docker_flags)
+            },
+            output_type: template.output_type,
+            output_paths: template.output_paths,
+            schedule_type: template.schedule_type,
+            cron_expression: template.cron_expression,
+            notes,
+            state: JobStateEnum::Submitted,
+            priority: template.priority,
+        })
+    }
+}
+
+/// Check a parameter value against its kind
+fn validate_value(
+    name: &str,
+    kind: &TemplateParamKindEnum,
+    value: &str,
+) -> Result<(), TemplateError> {
+    let invalid = |reason: &str| TemplateError::InvalidValue {
+        name: name.to_string(),
+        reason: reason.to_string(),
+    };
+    match kind {
+        TemplateParamKindEnum::ImageTag => {
+            if !is_image_tag(value) {
+                return Err(invalid("not a valid image tag"));
+            }
+        }
+        TemplateParamKindEnum::Env | TemplateParamKindEnum::Text => {
+            if value.contains('\n') || value.contains('\0') {
+                return Err(invalid("must be a single line"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Env variable names, [A-Za-z_][A-Za-z0-9_]*
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Replace (or add) the tag of an image reference, `repo/name:old` → `repo/name:new`
+fn with_image_tag(image_url: &str, tag: &str) -> String {
+    // digest pins the image, drop it since the tag is chosen explicitly
+    let without_digest = image_url.split('@').next().unwrap_or(image_url);
+    // a colon after the last slash is a tag, before it is a registry port
+    let name_start = without_digest.rfind('/').map(|i| i + 1).unwrap_or(0);
+    let base = match without_digest[name_start..].find(':') {
+        Some(i) => &without_digest[..name_start + i],
+        None => without_digest,
+    };
+    format!("{}:{}", base, tag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(parameters: Vec<TemplateParameter>) -> TemplateWithParameters {
+        let now = Utc::now().naive_utc();
+        TemplateWithParameters {
+            template: JobTemplate {
+                id: 1,
+                user_id: 1,
+                name: "nightly".into(),
+                image_url: "registry:5000/team/{{app}}:latest".into(),
+                image_format: ImageFormatEnum::DockerRegistry,
+                docker_flags: None,
+                output_type: OutputTypeEnum::Stdout,
+                output_paths: None,
+                schedule_type: ScheduleTypeEnum::Once,
+                cron_expression: None,
+                notes: Some("build of {{app}}".into()),
+                priority: 3,
+                created_at: now,
+                updated_at: now,
+            },
+            parameters,
+        }
+    }
+
+    fn param(name: &str, kind: TemplateParamKindEnum, default: Option<&str>) -> TemplateParameter {
+        TemplateParameter {
+            id: 0,
+            template_id: 1,
+            name: name.into(),
+            kind,
+            default_value: default.map(String::from),
+            required: true,
+        }
+    }
+
+    #[test]
+    fn render_applies_defaults_and_overrides() {
+        let t = template(vec![
+            param("app", TemplateParamKindEnum::Text, Some("api")),
+            param("tag", TemplateParamKindEnum::ImageTag, Some("v1")),
+            param("MODE", TemplateParamKindEnum::Env, None),
+        ]);
+        let run = RunTemplateRequest {
+            job_name: Some("run-1".into()),
+            overrides: HashMap::from([
+                ("tag".to_string(), "v2".to_string()),
+                ("MODE".to_string(), "fast".to_string()),
+            ]),
+        };
+        let job = t.render(run, 7).unwrap();
+        assert_eq!(job.user_id, 7);
+        assert_eq!(job.image_url, "registry:5000/team/api:v2");
+        assert_eq!(job.notes.as_deref(), Some("build of api"));
+        assert_eq!(job.docker_flags, Some(vec![Some("--env=MODE=fast".into())]));
+        assert_eq!(job.priority, 3);
+    }
+
+    #[test]
+    fn render_rejects_missing_and_unknown_parameters() {
+        let missing = template(vec![param("MODE", TemplateParamKindEnum::Env, None)])
+            .render(RunTemplateRequest::default(), 1)
+            .unwrap_err();
+        assert_eq!(missing, TemplateError::MissingParameter("MODE".into()));
+
+        let run = RunTemplateRequest {
+            overrides: HashMap::from([("nope".to_string(), "x".to_string())]),
+            ..Default::default()
+        };
+        let unknown = template(vec![]).render(run, 1).unwrap_err();
+        assert_eq!(unknown, TemplateError::UnknownParameter("nope".into()));
+    }
+
+    #[test]
+    fn image_tag_is_validated() {
+        let run = RunTemplateRequest {
+            overrides: HashMap::from([("tag".to_string(), "bad tag".to_string())]),
+            ..Default::default()
+        };
+        let err = template(vec![param("tag", TemplateParamKindEnum::ImageTag, None)])
+            .render(run, 1)
+            .unwrap_err();
+        assert!(matches!(err, TemplateError::InvalidValue { .. }));
+    }
+}
@@ -9,6 +9,7 @@ use rocket::Request;
 use rocket_db_pools::deadpool_redis::redis::AsyncCommands;
 use rocket_db_pools::Connection;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 /// User model, the user owning the seassion, connected to an account
 #[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable)]
@@ -76,7 +77,7 @@ pub struct NewUser {
 
 /// Updateable user, model for updating a user through JSON,
 /// Needed since we want optional password when updating
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct UpdateUserRequest {
     pub username: String,
     pub email: String,
@@ -85,7 +86,7 @@ pub struct UpdateUserRequest {
 
 /// New user request, used with JSON
 /// Needed since we dont want derived traits to clash
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct NewUserRequest {
     pub username: String,
     pub email: String,
@@ -94,7 +95,7 @@ pub struct NewUserRequest {
 
 /// User response returned by routes,
 /// Needed since password is not returned
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct UserResponse {
     pub id: i32,
     pub username: String,
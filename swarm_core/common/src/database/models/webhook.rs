@@ -0,0 +1,143 @@
+use chrono::{Duration, NaiveDateTime};
+use diesel::prelude::*;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use serde_json::Value;
+
+use crate::database::models::user::User;
+use crate::database::schema::{webhook_deliveries, webhooks};
+use crate::enums::webhook::{DeliveryStatusEnum, WebhookEventEnum};
+
+/// Length of generated signing secrets
+pub const WEBHOOK_SECRET_LEN: usize = 48;
+/// Attempts before a delivery is given up on
+pub const DEFAULT_MAX_DELIVERY_ATTEMPTS: i32 = 8;
+/// Delay before the first retry, doubled on every further attempt
+pub const RETRY_BASE_SECS: i64 = 10;
+/// Upper bound for the delay between two attempts
+pub const RETRY_MAX_SECS: i64 = 3600;
+
+/// A webhook registered by a user, called for every subscribed event on the users jobs and workers
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Associations, ToSchema)]
+#[diesel(belongs_to(User))] // FK: user_id
+#[diesel(table_name = webhooks)]
+pub struct Webhook {
+    pub id: i32,
+    // FK, owner, only the owners events are delivered
+    pub user_id: i32,
+    // receiver, called with POST
+    pub url: String,
+    // HMAC-SHA256 key, only handed out when the webhook is created
+    #[serde(skip_serializing, default)]
+    pub secret: String,
+    // subscribed events
+    pub events: Vec<WebhookEventEnum>,
+    // paused webhooks get no new deliveries
+    pub active: bool,
+    pub created_at: NaiveDateTime,
+}
+
+impl Webhook {
+    /// Whether the webhook wants an event
+    pub fn subscribes_to(&self, event: &WebhookEventEnum) -> bool {
+        self.active && self.events.contains(event)
+    }
+
+    /// Random alphanumeric signing secret
+    pub fn generate_secret() -> String {
+        rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(WEBHOOK_SECRET_LEN)
+            .map(char::from)
+            .collect()
+    }
+}
+
+/// Insertable webhook
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = webhooks)]
+pub struct NewWebhook {
+    pub user_id: i32,
+    pub url: String,
+    pub secret: String,
+    pub events: Vec<WebhookEventEnum>,
+}
+
+/// Body of a webhook registration, the owner is the caller and the secret is generated if missing
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct WebhookRequest {
+    pub url: String,
+    pub events: Vec<WebhookEventEnum>,
+    pub secret: Option<String>,
+}
+
+/// Partial update of a webhook, missing fields are left untouched
+#[derive(Debug, Clone, Default, Deserialize, AsChangeset, ToSchema)]
+#[diesel(table_name = webhooks)]
+pub struct UpdateWebhook {
+    pub url: Option<String>,
+    pub events: Option<Vec<WebhookEventEnum>>,
+    pub active: Option<bool>,
+}
+
+/// One event sent to one webhook, the delivery log
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Associations, ToSchema)]
+#[diesel(belongs_to(Webhook))] // FK: webhook_id
+#[diesel(table_name = webhook_deliveries)]
+pub struct WebhookDelivery {
+    pub id: i32,
+    // FK
+    pub webhook_id: i32,
+    pub event: WebhookEventEnum,
+    // signed and sent as the request body
+    pub payload: Value,
+    pub status: DeliveryStatusEnum,
+    // attempts made so far
+    pub attempts: i32,
+    // HTTP status of the last attempt, None if the receiver could not be reached
+    pub response_code: Option<i32>,
+    pub last_error: Option<String>,
+    // when a Pending delivery is tried next
+    pub next_attempt_at: NaiveDateTime,
+    pub created_at: NaiveDateTime,
+    pub delivered_at: Option<NaiveDateTime>,
+}
+
+/// Insertable delivery, starts Pending and due right away
+#[derive(Debug, Clone, Insertable)]
+#[diesel(table_name = webhook_deliveries)]
+pub struct NewWebhookDelivery {
+    pub webhook_id: i32,
+    pub event: WebhookEventEnum,
+    pub payload: Value,
+}
+
+/// Delay before the next attempt after `attempts` failed ones, exponential and capped
+/// # Arguments
+/// * `attempts` - Failed attempts so far, at least 1
+/// # Returns
+/// * `Duration` - 10s, 20s, 40s ... at most one hour
+pub fn retry_delay(attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 20) as u32;
+    Duration::seconds(
+        RETRY_BASE_SECS
+            .saturating_mul(2_i64.pow(exponent))
+            .min(RETRY_MAX_SECS),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_delay_doubles_and_caps() {
+        assert_eq!(retry_delay(1).num_seconds(), 10);
+        assert_eq!(retry_delay(2).num_seconds(), 20);
+        assert_eq!(retry_delay(4).num_seconds(), 80);
+        assert_eq!(retry_delay(12).num_seconds(), RETRY_MAX_SECS);
+        assert_eq!(retry_delay(i32::MAX).num_seconds(), RETRY_MAX_SECS);
+    }
+}
@@ -9,13 +9,14 @@ use std::fmt;
 use chrono::NaiveDateTime;
 use diesel::{Associations, Identifiable, Insertable, Queryable};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::database::models::user::User;
 use crate::database::schema::{worker_status, workers};
 use crate::enums::workers::{OSEnum, WorkerStatusEnum};
 
 /// Worker model, represents a connected swarm worker.
-#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Associations)]
+#[derive(Debug, Clone, Serialize, Deserialize, Queryable, Identifiable, Associations, ToSchema)]
 #[diesel(belongs_to(User))] // FK: user_id
 #[diesel(table_name = workers)]
 pub struct Worker {
@@ -66,7 +67,7 @@ impl fmt::Display for Worker {
 }
 
 // Insertable
-#[derive(Debug, Insertable, Deserialize)]
+#[derive(Debug, Insertable, Deserialize, ToSchema)]
 #[diesel(table_name = workers)]
 pub struct NewWorker {
     pub user_id: i32,
@@ -92,7 +93,7 @@ pub struct NewWorker {
 
 // Connects a worker/runner to a status.
 // only one status per worker
-#[derive(Debug, Serialize, Deserialize, Queryable, Identifiable, Associations)]
+#[derive(Debug, Serialize, Deserialize, Queryable, Identifiable, Associations, ToSchema)]
 #[diesel(belongs_to(Worker))]
 #[diesel(table_name = worker_status)]
 pub struct WorkerStatus {
@@ -117,7 +118,7 @@ pub struct WorkerStatus {
 }
 
 // Insertable
-#[derive(Debug, Insertable, Deserialize)]
+#[derive(Debug, Insertable, Deserialize, ToSchema)]
 #[diesel(table_name = worker_status)]
 pub struct NewWorkerStatus {
     // FK
@@ -0,0 +1,224 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Integer, Timestamp};
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde_json::Value;
+
+use crate::database::models::archive::{ArchivedJob, JobArchive};
+use crate::database::models::job::Job;
+use crate::database::repositories::listing::{FieldKind, ListField, ListQuery, ListSchema};
+use crate::database::schema::*;
+use crate::enums::job::JobStateEnum;
+
+/// Fields of `GET /archive/jobs`
+pub static ARCHIVE_LIST: ListSchema = ListSchema {
+    fields: &[
+        ListField {
+            name: "id",
+            column: "job_archives.id",
+            kind: FieldKind::Int,
+            sort_key: Some("id"),
+        },
+        ListField {
+            name: "user",
+            column: "job_archives.user_id",
+            kind: FieldKind::Int,
+            sort_key: Some("user_id"),
+        },
+        ListField {
+            name: "name",
+            column: "job_archives.job_name",
+            kind: FieldKind::Text,
+            sort_key: Some("job_name"),
+        },
+        ListField {
+            name: "image",
+            column: "job_archives.image_url",
+            kind: FieldKind::Text,
+            sort_key: None,
+        },
+        ListField {
+            name: "state",
+            column: "job_archives.state",
+            kind: FieldKind::Enum(JobStateEnum::variants),
+            sort_key: None,
+        },
+        ListField {
+            name: "created",
+            column: "job_archives.created_at",
+            kind: FieldKind::Timestamp,
+            sort_key: Some("created_at"),
+        },
+        ListField {
+            name: "finished",
+            column: "job_archives.finished_at",
+            kind: FieldKind::Timestamp,
+            sort_key: Some("finished_at"),
+        },
+        ListField {
+            name: "archived",
+            column: "job_archives.archived_at",
+            kind: FieldKind::Timestamp,
+            sort_key: Some("archived_at"),
+        },
+    ],
+    id_column: "job_archives.id",
+    default_sort: "-finished",
+};
+
+// Moves a batch of finished jobs into `job_archives` in one statement. Every part of a statement
+// sees the same snapshot, so the snapshot is taken before the delete cascades to the child rows.
+// The cascade also reaches the log entries naming the job, they are kept in the snapshot as well.
+const ARCHIVE_FINISHED: &str = "
+WITH picked AS (
+    SELECT id FROM jobs
+    WHERE (state = 'Completed' AND updated_at < $1) OR (state = 'Failed' AND updated_at < $2)
+    ORDER BY updated_at, id
+    LIMIT $3
+    FOR UPDATE SKIP LOCKED
+), archived AS (
+    INSERT INTO job_archives
+        (id, user_id, job_name, image_url, state, error_message, created_at, finished_at, result_count, data)
+    SELECT j.id, j.user_id, j.job_name, j.image_url, j.state, j.error_message, j.created_at, j.updated_at,
+        (SELECT COUNT(*) FROM job_results r WHERE r.job_id = j.id),
+        jsonb_build_object(
+            'job', to_jsonb(j),
+            'results', COALESCE((SELECT jsonb_agg(to_jsonb(r) ORDER BY r.id) FROM job_results r WHERE r.job_id = j.id), '[]'::jsonb),
+            'metrics', COALESCE((SELECT jsonb_agg(to_jsonb(m) ORDER BY m.id) FROM job_metrics m WHERE m.job_id = j.id), '[]'::jsonb),
+            'assignments', COALESCE((SELECT jsonb_agg(to_jsonb(a) ORDER BY a.id) FROM job_assignments a WHERE a.job_id = j.id), '[]'::jsonb),
+            'logs', COALESCE((SELECT jsonb_agg(to_jsonb(l) ORDER BY l.id) FROM logs l WHERE l.job_submitted_job_id = j.id OR l.job_completed_job_id = j.id), '[]'::jsonb)
+        )
+    FROM jobs j JOIN picked p ON p.id = j.id
+    ON CONFLICT (id) DO UPDATE SET
+        user_id = EXCLUDED.user_id, job_name = EXCLUDED.job_name, image_url = EXCLUDED.image_url,
+        state = EXCLUDED.state, error_message = EXCLUDED.error_message, created_at = EXCLUDED.created_at,
+        finished_at = EXCLUDED.finished_at, result_count = EXCLUDED.result_count,
+        archived_at = now(), data = EXCLUDED.data
+    RETURNING id
+)
+DELETE FROM jobs WHERE id IN (SELECT id FROM archived)";
+
+// Restoring inserts the job first, then its child rows, each with the columns of its table in table order
+const RESTORE_JOB: &str = "INSERT INTO jobs SELECT * FROM jsonb_populate_record(NULL::jobs, (SELECT data->'job' FROM job_archives WHERE id = $1))";
+const RESTORE_RESULTS: &str = "INSERT INTO job_results SELECT * FROM jsonb_populate_recordset(NULL::job_results, (SELECT data->'results' FROM job_archives WHERE id = $1))";
+// metrics and assignments of workers deleted in the meantime can not come back
+const RESTORE_METRICS: &str = "INSERT INTO job_metrics SELECT m.* FROM jsonb_populate_recordset(NULL::job_metrics, (SELECT data->'metrics' FROM job_archives WHERE id = $1)) m WHERE EXISTS (SELECT 1 FROM workers w WHERE w.id = m.worker_id)";
+const RESTORE_ASSIGNMENTS: &str = "INSERT INTO job_assignments SELECT a.* FROM jsonb_populate_recordset(NULL::job_assignments, (SELECT data->'assignments' FROM job_archives WHERE id = $1)) a WHERE EXISTS (SELECT 1 FROM workers w WHERE w.id = a.worker_id)";
+// entries archived before logs were kept have no `logs` key, nothing is inserted for them
+const RESTORE_LOGS: &str = "INSERT INTO logs SELECT * FROM jsonb_populate_recordset(NULL::logs, (SELECT data->'logs' FROM job_archives WHERE id = $1)) ON CONFLICT (id) DO NOTHING";
+
+/// Archive of finished jobs, written by the TaskArchive
+pub struct JobArchiveRepository;
+
+impl JobArchiveRepository {
+    /// One page of archived jobs matching a list query, see `listing` for the grammar
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `query` - Parsed filter, sort and cursor
+    /// # Returns
+    /// * `QueryResult<Vec<JobArchive>>` - up to `query.fetch_limit()` rows, hand them to `query.paginate`
+    pub async fn list(
+        c: &mut AsyncPgConnection,
+        query: &ListQuery,
+    ) -> QueryResult<Vec<JobArchive>> {
+        let (order, tie_breaker) = query.order();
+        let mut rows = job_archives::table
+            .select(JobArchive::as_select())
+            .into_boxed();
+        for predicate in query.predicates() {
+            rows = rows.filter(predicate);
+        }
+        rows.order(order)
+            .then_order_by(tie_breaker)
+            .limit(query.fetch_limit())
+            .load(c)
+            .await
+    }
+
+    /// An archived job with its results, metrics, assignments and log entries
+    pub async fn find_by_id(c: &mut AsyncPgConnection, id: i32) -> QueryResult<ArchivedJob> {
+        let (summary, data) = job_archives::table
+            .find(id)
+            .select((JobArchive::as_select(), job_archives::data))
+            .get_result::<(JobArchive, Value)>(c)
+            .await?;
+        Ok(ArchivedJob { summary, data })
+    }
+
+    /// Move finished jobs past their age into the archive, oldest first
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `completed_before` - `Completed` jobs last updated before this are archived
+    /// * `failed_before` - Same for `Failed` jobs
+    /// * `limit` - Most jobs moved in one call
+    /// # Returns
+    /// * `QueryResult<usize>` - number of archived jobs, `limit` means more may be waiting
+    pub async fn archive_finished(
+        c: &mut AsyncPgConnection,
+        completed_before: NaiveDateTime,
+        failed_before: NaiveDateTime,
+        limit: i64,
+    ) -> QueryResult<usize> {
+        diesel::sql_query(ARCHIVE_FINISHED)
+            .bind::<Timestamp, _>(completed_before)
+            .bind::<Timestamp, _>(failed_before)
+            .bind::<BigInt, _>(limit)
+            .execute(c)
+            .await
+    }
+
+    /// Put an archived job back into `jobs` with its results, metrics, assignments and log entries
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `id` - The id of the archived job
+    /// # Returns
+    /// * `QueryResult<Job>` - the restored job, its `updated_at` set to now, `NotFound` if it is not archived
+    pub async fn restore(c: &mut AsyncPgConnection, id: i32) -> QueryResult<Job> {
+        c.transaction::<_, diesel::result::Error, _>(|tx| {
+            Box::pin(async move {
+                // locks the entry so two restores of the same job can not both run
+                job_archives::table
+                    .find(id)
+                    .select(job_archives::id)
+                    .for_update()
+                    .get_result::<i32>(tx)
+                    .await?;
+
+                for statement in [
+                    RESTORE_JOB,
+                    RESTORE_RESULTS,
+                    RESTORE_METRICS,
+                    RESTORE_ASSIGNMENTS,
+                    RESTORE_LOGS,
+                ] {
+                    diesel::sql_query(statement)
+                        .bind::<Integer, _>(id)
+                        .execute(tx)
+                        .await?;
+                }
+                diesel::delete(job_archives::table.find(id))
+                    .execute(tx)
+                    .await?;
+
+                // a restored job counts as finished now, or the next pass would archive it again
+                diesel::update(jobs::table.find(id))
+                    .set(jobs::updated_at.eq(diesel::dsl::now))
+                    .get_result(tx)
+                    .await
+            })
+        })
+        .await
+    }
+
+    /// Drop archive entries for good
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `before` - Entries archived before this are deleted
+    /// # Returns
+    /// * `QueryResult<usize>` - number of deleted entries
+    pub async fn purge(c: &mut AsyncPgConnection, before: NaiveDateTime) -> QueryResult<usize> {
+        diesel::delete(job_archives::table.filter(job_archives::archived_at.lt(before)))
+            .execute(c)
+            .await
+    }
+}
@@ -0,0 +1,119 @@
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::sql_types::{Integer, Nullable, Text};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::Value;
+
+use crate::database::models::audit::{
+    audited_table, redact, AuditLog, AuditLogFilter, NewAuditLog,
+};
+use crate::database::schema::audit_logs;
+
+/// Audit trail repository, entries are only ever inserted and, once expired, deleted
+pub struct AuditLogRepository;
+
+#[derive(QueryableByName)]
+struct RowSnapshot {
+    #[diesel(sql_type = Nullable<Text>)]
+    row: Option<String>,
+}
+
+impl AuditLogRepository {
+    /// Append an entry to the audit trail
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `entry` - The entry to insert
+    /// # Returns
+    /// * `QueryResult<AuditLog>`
+    pub async fn create(c: &mut AsyncPgConnection, entry: NewAuditLog) -> QueryResult<AuditLog> {
+        diesel::insert_into(audit_logs::table)
+            .values(entry)
+            .get_result(c)
+            .await
+    }
+
+    /// Find an audit entry by ID
+    pub async fn find_by_id(c: &mut AsyncPgConnection, id: i32) -> QueryResult<AuditLog> {
+        audit_logs::table.find(id).get_result(c).await
+    }
+
+    /// Search the audit trail, newest first
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `filter` - Every set field must match, `username` and `resource` match exactly
+    /// * `limit`, `offset` - Pagination
+    /// # Returns
+    /// * `QueryResult<Vec<AuditLog>>`
+    pub async fn search(
+        c: &mut AsyncPgConnection,
+        filter: &AuditLogFilter,
+        limit: i64,
+        offset: i64,
+    ) -> QueryResult<Vec<AuditLog>> {
+        let mut query = audit_logs::table.into_boxed();
+        if let Some(username) = &filter.username {
+            query = query.filter(audit_logs::username.eq(username.clone()));
+        }
+        if let Some(resource) = &filter.resource {
+            query = query.filter(audit_logs::resource.eq(resource.clone()));
+        }
+        if let Some(resource_id) = filter.resource_id {
+            query = query.filter(audit_logs::resource_id.eq(resource_id));
+        }
+        if let Some(method) = &filter.method {
+            query = query.filter(audit_logs::method.eq(method.to_ascii_uppercase()));
+        }
+        if let Some(since) = filter.since {
+            query = query.filter(audit_logs::created_at.ge(since));
+        }
+        if let Some(until) = filter.until {
+            query = query.filter(audit_logs::created_at.lt(until));
+        }
+        query
+            .order((audit_logs::created_at.desc(), audit_logs::id.desc()))
+            .limit(limit)
+            .offset(offset)
+            .load(c)
+            .await
+    }
+
+    /// Delete entries past their `expires_at`, the only delete the table allows
+    /// # Returns
+    /// * `QueryResult<usize>` - number of deleted entries
+    pub async fn delete_expired(c: &mut AsyncPgConnection) -> QueryResult<usize> {
+        diesel::delete(audit_logs::table.filter(audit_logs::expires_at.le(Utc::now().naive_utc())))
+            .execute(c)
+            .await
+    }
+
+    /// Current row of an audited resource as JSON, secrets removed
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `resource` - First path segment of the route, e.g. `jobs`
+    /// * `id` - Key of the row
+    /// # Returns
+    /// * `QueryResult<Option<Value>>` - None if the resource is not audited or the row does not exist
+    pub async fn snapshot(
+        c: &mut AsyncPgConnection,
+        resource: &str,
+        id: i32,
+    ) -> QueryResult<Option<Value>> {
+        // table and column come from the fixed AUDITED_RESOURCES list, never from the request
+        let Some((table, key)) = audited_table(resource) else {
+            return Ok(None);
+        };
+        let snapshot: Option<RowSnapshot> = diesel::sql_query(format!(
+            "SELECT row_to_json(t)::text AS row FROM {} t WHERE t.{} = $1",
+            table, key
+        ))
+        .bind::<Integer, _>(id)
+        .get_result(c)
+        .await
+        .optional()?;
+
+        Ok(snapshot
+            .and_then(|s| s.row)
+            .and_then(|row| serde_json::from_str(&row).ok())
+            .map(redact))
+    }
+}
@@ -0,0 +1,158 @@
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Integer, Jsonb, Nullable, Text};
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::Value;
+
+/// Tables in a backup, parents before children so a restore can insert them in this order
+/// Table names in backup queries are only ever taken from this list.
+/// `audit_logs` is left out, it is append-only and outlives the data it describes.
+pub const BACKUP_TABLES: [&str; 15] = [
+    "users",
+    "user_quotas",
+    "log_retention_policies",
+    "workers",
+    "jobs",
+    "worker_status",
+    "job_assignments",
+    "job_results",
+    "job_metrics",
+    "job_archives",
+    "job_templates",
+    "job_template_parameters",
+    "webhooks",
+    "webhook_deliveries",
+    "logs",
+];
+
+/// Tables whose rows point at a worker, restored rows of deleted workers are skipped
+const WORKER_ROWS: [&str; 2] = ["job_assignments", "job_metrics"];
+
+/// The entry of `BACKUP_TABLES` named `table`, None for any other name
+pub fn backup_table(table: &str) -> Option<&'static str> {
+    BACKUP_TABLES.iter().find(|t| **t == table).copied()
+}
+
+#[derive(QueryableByName)]
+struct RowJson {
+    #[diesel(sql_type = Text)]
+    row: String,
+}
+
+#[derive(QueryableByName)]
+struct MigrationVersion {
+    #[diesel(sql_type = Nullable<Text>)]
+    version: Option<String>,
+}
+
+/// Raw table access for backups, rows travel as JSON objects keyed by column
+pub struct BackupRepository;
+
+impl BackupRepository {
+    /// Latest migration applied to the database, what a backup is tagged with
+    /// # Returns
+    /// * `QueryResult<Option<String>>` - None if no migration ran yet
+    pub async fn schema_version(c: &mut AsyncPgConnection) -> QueryResult<Option<String>> {
+        diesel::sql_query("SELECT MAX(version) AS version FROM __diesel_schema_migrations")
+            .get_result::<MigrationVersion>(c)
+            .await
+            .map(|v| v.version)
+    }
+
+    /// One page of a table in id order, run inside one transaction to get a consistent snapshot
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `table` - One of `BACKUP_TABLES`
+    /// * `after_id` - Rows with a larger id are returned, 0 for the first page
+    /// * `limit` - Page size
+    /// # Returns
+    /// * `QueryResult<Vec<Value>>` - `NotFound` if the table is not in `BACKUP_TABLES`
+    pub async fn dump_page(
+        c: &mut AsyncPgConnection,
+        table: &str,
+        after_id: i32,
+        limit: i64,
+    ) -> QueryResult<Vec<Value>> {
+        let table = backup_table(table).ok_or(diesel::result::Error::NotFound)?;
+        let rows: Vec<RowJson> = diesel::sql_query(format!(
+            "SELECT row_to_json(t)::text AS row FROM {table} t WHERE t.id > $1 ORDER BY t.id LIMIT $2"
+        ))
+        .bind::<Integer, _>(after_id)
+        .bind::<BigInt, _>(limit)
+        .load(c)
+        .await?;
+        rows.iter()
+            .map(|r| {
+                serde_json::from_str(&r.row)
+                    .map_err(|e| diesel::result::Error::DeserializationError(Box::new(e)))
+            })
+            .collect()
+    }
+
+    /// Empty every table of `BACKUP_TABLES` and restart their ids, the first step of a full restore
+    pub async fn truncate_all(c: &mut AsyncPgConnection) -> QueryResult<()> {
+        diesel::sql_query(format!(
+            "TRUNCATE {} RESTART IDENTITY",
+            BACKUP_TABLES.join(", ")
+        ))
+        .execute(c)
+        .await
+        .map(|_| ())
+    }
+
+    /// Whether the tables of a backup hold no users, a full restore only replaces data on request
+    pub async fn is_empty(c: &mut AsyncPgConnection) -> QueryResult<bool> {
+        use crate::database::schema::users;
+        let any_user: Option<i32> = users::table.select(users::id).first(c).await.optional()?;
+        Ok(any_user.is_none())
+    }
+
+    /// Insert rows of one table as they were dumped
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `table` - One of `BACKUP_TABLES`
+    /// * `rows` - JSON objects keyed by column, missing columns become NULL
+    /// * `skip_existing` - Rows clashing with a unique key are skipped instead of failing
+    /// # Returns
+    /// * `QueryResult<usize>` - number of inserted rows, `NotFound` for an unknown table
+    pub async fn insert_rows(
+        c: &mut AsyncPgConnection,
+        table: &str,
+        rows: Vec<Value>,
+        skip_existing: bool,
+    ) -> QueryResult<usize> {
+        let table = backup_table(table).ok_or(diesel::result::Error::NotFound)?;
+        if rows.is_empty() {
+            return Ok(0);
+        }
+        let worker_check = if WORKER_ROWS.contains(&table) {
+            " WHERE EXISTS (SELECT 1 FROM workers w WHERE w.id = r.worker_id)"
+        } else {
+            ""
+        };
+        let conflict = if skip_existing {
+            " ON CONFLICT DO NOTHING"
+        } else {
+            ""
+        };
+        diesel::sql_query(format!(
+            "INSERT INTO {table} SELECT r.* FROM jsonb_populate_recordset(NULL::{table}, $1) r{worker_check}{conflict}"
+        ))
+        .bind::<Jsonb, _>(Value::Array(rows))
+        .execute(c)
+        .await
+    }
+
+    /// Move the id sequences past the restored rows so new rows do not collide with them
+    pub async fn reset_sequences(c: &mut AsyncPgConnection) -> QueryResult<()> {
+        for table in BACKUP_TABLES {
+            // no sequence for tables without a serial id, e.g. job_archives
+            diesel::sql_query(format!(
+                "SELECT setval(seq, GREATEST((SELECT COALESCE(MAX(id), 0) + 1 FROM {table}), nextval(seq)), false) \
+                 FROM (SELECT pg_get_serial_sequence('{table}', 'id')::regclass AS seq) s WHERE seq IS NOT NULL"
+            ))
+            .execute(c)
+            .await?;
+        }
+        Ok(())
+    }
+}
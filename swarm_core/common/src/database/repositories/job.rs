@@ -2,16 +2,70 @@ use chrono::NaiveDateTime;
 use diesel::dsl::count_star;
 use diesel::dsl::now;
 use diesel::prelude::*;
-use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+use serde_json::Value;
+use thiserror::Error;
 
-use crate::database::models::job::{Job, NewJob};
+use crate::database::models::job::{Job, JobAssignment, NewJob, NewJobAssignment};
+use crate::database::repositories::listing::{FieldKind, ListField, ListQuery, ListSchema};
+use crate::database::repositories::WebhookDeliveryRepository;
 use crate::database::schema::*;
-use crate::enums::{job::JobStateEnum, schedule::ScheduleTypeEnum};
+use crate::enums::{job::JobStateEnum, schedule::ScheduleTypeEnum, webhook::WebhookEventEnum};
+
+/// Why a guarded state change did not happen
+#[derive(Debug, Error)]
+pub enum TransitionError {
+    #[error("Job {0} not found")]
+    NotFound(i32),
+    #[error("Job {id} is {from}, it can't move to {to}")]
+    Illegal {
+        id: i32,
+        from: JobStateEnum,
+        to: JobStateEnum,
+    },
+    #[error(transparent)]
+    Database(#[from] diesel::result::Error),
+}
 
 /// Job repository, functions for interacting with the database
+/// Fields of `GET /jobs`, `worker` is the worker of the latest assignment
+pub static JOB_LIST: ListSchema = ListSchema {
+    fields: &[
+        ListField { name: "id", column: "jobs.id", kind: FieldKind::Int, sort_key: Some("id") },
+        ListField { name: "state", column: "jobs.state", kind: FieldKind::Enum(JobStateEnum::variants), sort_key: Some("state") },
+        ListField { name: "user", column: "jobs.user_id", kind: FieldKind::Int, sort_key: Some("user_id") },
+        ListField { name: "worker", column: "(SELECT ja.worker_id FROM job_assignments ja WHERE ja.job_id = jobs.id ORDER BY ja.assigned_at DESC, ja.id DESC LIMIT 1)", kind: FieldKind::Int, sort_key: None },
+        ListField { name: "name", column: "jobs.job_name", kind: FieldKind::Text, sort_key: Some("job_name") },
+        ListField { name: "priority", column: "jobs.priority", kind: FieldKind::Int, sort_key: Some("priority") },
+        ListField { name: "created", column: "jobs.created_at", kind: FieldKind::Timestamp, sort_key: Some("created_at") },
+        ListField { name: "updated", column: "jobs.updated_at", kind: FieldKind::Timestamp, sort_key: Some("updated_at") },
+    ],
+    id_column: "jobs.id",
+    default_sort: "-created",
+};
+
 pub struct JobRepository;
 
 impl JobRepository {
+    /// One page of jobs matching a list query, see `listing` for the grammar
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `query` - Parsed filter, sort and cursor
+    /// # Returns
+    /// * `QueryResult<Vec<Job>>` - up to `query.fetch_limit()` rows, hand them to `query.paginate`
+    pub async fn list(c: &mut AsyncPgConnection, query: &ListQuery) -> QueryResult<Vec<Job>> {
+        let (order, tie_breaker) = query.order();
+        let mut rows = jobs::table.into_boxed();
+        for predicate in query.predicates() {
+            rows = rows.filter(predicate);
+        }
+        rows.order(order)
+            .then_order_by(tie_breaker)
+            .limit(query.fetch_limit())
+            .load(c)
+            .await
+    }
+
     /// Find a job by id from postgres
     /// # Arguments
     /// * `c` - The database connection
@@ -33,7 +87,7 @@ impl JobRepository {
         jobs::table.find(id).get_result(c).await
     }
 
-    /// Create a new job in the database
+    /// Create a new job in the database, records the `JobSubmitted` webhook event with it
     /// # Arguments
     /// * `c` - The database connection
     /// * `new_job` - The new job to create
@@ -53,28 +107,27 @@ impl JobRepository {
     /// # Panics
     /// Panics if the query fails, or if database connection fails
     pub async fn create(c: &mut AsyncPgConnection, new_job: NewJob) -> QueryResult<Job> {
-        dbg!(&new_job.state);
-
-        match diesel::insert_into(jobs::table)
-            .values(new_job)
-            .get_result::<Job>(c)
-            .await
-        {
-            Ok(job) => {
-                let _ = Self::mark_submitted(c, job.id).await;
+        c.transaction::<_, diesel::result::Error, _>(|tx| {
+            Box::pin(async move {
+                let job = diesel::insert_into(jobs::table)
+                    .values(new_job)
+                    .get_result::<Job>(tx)
+                    .await?;
+                Self::publish(tx, WebhookEventEnum::JobSubmitted, &job).await?;
                 Ok(job)
-            }
-            Err(e) => Err(e),
-        }
+            })
+        })
+        .await
     }
 
     /// Update a job in the database
+    /// A changed `state` must be a legal transition, checked in the same statement
     /// # Arguments
     /// * `c` - The database connection
     /// * `id` - The id of the job
     /// * `job` - The job to update
     /// # Returns
-    /// * `QueryResult<Job>`
+    /// * `Result<Job, TransitionError>`
     /// # Example
     /// ```
     /// use swarm_core::database::repositories::job::JobRepository;
@@ -87,8 +140,16 @@ impl JobRepository {
     /// ```
     /// # Panics
     /// Panics if the query fails, or if database connection fails
-    pub async fn update(c: &mut AsyncPgConnection, id: i32, job: Job) -> QueryResult<Job> {
-        diesel::update(jobs::table.find(id))
+    pub async fn update(
+        c: &mut AsyncPgConnection,
+        id: i32,
+        job: Job,
+    ) -> Result<Job, TransitionError> {
+        let to = job.state;
+        let mut sources = JobStateEnum::sources_of(to);
+        sources.push(to);
+
+        let updated = diesel::update(jobs::table.find(id).filter(jobs::state.eq_any(sources)))
             .set((
                 jobs::job_name.eq(job.job_name),
                 jobs::image_url.eq(job.image_url),
@@ -99,12 +160,15 @@ impl JobRepository {
                 jobs::schedule_type.eq(job.schedule_type),
                 jobs::cron_expression.eq(job.cron_expression),
                 jobs::notes.eq(job.notes),
-                jobs::state.eq(job.state),
+                jobs::state.eq(to),
                 jobs::error_message.eq(job.error_message),
+                jobs::priority.eq(job.priority),
                 jobs::updated_at.eq(now),
             ))
             .get_result(c)
             .await
+            .optional()?;
+        Self::transitioned(c, id, to, updated).await
     }
 
     /// Delete a job from the database
@@ -210,91 +274,281 @@ impl JobRepository {
             .await
     }
 
-    /// Mark a job as failed
+    /// Mark a job as failed, from any unfinished state (also used to cancel), records `JobFailed`
     /// # Arguments
     /// * `c` - The database connection
     /// * `id` - The id of the job
     /// * `message` - The error message
     /// # Returns
-    /// * `QueryResult<Job>`
+    /// * `Result<Job, TransitionError>` - `Illegal` if the job already finished
     pub async fn mark_failed(
         c: &mut AsyncPgConnection,
         id: i32,
         message: &str,
-    ) -> QueryResult<Job> {
-        diesel::update(jobs::table.find(id))
-            .set((
-                jobs::state.eq(JobStateEnum::Failed),
-                jobs::error_message.eq::<Option<String>>(Some(message.to_string())),
-                jobs::updated_at.eq(now),
-            ))
-            .get_result(c)
-            .await
+    ) -> Result<Job, TransitionError> {
+        let message = message.to_string();
+        c.transaction::<_, TransitionError, _>(|tx| {
+            Box::pin(async move {
+                let updated =
+                    diesel::update(jobs::table.find(id).filter(
+                        jobs::state.eq_any(JobStateEnum::sources_of(JobStateEnum::Failed)),
+                    ))
+                    .set((
+                        jobs::state.eq(JobStateEnum::Failed),
+                        jobs::error_message.eq(Some(message)),
+                        jobs::updated_at.eq(now),
+                    ))
+                    .get_result(tx)
+                    .await
+                    .optional()?;
+                let job = Self::transitioned(tx, id, JobStateEnum::Failed, updated).await?;
+                Self::publish(tx, WebhookEventEnum::JobFailed, &job).await?;
+                Ok(job)
+            })
+        })
+        .await
     }
 
-    /// Mark a job as succeeded
+    /// Mark a running job as succeeded, records `JobCompleted`
     /// # Arguments
     /// * `c` - The database connection
     /// * `id` - The id of the job
     /// # Returns
-    /// * `QueryResult<Job>`
-    pub async fn mark_succeeded(c: &mut AsyncPgConnection, id: i32) -> QueryResult<Job> {
-        diesel::update(jobs::table.find(id))
-            .set((
-                jobs::state.eq(JobStateEnum::Completed),
-                jobs::error_message.eq(None::<String>),
-                jobs::updated_at.eq(now),
-            ))
-            .get_result(c)
-            .await
+    /// * `Result<Job, TransitionError>` - `Illegal` unless the job was `Running`
+    pub async fn mark_succeeded(
+        c: &mut AsyncPgConnection,
+        id: i32,
+    ) -> Result<Job, TransitionError> {
+        c.transaction::<_, TransitionError, _>(|tx| {
+            Box::pin(async move {
+                let updated =
+                    diesel::update(jobs::table.find(id).filter(
+                        jobs::state.eq_any(JobStateEnum::sources_of(JobStateEnum::Completed)),
+                    ))
+                    .set((
+                        jobs::state.eq(JobStateEnum::Completed),
+                        jobs::error_message.eq(None::<String>),
+                        jobs::updated_at.eq(now),
+                    ))
+                    .get_result(tx)
+                    .await
+                    .optional()?;
+                let job = Self::transitioned(tx, id, JobStateEnum::Completed, updated).await?;
+                Self::publish(tx, WebhookEventEnum::JobCompleted, &job).await?;
+                Ok(job)
+            })
+        })
+        .await
     }
 
-    /// Mark a job as running
+    /// Mark a queued job as running, records `JobRunning`
     /// # Arguments
     /// * `c` - The database connection
     /// * `id` - The id of the job
     /// # Returns
-    /// * `QueryResult<Job>`
-    pub async fn mark_running(c: &mut AsyncPgConnection, id: i32) -> QueryResult<Job> {
-        diesel::update(jobs::table.find(id))
-            .set((
-                jobs::state.eq(JobStateEnum::Running),
-                jobs::updated_at.eq(now),
-            ))
-            .get_result(c)
-            .await
+    /// * `Result<Job, TransitionError>` - `Illegal` unless the job was `Queued`,
+    ///   so of two callers racing for the same job only one gets it
+    pub async fn mark_running(c: &mut AsyncPgConnection, id: i32) -> Result<Job, TransitionError> {
+        c.transaction::<_, TransitionError, _>(|tx| {
+            Box::pin(async move {
+                let updated =
+                    diesel::update(jobs::table.find(id).filter(
+                        jobs::state.eq_any(JobStateEnum::sources_of(JobStateEnum::Running)),
+                    ))
+                    .set((
+                        jobs::state.eq(JobStateEnum::Running),
+                        jobs::updated_at.eq(now),
+                    ))
+                    .get_result(tx)
+                    .await
+                    .optional()?;
+                let job = Self::transitioned(tx, id, JobStateEnum::Running, updated).await?;
+                Self::publish(tx, WebhookEventEnum::JobRunning, &job).await?;
+                Ok(job)
+            })
+        })
+        .await
     }
 
-    /// Mark a job as submitted
+    /// Mark a submitted job as queued, done by the Scheduler on admission
     /// # Arguments
     /// * `c` - The database connection
     /// * `id` - The id of the job
     /// # Returns
-    /// * `QueryResult<Job>`
-    pub async fn mark_submitted(c: &mut AsyncPgConnection, id: i32) -> QueryResult<Job> {
-        diesel::update(jobs::table.find(id))
-            .set((
-                jobs::state.eq(JobStateEnum::Submitted),
-                jobs::updated_at.eq(now),
-            ))
-            .get_result(c)
+    /// * `Result<Job, TransitionError>` - `Illegal` unless the job was `Submitted`
+    pub async fn mark_queued(c: &mut AsyncPgConnection, id: i32) -> Result<Job, TransitionError> {
+        let updated = diesel::update(
+            jobs::table
+                .find(id)
+                .filter(jobs::state.eq(JobStateEnum::Submitted)),
+        )
+        .set((
+            jobs::state.eq(JobStateEnum::Queued),
+            jobs::updated_at.eq(now),
+        ))
+        .get_result(c)
+        .await
+        .optional()?;
+        Self::transitioned(c, id, JobStateEnum::Queued, updated).await
+    }
+
+    /// Running jobs with the owner of the worker they run on, taken from their open assignment
+    /// A worker only claims jobs of its owner, so only these jobs free a worker for a queued job of that owner
+    /// # Returns
+    /// * `QueryResult<Vec<(Job, i32)>>` - each job with the `user_id` of its worker
+    pub async fn list_running_with_worker_owner(
+        c: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<(Job, i32)>> {
+        jobs::table
+            .inner_join(job_assignments::table.inner_join(workers::table))
+            .filter(jobs::state.eq(JobStateEnum::Running))
+            .filter(job_assignments::finished_at.is_null())
+            .select((jobs::all_columns, workers::user_id))
+            .load(c)
             .await
     }
 
-    /// Mark a job as queued
+    /// Put a preempted running job back in the queue
+    /// Closes the open assignment, frees the worker slot and records `JobRequeued` in one transaction
     /// # Arguments
     /// * `c` - The database connection
     /// * `id` - The id of the job
     /// # Returns
-    /// * `QueryResult<Job>`
-    pub async fn mark_queued(c: &mut AsyncPgConnection, id: i32) -> QueryResult<Job> {
-        diesel::update(jobs::table.find(id))
-            .set((
-                jobs::state.eq(JobStateEnum::Queued),
-                jobs::updated_at.eq(now),
-            ))
+    /// * `Result<Job, TransitionError>` - `Illegal` unless the job was `Running`, nothing is changed then
+    pub async fn requeue_preempted(
+        c: &mut AsyncPgConnection,
+        id: i32,
+    ) -> Result<Job, TransitionError> {
+        c.transaction::<_, TransitionError, _>(|tx| {
+            Box::pin(async move {
+                let updated = diesel::update(
+                    jobs::table
+                        .find(id)
+                        .filter(jobs::state.eq(JobStateEnum::Running)),
+                )
+                .set((
+                    jobs::state.eq(JobStateEnum::Queued),
+                    jobs::updated_at.eq(now),
+                ))
+                .get_result(tx)
+                .await
+                .optional()?;
+                let job = Self::transitioned(tx, id, JobStateEnum::Queued, updated).await?;
+
+                diesel::update(
+                    job_assignments::table
+                        .filter(job_assignments::job_id.eq(id))
+                        .filter(job_assignments::finished_at.is_null()),
+                )
+                .set(job_assignments::finished_at.eq(now))
+                .execute(tx)
+                .await?;
+
+                diesel::update(worker_status::table.filter(worker_status::active_job_id.eq(id)))
+                    .set(worker_status::active_job_id.eq(None::<i32>))
+                    .execute(tx)
+                    .await?;
+
+                Self::publish(tx, WebhookEventEnum::JobRequeued, &job).await?;
+                Ok(job)
+            })
+        })
+        .await
+    }
+
+    /// Claim the next queued one-time job of the workers owner and assign it to the worker
+    /// Highest priority first, oldest first on ties. Rows another claim holds are skipped
+    /// (`FOR UPDATE SKIP LOCKED`), so concurrent claims never get the same job and never wait
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `worker_id` - The id of the claiming worker
+    /// # Returns
+    /// * `QueryResult<Option<(Job, JobAssignment)>>` - None if nothing is queued, the job is `Running`
+    ///   and `JobRunning` is recorded
+    pub async fn claim_next(
+        c: &mut AsyncPgConnection,
+        worker_id: i32,
+    ) -> QueryResult<Option<(Job, JobAssignment)>> {
+        c.transaction::<_, diesel::result::Error, _>(|tx| {
+            Box::pin(async move {
+                let owner: i32 = workers::table
+                    .find(worker_id)
+                    .select(workers::user_id)
+                    .get_result(tx)
+                    .await?;
+
+                let next: Option<Job> = jobs::table
+                    .filter(jobs::user_id.eq(owner))
+                    .filter(jobs::state.eq(JobStateEnum::Queued))
+                    .filter(jobs::schedule_type.eq(ScheduleTypeEnum::Once))
+                    .order((
+                        jobs::priority.desc(),
+                        jobs::created_at.asc(),
+                        jobs::id.asc(),
+                    ))
+                    .for_update()
+                    .skip_locked()
+                    .first(tx)
+                    .await
+                    .optional()?;
+                let Some(next) = next else {
+                    return Ok(None);
+                };
+
+                // the row is locked and Queued, nothing else can move it until commit
+                let job: Job = diesel::update(jobs::table.find(next.id))
+                    .set((
+                        jobs::state.eq(JobStateEnum::Running),
+                        jobs::updated_at.eq(now),
+                    ))
+                    .get_result(tx)
+                    .await?;
+                let assignment: JobAssignment = diesel::insert_into(job_assignments::table)
+                    .values(NewJobAssignment {
+                        job_id: job.id,
+                        worker_id,
+                    })
+                    .get_result(tx)
+                    .await?;
+                Self::publish(tx, WebhookEventEnum::JobRunning, &job).await?;
+                Ok(Some((job, assignment)))
+            })
+        })
+        .await
+    }
+
+    /// Record the webhook event of a job change, in the transaction of the change
+    /// Every path creating or moving a job calls this, core-api routes, the Scheduler and CommandDeck alike
+    async fn publish(
+        c: &mut AsyncPgConnection,
+        event: WebhookEventEnum,
+        job: &Job,
+    ) -> QueryResult<()> {
+        let data = serde_json::to_value(job).unwrap_or(Value::Null);
+        WebhookDeliveryRepository::enqueue_event(c, event, job.user_id, data).await?;
+        Ok(())
+    }
+
+    /// Result of a guarded update: the job, or why no row matched
+    async fn transitioned(
+        c: &mut AsyncPgConnection,
+        id: i32,
+        to: JobStateEnum,
+        updated: Option<Job>,
+    ) -> Result<Job, TransitionError> {
+        if let Some(job) = updated {
+            return Ok(job);
+        }
+        let from: Option<JobStateEnum> = jobs::table
+            .find(id)
+            .select(jobs::state)
             .get_result(c)
             .await
+            .optional()?;
+        match from {
+            Some(from) => Err(TransitionError::Illegal { id, from, to }),
+            None => Err(TransitionError::NotFound(id)),
+        }
     }
 
     /// List all cron jobs that are due
@@ -342,6 +596,21 @@ impl JobRepository {
             .await
     }
 
+    /// Get the count of jobs per state, states without jobs are left out
+    /// # Arguments
+    /// * `c` - The database connection
+    /// # Returns
+    /// * `QueryResult<Vec<(JobStateEnum, i64)>>`
+    pub async fn count_per_state(
+        c: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<(JobStateEnum, i64)>> {
+        jobs::table
+            .group_by(jobs::state)
+            .select((jobs::state, count_star()))
+            .load::<(JobStateEnum, i64)>(c)
+            .await
+    }
+
     /// Get recent jobs
     /// # Arguments
     /// * `c` - The database connection
@@ -4,17 +4,16 @@ use diesel_async::{AsyncPgConnection, RunQueryDsl};
 
 use crate::database::models::job::{JobAssignment, NewJobAssignment};
 use crate::database::schema::*;
-use crate::utils::mark_running;
 
 pub struct JobAssignmentRepository;
 
 impl JobAssignmentRepository {
-    // Create a new job assignment
+    // Create a new job assignment, only a record: the job state is left alone,
+    // `JobRepository::claim_next` assigns and moves the job to Running together
     pub async fn create(
         c: &mut AsyncPgConnection,
         new_assignment: NewJobAssignment,
     ) -> QueryResult<JobAssignment> {
-        let _ = mark_running(new_assignment.job_id).await;
         diesel::insert_into(job_assignments::table)
             .values(new_assignment)
             .get_result(c)
@@ -2,11 +2,47 @@ use diesel::prelude::*;
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 
 use crate::database::models::job::{JobResult, NewJobResult};
+use crate::database::repositories::listing::{FieldKind, ListField, ListQuery, ListSchema};
 use crate::database::schema::*;
+use crate::enums::job::JobStateEnum;
+
+/// Fields of `GET /results`, `user`, `state`, `name` and `worker` are those of the job
+pub static RESULT_LIST: ListSchema = ListSchema {
+    fields: &[
+        ListField { name: "id", column: "job_results.id", kind: FieldKind::Int, sort_key: Some("id") },
+        ListField { name: "job", column: "job_results.job_id", kind: FieldKind::Int, sort_key: Some("job_id") },
+        ListField { name: "user", column: "(SELECT j.user_id FROM jobs j WHERE j.id = job_results.job_id)", kind: FieldKind::Int, sort_key: None },
+        ListField { name: "state", column: "(SELECT j.state FROM jobs j WHERE j.id = job_results.job_id)", kind: FieldKind::Enum(JobStateEnum::variants), sort_key: None },
+        ListField { name: "name", column: "(SELECT j.job_name FROM jobs j WHERE j.id = job_results.job_id)", kind: FieldKind::Text, sort_key: None },
+        ListField { name: "worker", column: "(SELECT ja.worker_id FROM job_assignments ja WHERE ja.job_id = job_results.job_id ORDER BY ja.assigned_at DESC, ja.id DESC LIMIT 1)", kind: FieldKind::Int, sort_key: None },
+        ListField { name: "created", column: "job_results.saved_at", kind: FieldKind::Timestamp, sort_key: Some("saved_at") },
+    ],
+    id_column: "job_results.id",
+    default_sort: "-created",
+};
 
 pub struct JobResultRepository;
 
 impl JobResultRepository {
+    /// One page of results matching a list query, see `listing` for the grammar
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `query` - Parsed filter, sort and cursor
+    /// # Returns
+    /// * `QueryResult<Vec<JobResult>>` - up to `query.fetch_limit()` rows, hand them to `query.paginate`
+    pub async fn list(c: &mut AsyncPgConnection, query: &ListQuery) -> QueryResult<Vec<JobResult>> {
+        let (order, tie_breaker) = query.order();
+        let mut rows = job_results::table.into_boxed();
+        for predicate in query.predicates() {
+            rows = rows.filter(predicate);
+        }
+        rows.order(order)
+            .then_order_by(tie_breaker)
+            .limit(query.fetch_limit())
+            .load(c)
+            .await
+    }
+
     pub async fn create(
         c: &mut AsyncPgConnection,
         new_result: NewJobResult,
@@ -0,0 +1,626 @@
+//! Generic list queries shared by the list endpoints (`GET /jobs`, `/workers`, `/logs`, `/results`)
+//!
+//! A request carries up to four parameters:
+//! * `filter` - comma separated clauses `<field><op><value>`, e.g. `state:Queued|Running,created>=2025-05-01`
+//!   `:` equals (`|` separates alternatives), `!:` not equal, `~` contains (text, case-insensitive),
+//!   `>`, `>=`, `<`, `<=` compare numbers and timestamps (`YYYY-MM-DD` or `YYYY-MM-DDTHH:MM:SS`)
+//! * `sort` - one sortable field, `-` prefix for descending, the row id breaks ties
+//! * `limit` - page size, default 50, at most 500
+//! * `cursor` - opaque `next_cursor` of the previous page, only valid with the same sort
+//!
+//! Each resource describes its fields in a `ListSchema`. Column expressions only ever come from
+//! those constants, values are always bound, so no request text ends up in the SQL.
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::dsl::sql;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::sql_types::{Array, Bool, Integer, Text, Timestamp};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use serde_json::Value;
+use thiserror::Error;
+
+/// A boxed SQL fragment usable in `.filter()` and `.order()` on any table
+pub type Predicate<QS> = Box<dyn BoxableExpression<QS, Pg, SqlType = Bool>>;
+
+/// Page size when `limit` is missing
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+/// Largest page a caller may ask for
+pub const MAX_PAGE_SIZE: i64 = 500;
+
+/// Type of a filterable field, decides which operators and values are accepted
+#[derive(Debug, Clone, Copy)]
+pub enum FieldKind {
+    Int,
+    Text,
+    Timestamp,
+    // stored as text, value must be one of the variants
+    Enum(fn() -> &'static [&'static str]),
+}
+
+/// A field of a list endpoint
+#[derive(Debug)]
+pub struct ListField {
+    // name used in `filter` and `sort`
+    pub name: &'static str,
+    // SQL expression, a column or a scalar subquery
+    pub column: &'static str,
+    pub kind: FieldKind,
+    // key of the value in the serialized row, set for sortable fields only
+    pub sort_key: Option<&'static str>,
+}
+
+/// Fields of one list endpoint
+#[derive(Debug)]
+pub struct ListSchema {
+    pub fields: &'static [ListField],
+    // SQL expression of the row id, tie breaker and second cursor component
+    pub id_column: &'static str,
+    // sort used when none is given, e.g. `-created`
+    pub default_sort: &'static str,
+}
+
+impl ListSchema {
+    fn field(&'static self, name: &str) -> Result<&'static ListField, ListQueryError> {
+        self.fields
+            .iter()
+            .find(|f| f.name == name)
+            .ok_or_else(|| ListQueryError::UnknownField(name.to_string()))
+    }
+
+    /// Names of every field, for error messages and docs
+    pub fn field_names(&self) -> Vec<&'static str> {
+        self.fields.iter().map(|f| f.name).collect()
+    }
+}
+
+/// Errors in `filter`, `sort`, `limit` or `cursor`
+#[derive(Debug, Error, PartialEq)]
+pub enum ListQueryError {
+    #[error("unknown field '{0}'")]
+    UnknownField(String),
+    #[error("malformed filter clause '{0}', expected <field><op><value>")]
+    MalformedClause(String),
+    #[error("operator '{op}' is not supported on field '{field}'")]
+    UnsupportedOperator { field: String, op: String },
+    #[error("invalid value '{value}' for field '{field}': {reason}")]
+    InvalidValue {
+        field: String,
+        value: String,
+        reason: String,
+    },
+    #[error("field '{0}' is not sortable")]
+    NotSortable(String),
+    #[error("invalid cursor")]
+    InvalidCursor,
+    #[error("cursor was issued for sort '{0}'")]
+    CursorSortMismatch(String),
+}
+
+/// Filter operator
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Contains,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl FilterOp {
+    // longest tokens first so `>=` is not read as `>`
+    const TOKENS: [(&'static str, FilterOp); 7] = [
+        ("!:", FilterOp::Ne),
+        (">=", FilterOp::Ge),
+        ("<=", FilterOp::Le),
+        (":", FilterOp::Eq),
+        ("~", FilterOp::Contains),
+        (">", FilterOp::Gt),
+        ("<", FilterOp::Lt),
+    ];
+
+    fn sql(&self) -> &'static str {
+        match self {
+            FilterOp::Eq => "=",
+            FilterOp::Ne => "IS DISTINCT FROM",
+            FilterOp::Contains => "ILIKE",
+            FilterOp::Gt => ">",
+            FilterOp::Ge => ">=",
+            FilterOp::Lt => "<",
+            FilterOp::Le => "<=",
+        }
+    }
+
+    fn allowed_on(&self, kind: &FieldKind) -> bool {
+        match self {
+            FilterOp::Eq | FilterOp::Ne => true,
+            FilterOp::Contains => matches!(kind, FieldKind::Text),
+            _ => matches!(kind, FieldKind::Int | FieldKind::Timestamp),
+        }
+    }
+}
+
+/// Bound values of a clause, typed by the field kind
+#[derive(Debug, Clone, PartialEq)]
+enum Values {
+    Int(Vec<i32>),
+    Text(Vec<String>),
+    Timestamp(Vec<NaiveDateTime>),
+}
+
+/// One `<field><op><value>` clause
+#[derive(Debug)]
+pub struct FilterClause {
+    field: &'static ListField,
+    op: FilterOp,
+    values: Values,
+}
+
+/// Position after the last row of a page
+#[derive(Debug, Clone, PartialEq)]
+struct CursorPosition {
+    value: Values,
+    id: i32,
+}
+
+/// What an opaque cursor encodes
+#[derive(Debug, Serialize, Deserialize)]
+struct CursorToken {
+    sort: String,
+    value: Value,
+    id: i32,
+}
+
+/// One page of a list endpoint
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    // pass as `cursor` to get the next page, None on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// A parsed and validated list request
+#[derive(Debug)]
+pub struct ListQuery {
+    pub filters: Vec<FilterClause>,
+    sort_field: &'static ListField,
+    descending: bool,
+    // sort as given, cursors are only valid for the same sort
+    sort_spec: String,
+    pub limit: i64,
+    after: Option<CursorPosition>,
+    id_column: &'static str,
+}
+
+impl ListQuery {
+    /// Parse the query parameters of a list endpoint
+    /// # Arguments
+    /// * `schema` - Fields of the endpoint
+    /// * `filter`, `sort`, `limit`, `cursor` - The raw query parameters, see module docs
+    /// # Returns
+    /// * `Result<ListQuery, ListQueryError>`
+    pub fn parse(
+        schema: &'static ListSchema,
+        filter: Option<&str>,
+        sort: Option<&str>,
+        limit: Option<i64>,
+        cursor: Option<&str>,
+    ) -> Result<Self, ListQueryError> {
+        let filters = filter
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .map(|c| parse_clause(schema, c))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let sort_spec = sort
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .unwrap_or(schema.default_sort)
+            .to_string();
+        let (descending, sort_name) = match sort_spec.strip_prefix('-') {
+            Some(name) => (true, name),
+            None => (false, sort_spec.as_str()),
+        };
+        let sort_field = schema.field(sort_name)?;
+        if sort_field.sort_key.is_none() {
+            return Err(ListQueryError::NotSortable(sort_name.to_string()));
+        }
+
+        let after = cursor
+            .map(|c| decode_cursor(c, &sort_spec, sort_field))
+            .transpose()?;
+
+        Ok(ListQuery {
+            filters,
+            sort_field,
+            descending,
+            sort_spec,
+            limit: limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE),
+            after,
+            id_column: schema.id_column,
+        })
+    }
+
+    /// Filter clauses and the keyset position, as predicates for `.filter()` on a boxed select
+    pub fn predicates<QS>(&self) -> Vec<Predicate<QS>> {
+        let mut predicates: Vec<Predicate<QS>> =
+            self.filters.iter().map(clause_predicate).collect();
+
+        if let Some(after) = &self.after {
+            let cmp = if self.descending { "<" } else { ">" };
+            let prefix = format!("({}, {}) {cmp} (", self.sort_field.column, self.id_column);
+            predicates.push(match &after.value {
+                Values::Int(v) => Box::new(
+                    sql::<Bool>(&prefix)
+                        .bind::<Integer, _>(v[0])
+                        .sql(", ")
+                        .bind::<Integer, _>(after.id)
+                        .sql(")"),
+                ),
+                Values::Text(v) => Box::new(
+                    sql::<Bool>(&prefix)
+                        .bind::<Text, _>(v[0].clone())
+                        .sql(", ")
+                        .bind::<Integer, _>(after.id)
+                        .sql(")"),
+                ),
+                Values::Timestamp(v) => Box::new(
+                    sql::<Bool>(&prefix)
+                        .bind::<Timestamp, _>(v[0])
+                        .sql(", ")
+                        .bind::<Integer, _>(after.id)
+                        .sql(")"),
+                ),
+            });
+        }
+        predicates
+    }
+
+    /// Sort field and id tie breaker, for `.order()` and `.then_order_by()`
+    pub fn order<QS>(&self) -> (Predicate<QS>, Predicate<QS>) {
+        let direction = if self.descending { "DESC" } else { "ASC" };
+        (
+            Box::new(sql::<Bool>(&format!(
+                "{} {direction}",
+                self.sort_field.column
+            ))),
+            Box::new(sql::<Bool>(&format!("{} {direction}", self.id_column))),
+        )
+    }
+
+    /// Rows to load, one more than `limit` so `paginate` can tell whether a next page exists
+    pub fn fetch_limit(&self) -> i64 {
+        self.limit + 1
+    }
+
+    /// Cut the loaded rows down to a page
+    /// # Arguments
+    /// * `rows` - At most `fetch_limit()` rows in sort order
+    /// # Returns
+    /// * `Page<T>` - `next_cursor` is set when more rows exist
+    pub fn paginate<T: Serialize>(&self, mut rows: Vec<T>) -> Page<T> {
+        let has_more = rows.len() as i64 > self.limit;
+        rows.truncate(self.limit as usize);
+        let next_cursor = if has_more {
+            rows.last().and_then(|last| self.cursor_after(last))
+        } else {
+            None
+        };
+        Page {
+            items: rows,
+            next_cursor,
+        }
+    }
+
+    fn cursor_after<T: Serialize>(&self, row: &T) -> Option<String> {
+        let row = serde_json::to_value(row).ok()?;
+        let token = CursorToken {
+            sort: self.sort_spec.clone(),
+            value: row.get(self.sort_field.sort_key?)?.clone(),
+            id: i32::try_from(row.get("id")?.as_i64()?).ok()?,
+        };
+        let json = serde_json::to_vec(&token).ok()?;
+        Some(URL_SAFE_NO_PAD.encode(json))
+    }
+}
+
+fn parse_clause(schema: &'static ListSchema, clause: &str) -> Result<FilterClause, ListQueryError> {
+    let name_end = clause
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .ok_or_else(|| ListQueryError::MalformedClause(clause.to_string()))?;
+    let (name, rest) = clause.split_at(name_end);
+    let (token, op) = FilterOp::TOKENS
+        .iter()
+        .find(|(token, _)| rest.starts_with(token))
+        .ok_or_else(|| ListQueryError::MalformedClause(clause.to_string()))?;
+    let raw = rest[token.len()..].trim();
+    if name.is_empty() || raw.is_empty() {
+        return Err(ListQueryError::MalformedClause(clause.to_string()));
+    }
+
+    let field = schema.field(name)?;
+    if !op.allowed_on(&field.kind) {
+        return Err(ListQueryError::UnsupportedOperator {
+            field: name.to_string(),
+            op: token.to_string(),
+        });
+    }
+    // alternatives only make sense for equality
+    let raw_values: Vec<&str> = match op {
+        FilterOp::Eq | FilterOp::Ne => raw.split('|').map(str::trim).collect(),
+        _ => vec![raw],
+    };
+    let values = parse_values(field, &raw_values)?;
+    Ok(FilterClause {
+        field,
+        op: *op,
+        values,
+    })
+}
+
+fn parse_values(field: &ListField, raw: &[&str]) -> Result<Values, ListQueryError> {
+    let invalid = |value: &str, reason: &str| ListQueryError::InvalidValue {
+        field: field.name.to_string(),
+        value: value.to_string(),
+        reason: reason.to_string(),
+    };
+    match field.kind {
+        FieldKind::Int => raw
+            .iter()
+            .map(|v| {
+                v.parse::<i32>()
+                    .map_err(|_| invalid(v, "expected an integer"))
+            })
+            .collect::<Result<_, _>>()
+            .map(Values::Int),
+        FieldKind::Text => Ok(Values::Text(raw.iter().map(|v| v.to_string()).collect())),
+        FieldKind::Timestamp => raw
+            .iter()
+            .map(|v| parse_timestamp(v).ok_or_else(|| invalid(v, "expected YYYY-MM-DD[THH:MM:SS]")))
+            .collect::<Result<_, _>>()
+            .map(Values::Timestamp),
+        FieldKind::Enum(variants) => raw
+            .iter()
+            .map(|v| {
+                variants()
+                    .iter()
+                    .find(|variant| variant.eq_ignore_ascii_case(v))
+                    .map(|variant| variant.to_string())
+                    .ok_or_else(|| {
+                        invalid(v, &format!("expected one of {}", variants().join(", ")))
+                    })
+            })
+            .collect::<Result<_, _>>()
+            .map(Values::Text),
+    }
+}
+
+fn parse_timestamp(value: &str) -> Option<NaiveDateTime> {
+    value
+        .parse::<NaiveDateTime>()
+        .ok()
+        .or_else(|| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+}
+
+fn decode_cursor(
+    cursor: &str,
+    sort_spec: &str,
+    field: &ListField,
+) -> Result<CursorPosition, ListQueryError> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(cursor.trim())
+        .map_err(|_| ListQueryError::InvalidCursor)?;
+    let token: CursorToken =
+        serde_json::from_slice(&bytes).map_err(|_| ListQueryError::InvalidCursor)?;
+    if token.sort != sort_spec {
+        return Err(ListQueryError::CursorSortMismatch(token.sort));
+    }
+    let value = match (&field.kind, &token.value) {
+        (FieldKind::Int, Value::Number(n)) => n
+            .as_i64()
+            .and_then(|n| i32::try_from(n).ok())
+            .map(|n| Values::Int(vec![n])),
+        (FieldKind::Timestamp, Value::String(s)) => {
+            parse_timestamp(s).map(|t| Values::Timestamp(vec![t]))
+        }
+        (FieldKind::Text | FieldKind::Enum(_), Value::String(s)) => {
+            Some(Values::Text(vec![s.clone()]))
+        }
+        _ => None,
+    }
+    .ok_or(ListQueryError::InvalidCursor)?;
+    Ok(CursorPosition {
+        value,
+        id: token.id,
+    })
+}
+
+/// `%` and `_` match literally in contains filters
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn clause_predicate<QS>(clause: &FilterClause) -> Predicate<QS> {
+    let column = clause.field.column;
+    let op = clause.op;
+    let single = format!("{column} {} ", op.sql());
+    // several alternatives: = ANY(..) / <> ALL(..)
+    let many = match op {
+        FilterOp::Ne => format!("{column} <> ALL("),
+        _ => format!("{column} = ANY("),
+    };
+
+    match &clause.values {
+        Values::Text(v) if op == FilterOp::Contains => {
+            Box::new(sql::<Bool>(&single).bind::<Text, _>(format!("%{}%", escape_like(&v[0]))))
+        }
+        Values::Int(v) if v.len() == 1 => Box::new(sql::<Bool>(&single).bind::<Integer, _>(v[0])),
+        Values::Int(v) => Box::new(
+            sql::<Bool>(&many)
+                .bind::<Array<Integer>, _>(v.clone())
+                .sql(")"),
+        ),
+        Values::Text(v) if v.len() == 1 => {
+            Box::new(sql::<Bool>(&single).bind::<Text, _>(v[0].clone()))
+        }
+        Values::Text(v) => Box::new(
+            sql::<Bool>(&many)
+                .bind::<Array<Text>, _>(v.clone())
+                .sql(")"),
+        ),
+        Values::Timestamp(v) if v.len() == 1 => {
+            Box::new(sql::<Bool>(&single).bind::<Timestamp, _>(v[0]))
+        }
+        Values::Timestamp(v) => Box::new(
+            sql::<Bool>(&many)
+                .bind::<Array<Timestamp>, _>(v.clone())
+                .sql(")"),
+        ),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::enums::job::JobStateEnum;
+    use serde_json::json;
+
+    static SCHEMA: ListSchema = ListSchema {
+        fields: &[
+            ListField {
+                name: "id",
+                column: "t.id",
+                kind: FieldKind::Int,
+                sort_key: Some("id"),
+            },
+            ListField {
+                name: "state",
+                column: "t.state",
+                kind: FieldKind::Enum(JobStateEnum::variants),
+                sort_key: None,
+            },
+            ListField {
+                name: "name",
+                column: "t.name",
+                kind: FieldKind::Text,
+                sort_key: Some("name"),
+            },
+            ListField {
+                name: "created",
+                column: "t.created_at",
+                kind: FieldKind::Timestamp,
+                sort_key: Some("created_at"),
+            },
+        ],
+        id_column: "t.id",
+        default_sort: "-created",
+    };
+
+    #[test]
+    fn parses_filter_clauses() {
+        let q = ListQuery::parse(
+            &SCHEMA,
+            Some("state:queued|Running, created>=2025-05-01,name~back_up"),
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(q.filters.len(), 3);
+        assert_eq!(q.filters[0].op, FilterOp::Eq);
+        assert_eq!(
+            q.filters[0].values,
+            Values::Text(vec!["Queued".into(), "Running".into()])
+        );
+        assert_eq!(q.filters[1].op, FilterOp::Ge);
+        assert_eq!(
+            q.filters[1].values,
+            Values::Timestamp(vec![parse_timestamp("2025-05-01T00:00:00").unwrap()])
+        );
+        assert_eq!(q.filters[2].op, FilterOp::Contains);
+        assert!(q.descending);
+        assert_eq!(q.limit, DEFAULT_PAGE_SIZE);
+        assert_eq!(escape_like("back_up"), "back\\_up");
+    }
+
+    #[test]
+    fn rejects_bad_requests() {
+        let parse = |filter, sort| ListQuery::parse(&SCHEMA, filter, sort, None, None).err();
+
+        assert_eq!(
+            parse(Some("owner:1"), None),
+            Some(ListQueryError::UnknownField("owner".into()))
+        );
+        assert!(matches!(
+            parse(Some("state~Run"), None),
+            Some(ListQueryError::UnsupportedOperator { .. })
+        ));
+        assert!(matches!(
+            parse(Some("state:Sleeping"), None),
+            Some(ListQueryError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            parse(Some("id>abc"), None),
+            Some(ListQueryError::InvalidValue { .. })
+        ));
+        assert_eq!(
+            parse(Some("state"), None),
+            Some(ListQueryError::MalformedClause("state".into()))
+        );
+        assert_eq!(
+            parse(None, Some("state")),
+            Some(ListQueryError::NotSortable("state".into()))
+        );
+    }
+
+    #[test]
+    fn cursor_round_trip() {
+        let q = ListQuery::parse(&SCHEMA, None, Some("name"), Some(2), None).unwrap();
+        let rows = vec![
+            json!({ "id": 1, "name": "a" }),
+            json!({ "id": 2, "name": "b" }),
+            json!({ "id": 3, "name": "c" }),
+        ];
+        let page = q.paginate(rows);
+        assert_eq!(page.items.len(), 2);
+        let cursor = page.next_cursor.unwrap();
+
+        let next = ListQuery::parse(&SCHEMA, None, Some("name"), Some(2), Some(&cursor)).unwrap();
+        assert_eq!(
+            next.after,
+            Some(CursorPosition {
+                value: Values::Text(vec!["b".into()]),
+                id: 2
+            })
+        );
+        // a cursor only continues the sort it was issued for
+        assert_eq!(
+            ListQuery::parse(&SCHEMA, None, Some("-name"), None, Some(&cursor)).err(),
+            Some(ListQueryError::CursorSortMismatch("name".into()))
+        );
+        assert_eq!(
+            ListQuery::parse(&SCHEMA, None, Some("name"), None, Some("garbage")).err(),
+            Some(ListQueryError::InvalidCursor)
+        );
+
+        // last page has no cursor
+        let last = next.paginate(vec![json!({ "id": 3, "name": "c" })]);
+        assert!(last.next_cursor.is_none());
+    }
+}
@@ -1,14 +1,91 @@
+use chrono::NaiveDateTime;
 use diesel::prelude::*;
-use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
 
 use crate::database::models::log::{DBLogEntry, NewDBLogEntry};
+use crate::database::repositories::listing::{FieldKind, ListField, ListQuery, ListSchema};
 use crate::database::schema::logs;
 use crate::enums::log::{LogActionEnum, LogLevelEnum};
 use crate::enums::system::SystemModuleEnum;
 
+/// Rows per INSERT statement, keeps the bind parameters far below the Postgres limit of 65535
+const LOG_INSERT_CHUNK: usize = 1000;
+
+/// Fields of `GET /logs`, `user` is the connecting client and `job` either job payload
+pub static LOG_LIST: ListSchema = ListSchema {
+    fields: &[
+        ListField {
+            name: "id",
+            column: "logs.id",
+            kind: FieldKind::Int,
+            sort_key: Some("id"),
+        },
+        ListField {
+            name: "level",
+            column: "logs.level",
+            kind: FieldKind::Enum(LogLevelEnum::variants),
+            sort_key: Some("level"),
+        },
+        ListField {
+            name: "module",
+            column: "logs.module",
+            kind: FieldKind::Enum(SystemModuleEnum::variants),
+            sort_key: Some("module"),
+        },
+        ListField {
+            name: "action",
+            column: "logs.action",
+            kind: FieldKind::Enum(LogActionEnum::variants),
+            sort_key: Some("action"),
+        },
+        ListField {
+            name: "user",
+            column: "logs.client_connected_username",
+            kind: FieldKind::Text,
+            sort_key: None,
+        },
+        ListField {
+            name: "job",
+            column: "COALESCE(logs.job_submitted_job_id, logs.job_completed_job_id)",
+            kind: FieldKind::Int,
+            sort_key: None,
+        },
+        ListField {
+            name: "created",
+            column: "logs.created_at",
+            kind: FieldKind::Timestamp,
+            sort_key: Some("created_at"),
+        },
+    ],
+    id_column: "logs.id",
+    default_sort: "-created",
+};
+
 pub struct LogEntryRepository;
 
 impl LogEntryRepository {
+    /// One page of log entries matching a list query, see `listing` for the grammar
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `query` - Parsed filter, sort and cursor
+    /// # Returns
+    /// * `QueryResult<Vec<DBLogEntry>>` - up to `query.fetch_limit()` rows, hand them to `query.paginate`
+    pub async fn list(
+        c: &mut AsyncPgConnection,
+        query: &ListQuery,
+    ) -> QueryResult<Vec<DBLogEntry>> {
+        let (order, tie_breaker) = query.order();
+        let mut rows = logs::table.into_boxed();
+        for predicate in query.predicates() {
+            rows = rows.filter(predicate);
+        }
+        rows.order(order)
+            .then_order_by(tie_breaker)
+            .limit(query.fetch_limit())
+            .load(c)
+            .await
+    }
+
     // Find log by ID
     pub async fn find_by_id(c: &mut AsyncPgConnection, id: i32) -> QueryResult<DBLogEntry> {
         logs::table
@@ -30,6 +107,58 @@ impl LogEntryRepository {
             .map(|db_log: DBLogEntry| db_log)
     }
 
+    /// Insert many log entries with multi-row INSERTs
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `entries` - Entries to insert, split into statements of `LOG_INSERT_CHUNK` rows
+    /// # Returns
+    /// * `QueryResult<usize>` - number of inserted rows, all or nothing
+    pub async fn create_many(
+        c: &mut AsyncPgConnection,
+        entries: &[NewDBLogEntry],
+    ) -> QueryResult<usize> {
+        c.transaction::<_, diesel::result::Error, _>(|tx| {
+            Box::pin(async move {
+                let mut inserted = 0;
+                for chunk in entries.chunks(LOG_INSERT_CHUNK) {
+                    inserted += diesel::insert_into(logs::table)
+                        .values(chunk)
+                        .execute(tx)
+                        .await?;
+                }
+                Ok(inserted)
+            })
+        })
+        .await
+    }
+
+    /// Expired log entries, oldest first
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `now` - Entries that expired before this are returned
+    /// * `limit` - Max entries returned
+    /// # Returns
+    /// * `QueryResult<Vec<DBLogEntry>>`
+    pub async fn list_expired(
+        c: &mut AsyncPgConnection,
+        now: NaiveDateTime,
+        limit: i64,
+    ) -> QueryResult<Vec<DBLogEntry>> {
+        logs::table
+            .filter(logs::expires_at.lt(now))
+            .order(logs::id.asc())
+            .limit(limit)
+            .load(c)
+            .await
+    }
+
+    /// Delete log entries by ID
+    pub async fn delete_many(c: &mut AsyncPgConnection, ids: &[i32]) -> QueryResult<usize> {
+        diesel::delete(logs::table.filter(logs::id.eq_any(ids)))
+            .execute(c)
+            .await
+    }
+
     // Delete a log entry by ID
     pub async fn delete(c: &mut AsyncPgConnection, id: i32) -> QueryResult<usize> {
         diesel::delete(logs::table.find(id)).execute(c).await
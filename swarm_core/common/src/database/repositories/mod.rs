@@ -1,18 +1,33 @@
 ///! Repositories for database models
+pub mod archive;
+pub mod audit;
+pub mod backup;
 pub mod job;
 pub mod job_assignment;
 pub mod job_metric;
 pub mod job_result;
+pub mod listing;
 pub mod log;
+pub mod quota;
+pub mod retention;
+pub mod template;
 pub mod user;
+pub mod webhook;
 pub mod worker;
 pub mod worker_status;
 
-pub use job::JobRepository;
+pub use archive::{JobArchiveRepository, ARCHIVE_LIST};
+pub use audit::AuditLogRepository;
+pub use backup::{BackupRepository, BACKUP_TABLES};
+pub use job::{JobRepository, TransitionError, JOB_LIST};
 pub use job_assignment::JobAssignmentRepository;
 pub use job_metric::JobMetricRepository;
-pub use job_result::JobResultRepository;
-pub use log::LogEntryRepository;
+pub use job_result::{JobResultRepository, RESULT_LIST};
+pub use log::{LogEntryRepository, LOG_LIST};
+pub use quota::QuotaRepository;
+pub use retention::LogRetentionRepository;
+pub use template::TemplateRepository;
 pub use user::UserRepository;
-pub use worker::WorkerRepository;
+pub use webhook::{WebhookDeliveryRepository, WebhookRepository};
+pub use worker::{WorkerRepository, WORKER_LIST};
 pub use worker_status::WorkerStatusRepository;
@@ -0,0 +1,153 @@
+use chrono::{Duration, Utc};
+use diesel::dsl::{count_star, now};
+use diesel::prelude::*;
+use diesel::sql_types::Integer;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+use crate::database::models::quota::{NewUserQuota, QuotaUsage, UserQuota, RATE_WINDOW_SECS};
+use crate::database::schema::*;
+use crate::enums::job::JobStateEnum;
+
+/// Quota repository, per user limits and the usage counted against them
+pub struct QuotaRepository;
+
+/// First key of the advisory locks serializing the submissions of a user, the second is the user id
+const SUBMISSION_LOCK_SPACE: i32 = 0x5157_0001;
+
+impl QuotaRepository {
+    /// Find the explicit quota of a user
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `user_id` - The id of the user
+    /// # Returns
+    /// * `QueryResult<Option<UserQuota>>` - None if the user runs on defaults
+    pub async fn find_by_user_id(
+        c: &mut AsyncPgConnection,
+        user_id: i32,
+    ) -> QueryResult<Option<UserQuota>> {
+        user_quotas::table
+            .filter(user_quotas::user_id.eq(user_id))
+            .first::<UserQuota>(c)
+            .await
+            .optional()
+    }
+
+    /// Find the quota in effect for a user, falls back to the defaults
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `user_id` - The id of the user
+    /// # Returns
+    /// * `QueryResult<UserQuota>`
+    pub async fn find_effective(c: &mut AsyncPgConnection, user_id: i32) -> QueryResult<UserQuota> {
+        Ok(Self::find_by_user_id(c, user_id)
+            .await?
+            .unwrap_or_else(|| UserQuota::default_for(user_id)))
+    }
+
+    /// Set the quota of a user, replaces any existing quota
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `new_quota` - The quota to set
+    /// # Returns
+    /// * `QueryResult<UserQuota>`
+    pub async fn upsert(
+        c: &mut AsyncPgConnection,
+        new_quota: NewUserQuota,
+    ) -> QueryResult<UserQuota> {
+        diesel::insert_into(user_quotas::table)
+            .values(&new_quota)
+            .on_conflict(user_quotas::user_id)
+            .do_update()
+            .set((&new_quota, user_quotas::updated_at.eq(now)))
+            .get_result(c)
+            .await
+    }
+
+    /// Remove the quota of a user, the user falls back to the defaults
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `user_id` - The id of the user
+    /// # Returns
+    /// * `QueryResult<usize>`
+    pub async fn delete_by_user_id(c: &mut AsyncPgConnection, user_id: i32) -> QueryResult<usize> {
+        diesel::delete(user_quotas::table.filter(user_quotas::user_id.eq(user_id)))
+            .execute(c)
+            .await
+    }
+
+    /// Wait until no other transaction submits for the user, held until this transaction ends.
+    /// Taken before counting the usage, so concurrent submissions can not both pass the check
+    /// # Arguments
+    /// * `c` - The database connection, inside a transaction
+    /// * `user_id` - The id of the user
+    /// # Returns
+    /// * `QueryResult<()>`
+    pub async fn lock_submissions(c: &mut AsyncPgConnection, user_id: i32) -> QueryResult<()> {
+        diesel::sql_query("SELECT pg_advisory_xact_lock($1, $2)")
+            .bind::<Integer, _>(SUBMISSION_LOCK_SPACE)
+            .bind::<Integer, _>(user_id)
+            .execute(c)
+            .await?;
+        Ok(())
+    }
+
+    /// Count the current usage of a user
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `user_id` - The id of the user
+    /// # Returns
+    /// * `QueryResult<QuotaUsage>`
+    pub async fn usage_for_user(
+        c: &mut AsyncPgConnection,
+        user_id: i32,
+    ) -> QueryResult<QuotaUsage> {
+        let per_state = jobs::table
+            .filter(jobs::user_id.eq(user_id))
+            .filter(jobs::state.eq_any(vec![
+                JobStateEnum::Submitted,
+                JobStateEnum::Queued,
+                JobStateEnum::Running,
+            ]))
+            .group_by(jobs::state)
+            .select((jobs::state, count_star()))
+            .load::<(JobStateEnum, i64)>(c)
+            .await?;
+
+        let window_start = Utc::now().naive_utc() - Duration::seconds(RATE_WINDOW_SECS);
+        let (submissions_last_minute, oldest_submission_in_window) = jobs::table
+            .filter(jobs::user_id.eq(user_id))
+            .filter(jobs::created_at.ge(window_start))
+            .select((count_star(), diesel::dsl::min(jobs::created_at)))
+            .first(c)
+            .await?;
+
+        let mut usage = QuotaUsage {
+            submissions_last_minute,
+            oldest_submission_in_window,
+            ..Default::default()
+        };
+        for (state, count) in per_state {
+            match state {
+                JobStateEnum::Running => usage.running_jobs += count,
+                _ => usage.queued_jobs += count,
+            }
+        }
+        Ok(usage)
+    }
+
+    /// Count jobs admitted by the Scheduler (Queued + Running) per user
+    /// # Arguments
+    /// * `c` - The database connection
+    /// # Returns
+    /// * `QueryResult<Vec<(i32, i64)>>` - (user_id, admitted jobs)
+    pub async fn count_admitted_per_user(
+        c: &mut AsyncPgConnection,
+    ) -> QueryResult<Vec<(i32, i64)>> {
+        jobs::table
+            .filter(jobs::state.eq_any(vec![JobStateEnum::Queued, JobStateEnum::Running]))
+            .group_by(jobs::user_id)
+            .select((jobs::user_id, count_star()))
+            .load::<(i32, i64)>(c)
+            .await
+    }
+}
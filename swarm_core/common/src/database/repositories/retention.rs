@@ -0,0 +1,56 @@
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+
+use crate::database::models::retention::{LogRetentionPolicy, NewLogRetentionPolicy};
+use crate::database::schema::log_retention_policies;
+
+/// Log retention policy repository
+pub struct LogRetentionRepository;
+
+impl LogRetentionRepository {
+    /// List every policy, least specific first
+    pub async fn list_all(c: &mut AsyncPgConnection) -> QueryResult<Vec<LogRetentionPolicy>> {
+        log_retention_policies::table
+            .order((
+                log_retention_policies::action.asc().nulls_first(),
+                log_retention_policies::module.asc().nulls_first(),
+                log_retention_policies::level.asc().nulls_first(),
+            ))
+            .load(c)
+            .await
+    }
+
+    /// Create the policy for (level, module, action) or replace its retention
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `policy` - The policy, empty fields match everything
+    /// # Returns
+    /// * `QueryResult<LogRetentionPolicy>`
+    pub async fn upsert(
+        c: &mut AsyncPgConnection,
+        policy: NewLogRetentionPolicy,
+    ) -> QueryResult<LogRetentionPolicy> {
+        diesel::insert_into(log_retention_policies::table)
+            .values(&policy)
+            .on_conflict((
+                log_retention_policies::level,
+                log_retention_policies::module,
+                log_retention_policies::action,
+            ))
+            .do_update()
+            .set((
+                log_retention_policies::retention_minutes.eq(policy.retention_minutes),
+                log_retention_policies::updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .get_result(c)
+            .await
+    }
+
+    /// Delete a policy by ID, entries it matched fall back to less specific policies
+    pub async fn delete(c: &mut AsyncPgConnection, id: i32) -> QueryResult<usize> {
+        diesel::delete(log_retention_policies::table.find(id))
+            .execute(c)
+            .await
+    }
+}
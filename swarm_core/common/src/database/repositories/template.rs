@@ -0,0 +1,117 @@
+use diesel::prelude::*;
+use diesel_async::{AsyncConnection, AsyncPgConnection, RunQueryDsl};
+
+use crate::database::models::template::{
+    JobTemplate, NewJobTemplate, NewTemplateParameter, TemplateParameter, TemplateWithParameters,
+};
+use crate::database::schema::*;
+
+/// Job template repository, templates are stored together with their typed parameters
+pub struct TemplateRepository;
+
+impl TemplateRepository {
+    /// Create a template and its parameters in one transaction
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `new_template` - The template to create
+    /// * `parameters` - Parameters, `template_id` is filled in after insert
+    /// # Returns
+    /// * `QueryResult<TemplateWithParameters>`
+    pub async fn create(
+        c: &mut AsyncPgConnection,
+        new_template: NewJobTemplate,
+        parameters: Vec<NewTemplateParameter>,
+    ) -> QueryResult<TemplateWithParameters> {
+        c.transaction::<_, diesel::result::Error, _>(|tx| {
+            Box::pin(async move {
+                let template: JobTemplate = diesel::insert_into(job_templates::table)
+                    .values(new_template)
+                    .get_result(tx)
+                    .await?;
+
+                let parameters: Vec<NewTemplateParameter> = parameters
+                    .into_iter()
+                    .map(|p| NewTemplateParameter {
+                        template_id: template.id,
+                        ..p
+                    })
+                    .collect();
+                let parameters: Vec<TemplateParameter> =
+                    diesel::insert_into(job_template_parameters::table)
+                        .values(parameters)
+                        .get_results(tx)
+                        .await?;
+
+                Ok(TemplateWithParameters {
+                    template,
+                    parameters,
+                })
+            })
+        })
+        .await
+    }
+
+    /// Find a template with its parameters
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `id` - The id of the template
+    /// # Returns
+    /// * `QueryResult<TemplateWithParameters>`
+    pub async fn find_by_id(
+        c: &mut AsyncPgConnection,
+        id: i32,
+    ) -> QueryResult<TemplateWithParameters> {
+        let template: JobTemplate = job_templates::table.find(id).get_result(c).await?;
+        let parameters = TemplateParameter::belonging_to(&template)
+            .order(job_template_parameters::id.asc())
+            .load(c)
+            .await?;
+        Ok(TemplateWithParameters {
+            template,
+            parameters,
+        })
+    }
+
+    /// List all templates of a user, with parameters
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `user_id` - The id of the user
+    /// # Returns
+    /// * `QueryResult<Vec<TemplateWithParameters>>`
+    pub async fn list_by_user(
+        c: &mut AsyncPgConnection,
+        user_id: i32,
+    ) -> QueryResult<Vec<TemplateWithParameters>> {
+        let templates: Vec<JobTemplate> = job_templates::table
+            .filter(job_templates::user_id.eq(user_id))
+            .order(job_templates::name.asc())
+            .load(c)
+            .await?;
+        let parameters: Vec<TemplateParameter> = TemplateParameter::belonging_to(&templates)
+            .order(job_template_parameters::id.asc())
+            .load(c)
+            .await?;
+
+        Ok(parameters
+            .grouped_by(&templates)
+            .into_iter()
+            .zip(templates)
+            .map(|(parameters, template)| TemplateWithParameters {
+                template,
+                parameters,
+            })
+            .collect())
+    }
+
+    /// Delete a template, parameters are removed by cascade
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `id` - The id of the template
+    /// # Returns
+    /// * `QueryResult<usize>`
+    pub async fn delete(c: &mut AsyncPgConnection, id: i32) -> QueryResult<usize> {
+        diesel::delete(job_templates::table.find(id))
+            .execute(c)
+            .await
+    }
+}
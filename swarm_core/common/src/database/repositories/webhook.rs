@@ -0,0 +1,250 @@
+use chrono::{NaiveDateTime, Utc};
+use diesel::prelude::*;
+use diesel_async::{AsyncPgConnection, RunQueryDsl};
+use serde_json::{json, Value};
+
+use crate::database::models::webhook::{
+    NewWebhook, NewWebhookDelivery, UpdateWebhook, Webhook, WebhookDelivery,
+};
+use crate::database::schema::{webhook_deliveries, webhooks};
+use crate::enums::webhook::{DeliveryStatusEnum, WebhookEventEnum};
+
+/// Webhook repository
+pub struct WebhookRepository;
+
+impl WebhookRepository {
+    /// Register a webhook
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `new_webhook` - The webhook, owner and secret already set
+    /// # Returns
+    /// * `QueryResult<Webhook>`
+    pub async fn create(
+        c: &mut AsyncPgConnection,
+        new_webhook: NewWebhook,
+    ) -> QueryResult<Webhook> {
+        diesel::insert_into(webhooks::table)
+            .values(new_webhook)
+            .get_result(c)
+            .await
+    }
+
+    /// Find a webhook by ID
+    pub async fn find_by_id(c: &mut AsyncPgConnection, id: i32) -> QueryResult<Webhook> {
+        webhooks::table.find(id).get_result(c).await
+    }
+
+    /// All webhooks of a user, oldest first
+    pub async fn list_for_user(
+        c: &mut AsyncPgConnection,
+        user_id: i32,
+    ) -> QueryResult<Vec<Webhook>> {
+        webhooks::table
+            .filter(webhooks::user_id.eq(user_id))
+            .order(webhooks::id.asc())
+            .load(c)
+            .await
+    }
+
+    /// Active webhooks of a user subscribed to an event
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `user_id` - Owner of the job or worker the event is about
+    /// * `event` - The event
+    /// # Returns
+    /// * `QueryResult<Vec<Webhook>>`
+    pub async fn list_subscribed(
+        c: &mut AsyncPgConnection,
+        user_id: i32,
+        event: WebhookEventEnum,
+    ) -> QueryResult<Vec<Webhook>> {
+        webhooks::table
+            .filter(webhooks::user_id.eq(user_id))
+            .filter(webhooks::active.eq(true))
+            .filter(webhooks::events.contains(vec![event]))
+            .load(c)
+            .await
+    }
+
+    /// Update url, events or active flag
+    pub async fn update(
+        c: &mut AsyncPgConnection,
+        id: i32,
+        changes: UpdateWebhook,
+    ) -> QueryResult<Webhook> {
+        diesel::update(webhooks::table.find(id))
+            .set(changes)
+            .get_result(c)
+            .await
+    }
+
+    /// Delete a webhook and its delivery log
+    pub async fn delete(c: &mut AsyncPgConnection, id: i32) -> QueryResult<usize> {
+        diesel::delete(webhooks::table.find(id)).execute(c).await
+    }
+}
+
+/// Webhook delivery log repository
+pub struct WebhookDeliveryRepository;
+
+impl WebhookDeliveryRepository {
+    /// Record a lifecycle event, one Pending delivery per active webhook of the owner subscribed to it
+    /// The Notifier POSTs them on its next pulse as `{"event", "occurred_at", "data"}`
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `event` - The event
+    /// * `user_id` - Owner of the job or worker the event is about
+    /// * `data` - The job or worker after the change
+    /// # Returns
+    /// * `QueryResult<usize>` - number of deliveries queued
+    pub async fn enqueue_event(
+        c: &mut AsyncPgConnection,
+        event: WebhookEventEnum,
+        user_id: i32,
+        data: Value,
+    ) -> QueryResult<usize> {
+        let webhooks = WebhookRepository::list_subscribed(c, user_id, event).await?;
+        if webhooks.is_empty() {
+            return Ok(0);
+        }
+
+        let payload = json!({
+            "event": event,
+            "occurred_at": Utc::now().naive_utc(),
+            "data": data,
+        });
+        let deliveries: Vec<NewWebhookDelivery> = webhooks
+            .iter()
+            .map(|w| NewWebhookDelivery {
+                webhook_id: w.id,
+                event,
+                payload: payload.clone(),
+            })
+            .collect();
+        Self::create_many(c, &deliveries).await
+    }
+
+    /// Queue deliveries, they are Pending and due right away
+    pub async fn create_many(
+        c: &mut AsyncPgConnection,
+        deliveries: &[NewWebhookDelivery],
+    ) -> QueryResult<usize> {
+        diesel::insert_into(webhook_deliveries::table)
+            .values(deliveries)
+            .execute(c)
+            .await
+    }
+
+    /// Find a delivery by ID
+    pub async fn find_by_id(c: &mut AsyncPgConnection, id: i32) -> QueryResult<WebhookDelivery> {
+        webhook_deliveries::table.find(id).get_result(c).await
+    }
+
+    /// Pending deliveries that are due, with their webhook, oldest first
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `now` - Deliveries with `next_attempt_at` up to now are due
+    /// * `limit` - Max deliveries returned
+    /// # Returns
+    /// * `QueryResult<Vec<(WebhookDelivery, Webhook)>>`
+    pub async fn list_due(
+        c: &mut AsyncPgConnection,
+        now: NaiveDateTime,
+        limit: i64,
+    ) -> QueryResult<Vec<(WebhookDelivery, Webhook)>> {
+        webhook_deliveries::table
+            .inner_join(webhooks::table)
+            .filter(webhook_deliveries::status.eq(DeliveryStatusEnum::Pending))
+            .filter(webhook_deliveries::next_attempt_at.le(now))
+            .order(webhook_deliveries::next_attempt_at.asc())
+            .limit(limit)
+            .load(c)
+            .await
+    }
+
+    /// Delivery log of a webhook, newest first
+    pub async fn list_for_webhook(
+        c: &mut AsyncPgConnection,
+        webhook_id: i32,
+        limit: i64,
+        offset: i64,
+    ) -> QueryResult<Vec<WebhookDelivery>> {
+        webhook_deliveries::table
+            .filter(webhook_deliveries::webhook_id.eq(webhook_id))
+            .order(webhook_deliveries::id.desc())
+            .limit(limit)
+            .offset(offset)
+            .load(c)
+            .await
+    }
+
+    /// Record a successful attempt
+    pub async fn mark_delivered(
+        c: &mut AsyncPgConnection,
+        id: i32,
+        response_code: i32,
+        now: NaiveDateTime,
+    ) -> QueryResult<usize> {
+        diesel::update(webhook_deliveries::table.find(id))
+            .set((
+                webhook_deliveries::status.eq(DeliveryStatusEnum::Delivered),
+                webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                webhook_deliveries::response_code.eq(Some(response_code)),
+                webhook_deliveries::last_error.eq(None::<String>),
+                webhook_deliveries::delivered_at.eq(Some(now)),
+            ))
+            .execute(c)
+            .await
+    }
+
+    /// Record a failed attempt
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `id` - The delivery
+    /// * `response_code` - HTTP status, None if the receiver could not be reached
+    /// * `error` - What went wrong
+    /// * `now` - Time of the attempt
+    /// * `retry_at` - When to try again, None gives up and marks the delivery Failed
+    /// # Returns
+    /// * `QueryResult<usize>`
+    pub async fn mark_attempt_failed(
+        c: &mut AsyncPgConnection,
+        id: i32,
+        response_code: Option<i32>,
+        error: &str,
+        now: NaiveDateTime,
+        retry_at: Option<NaiveDateTime>,
+    ) -> QueryResult<usize> {
+        let status = match retry_at {
+            Some(_) => DeliveryStatusEnum::Pending,
+            None => DeliveryStatusEnum::Failed,
+        };
+        diesel::update(webhook_deliveries::table.find(id))
+            .set((
+                webhook_deliveries::status.eq(status),
+                webhook_deliveries::attempts.eq(webhook_deliveries::attempts + 1),
+                webhook_deliveries::response_code.eq(response_code),
+                webhook_deliveries::last_error.eq(Some(error)),
+                webhook_deliveries::next_attempt_at.eq(retry_at.unwrap_or(now)),
+            ))
+            .execute(c)
+            .await
+    }
+
+    /// Send a delivery again from scratch, e.g. after the receiver was fixed
+    pub async fn redeliver(
+        c: &mut AsyncPgConnection,
+        id: i32,
+        now: NaiveDateTime,
+    ) -> QueryResult<WebhookDelivery> {
+        diesel::update(webhook_deliveries::table.find(id))
+            .set((
+                webhook_deliveries::status.eq(DeliveryStatusEnum::Pending),
+                webhook_deliveries::attempts.eq(0),
+                webhook_deliveries::next_attempt_at.eq(now),
+                webhook_deliveries::delivered_at.eq(None::<NaiveDateTime>),
+            ))
+            .get_result(c)
+            .await
+    }
+}
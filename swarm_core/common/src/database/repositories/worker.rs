@@ -4,11 +4,46 @@ use diesel_async::{AsyncPgConnection, RunQueryDsl};
 use chrono::NaiveDateTime;
 
 use crate::database::models::worker::{NewWorker, Worker};
+use crate::database::repositories::listing::{FieldKind, ListField, ListQuery, ListSchema};
 use crate::database::schema::*;
+use crate::enums::workers::WorkerStatusEnum;
+
+/// Fields of `GET /workers`, `status` is the latest reported status
+pub static WORKER_LIST: ListSchema = ListSchema {
+    fields: &[
+        ListField { name: "id", column: "workers.id", kind: FieldKind::Int, sort_key: Some("id") },
+        ListField { name: "user", column: "workers.user_id", kind: FieldKind::Int, sort_key: Some("user_id") },
+        ListField { name: "name", column: "workers.label", kind: FieldKind::Text, sort_key: Some("label") },
+        ListField { name: "hostname", column: "workers.hostname", kind: FieldKind::Text, sort_key: Some("hostname") },
+        ListField { name: "status", column: "(SELECT ws.status FROM worker_status ws WHERE ws.worker_id = workers.id ORDER BY ws.updated_at DESC, ws.id DESC LIMIT 1)", kind: FieldKind::Enum(WorkerStatusEnum::variants), sort_key: None },
+        ListField { name: "created", column: "workers.created_at", kind: FieldKind::Timestamp, sort_key: Some("created_at") },
+    ],
+    id_column: "workers.id",
+    default_sort: "-created",
+};
 
 pub struct WorkerRepository;
 
 impl WorkerRepository {
+    /// One page of workers matching a list query, see `listing` for the grammar
+    /// # Arguments
+    /// * `c` - The database connection
+    /// * `query` - Parsed filter, sort and cursor
+    /// # Returns
+    /// * `QueryResult<Vec<Worker>>` - up to `query.fetch_limit()` rows, hand them to `query.paginate`
+    pub async fn list(c: &mut AsyncPgConnection, query: &ListQuery) -> QueryResult<Vec<Worker>> {
+        let (order, tie_breaker) = query.order();
+        let mut rows = workers::table.into_boxed();
+        for predicate in query.predicates() {
+            rows = rows.filter(predicate);
+        }
+        rows.order(order)
+            .then_order_by(tie_breaker)
+            .limit(query.fetch_limit())
+            .load(c)
+            .await
+    }
+
     pub async fn create(c: &mut AsyncPgConnection, new_worker: NewWorker) -> QueryResult<Worker> {
         diesel::insert_into(workers::table)
             .values(new_worker)
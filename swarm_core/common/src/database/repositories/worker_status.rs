@@ -1,7 +1,8 @@
 use diesel::prelude::*;
+use diesel::sql_types::{Array, Integer, Timestamp};
 use diesel_async::{AsyncPgConnection, RunQueryDsl};
 
-use chrono::Utc;
+use chrono::{NaiveDateTime, Utc};
 
 use crate::database::models::worker::{NewWorkerStatus, WorkerStatus};
 use crate::database::schema::*;
@@ -113,6 +114,30 @@ impl WorkerStatusRepository {
             .await
     }
 
+    pub async fn count_by_status(
+        c: &mut AsyncPgConnection,
+        status: WorkerStatusEnum,
+    ) -> QueryResult<i64> {
+        worker_status::table
+            .filter(worker_status::status.eq(status))
+            .count()
+            .get_result(c)
+            .await
+    }
+
+    /// Owners of the workers currently in `status`, one entry per worker
+    pub async fn owners_by_status(
+        c: &mut AsyncPgConnection,
+        status: WorkerStatusEnum,
+    ) -> QueryResult<Vec<i32>> {
+        worker_status::table
+            .inner_join(workers::table)
+            .filter(worker_status::status.eq(status))
+            .select(workers::user_id)
+            .load(c)
+            .await
+    }
+
     pub async fn update_status_by_worker_id(
         c: &mut AsyncPgConnection,
         worker_id: i32,
@@ -123,4 +148,31 @@ impl WorkerStatusRepository {
             .get_result(c)
             .await
     }
+
+    /// Store a batch of heartbeats in one statement, sets `workers.last_seen_at` and
+    /// `worker_status.last_heartbeat` of every listed worker
+    /// # Arguments
+    /// * `beats` - worker id and the time its last heartbeat arrived, one entry per worker
+    /// # Returns
+    /// * `QueryResult<usize>` - `worker_status` rows updated, workers without a status row are skipped
+    pub async fn record_heartbeats(
+        c: &mut AsyncPgConnection,
+        beats: &[(i32, NaiveDateTime)],
+    ) -> QueryResult<usize> {
+        if beats.is_empty() {
+            return Ok(0);
+        }
+        let (worker_ids, seen_at): (Vec<i32>, Vec<NaiveDateTime>) = beats.iter().copied().unzip();
+        diesel::sql_query(
+            "WITH beats AS (SELECT * FROM unnest($1, $2) AS b(worker_id, seen_at)), \
+             seen AS (UPDATE workers SET last_seen_at = beats.seen_at \
+                      FROM beats WHERE workers.id = beats.worker_id) \
+             UPDATE worker_status SET last_heartbeat = beats.seen_at \
+             FROM beats WHERE worker_status.worker_id = beats.worker_id",
+        )
+        .bind::<Array<Integer>, _>(worker_ids)
+        .bind::<Array<Timestamp>, _>(seen_at)
+        .execute(c)
+        .await
+    }
 }
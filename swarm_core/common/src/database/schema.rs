@@ -1,5 +1,45 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    audit_logs (id) {
+        id -> Int4,
+        created_at -> Timestamp,
+        expires_at -> Timestamp,
+        user_id -> Nullable<Int4>,
+        username -> Nullable<Text>,
+        #[max_length = 16]
+        token_prefix -> Nullable<Varchar>,
+        client_ip -> Nullable<Text>,
+        #[max_length = 8]
+        method -> Varchar,
+        route -> Text,
+        path -> Text,
+        #[max_length = 64]
+        resource -> Nullable<Varchar>,
+        resource_id -> Nullable<Int4>,
+        status -> Int4,
+        before -> Nullable<Jsonb>,
+        after -> Nullable<Jsonb>,
+    }
+}
+
+diesel::table! {
+    job_archives (id) {
+        id -> Int4,
+        user_id -> Int4,
+        job_name -> Text,
+        image_url -> Text,
+        #[max_length = 64]
+        state -> Varchar,
+        error_message -> Nullable<Text>,
+        created_at -> Timestamp,
+        finished_at -> Timestamp,
+        result_count -> Int4,
+        archived_at -> Timestamp,
+        data -> Jsonb,
+    }
+}
+
 diesel::table! {
     job_assignments (id) {
         id -> Int4,
@@ -34,6 +74,40 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    job_template_parameters (id) {
+        id -> Int4,
+        template_id -> Int4,
+        name -> Text,
+        #[max_length = 64]
+        kind -> Varchar,
+        default_value -> Nullable<Text>,
+        required -> Bool,
+    }
+}
+
+diesel::table! {
+    job_templates (id) {
+        id -> Int4,
+        user_id -> Int4,
+        name -> Text,
+        image_url -> Text,
+        #[max_length = 64]
+        image_format -> Varchar,
+        docker_flags -> Nullable<Array<Nullable<Text>>>,
+        #[max_length = 64]
+        output_type -> Varchar,
+        output_paths -> Nullable<Array<Nullable<Text>>>,
+        #[max_length = 64]
+        schedule_type -> Varchar,
+        cron_expression -> Nullable<Text>,
+        notes -> Nullable<Text>,
+        priority -> Int4,
+        created_at -> Timestamp,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     jobs (id) {
         id -> Int4,
@@ -55,6 +129,21 @@ diesel::table! {
         error_message -> Nullable<Text>,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        priority -> Int4,
+    }
+}
+
+diesel::table! {
+    log_retention_policies (id) {
+        id -> Int4,
+        #[max_length = 64]
+        level -> Nullable<Varchar>,
+        #[max_length = 64]
+        module -> Nullable<Varchar>,
+        #[max_length = 64]
+        action -> Nullable<Varchar>,
+        retention_minutes -> Int8,
+        updated_at -> Timestamp,
     }
 }
 
@@ -82,6 +171,17 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    user_quotas (id) {
+        id -> Int4,
+        user_id -> Int4,
+        max_queued_jobs -> Int4,
+        max_running_jobs -> Int4,
+        max_submissions_per_minute -> Int4,
+        updated_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     users (id) {
         id -> Int4,
@@ -92,6 +192,36 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Int4,
+        webhook_id -> Int4,
+        #[max_length = 64]
+        event -> Varchar,
+        payload -> Jsonb,
+        #[max_length = 16]
+        status -> Varchar,
+        attempts -> Int4,
+        response_code -> Nullable<Int4>,
+        last_error -> Nullable<Text>,
+        next_attempt_at -> Timestamp,
+        created_at -> Timestamp,
+        delivered_at -> Nullable<Timestamp>,
+    }
+}
+
+diesel::table! {
+    webhooks (id) {
+        id -> Int4,
+        user_id -> Int4,
+        url -> Text,
+        secret -> Text,
+        events -> Array<Text>,
+        active -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     worker_status (id) {
         id -> Int4,
@@ -126,23 +256,37 @@ diesel::table! {
     }
 }
 
+diesel::joinable!(job_archives -> users (user_id));
 diesel::joinable!(job_assignments -> jobs (job_id));
 diesel::joinable!(job_assignments -> workers (worker_id));
 diesel::joinable!(job_metrics -> jobs (job_id));
 diesel::joinable!(job_metrics -> workers (worker_id));
 diesel::joinable!(job_results -> jobs (job_id));
+diesel::joinable!(job_template_parameters -> job_templates (template_id));
+diesel::joinable!(job_templates -> users (user_id));
 diesel::joinable!(jobs -> users (user_id));
+diesel::joinable!(user_quotas -> users (user_id));
+diesel::joinable!(webhook_deliveries -> webhooks (webhook_id));
+diesel::joinable!(webhooks -> users (user_id));
 diesel::joinable!(worker_status -> jobs (active_job_id));
 diesel::joinable!(worker_status -> workers (worker_id));
 diesel::joinable!(workers -> users (user_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    audit_logs,
+    job_archives,
     job_assignments,
     job_metrics,
     job_results,
+    job_template_parameters,
+    job_templates,
     jobs,
+    log_retention_policies,
     logs,
+    user_quotas,
     users,
+    webhook_deliveries,
+    webhooks,
     worker_status,
     workers,
 );
@@ -1,140 +1,114 @@
-use std::fmt;
-use std::io::Write;
-use std::str::FromStr;
-
-use diesel::deserialize::FromSql;
-use diesel::deserialize::FromSqlRow;
-use diesel::expression::AsExpression;
-use diesel::pg::{Pg, PgValue};
-use diesel::serialize::ToSql;
-use diesel::sql_types::Text;
-use serde::{Deserialize, Serialize};
-
-/// JobStateEnum, the state of a job
-#[derive(AsExpression, Debug, Deserialize, Serialize, FromSqlRow, PartialEq)]
-#[diesel(sql_type = Text)]
-pub enum JobStateEnum {
-    Submitted,
-    Queued,
-    Running,
-    Completed,
-    Failed, // Can store dynamic error message
-}
-
-// serialize to json, and display
-impl fmt::Display for JobStateEnum {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let label = match self {
-            JobStateEnum::Submitted => "Submitted",
-            JobStateEnum::Queued => "Queued",
-            JobStateEnum::Running => "Running",
-            JobStateEnum::Completed => "Completed",
-            JobStateEnum::Failed => "Failed",
-        };
-        write!(f, "{}", label)
+db_enum! {
+    /// JobStateEnum, the state of a job
+    JobStateEnum {
+        Submitted,
+        Queued,
+        Running,
+        Completed,
+        Failed, // Can store dynamic error message
     }
 }
 
-// deserialize from json
-impl FromStr for JobStateEnum {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "Submitted" => Ok(JobStateEnum::Submitted),
-            "Queued" => Ok(JobStateEnum::Queued),
-            "Running" => Ok(JobStateEnum::Running),
-            "Completed" => Ok(JobStateEnum::Completed),
-            "Failed" => Ok(JobStateEnum::Failed),
-            _ => Err(()),
-        }
+/// Every new job starts `Submitted`, the Scheduler admits it to `Queued` within the owners quota
+impl Default for JobStateEnum {
+    fn default() -> Self {
+        JobStateEnum::Submitted
     }
 }
 
-// deserialize from database
-impl FromSql<Text, Pg> for JobStateEnum {
-    fn from_sql(value: PgValue<'_>) -> diesel::deserialize::Result<Self> {
-        match value.as_bytes() {
-            b"Submitted" => Ok(JobStateEnum::Submitted),
-            b"Queued" => Ok(JobStateEnum::Queued),
-            b"Running" => Ok(JobStateEnum::Running),
-            b"Completed" => Ok(JobStateEnum::Completed),
-            b"Failed" => Ok(JobStateEnum::Failed),
-            _ => Err("Unexpected value".into()),
-        }
-    }
+impl JobStateEnum {
+    pub const ALL: [JobStateEnum; 5] = [
+        JobStateEnum::Submitted,
+        JobStateEnum::Queued,
+        JobStateEnum::Running,
+        JobStateEnum::Completed,
+        JobStateEnum::Failed,
+    ];
 }
 
-// serialize to database
-impl ToSql<Text, Pg> for JobStateEnum {
-    fn to_sql<'b>(
-        &'b self,
-        out: &mut diesel::serialize::Output<'b, '_, Pg>,
-    ) -> diesel::serialize::Result {
+/// The job state machine, every state change in core and CommandDeck goes through this table
+///
+/// ```text
+/// Submitted → Queued      admitted by the Scheduler
+/// Queued    → Running     claimed by a worker
+/// Running   → Completed
+/// Running   → Queued      preempted
+/// any unfinished → Failed also used to cancel
+/// ```
+/// Completed and Failed are final, a re-run is a new job.
+impl JobStateEnum {
+    /// States a job in `self` may move to
+    pub fn next_states(&self) -> &'static [JobStateEnum] {
         match self {
-            JobStateEnum::Submitted => out.write_all(b"Submitted")?,
-            JobStateEnum::Queued => out.write_all(b"Queued")?,
-            JobStateEnum::Running => out.write_all(b"Running")?,
-            JobStateEnum::Completed => out.write_all(b"Completed")?,
-            JobStateEnum::Failed => out.write_all(b"Failed")?,
+            JobStateEnum::Submitted => &[JobStateEnum::Queued, JobStateEnum::Failed],
+            JobStateEnum::Queued => &[JobStateEnum::Running, JobStateEnum::Failed],
+            JobStateEnum::Running => &[
+                JobStateEnum::Completed,
+                JobStateEnum::Failed,
+                JobStateEnum::Queued,
+            ],
+            JobStateEnum::Completed | JobStateEnum::Failed => &[],
         }
-        Ok(diesel::serialize::IsNull::No)
     }
-}
 
-/// JobScheduleEnum, how is the job scheduled
-#[derive(AsExpression, Debug, Deserialize, Serialize, FromSqlRow, PartialEq, Eq)]
-#[diesel(sql_type = Text)]
-pub enum JobScheduleEnum {
-    Once,
-    Cron,
-}
-
-// serialize to json, and display
-impl fmt::Display for JobScheduleEnum {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let label = match self {
-            JobScheduleEnum::Once => "Once",
-            JobScheduleEnum::Cron => "Cron",
-        };
-        write!(f, "{}", label)
+    pub fn can_transition_to(&self, next: JobStateEnum) -> bool {
+        self.next_states().contains(&next)
     }
-}
 
-// deserialize from json
-impl FromStr for JobScheduleEnum {
-    type Err = ();
+    /// States a job may be in to move to `next`, the guard of a conditional update
+    pub fn sources_of(next: JobStateEnum) -> Vec<JobStateEnum> {
+        JobStateEnum::ALL
+            .into_iter()
+            .filter(|state| state.can_transition_to(next))
+            .collect()
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "Once" => Ok(JobScheduleEnum::Once),
-            "Cron" => Ok(JobScheduleEnum::Cron), // We can initialize with an empty string
-            _ => Err(()),
-        }
+    pub fn is_final(&self) -> bool {
+        self.next_states().is_empty()
     }
 }
 
-// deserialize from database
-impl FromSql<Text, Pg> for JobScheduleEnum {
-    fn from_sql(value: PgValue<'_>) -> diesel::deserialize::Result<Self> {
-        match value.as_bytes() {
-            b"Once" => Ok(JobScheduleEnum::Once),
-            b"Cron" => Ok(JobScheduleEnum::Cron),
-            _ => Err("Unexpected value".into()),
-        }
+db_enum! {
+    /// JobScheduleEnum, how is the job scheduled
+    JobScheduleEnum {
+        Once,
+        Cron,
     }
 }
 
-// serialize to database
-impl ToSql<Text, Pg> for JobScheduleEnum {
-    fn to_sql<'b>(
-        &'b self,
-        out: &mut diesel::serialize::Output<'b, '_, Pg>,
-    ) -> diesel::serialize::Result {
-        match self {
-            JobScheduleEnum::Once => out.write_all(b"Once")?,
-            JobScheduleEnum::Cron => out.write_all(b"Cron")?,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finished_jobs_stay_finished() {
+        for state in [JobStateEnum::Completed, JobStateEnum::Failed] {
+            assert!(state.is_final());
+            assert!(JobStateEnum::ALL
+                .iter()
+                .all(|next| !state.can_transition_to(*next)));
         }
-        Ok(diesel::serialize::IsNull::No)
+        assert!(!JobStateEnum::Submitted.can_transition_to(JobStateEnum::Running));
+    }
+
+    #[test]
+    fn sources_follow_the_table() {
+        assert_eq!(
+            JobStateEnum::sources_of(JobStateEnum::Running),
+            [JobStateEnum::Queued]
+        );
+        assert_eq!(
+            JobStateEnum::sources_of(JobStateEnum::Queued),
+            [JobStateEnum::Submitted, JobStateEnum::Running]
+        );
+        assert_eq!(
+            JobStateEnum::sources_of(JobStateEnum::Failed),
+            [
+                JobStateEnum::Submitted,
+                JobStateEnum::Queued,
+                JobStateEnum::Running
+            ]
+        );
+        assert!(JobStateEnum::sources_of(JobStateEnum::Submitted).is_empty());
     }
 }
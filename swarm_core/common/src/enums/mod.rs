@@ -1,8 +1,198 @@
-///! Enums for the database stored in db
+//! Enums for the database stored in db
+/// Declares an enum stored as text, with `VARIANTS`, `variants()`, `as_str`, `Display`, `FromStr`
+/// and the diesel `FromSql`/`ToSql` impls, all from the variant names
+///
+/// The variant name is what goes over the wire and into the database,
+/// the check constraints of the `enum_checks_and_indexes` migration only allow these names.
+macro_rules! db_enum {
+    ($(#[$meta:meta])* $name:ident { $($variant:ident),+ $(,)? }) => {
+        $(#[$meta])*
+        #[derive(
+            diesel::expression::AsExpression,
+            diesel::deserialize::FromSqlRow,
+            Debug,
+            Clone,
+            Copy,
+            PartialEq,
+            Eq,
+            Hash,
+            serde::Serialize,
+            serde::Deserialize,
+            utoipa::ToSchema,
+        )]
+        #[diesel(sql_type = diesel::sql_types::Text)]
+        pub enum $name {
+            $($variant),+
+        }
+
+        impl $name {
+            /// Every variant name, in declaration order
+            pub const VARIANTS: &'static [&'static str] = &[$(stringify!($variant)),+];
+
+            // used for filter validation and selection menus
+            pub fn variants() -> &'static [&'static str] {
+                Self::VARIANTS
+            }
+
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $($name::$variant => stringify!($variant)),+
+                }
+            }
+        }
+
+        // serialize to json, and display
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        // deserialize from json
+        impl std::str::FromStr for $name {
+            type Err = ();
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $(stringify!($variant) => Ok($name::$variant),)+
+                    _ => Err(()),
+                }
+            }
+        }
+
+        // deserialize from database
+        impl diesel::deserialize::FromSql<diesel::sql_types::Text, diesel::pg::Pg> for $name {
+            fn from_sql(value: diesel::pg::PgValue<'_>) -> diesel::deserialize::Result<Self> {
+                let bytes = value.as_bytes();
+                std::str::from_utf8(bytes)
+                    .ok()
+                    .and_then(|s| s.parse().ok())
+                    .ok_or_else(|| {
+                        format!(
+                            "Unexpected {} '{}'",
+                            stringify!($name),
+                            String::from_utf8_lossy(bytes)
+                        )
+                        .into()
+                    })
+            }
+        }
+
+        // serialize to database
+        impl diesel::serialize::ToSql<diesel::sql_types::Text, diesel::pg::Pg> for $name {
+            fn to_sql<'b>(
+                &'b self,
+                out: &mut diesel::serialize::Output<'b, '_, diesel::pg::Pg>,
+            ) -> diesel::serialize::Result {
+                std::io::Write::write_all(out, self.as_str().as_bytes())?;
+                Ok(diesel::serialize::IsNull::No)
+            }
+        }
+    };
+}
+
 pub mod image_format;
 pub mod job;
 pub mod log;
 pub mod output;
 pub mod schedule;
 pub mod system;
+pub mod template;
+pub mod webhook;
 pub mod workers;
+
+#[cfg(test)]
+mod tests {
+    use super::image_format::ImageFormatEnum;
+    use super::job::JobStateEnum;
+    use super::log::{LogActionEnum, LogLevelEnum};
+    use super::output::OutputTypeEnum;
+    use super::schedule::ScheduleTypeEnum;
+    use super::system::SystemModuleEnum;
+    use super::template::TemplateParamKindEnum;
+    use super::webhook::{DeliveryStatusEnum, WebhookEventEnum};
+    use super::workers::{OSEnum, WorkerStatusEnum};
+
+    /// Migrations adding or replacing check constraints, oldest first
+    const CHECKS: [&str; 3] = [
+        include_str!("../../../migrations/2025-05-10-090000_enum_checks_and_indexes/up.sql"),
+        include_str!("../../../migrations/2025-05-12-090000_supervisor_module/up.sql"),
+        include_str!("../../../migrations/2025-05-13-090000_job_requeued_event/up.sql"),
+    ];
+
+    /// Names the check constraint `constraint` allows, in the order of its latest migration
+    fn allowed(constraint: &str) -> Vec<&'static str> {
+        let line = CHECKS
+            .iter()
+            .rev()
+            .flat_map(|migration| migration.lines())
+            .find(|line| line.contains(&format!("CONSTRAINT {} CHECK", constraint)))
+            .unwrap_or_else(|| panic!("{constraint} is not in the migrations"));
+        let list = line
+            .split_once("IN (")
+            .or_else(|| line.split_once("ARRAY["))
+            .map(|(_, rest)| rest)
+            .unwrap_or_default();
+        list[..list.find([')', ']']).unwrap_or(list.len())]
+            .split(',')
+            .map(|name| name.trim().trim_matches('\''))
+            .collect()
+    }
+
+    #[test]
+    fn check_constraints_match_the_enums() {
+        let checks: [(&str, &[&str]); 21] = [
+            ("jobs_state_check", JobStateEnum::VARIANTS),
+            ("jobs_image_format_check", ImageFormatEnum::VARIANTS),
+            ("jobs_output_type_check", OutputTypeEnum::VARIANTS),
+            ("jobs_schedule_type_check", ScheduleTypeEnum::VARIANTS),
+            (
+                "job_templates_image_format_check",
+                ImageFormatEnum::VARIANTS,
+            ),
+            ("job_templates_output_type_check", OutputTypeEnum::VARIANTS),
+            (
+                "job_templates_schedule_type_check",
+                ScheduleTypeEnum::VARIANTS,
+            ),
+            (
+                "job_template_parameters_kind_check",
+                TemplateParamKindEnum::VARIANTS,
+            ),
+            ("workers_os_check", OSEnum::VARIANTS),
+            ("worker_status_status_check", WorkerStatusEnum::VARIANTS),
+            ("logs_level_check", LogLevelEnum::VARIANTS),
+            ("logs_module_check", SystemModuleEnum::VARIANTS),
+            ("logs_action_check", LogActionEnum::VARIANTS),
+            ("logs_from_module_check", SystemModuleEnum::VARIANTS),
+            ("logs_to_module_check", SystemModuleEnum::VARIANTS),
+            ("log_retention_policies_level_check", LogLevelEnum::VARIANTS),
+            (
+                "log_retention_policies_module_check",
+                SystemModuleEnum::VARIANTS,
+            ),
+            (
+                "log_retention_policies_action_check",
+                LogActionEnum::VARIANTS,
+            ),
+            ("webhooks_events_check", WebhookEventEnum::VARIANTS),
+            ("webhook_deliveries_event_check", WebhookEventEnum::VARIANTS),
+            (
+                "webhook_deliveries_status_check",
+                DeliveryStatusEnum::VARIANTS,
+            ),
+        ];
+        for (constraint, variants) in checks {
+            assert_eq!(allowed(constraint), variants, "{constraint}");
+        }
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for name in LogActionEnum::VARIANTS {
+            let action: LogActionEnum = name.parse().unwrap_or(LogActionEnum::Custom);
+            assert_eq!(action.to_string(), *name);
+        }
+        assert!("Paused".parse::<JobStateEnum>().is_err());
+    }
+}
@@ -1,11 +1,4 @@
 use core::fmt;
-use diesel::deserialize::{FromSql, FromSqlRow};
-use diesel::expression::AsExpression;
-use diesel::pg::{Pg, PgValue};
-use diesel::serialize::ToSql;
-use diesel::sql_types::Text;
-use serde::{Deserialize, Serialize};
-use std::io::Write;
 use std::str::FromStr;
 
 /// not stored in db, used in IPC and logging
@@ -72,29 +65,17 @@ impl FromStr for Pulse {
     }
 }
 
-/// All available modules inside core, not stored in db, used in IPC and logging
-#[derive(AsExpression, Debug, Deserialize, Serialize, FromSqlRow, Clone)]
-#[diesel(sql_type = diesel::sql_types::VarChar)]
-pub enum SystemModuleEnum {
-    Dispatcher,
-    Harvester,
-    Hibernator,
-    Receiver,
-    Scheduler,
-    TaskArchive,
-}
-
-// used for logging alternatives, for choosing log level in dialoguer
-impl SystemModuleEnum {
-    pub fn variants() -> &'static [&'static str] {
-        &[
-            "Dispatcher",
-            "Harvester",
-            "Hibernator",
-            "Receiver",
-            "Scheduler",
-            "TaskArchive",
-        ]
+db_enum! {
+    /// All available modules inside core, used in IPC and logging, and stored with every log
+    SystemModuleEnum {
+        Dispatcher,
+        Harvester,
+        Hibernator,
+        Receiver,
+        Scheduler,
+        TaskArchive,
+        Notifier,
+        Supervisor,
     }
 }
 
@@ -108,68 +89,9 @@ impl From<usize> for SystemModuleEnum {
             3 => SystemModuleEnum::Receiver,
             4 => SystemModuleEnum::Scheduler,
             5 => SystemModuleEnum::TaskArchive,
+            6 => SystemModuleEnum::Notifier,
+            7 => SystemModuleEnum::Supervisor,
             _ => SystemModuleEnum::Dispatcher,
         }
     }
 }
-
-impl fmt::Display for SystemModuleEnum {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = match self {
-            SystemModuleEnum::Dispatcher => "Dispatcher",
-            SystemModuleEnum::Harvester => "Harvester",
-            SystemModuleEnum::Hibernator => "Hibernator",
-            SystemModuleEnum::Receiver => "Receiver",
-            SystemModuleEnum::Scheduler => "Scheduler",
-            SystemModuleEnum::TaskArchive => "TaskArchive",
-        };
-        write!(f, "{}", s)
-    }
-}
-
-impl FromStr for SystemModuleEnum {
-    type Err = ();
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "Dispatcher" => Ok(SystemModuleEnum::Dispatcher),
-            "Harvester" => Ok(SystemModuleEnum::Harvester),
-            "Hibernator" => Ok(SystemModuleEnum::Hibernator),
-            "Receiver" => Ok(SystemModuleEnum::Receiver),
-            "Scheduler" => Ok(SystemModuleEnum::Scheduler),
-            "TaskArchive" => Ok(SystemModuleEnum::TaskArchive),
-            _ => Err(()),
-        }
-    }
-}
-
-impl FromSql<Text, Pg> for SystemModuleEnum {
-    fn from_sql(value: PgValue<'_>) -> diesel::deserialize::Result<Self> {
-        match value.as_bytes() {
-            b"Dispatcher" => Ok(SystemModuleEnum::Dispatcher),
-            b"Harvester" => Ok(SystemModuleEnum::Harvester),
-            b"Hibernator" => Ok(SystemModuleEnum::Hibernator),
-            b"Receiver" => Ok(SystemModuleEnum::Receiver),
-            b"Scheduler" => Ok(SystemModuleEnum::Scheduler),
-            b"TaskArchive" => Ok(SystemModuleEnum::TaskArchive),
-            _ => Err("Unexpected value".into()),
-        }
-    }
-}
-
-impl ToSql<Text, Pg> for SystemModuleEnum {
-    fn to_sql<'b>(
-        &'b self,
-        out: &mut diesel::serialize::Output<'b, '_, Pg>,
-    ) -> diesel::serialize::Result {
-        match self {
-            SystemModuleEnum::Dispatcher => out.write_all(b"Dispatcher")?,
-            SystemModuleEnum::Harvester => out.write_all(b"Harvester")?,
-            SystemModuleEnum::Hibernator => out.write_all(b"Hibernator")?,
-            SystemModuleEnum::Receiver => out.write_all(b"Receiver")?,
-            SystemModuleEnum::Scheduler => out.write_all(b"Scheduler")?,
-            SystemModuleEnum::TaskArchive => out.write_all(b"TaskArchive")?,
-        }
-        Ok(diesel::serialize::IsNull::No)
-    }
-}
@@ -0,0 +1,19 @@
+db_enum! {
+    /// Kind of a job template parameter, decides where the value ends up in the rendered job
+    TemplateParamKindEnum {
+        ImageTag, // replaces the tag of the image url
+        Env,      // passed as `-e NAME=value` docker flag, parameter name is the variable name
+        Text,     // substituted for `{{name}}` in image url, docker flags and notes
+    }
+}
+
+// used for selection menu inside dialoguer
+impl From<usize> for TemplateParamKindEnum {
+    fn from(idx: usize) -> Self {
+        match idx {
+            0 => TemplateParamKindEnum::ImageTag,
+            1 => TemplateParamKindEnum::Env,
+            _ => TemplateParamKindEnum::Text,
+        }
+    }
+}
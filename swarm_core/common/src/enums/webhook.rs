@@ -0,0 +1,35 @@
+db_enum! {
+    /// WebhookEventEnum, the lifecycle events a webhook can subscribe to
+    WebhookEventEnum {
+        JobSubmitted,
+        JobRunning,
+        JobCompleted,
+        JobFailed,
+        JobRequeued, // a running job went back to the queue, e.g. preempted
+        WorkerUnreachable,
+    }
+}
+
+// used for selection menu inside dialoguer
+impl From<usize> for WebhookEventEnum {
+    fn from(idx: usize) -> Self {
+        match idx {
+            0 => WebhookEventEnum::JobSubmitted,
+            1 => WebhookEventEnum::JobRunning,
+            2 => WebhookEventEnum::JobCompleted,
+            3 => WebhookEventEnum::JobFailed,
+            4 => WebhookEventEnum::JobRequeued,
+            5 => WebhookEventEnum::WorkerUnreachable,
+            _ => WebhookEventEnum::JobSubmitted,
+        }
+    }
+}
+
+db_enum! {
+    /// DeliveryStatusEnum, where a webhook delivery stands
+    DeliveryStatusEnum {
+        Pending,   // waiting for its first or next attempt
+        Delivered, // receiver answered 2xx
+        Failed,    // gave up after the last attempt
+    }
+}
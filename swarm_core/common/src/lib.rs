@@ -1,6 +1,8 @@
 pub mod auth;
+pub mod backup;
 pub mod commands;
 pub mod database;
 pub mod enums;
 pub mod rocket;
 pub mod utils;
+pub mod validation;
@@ -206,41 +206,46 @@ pub async fn select_worker_with_any(user_id: i32) -> anyhow::Result<Option<i32>,
     }
 }
 
+/// TUI moving a job along the state machine, only the legal next states are offered
+///
+/// # Arguments
+/// * job_id - the job to move
 pub async fn move_job_state(job_id: i32) -> anyhow::Result<()> {
     let job = commands::get_job_by_id(job_id).await?;
-    if job.state == JobStateEnum::Running {
-        println!("⚠️ Job is currently RUNNING.");
-
-        let confirm = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("To move this job, you must first remove its assignment. Continue?")
-            .items(&["Cancel", "Remove Assignment"])
-            .default(0)
-            .interact()?;
-
-        if confirm == 0 {
-            println!("❌ Move cancelled.");
-            return Ok(());
-        }
-
-        if let Some(assign_id) = commands::get_assignment_id_for_job(job_id).await? {
-            commands::delete_assignment(assign_id).await?;
-            println!("🗑️ Assignment removed.");
-        } else {
-            println!("⚠️ No assignment found. Proceeding.");
-        }
+    // Running needs a worker, jobs only get there through a claim
+    let targets: Vec<JobStateEnum> = job
+        .state
+        .next_states()
+        .iter()
+        .copied()
+        .filter(|state| *state != JobStateEnum::Running)
+        .collect();
+    if targets.is_empty() {
+        println!(
+            "⚠️ Job is {}, finished jobs can't be moved. Re-run it instead.",
+            job.state
+        );
+        return Ok(());
     }
-    let states = vec!["Submitted", "Queued", "Completed", "Failed"];
+
+    let mut choices = vec!["Cancel".to_string()];
+    choices.extend(targets.iter().map(|state| state.to_string()));
     let choice = Select::with_theme(&ColorfulTheme::default())
-        .with_prompt("Move Job To Which State?")
+        .with_prompt(format!("Move Job From {} To Which State?", job.state))
         .default(0)
-        .items(&states)
+        .items(&choices)
         .interact()?;
+    if choice == 0 {
+        println!("❌ Move cancelled.");
+        return Ok(());
+    }
 
-    let result = match states[choice] {
-        "Submitted" => mark_submitted(job_id).await,
-        "Queued" => mark_queued(job_id).await,
-        "Completed" => mark_succeeded(job_id).await,
-        "Failed" => {
+    let result = match targets[choice - 1] {
+        // back to the queue, closes the open assignment
+        JobStateEnum::Queued if job.state == JobStateEnum::Running => requeue(job_id).await,
+        JobStateEnum::Queued => mark_queued(job_id).await,
+        JobStateEnum::Completed => mark_succeeded(job_id).await,
+        JobStateEnum::Failed => {
             let msg: String = Input::with_theme(&ColorfulTheme::default())
                 .with_prompt("Failure message?")
                 .interact_text()?;
@@ -261,14 +266,17 @@ pub async fn move_job_state(job_id: i32) -> anyhow::Result<()> {
     Ok(())
 }
 
-pub async fn mark_submitted(id: i32) -> anyhow::Result<Job> {
+pub async fn mark_queued(id: i32) -> anyhow::Result<Job> {
     let mut conn = load_db_connection().await?;
-    Ok(JobRepository::mark_submitted(&mut conn, id).await?)
+    Ok(JobRepository::mark_queued(&mut conn, id).await?)
 }
 
-pub async fn mark_queued(id: i32) -> anyhow::Result<Job> {
+/// Put a running job back in the queue and close its assignment
+/// # Arguments
+/// * `id` - The ID of the running job
+pub async fn requeue(id: i32) -> anyhow::Result<Job> {
     let mut conn = load_db_connection().await?;
-    Ok(JobRepository::mark_queued(&mut conn, id).await?)
+    Ok(JobRepository::requeue_preempted(&mut conn, id).await?)
 }
 
 pub async fn mark_running(id: i32) -> anyhow::Result<Job> {
@@ -0,0 +1,137 @@
+//! Field level validation of request bodies, run by the routes before anything is stored
+//!
+//! The rules live in `swarm-validation`, which `swarm-client` runs as well for callers that check
+//! before submitting. This module maps the database models onto them.
+use swarm_validation::{validate_job, validate_worker, JobFields, WorkerFields};
+
+use crate::database::models::job::NewJob;
+use crate::database::models::worker::NewWorker;
+use crate::enums::image_format::ImageFormatEnum;
+use crate::enums::output::OutputTypeEnum;
+use crate::enums::schedule::ScheduleTypeEnum;
+
+pub use swarm_validation::{
+    is_hostname, is_image_reference, is_image_tag, parse_cron, FieldError, ValidationErrors,
+    MAX_NAME_LEN,
+};
+
+/// Implemented by every body that is validated before insert
+pub trait Validate {
+    fn validate(&self) -> Result<(), ValidationErrors>;
+}
+
+impl Validate for NewJob {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        validate_job(&JobFields {
+            job_name: &self.job_name,
+            image_url: &self.image_url,
+            from_registry: self.image_format == ImageFormatEnum::DockerRegistry,
+            docker_flags: &self.docker_flags,
+            output_files: self.output_type == OutputTypeEnum::Files,
+            output_paths: &self.output_paths,
+            cron_schedule: self.schedule_type == ScheduleTypeEnum::Cron,
+            cron_expression: self.cron_expression.as_deref(),
+        })
+    }
+}
+
+impl Validate for NewWorker {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        validate_worker(&WorkerFields {
+            label: &self.label,
+            ip_address: &self.ip_address,
+            hostname: &self.hostname,
+            ssh_user: &self.ssh_user,
+            docker_version: &self.docker_version,
+            arch: &self.arch,
+            tags: &self.tags,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::enums::job::JobStateEnum;
+    use crate::enums::workers::OSEnum;
+
+    fn job() -> NewJob {
+        NewJob {
+            user_id: 1,
+            job_name: "nightly".into(),
+            image_url: "docker.io/library/alpine:3.20".into(),
+            image_format: ImageFormatEnum::DockerRegistry,
+            docker_flags: None,
+            output_type: OutputTypeEnum::Stdout,
+            output_paths: None,
+            schedule_type: ScheduleTypeEnum::Once,
+            cron_expression: None,
+            notes: None,
+            state: JobStateEnum::Submitted,
+            priority: 0,
+        }
+    }
+
+    fn fields(result: Result<(), ValidationErrors>) -> Vec<String> {
+        result
+            .err()
+            .map(|e| e.fields.into_iter().map(|f| f.field).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn accepts_a_plain_job() {
+        assert!(job().validate().is_ok());
+    }
+
+    #[test]
+    fn reports_every_invalid_field() {
+        let mut bad = job();
+        bad.job_name = " ".into();
+        bad.image_url = "Alpine Latest".into();
+        bad.output_type = OutputTypeEnum::Files;
+        bad.schedule_type = ScheduleTypeEnum::Cron;
+        bad.cron_expression = Some("every minute".into());
+        assert_eq!(
+            fields(bad.validate()),
+            ["job_name", "image_url", "output_paths", "cron_expression"]
+        );
+    }
+
+    #[test]
+    fn cron_and_output_must_match_their_type() {
+        let mut cron = job();
+        cron.schedule_type = ScheduleTypeEnum::Cron;
+        cron.cron_expression = Some("0 5 * * *".into());
+        assert!(cron.validate().is_ok());
+
+        let mut once = job();
+        once.cron_expression = Some("0 5 * * *".into());
+        once.output_paths = Some(vec![Some("/out".into())]);
+        assert_eq!(fields(once.validate()), ["output_paths", "cron_expression"]);
+    }
+
+    #[test]
+    fn worker_addresses() {
+        let worker = NewWorker {
+            user_id: 1,
+            label: "w1".into(),
+            ip_address: "10.0.0.7".into(),
+            hostname: "node-1.local".into(),
+            ssh_user: "root".into(),
+            ssh_key: String::new(),
+            docker_version: "24.0.2".into(),
+            arch: "x86_64".into(),
+            os: OSEnum::Linux,
+            tags: None,
+        };
+        assert!(worker.validate().is_ok());
+
+        let bad = NewWorker {
+            ip_address: "10.0.0".into(),
+            hostname: "-node".into(),
+            ..worker
+        };
+        assert_eq!(fields(bad.validate()), ["ip_address", "hostname"]);
+    }
+}
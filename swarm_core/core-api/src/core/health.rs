@@ -0,0 +1,247 @@
+//! Health registry, what `/health/live` and `/health/ready` report about the modules
+//! The `Supervisor` marks every module running when it starts it and records how it ended,
+//! a panic included. Modules driven by pulses stamp `last_pulse` when they handle one,
+//! a module whose last pulse is older than `STALL_AFTER` counts as stalled.
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use chrono::{Duration, NaiveDateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// A running module that did not see a pulse for this long is reported as stalled
+pub const STALL_AFTER: Duration = Duration::seconds(60);
+
+/// Every task spawned by the `ModuleInitializer`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CoreModule {
+    Dispatcher,
+    Harvester,
+    Hibernator,
+    Logger,
+    Notifier,
+    Reciever,
+    Scheduler,
+    TaskArchive,
+    Rocket,
+}
+
+impl CoreModule {
+    pub const ALL: [CoreModule; 9] = [
+        CoreModule::Dispatcher,
+        CoreModule::Harvester,
+        CoreModule::Hibernator,
+        CoreModule::Logger,
+        CoreModule::Notifier,
+        CoreModule::Reciever,
+        CoreModule::Scheduler,
+        CoreModule::TaskArchive,
+        CoreModule::Rocket,
+    ];
+
+    /// Name as serialized, e.g. `task_archive`
+    pub fn name(self) -> &'static str {
+        match self {
+            CoreModule::Dispatcher => "dispatcher",
+            CoreModule::Harvester => "harvester",
+            CoreModule::Hibernator => "hibernator",
+            CoreModule::Logger => "logger",
+            CoreModule::Notifier => "notifier",
+            CoreModule::Reciever => "reciever",
+            CoreModule::Scheduler => "scheduler",
+            CoreModule::TaskArchive => "task_archive",
+            CoreModule::Rocket => "rocket",
+        }
+    }
+
+    /// The module serialized as `name`, None for any other name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.name() == name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ModuleStatus {
+    Running,
+    // returned, after a shutdown this is expected
+    Stopped,
+    // returned an error or panicked
+    Failed,
+}
+
+/// What is known about one module task
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ModuleHealth {
+    pub module: CoreModule,
+    pub status: ModuleStatus,
+    // None for modules not driven by pulses and before the first one
+    pub last_pulse: Option<NaiveDateTime>,
+    pub stalled: bool,
+    pub restarts: u32,
+    pub last_error: Option<String>,
+}
+
+impl ModuleHealth {
+    fn new(module: CoreModule) -> Self {
+        ModuleHealth {
+            module,
+            status: ModuleStatus::Running,
+            last_pulse: None,
+            stalled: false,
+            restarts: 0,
+            last_error: None,
+        }
+    }
+
+    /// Running and not stalled
+    pub fn is_alive(&self) -> bool {
+        self.status == ModuleStatus::Running && !self.stalled
+    }
+}
+
+/// How a module task ended, `Err` for a module returning an error
+pub trait ModuleExit {
+    fn into_outcome(self) -> Result<(), String>;
+}
+
+impl ModuleExit for () {
+    fn into_outcome(self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl ModuleExit for anyhow::Result<()> {
+    fn into_outcome(self) -> Result<(), String> {
+        self.map_err(|e| format!("{e:#}"))
+    }
+}
+
+/// Module states shared by the module tasks and the health routes
+#[derive(Default)]
+pub struct HealthRegistry {
+    modules: Mutex<BTreeMap<CoreModule, ModuleHealth>>,
+    // set by the dispatcher once its UDP socket is bound
+    udp_bound: AtomicBool,
+}
+
+impl HealthRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&self, module: CoreModule, f: impl FnOnce(&mut ModuleHealth)) {
+        let mut modules = self.modules.lock().unwrap_or_else(|e| e.into_inner());
+        f(modules
+            .entry(module)
+            .or_insert_with(|| ModuleHealth::new(module)));
+    }
+
+    /// Mark a module as running, counting a restart if it ran before
+    pub fn started(&self, module: CoreModule) {
+        self.update(module, |m| {
+            if m.status != ModuleStatus::Running {
+                m.restarts += 1;
+            }
+            m.status = ModuleStatus::Running;
+        });
+    }
+
+    /// Record how a module task ended
+    pub fn ended(&self, module: CoreModule, outcome: Result<(), String>) {
+        self.update(module, |m| match outcome {
+            Ok(()) => m.status = ModuleStatus::Stopped,
+            Err(e) => {
+                m.status = ModuleStatus::Failed;
+                m.last_error = Some(e);
+            }
+        });
+    }
+
+    /// Stamp the time a module handled a pulse
+    pub fn pulse(&self, module: CoreModule) {
+        self.update(module, |m| m.last_pulse = Some(Utc::now().naive_utc()));
+    }
+
+    pub fn set_udp_bound(&self, bound: bool) {
+        self.udp_bound.store(bound, Ordering::Relaxed);
+    }
+
+    pub fn udp_bound(&self) -> bool {
+        self.udp_bound.load(Ordering::Relaxed)
+    }
+
+    /// Every registered module in `CoreModule` order, `stalled` evaluated at `now`
+    pub fn snapshot(&self, now: NaiveDateTime) -> Vec<ModuleHealth> {
+        let modules = self.modules.lock().unwrap_or_else(|e| e.into_inner());
+        modules
+            .values()
+            .cloned()
+            .map(|mut m| {
+                m.stalled = m.status == ModuleStatus::Running
+                    && m.last_pulse.is_some_and(|at| now - at > STALL_AFTER);
+                m
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ended_modules_count_a_restart_when_started_again() {
+        let health = HealthRegistry::new();
+        health.started(CoreModule::Dispatcher);
+        health.ended(
+            CoreModule::Dispatcher,
+            Err("Cannot bind UDP listener".to_string()),
+        );
+
+        let now = Utc::now().naive_utc();
+        let dispatcher = &health.snapshot(now)[0];
+        assert_eq!(dispatcher.status, ModuleStatus::Failed);
+        assert_eq!(
+            dispatcher.last_error.as_deref(),
+            Some("Cannot bind UDP listener")
+        );
+        assert!(!dispatcher.is_alive());
+
+        health.started(CoreModule::Dispatcher);
+        let dispatcher = &health.snapshot(now)[0];
+        assert_eq!(dispatcher.status, ModuleStatus::Running);
+        assert_eq!(dispatcher.restarts, 1);
+    }
+
+    #[test]
+    fn module_names_match_their_serialized_form() {
+        for module in CoreModule::ALL {
+            assert_eq!(
+                serde_json::to_value(module).unwrap(),
+                serde_json::json!(module.name())
+            );
+            assert_eq!(CoreModule::from_name(module.name()), Some(module));
+        }
+        assert_eq!(CoreModule::from_name("TaskArchive"), None);
+    }
+
+    #[test]
+    fn running_module_without_recent_pulse_is_stalled() {
+        let health = HealthRegistry::new();
+        health.started(CoreModule::Notifier);
+        health.started(CoreModule::Rocket);
+        health.pulse(CoreModule::Notifier);
+
+        let now = Utc::now().naive_utc();
+        assert!(health.snapshot(now).iter().all(ModuleHealth::is_alive));
+
+        // rocket has no pulses, it never stalls
+        let later = health.snapshot(now + STALL_AFTER + Duration::seconds(1));
+        assert!(later[0].stalled);
+        assert!(!later[1].stalled);
+    }
+}
@@ -0,0 +1,337 @@
+//! Prometheus metrics, one registry shared by all modules through `SharedResources`
+//! Modules update their own series, the Rocket `/metrics` route renders the registry.
+use std::time::Duration;
+
+use diesel_async::pooled_connection::deadpool::Pool;
+use diesel_async::AsyncPgConnection;
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec, Opts, Registry, TextEncoder,
+};
+
+use common::enums::job::JobStateEnum;
+use common::enums::workers::WorkerStatusEnum;
+
+/// Every job state, exported as 0 when no job is in it
+const JOB_STATES: [JobStateEnum; 5] = [
+    JobStateEnum::Submitted,
+    JobStateEnum::Queued,
+    JobStateEnum::Running,
+    JobStateEnum::Completed,
+    JobStateEnum::Failed,
+];
+
+/// Every worker status, exported as 0 when no worker is in it
+const WORKER_STATUSES: [WorkerStatusEnum; 4] = [
+    WorkerStatusEnum::Busy,
+    WorkerStatusEnum::Idle,
+    WorkerStatusEnum::Offline,
+    WorkerStatusEnum::Unreachable,
+];
+
+/// All core metrics
+pub struct Metrics {
+    registry: Registry,
+    /// Jobs per state, refreshed by the Scheduler every medium pulse
+    pub jobs_by_state: IntGaugeVec,
+    /// Workers per status, refreshed by the Dispatcher sweeper every fast pulse
+    pub workers_by_status: IntGaugeVec,
+    /// Heartbeat packets received on the Dispatcher UDP socket
+    pub heartbeats_received: IntCounter,
+    /// Heartbeat packets that could not be parsed
+    pub heartbeats_dropped: IntCounter,
+    /// Jobs waiting for admission or dispatch (Submitted + Queued)
+    pub queue_depth: IntGauge,
+    /// Seconds from submission until the Scheduler admits a job
+    pub scheduling_latency: Histogram,
+    /// Log entries waiting in the Logger buffer
+    pub logger_buffer_size: IntGauge,
+    /// Failed Logger flushes
+    pub logger_flush_failures: IntCounter,
+    /// Log entries dropped because the Logger buffer was full
+    pub logger_dropped_entries: IntCounter,
+    /// Log entries waiting in the on-disk spillover file
+    pub logger_spilled_entries: IntGauge,
+    /// HTTP request latency per method, route and status
+    pub http_request_duration: HistogramVec,
+    /// Webhook delivery attempts per outcome, delivered, retried or failed
+    pub webhook_deliveries: IntCounterVec,
+    /// Connections of the shared DB pool per state, idle or in_use, plus tasks waiting for one
+    pub db_pool_connections: IntGaugeVec,
+    /// Configured size of the shared DB pool
+    pub db_pool_max_connections: IntGauge,
+}
+
+impl Metrics {
+    /// Create and register all metrics
+    /// # Returns
+    /// * `Metrics`
+    /// # Panics
+    /// Only if a metric is registered twice, which is a programming error
+    #[allow(clippy::expect_used)]
+    pub fn new() -> Self {
+        let registry =
+            Registry::new_custom(Some("swarmsync".into()), None).expect("metric prefix is valid");
+
+        let jobs_by_state = IntGaugeVec::new(Opts::new("jobs", "Jobs per state"), &["state"])
+            .expect("valid metric");
+        let workers_by_status =
+            IntGaugeVec::new(Opts::new("workers", "Workers per status"), &["status"])
+                .expect("valid metric");
+        let heartbeats_received = IntCounter::new(
+            "dispatcher_heartbeats_received_total",
+            "Heartbeat packets received by the Dispatcher",
+        )
+        .expect("valid metric");
+        let heartbeats_dropped = IntCounter::new(
+            "dispatcher_heartbeats_dropped_total",
+            "Heartbeat packets dropped because they could not be parsed",
+        )
+        .expect("valid metric");
+        let queue_depth = IntGauge::new(
+            "scheduler_queue_depth",
+            "Jobs waiting for admission or dispatch",
+        )
+        .expect("valid metric");
+        let scheduling_latency = Histogram::with_opts(
+            HistogramOpts::new(
+                "scheduler_latency_seconds",
+                "Seconds from submission until admission",
+            )
+            .buckets(vec![
+                1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 900.0, 3600.0,
+            ]),
+        )
+        .expect("valid metric");
+        let logger_buffer_size = IntGauge::new(
+            "logger_buffer_entries",
+            "Log entries waiting in the Logger buffer",
+        )
+        .expect("valid metric");
+        let logger_flush_failures = IntCounter::new(
+            "logger_flush_failures_total",
+            "Logger flushes that failed and were spilled to disk or returned to the buffer",
+        )
+        .expect("valid metric");
+        let logger_dropped_entries = IntCounter::new(
+            "logger_dropped_entries_total",
+            "Log entries dropped because the Logger buffer was full",
+        )
+        .expect("valid metric");
+        let logger_spilled_entries = IntGauge::new(
+            "logger_spilled_entries",
+            "Log entries waiting in the on-disk spillover file",
+        )
+        .expect("valid metric");
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "HTTP request latency per route",
+            ),
+            &["method", "route", "status"],
+        )
+        .expect("valid metric");
+
+        let webhook_deliveries = IntCounterVec::new(
+            Opts::new(
+                "notifier_webhook_deliveries_total",
+                "Webhook delivery attempts per outcome",
+            ),
+            &["outcome"],
+        )
+        .expect("valid metric");
+
+        let db_pool_connections = IntGaugeVec::new(
+            Opts::new(
+                "db_pool_connections",
+                "Connections of the shared DB pool per state, and tasks waiting for one",
+            ),
+            &["state"],
+        )
+        .expect("valid metric");
+        let db_pool_max_connections = IntGauge::new(
+            "db_pool_max_connections",
+            "Configured size of the shared DB pool",
+        )
+        .expect("valid metric");
+
+        for state in JOB_STATES {
+            jobs_by_state
+                .with_label_values(&[&state.to_string()])
+                .set(0);
+        }
+        for status in WORKER_STATUSES {
+            workers_by_status
+                .with_label_values(&[&status.to_string()])
+                .set(0);
+        }
+
+        registry
+            .register(Box::new(jobs_by_state.clone()))
+            .expect("registered once");
+        registry
+            .register(Box::new(workers_by_status.clone()))
+            .expect("registered once");
+        registry
+            .register(Box::new(heartbeats_received.clone()))
+            .expect("registered once");
+        registry
+            .register(Box::new(heartbeats_dropped.clone()))
+            .expect("registered once");
+        registry
+            .register(Box::new(queue_depth.clone()))
+            .expect("registered once");
+        registry
+            .register(Box::new(scheduling_latency.clone()))
+            .expect("registered once");
+        registry
+            .register(Box::new(logger_buffer_size.clone()))
+            .expect("registered once");
+        registry
+            .register(Box::new(logger_flush_failures.clone()))
+            .expect("registered once");
+        registry
+            .register(Box::new(logger_dropped_entries.clone()))
+            .expect("registered once");
+        registry
+            .register(Box::new(logger_spilled_entries.clone()))
+            .expect("registered once");
+        registry
+            .register(Box::new(http_request_duration.clone()))
+            .expect("registered once");
+        registry
+            .register(Box::new(webhook_deliveries.clone()))
+            .expect("registered once");
+        registry
+            .register(Box::new(db_pool_connections.clone()))
+            .expect("registered once");
+        registry
+            .register(Box::new(db_pool_max_connections.clone()))
+            .expect("registered once");
+
+        Metrics {
+            registry,
+            jobs_by_state,
+            workers_by_status,
+            heartbeats_received,
+            heartbeats_dropped,
+            queue_depth,
+            scheduling_latency,
+            logger_buffer_size,
+            logger_flush_failures,
+            logger_dropped_entries,
+            logger_spilled_entries,
+            http_request_duration,
+            webhook_deliveries,
+            db_pool_connections,
+            db_pool_max_connections,
+        }
+    }
+
+    /// Replace the job state gauges with fresh counts, missing states are set to 0
+    pub fn set_jobs_by_state(&self, counts: &[(JobStateEnum, i64)]) {
+        for state in JOB_STATES {
+            let count = counts
+                .iter()
+                .find(|(s, _)| *s == state)
+                .map(|(_, c)| *c)
+                .unwrap_or(0);
+            self.jobs_by_state
+                .with_label_values(&[&state.to_string()])
+                .set(count);
+        }
+    }
+
+    /// Replace the worker status gauges from the Dispatcher's in-memory status map
+    pub fn set_workers_by_status<'a>(&self, statuses: impl Iterator<Item = &'a WorkerStatusEnum>) {
+        let statuses: Vec<&WorkerStatusEnum> = statuses.collect();
+        for status in WORKER_STATUSES {
+            let count = statuses.iter().filter(|s| ***s == status).count();
+            self.workers_by_status
+                .with_label_values(&[&status.to_string()])
+                .set(count as i64);
+        }
+    }
+
+    /// Record one webhook delivery attempt, `outcome` is delivered, retried or failed
+    pub fn observe_webhook_delivery(&self, outcome: &str) {
+        self.webhook_deliveries.with_label_values(&[outcome]).inc();
+    }
+
+    /// Copy the current state of the shared DB pool into the pool gauges, done on every scrape
+    pub fn set_db_pool(&self, pool: &Pool<AsyncPgConnection>) {
+        let status = pool.status();
+        // deadpool reports waiting tasks as negative availability
+        let idle = status.available.max(0) as i64;
+        let waiting = (-status.available).max(0) as i64;
+        let series = [
+            ("idle", idle),
+            ("in_use", status.size as i64 - idle),
+            ("waiting", waiting),
+        ];
+        for (state, count) in series {
+            self.db_pool_connections
+                .with_label_values(&[state])
+                .set(count);
+        }
+        self.db_pool_max_connections.set(status.max_size as i64);
+    }
+
+    /// Record one HTTP request
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        self.http_request_duration
+            .with_label_values(&[method, route, &status.to_string()])
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Render all metrics in the Prometheus text format
+    /// # Returns
+    /// * `String` - the exposition, `text/plain; version=0.0.4`
+    pub fn render(&self) -> anyhow::Result<String> {
+        let mut buf = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buf)?;
+        Ok(String::from_utf8(buf)?)
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use diesel_async::pooled_connection::AsyncDieselConnectionManager;
+
+    #[test]
+    fn render_contains_every_series() {
+        let metrics = Metrics::new();
+        metrics.heartbeats_received.inc();
+        metrics.set_jobs_by_state(&[(JobStateEnum::Running, 3)]);
+        metrics.observe_request("GET", "/jobs/<id>", 200, Duration::from_millis(5));
+
+        let text = metrics.render().unwrap();
+        assert!(text.contains("swarmsync_dispatcher_heartbeats_received_total 1"));
+        assert!(text.contains("swarmsync_jobs{state=\"Running\"} 3"));
+        assert!(text.contains("swarmsync_jobs{state=\"Failed\"} 0"));
+        assert!(text.contains("swarmsync_workers{status=\"Idle\"} 0"));
+        assert!(text.contains("route=\"/jobs/<id>\""));
+    }
+
+    #[tokio::test]
+    async fn pool_gauges_follow_the_pool() {
+        // connections are opened lazily, building the pool needs no database
+        let manager = AsyncDieselConnectionManager::<AsyncPgConnection>::new("postgres://nowhere");
+        let pool = Pool::builder(manager).max_size(4).build().unwrap();
+        let metrics = Metrics::new();
+        metrics.set_db_pool(&pool);
+
+        let text = metrics.render().unwrap();
+        assert!(text.contains("swarmsync_db_pool_max_connections 4"));
+        assert!(text.contains("swarmsync_db_pool_connections{state=\"in_use\"} 0"));
+        assert!(text.contains("swarmsync_db_pool_connections{state=\"waiting\"} 0"));
+    }
+}
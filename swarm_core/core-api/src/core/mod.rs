@@ -1,9 +1,15 @@
 ///! Core API module, provides the core functionality of the swarm.
+pub mod health;
+pub mod metrics;
 pub mod module_initializer;
 pub mod pulse_broadcaster;
+pub mod schema;
 pub mod service_initializer;
 pub mod shared_resources;
+pub mod supervisor;
 
+pub use health::HealthRegistry;
+pub use metrics::Metrics;
 pub use module_initializer::ModuleInitializer;
 pub use pulse_broadcaster::{PulseBroadcaster, PulseSubscriptions};
 pub use service_initializer::ServiceInitializer;
@@ -2,17 +2,22 @@
 use std::sync::Arc;
 use tokio::task;
 
+use crate::core::health::CoreModule;
 use crate::core::shared_resources::SharedResources;
+use crate::core::supervisor::supervise;
 use crate::modules::logger::Logger;
-use crate::modules::{Dispatcher, Harvester, Hibernator, Reciever, Scheduler, TaskArchive};
+use crate::modules::{
+    Dispatcher, Harvester, Hibernator, Notifier, Reciever, Scheduler, TaskArchive,
+};
 use crate::rocket_api;
 
-/// Holds tokio handles to all modules
+/// Holds tokio handles to the supervisors of all modules, they finish after a shutdown
 pub struct ModuleInitializer {
-    pub dispatcher_task: task::JoinHandle<anyhow::Result<(), anyhow::Error>>,
+    pub dispatcher_task: task::JoinHandle<()>,
     pub harvester_task: task::JoinHandle<()>,
     pub hibernator_task: task::JoinHandle<()>,
     pub logger_task: task::JoinHandle<()>,
+    pub notifier_task: task::JoinHandle<()>,
     pub reciever_task: task::JoinHandle<()>,
     pub scheduler_task: task::JoinHandle<()>,
     pub task_archive_task: task::JoinHandle<()>,
@@ -21,31 +26,56 @@ pub struct ModuleInitializer {
 
 impl ModuleInitializer {
     // Create a new ModuleInitializer with a shared_resource
+    // every module is supervised, the closures build a fresh instance on each (re)start
     pub fn new(shared_resources: Arc<SharedResources>) -> Self {
-        let dispatcher = Dispatcher::new(Arc::clone(&shared_resources));
-        let dispatcher_task = task::spawn(dispatcher.init());
+        let shared = Arc::clone(&shared_resources);
+        let dispatcher_task = supervise(Arc::clone(&shared), CoreModule::Dispatcher, move || {
+            Dispatcher::new(Arc::clone(&shared)).init()
+        });
+
+        let shared = Arc::clone(&shared_resources);
+        let harvester_task = supervise(Arc::clone(&shared), CoreModule::Harvester, move || {
+            Harvester::new(Arc::clone(&shared)).init()
+        });
 
-        let harvester = Harvester::new(Arc::clone(&shared_resources));
-        let harvester_task = task::spawn(harvester.init());
+        let shared = Arc::clone(&shared_resources);
+        let hibernator_task = supervise(Arc::clone(&shared), CoreModule::Hibernator, move || {
+            Hibernator::new(Arc::clone(&shared)).init()
+        });
 
-        let hibernator = Hibernator::new(Arc::clone(&shared_resources));
-        let hibernator_task = task::spawn(hibernator.init());
+        // the logger is shared by everyone, a restart runs the same instance again
+        let logger = shared_resources.get_logger();
+        let logger_task = supervise(
+            Arc::clone(&shared_resources),
+            CoreModule::Logger,
+            move || Logger::init(Arc::clone(&logger)),
+        );
 
-        let logger = Logger::init(shared_resources.get_logger());
-        let logger_task = task::spawn(logger);
+        let shared = Arc::clone(&shared_resources);
+        let notifier_task = supervise(Arc::clone(&shared), CoreModule::Notifier, move || {
+            Notifier::new(Arc::clone(&shared)).init()
+        });
 
-        let reciever = Reciever::new(Arc::clone(&shared_resources));
-        let reciever_task = task::spawn(reciever.init());
+        let shared = Arc::clone(&shared_resources);
+        let reciever_task = supervise(Arc::clone(&shared), CoreModule::Reciever, move || {
+            Reciever::new(Arc::clone(&shared)).init()
+        });
 
-        let scheduler = Scheduler::new(Arc::clone(&shared_resources));
-        let scheduler_task = task::spawn(scheduler.init());
+        let shared = Arc::clone(&shared_resources);
+        let scheduler_task = supervise(Arc::clone(&shared), CoreModule::Scheduler, move || {
+            Scheduler::new(Arc::clone(&shared)).init()
+        });
 
-        let task_archive = TaskArchive::new(Arc::clone(&shared_resources));
-        let task_archive_task = task::spawn(task_archive.init());
+        let shared = Arc::clone(&shared_resources);
+        let task_archive_task =
+            supervise(Arc::clone(&shared), CoreModule::TaskArchive, move || {
+                TaskArchive::new(Arc::clone(&shared)).init()
+            });
 
         // The rocket server
-        let rocket_task = task::spawn(async move {
-            rocket_api::rocket_server::launch_rocket(Arc::clone(&shared_resources)).await;
+        let shared = Arc::clone(&shared_resources);
+        let rocket_task = supervise(shared_resources, CoreModule::Rocket, move || {
+            rocket_api::rocket_server::launch_rocket(Arc::clone(&shared))
         });
 
         ModuleInitializer {
@@ -53,6 +83,7 @@ impl ModuleInitializer {
             harvester_task,
             hibernator_task,
             logger_task,
+            notifier_task,
             reciever_task,
             scheduler_task,
             task_archive_task,
@@ -66,6 +97,7 @@ impl ModuleInitializer {
             self.harvester_task,
             self.hibernator_task,
             self.logger_task,
+            self.notifier_task,
             self.reciever_task,
             self.scheduler_task,
             self.task_archive_task,
@@ -0,0 +1,166 @@
+//! Migrations embedded at build time and the schema check run before anything touches the database
+//! Core refuses to start while migrations are pending or the database is ahead of this build,
+//! with `CORE_AUTO_MIGRATE=true` it applies the pending ones itself.
+use std::collections::BTreeSet;
+use std::env;
+
+use anyhow::{anyhow, Context};
+use diesel::migration::MigrationSource;
+use diesel::pg::Pg;
+use diesel::Connection;
+use diesel_async::async_connection_wrapper::AsyncConnectionWrapper;
+use diesel_async::AsyncPgConnection;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Every migration in `swarm_core/migrations`, compiled into the binary
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("../migrations");
+
+/// Versions of the embedded migrations, e.g. `20250511090000` for `2025-05-11-090000_job_archives`
+pub fn embedded_versions() -> BTreeSet<String> {
+    MigrationSource::<Pg>::migrations(&MIGRATIONS)
+        .map(|migrations| {
+            migrations
+                .iter()
+                .map(|m| m.name().version().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Latest embedded migration, the schema version this build expects
+pub fn expected_version() -> String {
+    embedded_versions().pop_last().unwrap_or_default()
+}
+
+/// Database schema compared with the embedded migrations
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct SchemaStatus {
+    // latest migration applied to the database, None on an empty database
+    pub current: Option<String>,
+    // latest embedded migration
+    pub expected: String,
+    // embedded migrations not applied yet
+    pub pending: Vec<String>,
+    // applied migrations this build does not know, the database is newer than core
+    pub unknown: Vec<String>,
+}
+
+impl SchemaStatus {
+    /// Compare applied migration versions with the embedded ones
+    pub fn compare(applied: &BTreeSet<String>, embedded: &BTreeSet<String>) -> Self {
+        SchemaStatus {
+            current: applied.last().cloned(),
+            expected: embedded.last().cloned().unwrap_or_default(),
+            pending: embedded.difference(applied).cloned().collect(),
+            unknown: applied.difference(embedded).cloned().collect(),
+        }
+    }
+
+    pub fn is_current(&self) -> bool {
+        self.pending.is_empty() && self.unknown.is_empty()
+    }
+
+    /// Why core can not run against this schema, None if it can
+    pub fn problem(&self) -> Option<String> {
+        if !self.unknown.is_empty() {
+            Some(format!(
+                "The database schema is newer than this build of core-api: migrations {} are unknown. \
+                 Deploy a core-api built with them.",
+                self.unknown.join(", ")
+            ))
+        } else if !self.pending.is_empty() {
+            Some(format!(
+                "The database schema is outdated: {} migration(s) pending ({}), expected version {}. \
+                 Run `diesel migration run` or start core-api with CORE_AUTO_MIGRATE=true.",
+                self.pending.len(),
+                self.pending.join(", "),
+                self.expected
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+/// Check the schema of `DATABASE_URL`, applying pending migrations when `CORE_AUTO_MIGRATE` is set
+/// # Returns
+/// * `anyhow::Result<SchemaStatus>` - the status after migrating, Error if core can not run on it
+pub async fn ensure_schema() -> anyhow::Result<SchemaStatus> {
+    let url = env::var("DATABASE_URL").context("Cannot load DB url from environment")?;
+    let auto_migrate = env::var("CORE_AUTO_MIGRATE")
+        .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false);
+
+    // the migration harness is synchronous, it runs its own runtime off the async workers
+    let status = tokio::task::spawn_blocking(move || -> anyhow::Result<SchemaStatus> {
+        let mut conn = AsyncConnectionWrapper::<AsyncPgConnection>::establish(&url)
+            .context("Cannot connect to Postgres")?;
+        let applied = |conn: &mut AsyncConnectionWrapper<AsyncPgConnection>| {
+            conn.applied_migrations()
+                .map(|versions| versions.iter().map(|v| v.to_string()).collect())
+                .map_err(|e| anyhow!("Reading applied migrations failed: {e}"))
+        };
+
+        let status = SchemaStatus::compare(&applied(&mut conn)?, &embedded_versions());
+        if !auto_migrate || !status.unknown.is_empty() || status.pending.is_empty() {
+            return Ok(status);
+        }
+        for version in conn
+            .run_pending_migrations(MIGRATIONS)
+            .map_err(|e| anyhow!("Running migrations failed: {e}"))?
+        {
+            println!("Schema: applied migration {version}");
+        }
+        Ok(SchemaStatus::compare(
+            &applied(&mut conn)?,
+            &embedded_versions(),
+        ))
+    })
+    .await??;
+
+    match status.problem() {
+        Some(problem) => Err(anyhow!(problem)),
+        None => Ok(status),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(v: &[&str]) -> BTreeSet<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn every_migration_directory_is_embedded() {
+        let embedded = embedded_versions();
+        assert!(embedded.contains("00000000000000"));
+        assert!(embedded.contains("20250511090000"));
+        assert_eq!(expected_version(), embedded.last().cloned().unwrap());
+    }
+
+    #[test]
+    fn status_tells_outdated_from_newer() {
+        let embedded = versions(&["1", "2", "3"]);
+
+        let current = SchemaStatus::compare(&versions(&["1", "2", "3"]), &embedded);
+        assert!(current.is_current());
+        assert_eq!(current.problem(), None);
+
+        let outdated = SchemaStatus::compare(&versions(&["1"]), &embedded);
+        assert_eq!(outdated.current.as_deref(), Some("1"));
+        assert_eq!(outdated.pending, vec!["2", "3"]);
+        assert!(outdated.problem().unwrap().contains("outdated"));
+
+        let newer = SchemaStatus::compare(&versions(&["1", "2", "3", "4"]), &embedded);
+        assert_eq!(newer.unknown, vec!["4"]);
+        assert!(newer.problem().unwrap().contains("newer"));
+
+        let empty = SchemaStatus::compare(&BTreeSet::new(), &embedded);
+        assert_eq!(empty.current, None);
+        assert_eq!(empty.expected, "3");
+    }
+}
@@ -1,19 +1,29 @@
 ///! Shared resources between modules,
 ///! Logger - used by all modules, to log internal events, no mutex needed, have internal mutexes
 ///! Pulse subscriptions - used by all modules to subscribe to pulses, no mutex needed
+///! Metrics - prometheus registry, every module updates its own series
+///! Health - module states for the health routes, modules stamp their pulses into it
+///! DB pool - Postgres connections for the modules, the same pool `common::commands` borrows from
 ///! Service channels - broadcast channels for one-to-many communication, subscribe to core event
 ///! Service wiring - one-to-one communication channels between modules,
 ///!                  each module take() its channel sides upon initialization
+use anyhow::Context;
+use common::commands::DbConnection;
+use diesel_async::pooled_connection::deadpool::Pool;
+use diesel_async::AsyncPgConnection;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-use crate::core::PulseSubscriptions;
+use crate::core::{HealthRegistry, Metrics, PulseSubscriptions};
 use crate::modules::Logger;
 use crate::services::{ServiceChannels, ServiceWiring};
 
 /// All systemwide shared resources
 pub struct SharedResources {
     pub logger: Arc<Logger>,
+    pub metrics: Arc<Metrics>,
+    pub health: Arc<HealthRegistry>,
+    pub db_pool: Pool<AsyncPgConnection>, // cheap to clone, clones share the connections
     pub pulse_subscriptions: Arc<PulseSubscriptions>,
     pub service_channels: Arc<ServiceChannels>, //only has ref ones so only Arc
     pub service_wiring: Arc<Mutex<ServiceWiring>>, // builds on take() so needs write = MutexLock
@@ -22,12 +32,18 @@ pub struct SharedResources {
 impl SharedResources {
     pub fn new(
         logger: Arc<Logger>,
+        metrics: Arc<Metrics>,
+        health: Arc<HealthRegistry>,
+        db_pool: Pool<AsyncPgConnection>,
         pulse_subscriptions: Arc<PulseSubscriptions>,
         service_channels: Arc<ServiceChannels>,
         service_wiring: Arc<Mutex<ServiceWiring>>,
     ) -> Self {
         SharedResources {
             logger,
+            metrics,
+            health,
+            db_pool,
             pulse_subscriptions,
             service_channels,
             service_wiring,
@@ -39,6 +55,26 @@ impl SharedResources {
         Arc::clone(&self.logger)
     }
 
+    pub fn get_metrics(&self) -> Arc<Metrics> {
+        Arc::clone(&self.metrics)
+    }
+
+    pub fn get_health(&self) -> Arc<HealthRegistry> {
+        Arc::clone(&self.health)
+    }
+
+    pub fn get_db_pool(&self) -> Pool<AsyncPgConnection> {
+        self.db_pool.clone()
+    }
+
+    /// Borrow a connection from the pool, it goes back when dropped
+    pub async fn get_db_connection(&self) -> anyhow::Result<DbConnection> {
+        self.db_pool
+            .get()
+            .await
+            .context("Cannot connect to Postgres")
+    }
+
     pub fn get_pulse_subscriptions(&self) -> Arc<PulseSubscriptions> {
         Arc::clone(&self.pulse_subscriptions)
     }
@@ -0,0 +1,313 @@
+//! Supervisor, keeps every module of core running
+//! Each module runs in a task of its own, watched by a supervising task that builds it from a factory.
+//! A module returning an error or panicking is logged as `Fatal` and built again after a backoff
+//! of 1s, 2s, 4s ... capped at a minute, reset once a module ran for a minute.
+//! Modules return on `CoreEvent::Restart`, they are built again right away, so a restart drops their state.
+//! `ControlCommand::RestartModule` restarts a single module the same way, a module that does not return
+//! on its own within `RESTART_GRACE` is aborted.
+//! After `CoreEvent::Shutdown` nothing is restarted.
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use tokio::select;
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+use tokio::sync::broadcast::Receiver;
+use tokio::task::{self, JoinError, JoinHandle};
+use tokio::time::timeout;
+
+use crate::core::health::{CoreModule, ModuleExit};
+use crate::core::shared_resources::SharedResources;
+use crate::modules::Logger;
+use crate::services::ControlCommand;
+use common::enums::log::{LogActionEnum, LogLevelEnum};
+use common::enums::system::{CoreEvent, SystemModuleEnum};
+
+/// Wait before the first restart of a failed module
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// Longest wait between restarts of a module that keeps failing
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// A module that ran this long before failing starts over at `INITIAL_BACKOFF`
+const STABLE_AFTER: Duration = Duration::from_secs(60);
+/// Time a module gets to return by itself after a restart was requested
+const RESTART_GRACE: Duration = Duration::from_secs(5);
+
+/// Doubling delay between restarts
+#[derive(Debug)]
+pub struct Backoff {
+    next: Duration,
+}
+
+impl Backoff {
+    pub fn new() -> Self {
+        Backoff {
+            next: INITIAL_BACKOFF,
+        }
+    }
+
+    /// The delay to wait now, the one after is twice as long up to `MAX_BACKOFF`
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.next;
+        self.next = (self.next * 2).min(MAX_BACKOFF);
+        delay
+    }
+
+    pub fn reset(&mut self) {
+        self.next = INITIAL_BACKOFF;
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Core events that queued up while a module ran
+#[derive(Debug, Default, PartialEq)]
+struct Pending {
+    shutdown: bool,
+    restart: bool,
+}
+
+fn drain_events(core_event_rx: &mut Receiver<CoreEvent>) -> Pending {
+    let mut pending = Pending::default();
+    loop {
+        match core_event_rx.try_recv() {
+            Ok(CoreEvent::Shutdown) | Err(TryRecvError::Closed) => {
+                pending.shutdown = true;
+                return pending;
+            }
+            Ok(CoreEvent::Restart) => pending.restart = true,
+            Ok(CoreEvent::Startup) | Err(TryRecvError::Lagged(_)) => {}
+            Err(TryRecvError::Empty) => return pending,
+        }
+    }
+}
+
+/// Resolves once a restart of `module` is requested, never if the control channel closed
+async fn restart_requested(control_rx: &mut Receiver<ControlCommand>, module: CoreModule) {
+    loop {
+        match control_rx.recv().await {
+            Ok(ControlCommand::RestartModule(m)) if m == module => return,
+            Ok(_) | Err(RecvError::Lagged(_)) => {}
+            Err(RecvError::Closed) => std::future::pending().await,
+        }
+    }
+}
+
+/// Run a module in a task of its own until it returns or a restart is requested
+/// # Returns
+/// * `(Result<(), String>, bool)` - how it ended, panics as `Err`, and whether a restart was requested
+async fn run_module<F>(
+    fut: F,
+    control_rx: &mut Receiver<ControlCommand>,
+    module: CoreModule,
+) -> (Result<(), String>, bool)
+where
+    F: Future + Send + 'static,
+    F::Output: ModuleExit + Send + 'static,
+{
+    let mut handle = task::spawn(fut);
+    select! {
+        joined = &mut handle => (module_outcome(joined), false),
+        _ = restart_requested(control_rx, module) => {
+            let joined = match timeout(RESTART_GRACE, &mut handle).await {
+                Ok(joined) => joined,
+                Err(_) => {
+                    handle.abort();
+                    handle.await
+                }
+            };
+            (module_outcome(joined), true)
+        }
+    }
+}
+
+/// How a module task ended, an aborted task counts as a clean stop
+fn module_outcome<T: ModuleExit>(joined: Result<T, JoinError>) -> Result<(), String> {
+    match joined {
+        Ok(out) => out.into_outcome(),
+        Err(e) if e.is_panic() => Err(panic_message(e.into_panic())),
+        Err(e) if e.is_cancelled() => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+pub(crate) fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    let msg = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string());
+    format!("panicked: {msg}")
+}
+
+/// Spawn the supervising task of one module
+/// # Arguments
+/// * `shared_resources` - health registry, logger, the core event and the control channel
+/// * `module` - which module `build` creates, reported to the health registry
+/// * `build` - creates a fresh instance of the module and returns its `init` future
+/// # Returns
+/// * `JoinHandle<()>` - finishes once the module stopped for a shutdown
+pub fn supervise<B, F>(
+    shared_resources: Arc<SharedResources>,
+    module: CoreModule,
+    build: B,
+) -> JoinHandle<()>
+where
+    B: Fn() -> F + Send + 'static,
+    F: Future + Send + 'static,
+    F::Output: ModuleExit + Send + 'static,
+{
+    // subscribed before the module, so every event the module sees is queued here too
+    let mut core_event_rx = shared_resources
+        .get_service_channels()
+        .subscribe_to_core_event();
+    let mut control_rx = shared_resources
+        .get_service_channels()
+        .subscribe_to_control();
+    let health = shared_resources.get_health();
+    let logger = shared_resources.get_logger();
+    health.started(module);
+
+    task::spawn(async move {
+        let mut backoff = Backoff::new();
+        loop {
+            let started_at = Instant::now();
+            let (outcome, requested) = run_module(build(), &mut control_rx, module).await;
+            health.ended(module, outcome.clone());
+
+            let pending = drain_events(&mut core_event_rx);
+            if pending.shutdown {
+                break;
+            }
+            let error = match outcome {
+                Ok(()) if pending.restart || requested => None,
+                Ok(()) => Some("returned while core is running".to_string()),
+                Err(e) => Some(e),
+            };
+
+            match error {
+                None => {
+                    backoff.reset();
+                    log(&logger, LogLevelEnum::Info, format!("{module:?} restarted")).await;
+                }
+                Some(e) => {
+                    if started_at.elapsed() >= STABLE_AFTER {
+                        backoff.reset();
+                    }
+                    let delay = backoff.next_delay();
+                    eprintln!("Supervisor: {module:?} failed: {e}");
+                    log(
+                        &logger,
+                        LogLevelEnum::Fatal,
+                        format!("{module:?} failed: {e}, restarting in {}s", delay.as_secs()),
+                    )
+                    .await;
+                    // a shutdown cancels the restart, a restart request skips the wait
+                    select! {
+                        _ = tokio::time::sleep(delay) => {}
+                        ev = core_event_rx.recv() => {
+                            if matches!(ev, Ok(CoreEvent::Shutdown) | Err(RecvError::Closed)) {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            health.started(module);
+        }
+    })
+}
+
+async fn log(logger: &Arc<Logger>, level: LogLevelEnum, msg: String) {
+    Logger::log(
+        Arc::clone(logger),
+        level,
+        SystemModuleEnum::Supervisor,
+        LogActionEnum::Custom,
+        None,
+        None,
+        None,
+        Some(msg),
+    )
+    .await;
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tokio::sync::broadcast;
+
+    async fn panicking() {
+        panic!("boom")
+    }
+
+    #[tokio::test]
+    async fn module_outcomes_include_panics() {
+        let (_tx, mut rx) = broadcast::channel(16);
+        let module = CoreModule::Scheduler;
+        assert_eq!(run_module(async {}, &mut rx, module).await, (Ok(()), false));
+        assert_eq!(
+            run_module(
+                async { Err::<(), _>(anyhow::anyhow!("Cannot bind UDP listener")) },
+                &mut rx,
+                module
+            )
+            .await,
+            (Err("Cannot bind UDP listener".to_string()), false)
+        );
+        assert_eq!(
+            run_module(panicking(), &mut rx, module).await,
+            (Err("panicked: boom".to_string()), false)
+        );
+    }
+
+    #[tokio::test]
+    async fn requested_restart_stops_only_the_named_module() {
+        let (tx, mut rx) = broadcast::channel(16);
+        tx.send(ControlCommand::RestartModule(CoreModule::Notifier))
+            .unwrap();
+        tx.send(ControlCommand::RestartModule(CoreModule::Scheduler))
+            .unwrap();
+
+        // returns by itself within the grace period, like modules handling the request do
+        let module = tokio::time::sleep(Duration::from_millis(20));
+        let (outcome, requested) = run_module(module, &mut rx, CoreModule::Scheduler).await;
+        assert_eq!(outcome, Ok(()));
+        assert!(requested);
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        let mut backoff = Backoff::new();
+        let delays: Vec<u64> = (0..8).map(|_| backoff.next_delay().as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), INITIAL_BACKOFF);
+    }
+
+    #[test]
+    fn queued_shutdown_wins_over_restart() {
+        let (tx, mut rx) = broadcast::channel(16);
+        assert_eq!(drain_events(&mut rx), Pending::default());
+
+        tx.send(CoreEvent::Restart).unwrap();
+        assert_eq!(
+            drain_events(&mut rx),
+            Pending {
+                shutdown: false,
+                restart: true
+            }
+        );
+
+        tx.send(CoreEvent::Restart).unwrap();
+        tx.send(CoreEvent::Shutdown).unwrap();
+        assert!(drain_events(&mut rx).shutdown);
+
+        drop(tx);
+        assert!(drain_events(&mut rx).shutdown);
+    }
+}
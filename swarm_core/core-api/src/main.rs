@@ -1,11 +1,14 @@
+use crate::core::schema::ensure_schema;
 use crate::core::shared_resources::SharedResources;
-use crate::core::PulseBroadcaster;
+use crate::core::{HealthRegistry, Metrics, PulseBroadcaster};
 use crate::core::{ModuleInitializer, ServiceInitializer};
 use crate::modules::Logger;
-use crate::services::{ServiceChannels, ServiceWiring};
+use crate::services::{ControlCommand, ServiceChannels, ServiceWiring};
+use common::commands::db_pool;
 use common::enums::system::CoreEvent;
 use std::sync::Arc;
 use tokio::runtime::Runtime;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::Mutex;
 pub mod core;
 pub mod modules;
@@ -24,16 +27,31 @@ fn main() {
 }
 
 async fn tokio_async_runtime() -> anyhow::Result<(), anyhow::Error> {
+    // refuse to run on a schema this build was not made for, CORE_AUTO_MIGRATE=true migrates instead
+    let schema = ensure_schema().await?;
+    println!("Database schema at version {}.", schema.expected);
+
     // Initialize necessary components
     let service_channels = Arc::new(ServiceChannels::new());
     let service_wiring = Arc::new(Mutex::new(ServiceWiring::new()));
     let pulse_broadcaster = PulseBroadcaster::new(service_channels.subscribe_to_core_event());
+    let metrics = Arc::new(Metrics::new());
+    let health = Arc::new(HealthRegistry::new());
+    // one pool for the modules and the common helpers, sized by DATABASE_POOL_SIZE
+    let db_pool = db_pool()?;
     let logger = Arc::new(Logger::new(
         service_channels.subscribe_to_core_event(),
+        service_channels.subscribe_to_control(),
         Arc::new(pulse_broadcaster.subscriptions()),
-    ));
+        Arc::clone(&metrics),
+        Arc::clone(&health),
+        db_pool.clone(),
+    )?);
     let shared_resources = Arc::new(SharedResources::new(
         logger,
+        metrics,
+        health,
+        db_pool,
         Arc::new(pulse_broadcaster.subscriptions()),
         Arc::clone(&service_channels),
         service_wiring,
@@ -43,6 +61,8 @@ async fn tokio_async_runtime() -> anyhow::Result<(), anyhow::Error> {
     let initializer =
         ServiceInitializer::new(Arc::clone(&shared_resources), pulse_broadcaster).await;
     let shutdown_notify = initializer.shutdown_notify.clone();
+    // subscribed before any admin route can send a shutdown
+    let mut control_rx = service_channels.subscribe_to_control();
     let _ = initializer.start()?;
     // ==== create and start modules ====
     let service_handles = ModuleInitializer::new(Arc::clone(&shared_resources));
@@ -53,9 +73,18 @@ async fn tokio_async_runtime() -> anyhow::Result<(), anyhow::Error> {
         .await;
     println!("System started. Awaiting commands...");
 
-    // Loop to handle shutdown events
-    shutdown_notify.notified().await;
-    println!("Notify‐based shutdown triggered.");
+    // Wait for ctrl-c or a shutdown requested on the admin API
+    tokio::select! {
+        _ = shutdown_notify.notified() => println!("Notify‐based shutdown triggered."),
+        _ = async {
+            loop {
+                match control_rx.recv().await {
+                    Ok(ControlCommand::Shutdown) | Err(RecvError::Closed) => break,
+                    Ok(_) | Err(RecvError::Lagged(_)) => {}
+                }
+            }
+        } => println!("Shutdown requested on the admin API."),
+    }
     service_channels
         .send_event_to_all_services(CoreEvent::Shutdown)
         .await;
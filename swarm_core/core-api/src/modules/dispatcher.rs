@@ -1,26 +1,41 @@
 ///! The dispatcher module, it hosts a UDP listener aswell as a sweeper
 ///! By listening for UDP heartbeets it updates the state of the workers
-///! by sequentiall sweeping , it sees if a worker is unreachable and marks it as such
-///! after a pulse, it updates status to match recieved status
+///! by sequentiall sweeping , it sees if a worker is unreachable and marks it as such,
+///! publishing a WorkerUnreachable lifecycle event for the Notifier
+///! Status changes are written right away, plain heartbeats only update memory and are flushed
+///! to `workers.last_seen_at` and `worker_status.last_heartbeat` in one statement every medium pulse
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use anyhow::{Context, Result};
-use chrono::Utc;
+use anyhow::{anyhow, Context, Result};
+use chrono::{NaiveDateTime, Utc};
+use diesel_async::pooled_connection::deadpool::Pool;
 use diesel_async::AsyncPgConnection;
+use serde_json::{json, Value};
 use tokio::net::UdpSocket;
-use tokio::sync::{broadcast::Receiver, RwLock};
+use tokio::select;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+use tokio::sync::{Mutex, RwLock};
+use tokio::task::JoinSet;
 
+use crate::core::health::CoreModule;
 use crate::core::shared_resources::SharedResources;
+use crate::core::supervisor::panic_message;
+use crate::core::Metrics;
 use crate::modules::Logger;
-use common::commands::load_db_connection;
+use crate::services::{ControlCommand, LifecycleEvent, ModuleDump};
 use common::database::models::worker::Worker;
 use common::database::repositories::{WorkerRepository, WorkerStatusRepository};
 use common::enums::log::{LogActionEnum, LogLevelEnum};
 use common::enums::system::{CoreEvent, SystemModuleEnum};
 use common::enums::workers::WorkerStatusEnum;
 
+/// Where worker heartbeats are received
+const UDP_LISTEN_ADDR: &str = "0.0.0.0:5001";
+/// Silence after which the sweeper marks a worker unreachable
+const UNREACHABLE_AFTER: Duration = Duration::from_secs(2);
+
 /// Shared state for dispatcher tasks
 struct DispatcherState {
     // Maps worker_id to worker, loaded in during startup
@@ -29,6 +44,8 @@ struct DispatcherState {
     status_map: RwLock<HashMap<i32, WorkerStatusEnum>>,
     // Maps worker_id to last_seen
     last_seen: RwLock<HashMap<i32, Instant>>,
+    // Maps worker_id to the time of its latest heartbeat not yet written to the DB
+    pending_heartbeats: Mutex<HashMap<i32, NaiveDateTime>>,
 }
 
 impl DispatcherState {
@@ -38,14 +55,48 @@ impl DispatcherState {
             worker_map: RwLock::new(HashMap::new()),
             status_map: RwLock::new(HashMap::new()),
             last_seen: RwLock::new(HashMap::new()),
+            pending_heartbeats: Mutex::new(HashMap::new()),
         }
     }
+
+    /// The maps as JSON keyed by worker id, workers without their SSH credentials
+    async fn dump(&self) -> Value {
+        let workers: HashMap<i32, Value> = self
+            .worker_map
+            .read()
+            .await
+            .iter()
+            .map(|(id, w)| {
+                let worker = json!({
+                    "user_id": w.user_id,
+                    "label": w.label,
+                    "hostname": w.hostname,
+                    "ip_address": w.ip_address,
+                });
+                (*id, worker)
+            })
+            .collect();
+        let last_seen_secs_ago: HashMap<i32, u64> = self
+            .last_seen
+            .read()
+            .await
+            .iter()
+            .map(|(id, at)| (*id, at.elapsed().as_secs()))
+            .collect();
+        json!({
+            "workers": workers,
+            "status": *self.status_map.read().await,
+            "last_seen_secs_ago": last_seen_secs_ago,
+            "pending_heartbeats": *self.pending_heartbeats.lock().await,
+        })
+    }
 }
 
 /// Dispatcher module
 pub struct Dispatcher {
     shared_resources: Arc<SharedResources>,
     core_event_rx: Receiver<CoreEvent>,
+    control_rx: Receiver<ControlCommand>,
     state: Arc<DispatcherState>,
 }
 
@@ -57,200 +108,309 @@ impl Dispatcher {
             core_event_rx: shared_resources
                 .get_service_channels()
                 .subscribe_to_core_event(),
+            control_rx: shared_resources
+                .get_service_channels()
+                .subscribe_to_control(),
             state: Arc::new(DispatcherState::new()),
         }
     }
 
-    /// Initialize the dispatcher init loop,
-    /// During startup UDP listener is spawned aswell as a sweeper
+    /// Run the dispatcher, the worker maps are loaded and the UDP listener, sweeper and heartbeat flush
+    /// are spawned right away, so an instance built by the supervisor after a restart comes up the same way.
+    /// Returns an error once one of those tasks ends, the supervisor then builds a new dispatcher.
     pub async fn init(mut self) -> anyhow::Result<(), anyhow::Error> {
-        loop {
-            match self.core_event_rx.recv().await {
-                Ok(CoreEvent::Startup) => {
-                    // Load all workers into state (imagined function)
-                    let mut conn: AsyncPgConnection = load_db_connection()
-                        .await
-                        .context("Failed to load DB connection")?;
-                    let workers = WorkerRepository::list_all(&mut conn)
-                        .await
-                        .unwrap_or_default();
-                    {
-                        let mut map = self.state.worker_map.write().await;
-                        let mut status = self.state.status_map.write().await;
-                        let mut seen = self.state.last_seen.write().await;
-                        for worker in workers {
-                            let id = worker.id;
-                            map.insert(id, worker.clone());
-                            status.insert(id, WorkerStatusEnum::Offline);
-                            seen.insert(id, Instant::now());
-                        }
+        let mut tasks = self.start().await?;
+        let result = loop {
+            select! {
+                ev = self.core_event_rx.recv() => match ev {
+                    Ok(CoreEvent::Startup) => println!("Dispatcher: Startup event received."),
+                    Ok(CoreEvent::Restart) => {
+                        println!("Dispatcher: Restart event received. Stopping for re-init...");
+                        break Ok(());
                     }
-                    // Spawn UDP listener task
-                    let udp_state = Arc::clone(&self.state);
-                    let udp_resources = Arc::clone(&self.shared_resources);
-                    tokio::spawn(async move {
-                        let socket = UdpSocket::bind("0.0.0.0:5001").await.unwrap();
-                        let mut buf = [0u8; 1024];
-                        loop {
-                            if let Ok((len, _addr)) = socket.recv_from(&mut buf).await {
-                                if let Ok(text) = std::str::from_utf8(&buf[..len]) {
-                                    let msg = text.trim().to_string();
-
-                                    // *** debug‑rad ***
-                                    //println!("DISPATCHER RX → {}", msg);
-
-                                    let state = Arc::clone(&udp_state);
-                                    let logger = udp_resources.get_logger();
-                                    tokio::spawn(async move {
-                                        handle_message(msg, state, logger).await;
-                                    });
-                                }
-                            }
-                        }
-                    });
-
-                    // Spawn unreachable sweep task (fast pulses)
-                    let sweep_state = Arc::clone(&self.state);
-                    let sweep_logger = self.shared_resources.get_logger();
-                    let mut pulse_rx = self
-                        .shared_resources
-                        .get_pulse_subscriptions()
-                        .subscribe_fast();
-
-                    let mut conn: AsyncPgConnection = load_db_connection()
-                        .await
-                        .context("Failed to load DB connection")?;
-
-                    tokio::spawn(async move {
-                        // single DB connection moved in
-                        loop {
-                            if pulse_rx.recv().await.is_ok() {
-                                let now = Instant::now();
-
-                                // 1) grab both locks (write for status, read for last_seen)
-                                let mut status_map = sweep_state.status_map.write().await;
-                                let last_seen = sweep_state.last_seen.read().await;
-
-                                // 2) collect all the worker IDs that need marking
-                                let mut to_mark = Vec::new();
-                                for (id, st) in status_map.iter() {
-                                    // `id: &i32`, `st: &WorkerStatusEnum`
-                                    if *st != WorkerStatusEnum::Offline {
-                                        if let Some(last) = last_seen.get(id) {
-                                            // `last: &Instant`
-                                            if now.duration_since(*last) > Duration::from_secs(2) {
-                                                to_mark.push(*id);
-                                            }
-                                        }
-                                    }
-                                }
-                                // 3) now mutate and persist/log
-                                for id in to_mark {
-                                    status_map.insert(id, WorkerStatusEnum::Unreachable);
-                                    if let Err(e) =
-                                        WorkerStatusRepository::update_status_by_worker_id(
-                                            &mut conn,
-                                            id,
-                                            WorkerStatusEnum::Unreachable,
-                                        )
-                                        .await
-                                    {
-                                        let err_msg =
-                                            format!("DB error marking {} unreachable: {}", id, e);
-                                        Logger::log(
-                                            sweep_logger.clone(),
-                                            LogLevelEnum::Error,
-                                            SystemModuleEnum::Dispatcher,
-                                            LogActionEnum::ClientConnected,
-                                            None,
-                                            None,
-                                            None,
-                                            Some(err_msg),
-                                        )
-                                        .await;
-                                        continue;
-                                    }
-                                    let msg = format!("Worker {} marked UNREACHABLE", id);
-                                    Logger::log(
-                                        sweep_logger.clone(),
-                                        LogLevelEnum::Warning,
-                                        SystemModuleEnum::Dispatcher,
-                                        LogActionEnum::ClientConnected,
-                                        None,
-                                        None,
-                                        None,
-                                        Some(msg),
-                                    )
-                                    .await;
-                                }
-                            } else {
-                                break;
-                            }
+                    Ok(CoreEvent::Shutdown) | Err(RecvError::Closed) => break Ok(()),
+                    Err(RecvError::Lagged(_)) => {}
+                },
+                Ok(command) = self.control_rx.recv() => match command {
+                    ControlCommand::DumpState(reply) => {
+                        let dump = ModuleDump {
+                            module: CoreModule::Dispatcher,
+                            state: self.state.dump().await,
+                        };
+                        let _ = reply.send(dump).await;
+                    }
+                    // returning lets the supervisor build a new dispatcher without aborting this one
+                    ControlCommand::RestartModule(CoreModule::Dispatcher) => {
+                        println!("Dispatcher: Restart requested. Stopping for re-init...");
+                        break Ok(());
+                    }
+                    _ => {}
+                },
+                Some(ended) = tasks.join_next() => {
+                    break Err(match ended {
+                        Ok(task) => anyhow!("Dispatcher {task} stopped"),
+                        Err(e) if e.is_panic() => {
+                            anyhow!("Dispatcher task {}", panic_message(e.into_panic()))
                         }
+                        Err(e) => anyhow!("Dispatcher task failed: {e}"),
                     });
                 }
-                Ok(CoreEvent::Restart) => {
-                    // (Optional) handle restart if needed
-                }
-                Ok(CoreEvent::Shutdown) => {
-                    break;
+            }
+        };
+
+        // the UDP socket is released before a new dispatcher binds it
+        tasks.shutdown().await;
+        self.shared_resources.get_health().set_udp_bound(false);
+        // heartbeats since the last medium pulse
+        flush_heartbeats(
+            &self.state,
+            &self.shared_resources.get_db_pool(),
+            &self.shared_resources.get_logger(),
+        )
+        .await;
+        result
+    }
+
+    /// Load all workers into state and spawn the UDP listener, sweeper and heartbeat flush
+    /// # Returns
+    /// * `JoinSet<&'static str>` - the tasks, each one returns its name if it ever ends
+    async fn start(&self) -> anyhow::Result<JoinSet<&'static str>> {
+        // Load all workers into state (imagined function)
+        let mut conn = self
+            .shared_resources
+            .get_db_connection()
+            .await
+            .context("Failed to load DB connection")?;
+        let workers = WorkerRepository::list_all(&mut conn)
+            .await
+            .unwrap_or_default();
+        {
+            let mut map = self.state.worker_map.write().await;
+            let mut status = self.state.status_map.write().await;
+            let mut seen = self.state.last_seen.write().await;
+            for worker in workers {
+                let id = worker.id;
+                map.insert(id, worker.clone());
+                status.insert(id, WorkerStatusEnum::Offline);
+                seen.insert(id, Instant::now());
+            }
+        }
+        // Bind before spawning, a taken port fails the dispatcher instead of a detached task
+        let socket = UdpSocket::bind(UDP_LISTEN_ADDR)
+            .await
+            .with_context(|| format!("Cannot bind UDP listener on {UDP_LISTEN_ADDR}"))?;
+        self.shared_resources.get_health().set_udp_bound(true);
+
+        let mut tasks = JoinSet::new();
+
+        // Spawn UDP listener task
+        let udp_state = Arc::clone(&self.state);
+        let udp_resources = Arc::clone(&self.shared_resources);
+        tasks.spawn(async move {
+            let mut buf = [0u8; 1024];
+            loop {
+                if let Ok((len, _addr)) = socket.recv_from(&mut buf).await {
+                    let metrics = udp_resources.get_metrics();
+                    metrics.heartbeats_received.inc();
+                    if let Ok(text) = std::str::from_utf8(&buf[..len]) {
+                        let msg = text.trim().to_string();
+
+                        // *** debug‑rad ***
+                        //println!("DISPATCHER RX → {}", msg);
+
+                        let state = Arc::clone(&udp_state);
+                        let logger = udp_resources.get_logger();
+                        let pool = udp_resources.get_db_pool();
+                        tokio::spawn(async move {
+                            handle_message(msg, state, logger, metrics, pool).await;
+                        });
+                    } else {
+                        metrics.heartbeats_dropped.inc();
+                    }
                 }
-                Err(_) => {
+            }
+        });
+
+        // Spawn unreachable sweep task (fast pulses)
+        let sweep_state = Arc::clone(&self.state);
+        let sweep_logger = self.shared_resources.get_logger();
+        let sweep_metrics = self.shared_resources.get_metrics();
+        let sweep_channels = self.shared_resources.get_service_channels();
+        let sweep_pool = self.shared_resources.get_db_pool();
+        let mut pulse_rx = self
+            .shared_resources
+            .get_pulse_subscriptions()
+            .subscribe_fast();
+
+        tasks.spawn(async move {
+            loop {
+                if pulse_rx.recv().await.is_ok() {
+                    let now = Instant::now();
+
+                    // 1) grab the locks (write for status, read for workers and last_seen)
+                    let worker_map = sweep_state.worker_map.read().await;
+                    let mut status_map = sweep_state.status_map.write().await;
+                    let last_seen = sweep_state.last_seen.read().await;
+
+                    // 2) flip the workers that just went silent, ones already unreachable are skipped
+                    let to_mark = sweep_stale(&mut status_map, &last_seen, now);
+                    // 3) now persist/log, once per transition
+                    for (id, previous) in to_mark {
+                        if let Err(e) = mark_unreachable(&sweep_pool, id).await {
+                            // back to the old status, the next sweep tries again
+                            status_map.insert(id, previous);
+                            let err_msg = format!("DB error marking {} unreachable: {}", id, e);
+                            Logger::log(
+                                sweep_logger.clone(),
+                                LogLevelEnum::Error,
+                                SystemModuleEnum::Dispatcher,
+                                LogActionEnum::ClientConnected,
+                                None,
+                                None,
+                                None,
+                                Some(err_msg),
+                            )
+                            .await;
+                            continue;
+                        }
+                        if let Some(worker) = worker_map.get(&id) {
+                            sweep_channels.publish_lifecycle_event(
+                                LifecycleEvent::worker_unreachable(worker),
+                            );
+                        }
+                        let msg = format!("Worker {} marked UNREACHABLE", id);
+                        Logger::log(
+                            sweep_logger.clone(),
+                            LogLevelEnum::Warning,
+                            SystemModuleEnum::Dispatcher,
+                            LogActionEnum::ClientConnected,
+                            None,
+                            None,
+                            None,
+                            Some(msg),
+                        )
+                        .await;
+                    }
+                    sweep_metrics.set_workers_by_status(status_map.values());
+                } else {
                     break;
                 }
             }
+            "sweeper"
+        });
+
+        // Spawn heartbeat flush task (medium pulses)
+        let flush_state = Arc::clone(&self.state);
+        let flush_pool = self.shared_resources.get_db_pool();
+        let flush_logger = self.shared_resources.get_logger();
+        let flush_health = self.shared_resources.get_health();
+        let mut flush_rx = self
+            .shared_resources
+            .get_pulse_subscriptions()
+            .subscribe_medium();
+        tasks.spawn(async move {
+            // a lagged receiver missed pulses, not heartbeats, flush anyway
+            while let Ok(_) | Err(RecvError::Lagged(_)) = flush_rx.recv().await {
+                flush_health.pulse(CoreModule::Dispatcher);
+                flush_heartbeats(&flush_state, &flush_pool, &flush_logger).await;
+            }
+            "heartbeat flush"
+        });
+
+        Ok(tasks)
+    }
+}
+
+/// Persist a worker that stopped sending heartbeats, a pooled connection is borrowed per worker
+async fn mark_unreachable(pool: &Pool<AsyncPgConnection>, worker_id: i32) -> Result<()> {
+    let mut conn = pool.get().await?;
+    WorkerStatusRepository::update_status_by_worker_id(
+        &mut conn,
+        worker_id,
+        WorkerStatusEnum::Unreachable,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Mark workers silent for longer than `UNREACHABLE_AFTER` as `Unreachable` in `status_map`
+/// Returns them with their previous status. Offline and already unreachable workers are left
+/// alone, so a worker that stays silent is reported by one sweep only.
+fn sweep_stale(
+    status_map: &mut HashMap<i32, WorkerStatusEnum>,
+    last_seen: &HashMap<i32, Instant>,
+    now: Instant,
+) -> Vec<(i32, WorkerStatusEnum)> {
+    let mut stale = Vec::new();
+    for (id, status) in status_map.iter_mut() {
+        if matches!(
+            status,
+            WorkerStatusEnum::Offline | WorkerStatusEnum::Unreachable
+        ) {
+            continue;
+        }
+        if last_seen
+            .get(id)
+            .is_some_and(|last| now.duration_since(*last) > UNREACHABLE_AFTER)
+        {
+            stale.push((*id, *status));
+            *status = WorkerStatusEnum::Unreachable;
         }
-        Ok(())
     }
+    stale
+}
+
+/// Parse a heartbeat packet, `<worker_id>,<CONNECT|IDLE|BUSY|DISCONNECT>`
+fn parse_heartbeat(msg: &str) -> Option<(i32, WorkerStatusEnum)> {
+    let (id, cmd) = msg.split_once(',')?;
+    let id: i32 = id.trim().parse().ok()?;
+    let status = match cmd.trim() {
+        "CONNECT" | "IDLE" => WorkerStatusEnum::Idle,
+        "BUSY" => WorkerStatusEnum::Busy,
+        "DISCONNECT" => WorkerStatusEnum::Offline,
+        _ => return None,
+    };
+    Some((id, status))
 }
 
 /// Handle an incoming worker status message
+/// Only a status change touches the DB here, the heartbeat itself waits for the next flush
 async fn handle_message(
     msg: String,
     state: Arc<DispatcherState>,
     logger: Arc<Logger>,
+    metrics: Arc<Metrics>,
+    pool: Pool<AsyncPgConnection>,
 ) -> Result<()> {
-    let parts: Vec<&str> = msg.split(',').collect();
-    if parts.len() != 2 {
-        return Ok(());
-    }
-
     /* --- 1. Parse ---------------------------------------------------------------------------- */
-    let id: i32 = match parts[0].trim().parse() {
-        Ok(i) => i,
-        Err(_) => return Ok(()),
-    };
-    let cmd = parts[1].trim();
-    let new_status = match cmd {
-        "CONNECT" | "IDLE" => WorkerStatusEnum::Idle,
-        "BUSY" => WorkerStatusEnum::Busy,
-        "DISCONNECT" => WorkerStatusEnum::Offline,
-        _ => return Ok(()),
+    let Some((id, new_status)) = parse_heartbeat(&msg) else {
+        metrics.heartbeats_dropped.inc();
+        return Ok(());
     };
 
     /* --- 2. Fast in‑memory update ------------------------------------------------------------- */
-    {
+    let previous = {
         let mut status_map = state.status_map.write().await;
         let mut last_seen = state.last_seen.write().await;
-        status_map.insert(id, new_status.clone());
         last_seen.insert(id, Instant::now());
+        status_map.insert(id, new_status)
+    };
+    state
+        .pending_heartbeats
+        .lock()
+        .await
+        .insert(id, Utc::now().naive_utc());
+    if previous == Some(new_status) {
+        return Ok(());
     }
 
-    /* --- 3. Persist to DB --------------------------------------------------------------------- */
-    let mut conn: AsyncPgConnection = load_db_connection()
+    /* --- 3. Persist the status change --------------------------------------------------------- */
+    let mut conn = pool
+        .get()
         .await
         .context("Failed DB connection in handle_message")?;
-
-    let now_ts = Utc::now().naive_utc();
-
-    // 3.1 update workers.last_seen_at (ignore error if row missing)
-    let _ = WorkerRepository::update_last_seen_at(&mut conn, id, now_ts).await;
-
-    // 3.2 update worker_status row (status + last_heartbeat)
-    if let Ok(Some(ws)) = WorkerStatusRepository::find_by_worker_id(&mut conn, id).await {
-        let _ = WorkerStatusRepository::update_status(&mut conn, ws.id, new_status.clone()).await;
-        let _ = WorkerStatusRepository::update_last_heartbeat(&mut conn, ws.id).await;
-    }
+    // ignore error if the worker has no status row
+    let _ = WorkerStatusRepository::update_status_by_worker_id(&mut conn, id, new_status).await;
 
     /* --- 4. Log ------------------------------------------------------------------------------ */
     let log_msg = format!("Worker {id} status → {new_status:?}");
@@ -267,3 +427,83 @@ async fn handle_message(
     .await;
     Ok(())
 }
+
+/// Write every pending heartbeat in one statement
+/// On failure they stay pending for the next pulse, unless a newer heartbeat arrived meanwhile
+async fn flush_heartbeats(
+    state: &DispatcherState,
+    pool: &Pool<AsyncPgConnection>,
+    logger: &Arc<Logger>,
+) {
+    let beats: Vec<(i32, NaiveDateTime)> = state.pending_heartbeats.lock().await.drain().collect();
+    if beats.is_empty() {
+        return;
+    }
+
+    let result = match pool.get().await {
+        Ok(mut conn) => WorkerStatusRepository::record_heartbeats(&mut conn, &beats)
+            .await
+            .map_err(anyhow::Error::from),
+        Err(e) => Err(e.into()),
+    };
+    if let Err(e) = result {
+        let count = beats.len();
+        {
+            let mut pending = state.pending_heartbeats.lock().await;
+            for (id, seen_at) in beats {
+                pending.entry(id).or_insert(seen_at);
+            }
+        }
+        Logger::log(
+            Arc::clone(logger),
+            LogLevelEnum::Error,
+            SystemModuleEnum::Dispatcher,
+            LogActionEnum::Custom,
+            None,
+            None,
+            None,
+            Some(format!("Flushing {count} heartbeats failed: {e}")),
+        )
+        .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heartbeat_packets() {
+        assert_eq!(parse_heartbeat("7,IDLE"), Some((7, WorkerStatusEnum::Idle)));
+        assert_eq!(
+            parse_heartbeat(" 7 , BUSY "),
+            Some((7, WorkerStatusEnum::Busy))
+        );
+        assert_eq!(
+            parse_heartbeat("7,DISCONNECT"),
+            Some((7, WorkerStatusEnum::Offline))
+        );
+        for bad in ["7", "seven,IDLE", "7,SLEEPING", "7,IDLE,extra"] {
+            assert_eq!(parse_heartbeat(bad), None, "{bad}");
+        }
+    }
+
+    #[test]
+    fn a_silent_worker_is_reported_once() {
+        let seen = Instant::now();
+        let now = seen + UNREACHABLE_AFTER + Duration::from_millis(1);
+        let mut status_map = HashMap::from([
+            (1, WorkerStatusEnum::Busy),
+            (2, WorkerStatusEnum::Offline),
+            (3, WorkerStatusEnum::Idle),
+        ]);
+        let last_seen = HashMap::from([(1, seen), (2, seen), (3, now)]);
+
+        let first = sweep_stale(&mut status_map, &last_seen, now);
+        assert_eq!(first, [(1, WorkerStatusEnum::Busy)]);
+        assert_eq!(status_map[&1], WorkerStatusEnum::Unreachable);
+        // the next fast pulse sees the same silence, nothing new to report
+        let second = sweep_stale(&mut status_map, &last_seen, now + Duration::from_millis(50));
+        assert!(second.is_empty());
+    }
+}
@@ -24,7 +24,11 @@ impl Harvester {
         loop {
             match self.core_event_rx.recv().await {
                 Ok(CoreEvent::Startup) => println!("Harvester: Startup event received."),
-                Ok(CoreEvent::Restart) => println!("Harvester: Restart event received."),
+                Ok(CoreEvent::Restart) => {
+                    // the supervisor builds a fresh instance
+                    println!("Harvester: Restart event received. Stopping for re-init...");
+                    break;
+                }
                 Ok(CoreEvent::Shutdown) => {
                     println!("Harvester: Shutdown event received. Stopping...");
                     break;
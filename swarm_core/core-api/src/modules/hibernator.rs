@@ -23,7 +23,11 @@ impl Hibernator {
         loop {
             match self.core_event_rx.recv().await {
                 Ok(CoreEvent::Startup) => println!("Hibernator: Startup event received."),
-                Ok(CoreEvent::Restart) => println!("Hibernator: Restart event received."),
+                Ok(CoreEvent::Restart) => {
+                    // the supervisor builds a fresh instance
+                    println!("Hibernator: Restart event received. Stopping for re-init...");
+                    break;
+                }
                 Ok(CoreEvent::Shutdown) => {
                     println!("Hibernator: Shutdown event received. Stopping...");
                     break;
@@ -0,0 +1,656 @@
+//! External log sinks, the Logger fans every flushed batch out to them next to Postgres
+//!
+//! Sinks are read from the JSON file named by `LOG_SINKS_CONFIG`, a list like:
+//! ```json
+//! [
+//!   { "type": "file", "path": "logs/core.jsonl", "max_bytes": 10485760, "keep": 5 },
+//!   { "type": "syslog", "address": "127.0.0.1:514", "protocol": "udp", "min_level": "Warning" },
+//!   { "type": "webhook", "url": "http://collector/logs", "batch_size": 100,
+//!     "modules": ["Scheduler", "Dispatcher"] }
+//! ]
+//! ```
+//! Every sink takes an optional `min_level` and `modules` filter. A failing sink only
+//! reports to stderr, it never holds up the other sinks. Deliveries run on their own task
+//! behind a `SinkQueue`, so the Postgres flush never waits for a sink either.
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use async_trait::async_trait;
+use serde::Deserialize;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpStream, UdpSocket};
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+use common::database::models::log::LogEntry;
+use common::enums::log::LogLevelEnum;
+use common::enums::system::SystemModuleEnum;
+
+/// Upper bound for a single sink delivery
+const SINK_TIMEOUT: Duration = Duration::from_secs(5);
+/// Syslog APP-NAME
+const SYSLOG_APP_NAME: &str = "swarmsync";
+/// Flushed batches waiting for the sinks, further batches are dropped for the sinks only
+const SINK_QUEUE_BATCHES: usize = 16;
+
+/// A destination for log entries besides Postgres
+#[async_trait]
+pub trait LogSink: Send + Sync {
+    /// Name used in error messages
+    fn name(&self) -> String;
+    /// Deliver a batch, entries are already filtered
+    async fn send(&self, entries: &[&LogEntry]) -> anyhow::Result<()>;
+}
+
+/// Which entries a sink receives, an empty field lets everything through
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SinkFilter {
+    #[serde(default)]
+    pub min_level: Option<LogLevelEnum>,
+    #[serde(default)]
+    pub modules: Option<Vec<SystemModuleEnum>>,
+}
+
+impl SinkFilter {
+    pub fn accepts(&self, entry: &LogEntry) -> bool {
+        self.min_level
+            .as_ref()
+            .is_none_or(|min| level_rank(&entry.level) >= level_rank(min))
+            && self
+                .modules
+                .as_ref()
+                .is_none_or(|modules| modules.contains(&entry.module))
+    }
+}
+
+/// Levels from least to most severe
+fn level_rank(level: &LogLevelEnum) -> u8 {
+    match level {
+        LogLevelEnum::Info => 0,
+        LogLevelEnum::Success => 1,
+        LogLevelEnum::Warning => 2,
+        LogLevelEnum::Error => 3,
+        LogLevelEnum::Fatal => 4,
+    }
+}
+
+/// One entry of the sinks config file
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SinkConfig {
+    File {
+        path: PathBuf,
+        // rotate once the file grows past this size
+        #[serde(default = "default_max_bytes")]
+        max_bytes: u64,
+        // rotated files kept next to the live one: path.1 … path.<keep>
+        #[serde(default = "default_keep")]
+        keep: usize,
+        #[serde(flatten)]
+        filter: SinkFilter,
+    },
+    Syslog {
+        // host:port of the syslog server
+        address: String,
+        #[serde(default)]
+        protocol: SyslogProtocol,
+        // syslog facility, local0 by default
+        #[serde(default = "default_facility")]
+        facility: u8,
+        #[serde(flatten)]
+        filter: SinkFilter,
+    },
+    Webhook {
+        url: String,
+        // entries per POST
+        #[serde(default = "default_batch_size")]
+        batch_size: usize,
+        #[serde(flatten)]
+        filter: SinkFilter,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SyslogProtocol {
+    #[default]
+    Udp,
+    Tcp,
+}
+
+fn default_max_bytes() -> u64 {
+    10 * 1024 * 1024
+}
+
+fn default_keep() -> usize {
+    5
+}
+
+fn default_facility() -> u8 {
+    16
+}
+
+fn default_batch_size() -> usize {
+    100
+}
+
+/// All configured sinks, each with its filter
+#[derive(Default)]
+pub struct LogSinks {
+    sinks: Vec<(SinkFilter, Box<dyn LogSink>)>,
+}
+
+impl LogSinks {
+    /// Build the sinks from `LOG_SINKS_CONFIG`, no variable means no sinks
+    /// # Returns
+    /// * `LogSinks` - Error if the file can't be read or holds an invalid sink
+    pub fn from_env() -> anyhow::Result<Self> {
+        match std::env::var("LOG_SINKS_CONFIG") {
+            Ok(path) => {
+                let raw = std::fs::read_to_string(&path)
+                    .with_context(|| format!("reading log sinks config {}", path))?;
+                let configs: Vec<SinkConfig> = serde_json::from_str(&raw)
+                    .with_context(|| format!("parsing log sinks config {}", path))?;
+                Self::from_configs(configs)
+            }
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn from_configs(configs: Vec<SinkConfig>) -> anyhow::Result<Self> {
+        let mut sinks: Vec<(SinkFilter, Box<dyn LogSink>)> = Vec::new();
+        for config in configs {
+            match config {
+                SinkConfig::File {
+                    path,
+                    max_bytes,
+                    keep,
+                    filter,
+                } => sinks.push((
+                    filter,
+                    Box::new(FileSink {
+                        path,
+                        max_bytes,
+                        keep,
+                    }),
+                )),
+                SinkConfig::Syslog {
+                    address,
+                    protocol,
+                    facility,
+                    filter,
+                } => {
+                    if facility > 23 {
+                        anyhow::bail!("syslog facility must be 0-23, got {}", facility);
+                    }
+                    sinks.push((
+                        filter,
+                        Box::new(SyslogSink {
+                            address,
+                            protocol,
+                            facility,
+                            hostname: std::env::var("HOSTNAME").unwrap_or_else(|_| "-".into()),
+                        }),
+                    ))
+                }
+                SinkConfig::Webhook {
+                    url,
+                    batch_size,
+                    filter,
+                } => sinks.push((
+                    filter,
+                    Box::new(WebhookSink {
+                        client: reqwest::Client::builder().timeout(SINK_TIMEOUT).build()?,
+                        url,
+                        batch_size: batch_size.max(1),
+                    }),
+                )),
+            }
+        }
+        Ok(Self { sinks })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    /// Hand a flushed batch to every sink concurrently, failures are reported and dropped
+    pub async fn dispatch(&self, entries: &[LogEntry]) {
+        let deliveries = self.sinks.iter().map(|(filter, sink)| async move {
+            let accepted: Vec<&LogEntry> = entries.iter().filter(|e| filter.accepts(e)).collect();
+            if accepted.is_empty() {
+                return;
+            }
+            match tokio::time::timeout(SINK_TIMEOUT, sink.send(&accepted)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => eprintln!("Logger: sink {} failed: {e:#}", sink.name()),
+                Err(_) => eprintln!("Logger: sink {} timed out", sink.name()),
+            }
+        });
+        futures::future::join_all(deliveries).await;
+    }
+
+    /// Move the sinks onto their own task, batches are delivered one after the other in flush order
+    pub fn start(self) -> SinkQueue {
+        if self.is_empty() {
+            return SinkQueue { tx: None };
+        }
+        let (tx, mut rx) = mpsc::channel::<Vec<LogEntry>>(SINK_QUEUE_BATCHES);
+        tokio::spawn(async move {
+            while let Some(batch) = rx.recv().await {
+                self.dispatch(&batch).await;
+            }
+        });
+        SinkQueue { tx: Some(tx) }
+    }
+}
+
+/// Hands flushed batches to the sink task without waiting for the delivery
+pub struct SinkQueue {
+    tx: Option<mpsc::Sender<Vec<LogEntry>>>,
+}
+
+impl SinkQueue {
+    /// Queue a batch for the sinks, dropped (for the sinks only) when they fall too far behind
+    pub fn forward(&self, entries: &[LogEntry]) {
+        let Some(tx) = &self.tx else { return };
+        if entries.is_empty() {
+            return;
+        }
+        match tx.try_send(entries.to_vec()) {
+            Ok(()) => {}
+            Err(TrySendError::Full(batch)) => eprintln!(
+                "Logger: sinks are {} batches behind, {} entries not forwarded",
+                SINK_QUEUE_BATCHES,
+                batch.len()
+            ),
+            Err(TrySendError::Closed(_)) => eprintln!("Logger: sink task is gone"),
+        }
+    }
+}
+
+/* ---------------- JSON-lines file ------------------------------------ */
+
+/// Appends entries as JSON lines, rotating `path` → `path.1` → … → `path.<keep>`
+pub struct FileSink {
+    path: PathBuf,
+    max_bytes: u64,
+    keep: usize,
+}
+
+impl FileSink {
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+
+    async fn rotate_if_full(&self) -> anyhow::Result<()> {
+        let size = match tokio::fs::metadata(&self.path).await {
+            Ok(meta) => meta.len(),
+            Err(_) => return Ok(()),
+        };
+        if size < self.max_bytes {
+            return Ok(());
+        }
+        if self.keep == 0 {
+            tokio::fs::remove_file(&self.path).await?;
+            return Ok(());
+        }
+        for n in (1..self.keep).rev() {
+            if tokio::fs::try_exists(self.rotated(n)).await? {
+                tokio::fs::rename(self.rotated(n), self.rotated(n + 1)).await?;
+            }
+        }
+        tokio::fs::rename(&self.path, self.rotated(1)).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LogSink for FileSink {
+    fn name(&self) -> String {
+        format!("file:{}", self.path.display())
+    }
+
+    async fn send(&self, entries: &[&LogEntry]) -> anyhow::Result<()> {
+        self.rotate_if_full().await?;
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        let mut lines = String::new();
+        for entry in entries {
+            lines.push_str(&serde_json::to_string(entry)?);
+            lines.push('\n');
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(lines.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+}
+
+/* ---------------- syslog --------------------------------------------- */
+
+/// RFC 5424 syslog, one datagram per entry over UDP, octet-counted frames over TCP (RFC 6587)
+pub struct SyslogSink {
+    address: String,
+    protocol: SyslogProtocol,
+    facility: u8,
+    hostname: String,
+}
+
+/// Syslog severity of a level
+fn syslog_severity(level: &LogLevelEnum) -> u8 {
+    match level {
+        LogLevelEnum::Fatal => 2,   // critical
+        LogLevelEnum::Error => 3,   // error
+        LogLevelEnum::Warning => 4, // warning
+        LogLevelEnum::Success => 5, // notice
+        LogLevelEnum::Info => 6,    // informational
+    }
+}
+
+impl SyslogSink {
+    /// Format one entry, the message is the entry as JSON
+    fn format(&self, entry: &LogEntry) -> anyhow::Result<String> {
+        let pri = self.facility * 8 + syslog_severity(&entry.level);
+        Ok(format!(
+            "<{}>1 {} {} {} {} {} - {}",
+            pri,
+            entry.created_at.and_utc().to_rfc3339(),
+            self.hostname,
+            SYSLOG_APP_NAME,
+            entry.module,
+            entry.action,
+            serde_json::to_string(entry)?
+        ))
+    }
+}
+
+#[async_trait]
+impl LogSink for SyslogSink {
+    fn name(&self) -> String {
+        format!("syslog:{}", self.address)
+    }
+
+    async fn send(&self, entries: &[&LogEntry]) -> anyhow::Result<()> {
+        match self.protocol {
+            SyslogProtocol::Udp => {
+                let socket = UdpSocket::bind("0.0.0.0:0").await?;
+                socket.connect(&self.address).await?;
+                for entry in entries {
+                    socket.send(self.format(entry)?.as_bytes()).await?;
+                }
+            }
+            SyslogProtocol::Tcp => {
+                let mut stream = TcpStream::connect(&self.address).await?;
+                for entry in entries {
+                    let msg = self.format(entry)?;
+                    stream
+                        .write_all(format!("{} {}", msg.len(), msg).as_bytes())
+                        .await?;
+                }
+                stream.flush().await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* ---------------- HTTP webhook --------------------------------------- */
+
+/// POSTs JSON arrays of at most `batch_size` entries
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+    batch_size: usize,
+}
+
+#[async_trait]
+impl LogSink for WebhookSink {
+    fn name(&self) -> String {
+        format!("webhook:{}", self.url)
+    }
+
+    async fn send(&self, entries: &[&LogEntry]) -> anyhow::Result<()> {
+        for batch in entries.chunks(self.batch_size) {
+            self.client
+                .post(&self.url)
+                .json(batch)
+                .send()
+                .await?
+                .error_for_status()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    use chrono::Utc;
+    use common::enums::log::LogActionEnum;
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+    use tokio::net::TcpListener;
+
+    fn entry(level: LogLevelEnum, module: SystemModuleEnum) -> LogEntry {
+        let now = Utc::now().naive_utc();
+        LogEntry {
+            id: 0,
+            created_at: now,
+            level,
+            module,
+            action: LogActionEnum::Custom,
+            expires_at: now,
+            client_connected_payload: None,
+            job_submitted_payload: None,
+            job_completed_payload: None,
+            custom_msg: Some("hello".into()),
+        }
+    }
+
+    fn rotated_path(path: &std::path::Path, n: usize) -> PathBuf {
+        FileSink {
+            path: path.to_path_buf(),
+            max_bytes: 0,
+            keep: 0,
+        }
+        .rotated(n)
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("swarmsync-sinks-{}", uuid::Uuid::new_v4()))
+            .join(name)
+    }
+
+    #[test]
+    fn filter_by_level_and_module() {
+        let filter: SinkFilter =
+            serde_json::from_str(r#"{ "min_level": "Warning", "modules": ["Scheduler"] }"#)
+                .unwrap();
+        assert!(filter.accepts(&entry(LogLevelEnum::Error, SystemModuleEnum::Scheduler)));
+        assert!(!filter.accepts(&entry(LogLevelEnum::Info, SystemModuleEnum::Scheduler)));
+        assert!(!filter.accepts(&entry(LogLevelEnum::Fatal, SystemModuleEnum::Dispatcher)));
+        assert!(
+            SinkFilter::default().accepts(&entry(LogLevelEnum::Info, SystemModuleEnum::Receiver))
+        );
+    }
+
+    #[tokio::test]
+    async fn file_sink_rotates() {
+        let path = temp_path("core.jsonl");
+        let sinks = LogSinks::from_configs(vec![SinkConfig::File {
+            path: path.clone(),
+            max_bytes: 1,
+            keep: 2,
+            filter: SinkFilter::default(),
+        }])
+        .unwrap();
+        let batch = vec![entry(LogLevelEnum::Info, SystemModuleEnum::Scheduler)];
+        for _ in 0..4 {
+            sinks.dispatch(&batch).await;
+        }
+
+        let live = tokio::fs::read_to_string(&path).await.unwrap();
+        assert_eq!(live.lines().count(), 1);
+        assert!(live.contains("\"custom_msg\":\"hello\""));
+        assert!(rotated_path(&path, 1).exists());
+        assert!(rotated_path(&path, 2).exists());
+        assert!(!rotated_path(&path, 3).exists());
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[tokio::test]
+    async fn syslog_sink_sends_rfc5424_over_udp_and_tcp() {
+        let udp = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let tcp = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let sinks = LogSinks::from_configs(vec![
+            SinkConfig::Syslog {
+                address: udp.local_addr().unwrap().to_string(),
+                protocol: SyslogProtocol::Udp,
+                facility: 16,
+                filter: SinkFilter::default(),
+            },
+            SinkConfig::Syslog {
+                address: tcp.local_addr().unwrap().to_string(),
+                protocol: SyslogProtocol::Tcp,
+                facility: 1,
+                filter: SinkFilter::default(),
+            },
+        ])
+        .unwrap();
+
+        let batch = vec![entry(LogLevelEnum::Error, SystemModuleEnum::Dispatcher)];
+        let (_, (mut stream, _)) = tokio::join!(sinks.dispatch(&batch), async {
+            tcp.accept().await.unwrap()
+        });
+
+        let mut buf = [0u8; 2048];
+        let n = udp.recv(&mut buf).await.unwrap();
+        let datagram = String::from_utf8_lossy(&buf[..n]).to_string();
+        // local0 (16) * 8 + error (3)
+        assert!(datagram.starts_with("<131>1 "));
+        assert!(datagram.contains(" swarmsync Dispatcher Custom - {"));
+
+        let mut framed = String::new();
+        stream.read_to_string(&mut framed).await.unwrap();
+        let (len, msg) = framed.split_once(' ').unwrap();
+        assert_eq!(len.parse::<usize>().unwrap(), msg.len());
+        assert!(msg.starts_with("<11>1 "));
+    }
+
+    #[tokio::test]
+    async fn webhook_sink_posts_batches() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/logs", listener.local_addr().unwrap());
+        let sinks = LogSinks::from_configs(vec![SinkConfig::Webhook {
+            url,
+            batch_size: 2,
+            filter: SinkFilter::default(),
+        }])
+        .unwrap();
+
+        // fake HTTP server answering 200 and recording the size of every JSON batch
+        let server = tokio::spawn(async move {
+            let mut batches = Vec::new();
+            while batches.len() < 2 {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut reader = BufReader::new(stream);
+                loop {
+                    let mut content_length = 0;
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).await.unwrap() == 0 {
+                        break;
+                    }
+                    loop {
+                        let mut header = String::new();
+                        reader.read_line(&mut header).await.unwrap();
+                        if header == "\r\n" {
+                            break;
+                        }
+                        if let Some(v) = header.to_ascii_lowercase().strip_prefix("content-length:")
+                        {
+                            content_length = v.trim().parse().unwrap();
+                        }
+                    }
+                    let mut body = vec![0u8; content_length];
+                    reader.read_exact(&mut body).await.unwrap();
+                    let batch: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+                    batches.push(batch.len());
+                    reader
+                        .get_mut()
+                        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                        .await
+                        .unwrap();
+                    if batches.len() == 2 {
+                        break;
+                    }
+                }
+            }
+            batches
+        });
+
+        let batch: Vec<LogEntry> = (0..3)
+            .map(|_| entry(LogLevelEnum::Info, SystemModuleEnum::Harvester))
+            .collect();
+        sinks.dispatch(&batch).await;
+        assert_eq!(server.await.unwrap(), vec![2, 1]);
+    }
+
+    /// Remembers the custom message of every entry per batch, slowly
+    struct SlowSink(Arc<Mutex<Vec<Vec<String>>>>);
+
+    #[async_trait]
+    impl LogSink for SlowSink {
+        fn name(&self) -> String {
+            "slow".into()
+        }
+        async fn send(&self, entries: &[&LogEntry]) -> anyhow::Result<()> {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let batch = entries
+                .iter()
+                .filter_map(|e| e.custom_msg.clone())
+                .collect();
+            self.0.lock().unwrap().push(batch);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn queue_does_not_wait_for_the_sinks_and_keeps_the_order() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let queue = LogSinks {
+            sinks: vec![(SinkFilter::default(), Box::new(SlowSink(seen.clone())))],
+        }
+        .start();
+
+        let started = std::time::Instant::now();
+        for n in 0..3 {
+            let mut e = entry(LogLevelEnum::Info, SystemModuleEnum::Harvester);
+            e.custom_msg = Some(n.to_string());
+            queue.forward(&[e]);
+        }
+        queue.forward(&[]);
+        assert!(started.elapsed() < Duration::from_millis(50));
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        let seen = seen.lock().unwrap().clone();
+        assert_eq!(seen, vec![vec!["0"], vec!["1"], vec!["2"]]);
+    }
+
+    #[test]
+    fn queue_without_sinks_spawns_nothing() {
+        // no runtime here, `start` would panic if it spawned the task
+        let queue = LogSinks::default().start();
+        queue.forward(&[entry(LogLevelEnum::Info, SystemModuleEnum::Harvester)]);
+    }
+}
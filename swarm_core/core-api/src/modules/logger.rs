@@ -1,59 +1,268 @@
+use std::collections::VecDeque;
+use std::env;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
+use anyhow::Context;
 use chrono::{NaiveDateTime, Utc};
 use diesel::prelude::{ExpressionMethods, QueryDsl};
+use diesel_async::pooled_connection::deadpool::Pool;
 use diesel_async::AsyncPgConnection;
-use diesel_async::RunQueryDsl; // async execute/delete
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde_json::json;
+use tokio::io::AsyncWriteExt;
 use tokio::select;
-use tokio::sync::{broadcast::Receiver, Mutex, RwLock};
+use tokio::sync::{broadcast::Receiver, Mutex, Notify, RwLock};
 
-use crate::core::PulseSubscriptions;
-use common::commands::load_db_connection;
+use crate::core::health::CoreModule;
+use crate::core::{HealthRegistry, Metrics, PulseSubscriptions};
+use crate::modules::log_sinks::{LogSinks, SinkQueue};
+use crate::services::{ControlCommand, ModuleDump};
 use common::database::models::log::{
     ClientConnectedPayload, JobCompletedPayload, JobSubmittedPayload, LogEntry, NewDBLogEntry,
 };
-use common::database::repositories::LogEntryRepository;
+use common::database::models::retention::{resolve_retention, LogRetentionPolicy};
+use common::database::repositories::{
+    AuditLogRepository, LogEntryRepository, LogRetentionRepository,
+};
 use common::enums::log::{LogActionEnum, LogLevelEnum};
 use common::enums::system::{CoreEvent, Pulse, SystemModuleEnum};
 
+/// Entries kept in memory when `LOGGER_BUFFER_CAPACITY` is not set
+const DEFAULT_BUFFER_CAPACITY: usize = 10_000;
+/// Spillover file used when `LOGGER_SPILL_PATH` is not set
+const DEFAULT_SPILL_PATH: &str = "logger_spill.jsonl";
+/// The spillover file stops growing past this size, further entries stay in the buffer
+const MAX_SPILL_BYTES: u64 = 64 * 1024 * 1024;
+/// Expired entries archived per slow pulse
+const ARCHIVE_BATCH: i64 = 10_000;
+
+/// Bounded log buffer
+/// When full, the oldest Info entry makes room. Without Info entries a new Info entry is dropped
+/// itself, anything else pushes out the oldest entry.
+struct LogBuffer {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+}
+
+impl LogBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Append an entry, returns the number of dropped entries (0 or 1)
+    fn push(&mut self, entry: LogEntry) -> u64 {
+        if self.entries.len() < self.capacity {
+            self.entries.push_back(entry);
+            return 0;
+        }
+        match self.oldest_info() {
+            Some(idx) => {
+                self.entries.remove(idx);
+            }
+            None if matches!(entry.level, LogLevelEnum::Info) => return 1,
+            None => {
+                self.entries.pop_front();
+            }
+        }
+        self.entries.push_back(entry);
+        1
+    }
+
+    /// Put entries of a failed flush back in front of newer ones, returns the number of dropped entries
+    fn requeue(&mut self, older: Vec<LogEntry>) -> u64 {
+        for entry in older.into_iter().rev() {
+            self.entries.push_front(entry);
+        }
+        let mut dropped = 0;
+        while self.entries.len() > self.capacity {
+            let idx = self.oldest_info().unwrap_or(0);
+            self.entries.remove(idx);
+            dropped += 1;
+        }
+        dropped
+    }
+
+    fn drain(&mut self) -> Vec<LogEntry> {
+        self.entries.drain(..).collect()
+    }
+
+    fn oldest_info(&self) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|e| matches!(e.level, LogLevelEnum::Info))
+    }
+}
+
+/// Local JSON-lines file holding entries that could not reach Postgres, replayed on recovery
+struct SpillFile {
+    path: PathBuf,
+}
+
+impl SpillFile {
+    /// Append rows, one JSON document per line
+    async fn append(&self, rows: &[NewDBLogEntry]) -> anyhow::Result<()> {
+        let size = tokio::fs::metadata(&self.path)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+        if size >= MAX_SPILL_BYTES {
+            anyhow::bail!("spill file {} is full", self.path.display());
+        }
+        let mut lines = String::new();
+        for row in rows {
+            lines.push_str(&serde_json::to_string(row)?);
+            lines.push('\n');
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .with_context(|| format!("opening {}", self.path.display()))?;
+        file.write_all(lines.as_bytes()).await?;
+        file.flush().await?;
+        Ok(())
+    }
+
+    /// Every spilled row, unreadable lines are skipped
+    async fn read_rows(&self) -> anyhow::Result<Vec<NewDBLogEntry>> {
+        let raw = match tokio::fs::read_to_string(&self.path).await {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(raw
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    async fn clear(&self) -> anyhow::Result<()> {
+        match tokio::fs::remove_file(&self.path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
 /// Central logger accessed by all modules
 pub struct Logger {
-    /// in-memory log buffer, flushed on pulse/shutdown
-    buffer_logs: RwLock<Vec<LogEntry>>,
+    /// in-memory log buffer, bounded, flushed on pulse/shutdown or when it fills up
+    buffer_logs: RwLock<LogBuffer>,
+    /// Flushes early once the buffer is three quarters full
+    flush_now: Notify,
+    /// Entries dropped because the buffer was full, since startup
+    dropped: AtomicU64,
+    /// Connections for flushes and cleanup, the shared pool from `SharedResources`
+    pool: Pool<AsyncPgConnection>,
+    /// Entries that could not be written while Postgres was down
+    spill: SpillFile,
+    /// Retention policies, reloaded every slow pulse so changes apply at runtime
+    retention: RwLock<Vec<LogRetentionPolicy>>,
+    /// Expired entries are written here as `.jsonl.gz` before deletion, from `LOG_ARCHIVE_DIR`
+    archive_dir: Option<PathBuf>,
+    /// External destinations every flushed batch is forwarded to, from `LOG_SINKS_CONFIG`
+    sinks: SinkQueue,
     /// Life cycle events, controls Logger behavior
     core_event_rx: Mutex<Receiver<CoreEvent>>,
+    /// Admin commands, answers state dumps and returns when its restart is requested
+    control_rx: Mutex<Receiver<ControlCommand>>,
     /// Slow pulse every ~2 s
     pulse_rx: Mutex<Receiver<Pulse>>,
+    /// Buffer size and flush failures are exported here
+    metrics: Arc<Metrics>,
+    /// Handled pulses are stamped here for the health routes
+    health: Arc<HealthRegistry>,
 }
 
 impl Logger {
     /* ---------------- construction + background loop -------------------- */
     /// Init logger, only one instance ever running
     //TODO: use some design pattern to only allow one instance to reduce bugs
-    pub fn new(core_rx: Receiver<CoreEvent>, pulse_subs: Arc<PulseSubscriptions>) -> Self {
-        Self {
-            buffer_logs: RwLock::new(Vec::new()),
+    /// Buffer size, spill file, archive directory and sinks are read from `LOGGER_BUFFER_CAPACITY`,
+    /// `LOGGER_SPILL_PATH`, `LOG_ARCHIVE_DIR` and `LOG_SINKS_CONFIG`
+    pub fn new(
+        core_rx: Receiver<CoreEvent>,
+        control_rx: Receiver<ControlCommand>,
+        pulse_subs: Arc<PulseSubscriptions>,
+        metrics: Arc<Metrics>,
+        health: Arc<HealthRegistry>,
+        pool: Pool<AsyncPgConnection>,
+    ) -> anyhow::Result<Self> {
+        let capacity = env::var("LOGGER_BUFFER_CAPACITY")
+            .ok()
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(DEFAULT_BUFFER_CAPACITY);
+        let spill_path =
+            env::var("LOGGER_SPILL_PATH").unwrap_or_else(|_| DEFAULT_SPILL_PATH.to_string());
+
+        Ok(Self {
+            buffer_logs: RwLock::new(LogBuffer::new(capacity)),
+            flush_now: Notify::new(),
+            dropped: AtomicU64::new(0),
+            pool,
+            spill: SpillFile {
+                path: PathBuf::from(spill_path),
+            },
+            retention: RwLock::new(Vec::new()),
+            archive_dir: env::var("LOG_ARCHIVE_DIR").ok().map(PathBuf::from),
+            sinks: LogSinks::from_env()?.start(),
             core_event_rx: Mutex::new(core_rx),
+            control_rx: Mutex::new(control_rx),
             pulse_rx: Mutex::new(pulse_subs.subscribe_slow()),
-        }
+            metrics,
+            health,
+        })
     }
 
     /// Spawn this on Tokio runtime: `tokio::spawn(logger.clone().init())`.
     pub async fn init(self: Arc<Self>) {
         let mut core_rx = self.core_event_rx.lock().await;
+        let mut control_rx = self.control_rx.lock().await;
         let mut pulse_rx = self.pulse_rx.lock().await;
+        self.refresh_retention().await;
         loop {
             select! {
                 Ok(ev) = core_rx.recv() => match ev {
                     CoreEvent::Startup => {},
-                    CoreEvent::Restart => { self.store_all_logs().await; },
+                    // shared by every module, so re-init in place: flush and reload the policies
+                    CoreEvent::Restart => {
+                        self.store_all_logs().await;
+                        self.refresh_retention().await;
+                    },
                     CoreEvent::Shutdown => { self.store_all_logs().await; break; },
                 },
+                Ok(command) = control_rx.recv() => match command {
+                    ControlCommand::DumpState(reply) => {
+                        let _ = reply.send(self.dump_state().await).await;
+                    },
+                    // the supervisor runs this instance again, nothing buffered gets lost
+                    ControlCommand::RestartModule(CoreModule::Logger) => {
+                        self.store_all_logs().await;
+                        break;
+                    },
+                    _ => {},
+                },
+                _ = self.flush_now.notified() => { self.store_all_logs().await; },
                 Ok(pulse) = pulse_rx.recv() => {
-                    // On pulse we do 2 things
-                    // 1. flush buffer to DB
-                    // 2. delete expired rows
+                    // On pulse we do 3 things
+                    // 1. reload retention policies
+                    // 2. delete (or archive) expired rows
+                    // 3. flush buffer to DB
                     if matches!(pulse, Pulse::Slow) {
+                        self.health.pulse(CoreModule::Logger);
+                        self.refresh_retention().await;
                         self.try_clean().await;
                         self.store_all_logs().await;
                     }
@@ -68,7 +277,7 @@ impl Logger {
     pub async fn log(
         // the logger, in a thread safe ref counter
         logger: Arc<Self>,
-        // the log level, determine expiration time
+        // the log level, with module and action it selects the retention policy
         level: LogLevelEnum,
         // the module emitting the log
         module: SystemModuleEnum,
@@ -84,13 +293,10 @@ impl Logger {
         custom: Option<String>,
     ) {
         let now = Utc::now().naive_utc();
-        let expires_at = match level {
-            LogLevelEnum::Info => now + chrono::Duration::minutes(5),
-            LogLevelEnum::Success => now + chrono::Duration::days(1),
-            LogLevelEnum::Warning => now + chrono::Duration::days(3),
-            LogLevelEnum::Error | LogLevelEnum::Fatal => now + chrono::Duration::days(7),
-        };
-        logger.buffer_logs.write().await.push(LogEntry {
+        let expires_at =
+            now + resolve_retention(&logger.retention.read().await, &level, &module, &action);
+        let mut buffer = logger.buffer_logs.write().await;
+        let dropped = buffer.push(LogEntry {
             id: 0,
             created_at: now,
             level,
@@ -102,33 +308,129 @@ impl Logger {
             job_completed_payload: completed,
             custom_msg: custom,
         });
+        logger.count_dropped(dropped);
+        logger.metrics.logger_buffer_size.set(buffer.len() as i64);
+        if buffer.len() >= buffer.capacity / 4 * 3 {
+            logger.flush_now.notify_one();
+        }
+    }
+
+    /// Entries dropped because the buffer was full, since startup
+    pub fn dropped_entries(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Buffer depth and drops, the answer to `ControlCommand::DumpState`
+    async fn dump_state(&self) -> ModuleDump {
+        let buffer = self.buffer_logs.read().await;
+        ModuleDump {
+            module: CoreModule::Logger,
+            state: json!({
+                "buffered_entries": buffer.len(),
+                "buffer_capacity": buffer.capacity,
+                "dropped_entries": self.dropped_entries(),
+            }),
+        }
     }
 
     /* ---------------- internal helpers --------------------------------- */
 
-    /// Flush buffer → DB (best‑effort).
+    /// Flush buffer → DB, spilled entries are replayed first. On failure the batch goes to the
+    /// spill file, and only if that fails too back into the (bounded) buffer.
+    /// A batch is queued for the external sinks once it is written or spilled, without waiting
+    /// on them; a requeued batch is forwarded by the flush that finally stores it, so only once.
     pub async fn store_all_logs(&self) {
-        let mut pending: Vec<LogEntry> = {
+        let pending: Vec<LogEntry> = {
             let mut guard = self.buffer_logs.write().await;
-            guard.drain(..).collect()
+            self.metrics.logger_buffer_size.set(0);
+            guard.drain()
         };
-        if pending.is_empty() {
+        let rows: Vec<NewDBLogEntry> = pending.iter().map(NewDBLogEntry::from).collect();
+
+        let mut conn = match self.pool.get().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("Logger: no DB connection: {e}");
+                self.flush_failed(pending, rows).await;
+                return;
+            }
+        };
+
+        if let Err(e) = self.replay_spill(&mut conn).await {
+            eprintln!("Logger: spill replay failed: {e}");
+        }
+        if rows.is_empty() {
             return;
         }
-        println!("Logger: flushing {} entries to DB…", pending.len());
+        println!("Logger: flushing {} entries to DB…", rows.len());
+        match LogEntryRepository::create_many(&mut conn, &rows).await {
+            Ok(_) => {
+                println!("Logger: flush OK");
+                self.sinks.forward(&pending);
+            }
+            Err(e) => {
+                eprintln!("Logger: flush failed: {e}");
+                self.flush_failed(pending, rows).await;
+            }
+        }
+    }
 
-        match Self::insert_batch(&mut pending).await {
-            Ok(_) => println!("Logger: flush OK"),
+    /// Keep a batch that could not be written, on disk if possible, a spilled batch goes to the sinks
+    async fn flush_failed(&self, pending: Vec<LogEntry>, rows: Vec<NewDBLogEntry>) {
+        if pending.is_empty() {
+            return;
+        }
+        self.metrics.logger_flush_failures.inc();
+        match self.spill.append(&rows).await {
+            Ok(()) => {
+                self.metrics.logger_spilled_entries.add(rows.len() as i64);
+                eprintln!("Logger: spilled {} entries to disk", rows.len());
+                self.sinks.forward(&pending);
+            }
             Err(e) => {
-                eprintln!("Logger: flush failed – returning to buffer: {e}");
-                self.buffer_logs.write().await.extend(pending);
+                eprintln!("Logger: spill failed – returning to buffer: {e}");
+                let mut buffer = self.buffer_logs.write().await;
+                let dropped = buffer.requeue(pending);
+                self.count_dropped(dropped);
+                self.metrics.logger_buffer_size.set(buffer.len() as i64);
             }
         }
     }
 
-    /// Delete expired rows from DB once per slow‑pulse.
+    /// Insert everything in the spill file and remove it
+    async fn replay_spill(&self, conn: &mut AsyncPgConnection) -> anyhow::Result<()> {
+        let rows = self.spill.read_rows().await?;
+        if rows.is_empty() {
+            return Ok(());
+        }
+        LogEntryRepository::create_many(conn, &rows).await?;
+        self.spill.clear().await?;
+        self.metrics.logger_spilled_entries.set(0);
+        println!("Logger: replayed {} spilled entries", rows.len());
+        Ok(())
+    }
+
+    fn count_dropped(&self, dropped: u64) {
+        if dropped > 0 {
+            self.dropped.fetch_add(dropped, Ordering::Relaxed);
+            self.metrics.logger_dropped_entries.inc_by(dropped);
+        }
+    }
+
+    /// Reload retention policies, keeps the previous ones if the DB is unreachable
+    pub async fn refresh_retention(&self) {
+        let Ok(mut conn) = self.pool.get().await else {
+            return;
+        };
+        match LogRetentionRepository::list_all(&mut conn).await {
+            Ok(policies) => *self.retention.write().await = policies,
+            Err(e) => eprintln!("Logger: loading retention policies failed: {e}"),
+        }
+    }
+
+    /// Delete expired rows from DB once per slow‑pulse, archiving them first if `LOG_ARCHIVE_DIR` is set.
     pub async fn try_clean(&self) {
-        let mut conn = match load_db_connection().await {
+        let mut conn = match self.pool.get().await {
             Ok(c) => c,
             Err(e) => {
                 eprintln!("Logger: cleanup DB connect error: {e}");
@@ -136,22 +438,126 @@ impl Logger {
             }
         };
         let now: NaiveDateTime = Utc::now().naive_utc();
-        use common::database::schema::logs::dsl as l;
-        if let Err(e) = diesel::delete(l::logs.filter(l::expires_at.lt(now)))
-            .execute(&mut conn)
-            .await
-        {
-            eprintln!("Logger: cleanup delete error: {e}");
+        if let Some(dir) = &self.archive_dir {
+            if let Err(e) = Self::archive_expired(&mut conn, dir, now).await {
+                eprintln!("Logger: archiving expired logs failed, keeping them: {e}");
+            }
+        } else {
+            use common::database::schema::logs::dsl as l;
+            use diesel_async::RunQueryDsl; // async execute/delete
+            if let Err(e) = diesel::delete(l::logs.filter(l::expires_at.lt(now)))
+                .execute(&mut conn)
+                .await
+            {
+                eprintln!("Logger: cleanup delete error: {e}");
+            }
+        }
+        // the audit trail has its own retention, expired entries are the only ones it lets go
+        if let Err(e) = AuditLogRepository::delete_expired(&mut conn).await {
+            eprintln!("Logger: audit cleanup error: {e}");
         }
     }
 
-    /// Insert a batch of log entries to the DB.
-    async fn insert_batch(entries: &[LogEntry]) -> anyhow::Result<()> {
-        let mut conn: AsyncPgConnection = load_db_connection().await?;
-        for e in entries {
-            let new_row: NewDBLogEntry = e.into();
-            let _ = LogEntryRepository::create(&mut conn, new_row).await;
+    /// Write a batch of expired entries to a gzipped JSON-lines file, then delete them.
+    /// Nothing is deleted unless the file was written.
+    async fn archive_expired(
+        conn: &mut AsyncPgConnection,
+        dir: &std::path::Path,
+        now: NaiveDateTime,
+    ) -> anyhow::Result<()> {
+        let expired = LogEntryRepository::list_expired(conn, now, ARCHIVE_BATCH).await?;
+        if expired.is_empty() {
+            return Ok(());
+        }
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        for entry in &expired {
+            serde_json::to_writer(&mut encoder, entry)?;
+            encoder.write_all(b"\n")?;
         }
+        let compressed = encoder.finish()?;
+
+        tokio::fs::create_dir_all(dir)
+            .await
+            .with_context(|| format!("creating {}", dir.display()))?;
+        let first_id = expired.first().map(|e| e.id).unwrap_or_default();
+        let path = dir.join(format!(
+            "logs-{}-{}.jsonl.gz",
+            now.format("%Y%m%d-%H%M%S"),
+            first_id
+        ));
+        tokio::fs::write(&path, compressed)
+            .await
+            .with_context(|| format!("writing {}", path.display()))?;
+
+        let ids: Vec<i32> = expired.iter().map(|e| e.id).collect();
+        LogEntryRepository::delete_many(conn, &ids).await?;
+        println!(
+            "Logger: archived {} expired entries to {}",
+            ids.len(),
+            path.display()
+        );
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: LogLevelEnum, msg: &str) -> LogEntry {
+        let now = Utc::now().naive_utc();
+        LogEntry {
+            id: 0,
+            created_at: now,
+            level,
+            module: SystemModuleEnum::Dispatcher,
+            action: LogActionEnum::Custom,
+            expires_at: now,
+            client_connected_payload: None,
+            job_submitted_payload: None,
+            job_completed_payload: None,
+            custom_msg: Some(msg.to_string()),
+        }
+    }
+
+    fn messages(buffer: &LogBuffer) -> Vec<&str> {
+        buffer
+            .entries
+            .iter()
+            .filter_map(|e| e.custom_msg.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn full_buffer_drops_oldest_info_first() {
+        let mut buffer = LogBuffer::new(3);
+        assert_eq!(buffer.push(entry(LogLevelEnum::Error, "e1")), 0);
+        assert_eq!(buffer.push(entry(LogLevelEnum::Info, "i1")), 0);
+        assert_eq!(buffer.push(entry(LogLevelEnum::Info, "i2")), 0);
+
+        assert_eq!(buffer.push(entry(LogLevelEnum::Warning, "w1")), 1);
+        assert_eq!(messages(&buffer), ["e1", "i2", "w1"]);
+        assert_eq!(buffer.push(entry(LogLevelEnum::Fatal, "f1")), 1);
+        assert_eq!(messages(&buffer), ["e1", "w1", "f1"]);
+
+        // no Info left: a new Info entry is the one dropped
+        assert_eq!(buffer.push(entry(LogLevelEnum::Info, "i3")), 1);
+        assert_eq!(messages(&buffer), ["e1", "w1", "f1"]);
+        // otherwise the oldest entry goes
+        assert_eq!(buffer.push(entry(LogLevelEnum::Error, "e2")), 1);
+        assert_eq!(messages(&buffer), ["w1", "f1", "e2"]);
+    }
+
+    #[test]
+    fn requeue_keeps_order_and_capacity() {
+        let mut buffer = LogBuffer::new(3);
+        buffer.push(entry(LogLevelEnum::Error, "new"));
+        let dropped = buffer.requeue(vec![
+            entry(LogLevelEnum::Info, "old-info"),
+            entry(LogLevelEnum::Error, "old-1"),
+            entry(LogLevelEnum::Error, "old-2"),
+        ]);
+        assert_eq!(dropped, 1);
+        assert_eq!(messages(&buffer), ["old-1", "old-2", "new"]);
+    }
+}
@@ -1,7 +1,9 @@
 pub mod dispatcher;
 pub mod harvester;
 pub mod hibernator;
+pub mod log_sinks;
 pub mod logger;
+pub mod notifier;
 pub mod reciever;
 pub mod scheduler;
 pub mod task_archive;
@@ -10,6 +12,7 @@ pub use dispatcher::Dispatcher;
 pub use harvester::Harvester;
 pub use hibernator::Hibernator;
 pub use logger::Logger;
+pub use notifier::Notifier;
 pub use reciever::Reciever;
 pub use scheduler::Scheduler;
 pub use task_archive::TaskArchive;
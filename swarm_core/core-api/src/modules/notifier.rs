@@ -0,0 +1,293 @@
+//! The notifier module, delivers webhooks for job and worker lifecycle events
+//! Job events are stored by `JobRepository` with the change itself, as one Pending
+//! `webhook_deliveries` row per subscribed webhook of the owner, whoever made it (routes, Scheduler,
+//! CommandDeck). Worker events arrive on the `ServiceChannels` lifecycle channel and are stored the same way.
+//! Every medium pulse the due deliveries are POSTed, signed with the webhooks secret.
+//! A failed attempt is retried with exponential backoff until `WEBHOOK_MAX_ATTEMPTS` is reached,
+//! the delivery log keeps the outcome of every delivery.
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{NaiveDateTime, Utc};
+use futures::future::join_all;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tokio::select;
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::core::health::CoreModule;
+use crate::core::shared_resources::SharedResources;
+use crate::modules::Logger;
+use crate::services::LifecycleEvent;
+use common::database::models::webhook::{
+    retry_delay, Webhook, WebhookDelivery, DEFAULT_MAX_DELIVERY_ATTEMPTS,
+};
+use common::database::repositories::WebhookDeliveryRepository;
+use common::enums::log::{LogActionEnum, LogLevelEnum};
+use common::enums::system::{CoreEvent, Pulse, SystemModuleEnum};
+
+/// Due deliveries sent per pulse
+const DELIVERY_BATCH: i64 = 50;
+/// Time a receiver gets to answer
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+/// Header carrying the signature, `sha256=<hex>` of `<timestamp>.<body>`
+pub const SIGNATURE_HEADER: &str = "X-Swarm-Signature";
+/// Header carrying the unix timestamp that was signed
+pub const TIMESTAMP_HEADER: &str = "X-Swarm-Timestamp";
+pub const EVENT_HEADER: &str = "X-Swarm-Event";
+pub const DELIVERY_HEADER: &str = "X-Swarm-Delivery";
+
+/// Outcome of one attempt
+enum Attempt {
+    Delivered(i32),
+    Failed(Option<i32>, String),
+}
+
+pub struct Notifier {
+    shared_resources: Arc<SharedResources>,
+    core_event_rx: broadcast::Receiver<CoreEvent>,
+    lifecycle_rx: broadcast::Receiver<LifecycleEvent>,
+    pulse_rx: broadcast::Receiver<Pulse>,
+    client: reqwest::Client,
+    // Read from WEBHOOK_MAX_ATTEMPTS
+    max_attempts: i32,
+}
+
+impl Notifier {
+    pub fn new(shared_resources: Arc<SharedResources>) -> Self {
+        let max_attempts = env::var("WEBHOOK_MAX_ATTEMPTS")
+            .ok()
+            .and_then(|v| v.parse::<i32>().ok())
+            .filter(|v| *v > 0)
+            .unwrap_or(DEFAULT_MAX_DELIVERY_ATTEMPTS);
+        let channels = shared_resources.get_service_channels();
+
+        Notifier {
+            shared_resources: Arc::clone(&shared_resources),
+            core_event_rx: channels.subscribe_to_core_event(),
+            lifecycle_rx: channels.subscribe_to_lifecycle_events(),
+            pulse_rx: shared_resources
+                .get_pulse_subscriptions()
+                .subscribe_medium(),
+            client: reqwest::Client::builder()
+                .timeout(DELIVERY_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            max_attempts,
+        }
+    }
+
+    pub async fn init(mut self) {
+        loop {
+            select! {
+                ev = self.core_event_rx.recv() => match ev {
+                    Ok(CoreEvent::Startup) => println!("Notifier: Startup event received."),
+                    Ok(CoreEvent::Restart) => {
+                        // the supervisor builds a fresh instance
+                        println!("Notifier: Restart event received. Stopping for re-init...");
+                        break;
+                    }
+                    Ok(CoreEvent::Shutdown) => {
+                        println!("Notifier: Shutdown event received. Stopping...");
+                        break;
+                    }
+                    Err(_) => {
+                        println!("Notifier: Channel closed. Exiting...");
+                        break;
+                    }
+                },
+                ev = self.lifecycle_rx.recv() => match ev {
+                    Ok(event) => {
+                        if let Err(e) = self.enqueue(event).await {
+                            eprintln!("Notifier: queueing deliveries failed: {e}");
+                        }
+                    }
+                    Err(RecvError::Lagged(missed)) => {
+                        self.log(
+                            LogLevelEnum::Warning,
+                            format!("{missed} lifecycle events missed, their webhooks were not called"),
+                        )
+                        .await;
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                Ok(pulse) = self.pulse_rx.recv() => {
+                    if matches!(pulse, Pulse::Medium) {
+                        self.shared_resources.get_health().pulse(CoreModule::Notifier);
+                        if let Err(e) = self.deliver_due().await {
+                            eprintln!("Notifier: delivery round failed: {e}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Store one Pending delivery per subscribed webhook of the events owner
+    async fn enqueue(&self, event: LifecycleEvent) -> anyhow::Result<()> {
+        let mut conn = self.shared_resources.get_db_connection().await?;
+        WebhookDeliveryRepository::enqueue_event(&mut conn, event.event, event.user_id, event.data)
+            .await?;
+        Ok(())
+    }
+
+    /// Send every due delivery, concurrently, and record the outcomes
+    async fn deliver_due(&self) -> anyhow::Result<()> {
+        let mut conn = self.shared_resources.get_db_connection().await?;
+        let now = Utc::now().naive_utc();
+        let due = WebhookDeliveryRepository::list_due(&mut conn, now, DELIVERY_BATCH).await?;
+        if due.is_empty() {
+            return Ok(());
+        }
+
+        let attempts = join_all(
+            due.iter()
+                .map(|(delivery, webhook)| self.attempt(delivery, webhook)),
+        )
+        .await;
+
+        let metrics = self.shared_resources.get_metrics();
+        let now = Utc::now().naive_utc();
+        for ((delivery, webhook), attempt) in due.iter().zip(attempts) {
+            match attempt {
+                Attempt::Delivered(code) => {
+                    WebhookDeliveryRepository::mark_delivered(&mut conn, delivery.id, code, now)
+                        .await?;
+                    metrics.observe_webhook_delivery("delivered");
+                }
+                Attempt::Failed(code, error) => {
+                    let retry_at = next_attempt_at(delivery.attempts + 1, self.max_attempts, now);
+                    WebhookDeliveryRepository::mark_attempt_failed(
+                        &mut conn,
+                        delivery.id,
+                        code,
+                        &error,
+                        now,
+                        retry_at,
+                    )
+                    .await?;
+                    if retry_at.is_some() {
+                        metrics.observe_webhook_delivery("retried");
+                    } else {
+                        metrics.observe_webhook_delivery("failed");
+                        self.log(
+                            LogLevelEnum::Warning,
+                            format!(
+                                "Webhook {} gave up on {} delivery {} after {} attempts: {}",
+                                webhook.id,
+                                delivery.event,
+                                delivery.id,
+                                delivery.attempts + 1,
+                                error
+                            ),
+                        )
+                        .await;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// POST a delivery, signed with the webhooks secret
+    async fn attempt(&self, delivery: &WebhookDelivery, webhook: &Webhook) -> Attempt {
+        let body = match serde_json::to_vec(&delivery.payload) {
+            Ok(body) => body,
+            Err(e) => return Attempt::Failed(None, e.to_string()),
+        };
+        let timestamp = Utc::now().timestamp();
+
+        let response = self
+            .client
+            .post(&webhook.url)
+            .header("Content-Type", "application/json")
+            .header(EVENT_HEADER, delivery.event.to_string())
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SIGNATURE_HEADER, sign(&webhook.secret, timestamp, &body))
+            .body(body)
+            .send()
+            .await;
+
+        match response {
+            Ok(res) if res.status().is_success() => {
+                Attempt::Delivered(i32::from(res.status().as_u16()))
+            }
+            Ok(res) => Attempt::Failed(
+                Some(i32::from(res.status().as_u16())),
+                format!("receiver answered {}", res.status()),
+            ),
+            Err(e) => Attempt::Failed(None, e.to_string()),
+        }
+    }
+
+    async fn log(&self, level: LogLevelEnum, msg: String) {
+        Logger::log(
+            self.shared_resources.get_logger(),
+            level,
+            SystemModuleEnum::Notifier,
+            LogActionEnum::Custom,
+            None,
+            None,
+            None,
+            Some(msg),
+        )
+        .await;
+    }
+}
+
+/// Signature of a request body, receivers recompute it with their copy of the secret
+/// # Arguments
+/// * `secret` - The webhooks secret
+/// * `timestamp` - Unix timestamp sent in `X-Swarm-Timestamp`, signed to prevent replays
+/// * `body` - The exact request body
+/// # Returns
+/// * `String` - `sha256=` followed by the hex encoded HMAC-SHA256 of `<timestamp>.<body>`
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    #[allow(clippy::expect_used)]
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// When to try again after `attempts` failed attempts, None once `max_attempts` is used up
+fn next_attempt_at(attempts: i32, max_attempts: i32, now: NaiveDateTime) -> Option<NaiveDateTime> {
+    (attempts < max_attempts).then(|| now + retry_delay(attempts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_covers_timestamp_and_body() {
+        let body = br#"{"event":"JobFailed"}"#;
+        let signature = sign("secret", 1_700_000_000, body);
+
+        // python3 -c 'import hmac; print(hmac.new(b"secret", b"1700000000." + body, "sha256").hexdigest())'
+        assert_eq!(
+            signature,
+            "sha256=c39622d4f0fe0da4c0b2735807c0cb6fb52466dc739a36db55f3fa35ca2f0569"
+        );
+        assert_ne!(signature, sign("secret", 1_700_000_001, body));
+        assert_ne!(signature, sign("other", 1_700_000_000, body));
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let now = NaiveDateTime::default();
+        assert_eq!(
+            next_attempt_at(1, 3, now),
+            Some(now + chrono::Duration::seconds(10))
+        );
+        assert_eq!(
+            next_attempt_at(2, 3, now),
+            Some(now + chrono::Duration::seconds(20))
+        );
+        assert_eq!(next_attempt_at(3, 3, now), None);
+    }
+}
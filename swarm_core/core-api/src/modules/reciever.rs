@@ -23,7 +23,11 @@ impl Reciever {
         loop {
             match self.core_event_rx.recv().await {
                 Ok(CoreEvent::Startup) => println!("Reciever: Startup event received."),
-                Ok(CoreEvent::Restart) => println!("Reciever: Restart event received."),
+                Ok(CoreEvent::Restart) => {
+                    // the supervisor builds a fresh instance
+                    println!("Reciever: Restart event received. Stopping for re-init...");
+                    break;
+                }
                 Ok(CoreEvent::Shutdown) => {
                     println!("Reciever: Shutdown event received. Stopping...");
                     break;
@@ -1,38 +1,449 @@
-use crate::core::shared_resources::SharedResources;
-use common::enums::system::CoreEvent;
+//! The scheduler module, decides which submitted jobs may be handed to the dispatcher
+//! On every medium pulse submitted jobs are admitted (Submitted → Queued) by aged priority,
+//! as long as the owner stays within its `max_running_jobs` quota. Each round also refreshes
+//! the job state gauges, queue depth and scheduling latency in `Metrics`.
+//! With `SCHEDULER_PREEMPTION` set, a queued job that waited too long while its owners workers are busy
+//! can evict a much lower priority running job from one of them back to the queue
+//! While paused through `ServiceChannels` nothing is admitted or preempted, jobs are still submitted
+//! and the gauges kept current.
+use std::collections::{HashMap, HashSet};
+use std::env;
 use std::sync::Arc;
-use tokio::sync::broadcast;
 
-#[allow(dead_code)]
+use chrono::{NaiveDateTime, Utc};
+use diesel_async::AsyncPgConnection;
+use tokio::select;
+use tokio::sync::{broadcast, watch};
+
+use crate::core::health::CoreModule;
+use crate::core::shared_resources::SharedResources;
+use crate::modules::Logger;
+use common::database::models::job::Job;
+use common::database::models::log::JobSubmittedPayload;
+use common::database::repositories::{
+    JobRepository, QuotaRepository, TransitionError, WorkerStatusRepository,
+};
+use common::enums::job::JobStateEnum;
+use common::enums::log::{LogActionEnum, LogLevelEnum};
+use common::enums::system::{CoreEvent, Pulse, SystemModuleEnum};
+use common::enums::workers::WorkerStatusEnum;
+
+/// Effective priority a waiting job needs above a running job to evict it
+const PREEMPTION_PRIORITY_GAP: i64 = 5;
+/// Seconds a queued job must have waited before it may preempt
+const PREEMPTION_MIN_WAIT_SECS: i64 = 60;
+/// Seconds a job requeued by a preemption may not preempt another job itself
+const PREEMPTION_COOLDOWN_SECS: i64 = 600;
+
 pub struct Scheduler {
     shared_resources: Arc<SharedResources>,
     core_event_rx: broadcast::Receiver<CoreEvent>,
+    pulse_rx: broadcast::Receiver<Pulse>,
+    // Set by the admin API, kept in `ServiceChannels` so it survives restarts of the scheduler
+    paused_rx: watch::Receiver<bool>,
+    // Opt-in, read from SCHEDULER_PREEMPTION
+    preemption_enabled: bool,
+    // Queued jobs that already evicted a job, each job only preempts once
+    preempted_for: HashSet<i32>,
+    // Jobs put back in the queue by a preemption, they do not preempt for `PREEMPTION_COOLDOWN_SECS`
+    recently_preempted: HashMap<i32, NaiveDateTime>,
 }
 
 impl Scheduler {
     pub fn new(shared_resources: Arc<SharedResources>) -> Self {
+        let preemption_enabled = env::var("SCHEDULER_PREEMPTION")
+            .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes"))
+            .unwrap_or(false);
+
         Scheduler {
             shared_resources: Arc::clone(&shared_resources),
             core_event_rx: shared_resources
                 .get_service_channels()
                 .subscribe_to_core_event(),
+            pulse_rx: shared_resources
+                .get_pulse_subscriptions()
+                .subscribe_medium(),
+            paused_rx: shared_resources
+                .get_service_channels()
+                .subscribe_scheduler_paused(),
+            preemption_enabled,
+            preempted_for: HashSet::new(),
+            recently_preempted: HashMap::new(),
         }
     }
 
     pub async fn init(mut self) {
         loop {
-            match self.core_event_rx.recv().await {
-                Ok(CoreEvent::Startup) => println!("Scheduler: Startup event received."),
-                Ok(CoreEvent::Restart) => println!("Scheduler: Restart event received."),
-                Ok(CoreEvent::Shutdown) => {
-                    println!("Scheduler: Shutdown event received. Stopping...");
-                    break;
+            select! {
+                ev = self.core_event_rx.recv() => match ev {
+                    Ok(CoreEvent::Startup) => println!("Scheduler: Startup event received."),
+                    Ok(CoreEvent::Restart) => {
+                        // the supervisor builds a fresh instance
+                        println!("Scheduler: Restart event received. Stopping for re-init...");
+                        break;
+                    }
+                    Ok(CoreEvent::Shutdown) => {
+                        println!("Scheduler: Shutdown event received. Stopping...");
+                        break;
+                    }
+                    Err(_) => {
+                        println!("Scheduler: Channel closed. Exiting...");
+                        break;
+                    }
+                },
+                Ok(pulse) = self.pulse_rx.recv() => {
+                    if matches!(pulse, Pulse::Medium) {
+                        self.shared_resources.get_health().pulse(CoreModule::Scheduler);
+                        if let Err(e) = self.tick().await {
+                            eprintln!("Scheduler: scheduling failed: {e}");
+                        }
+                    }
                 }
-                Err(_) => {
-                    println!("Scheduler: Channel closed. Exiting...");
-                    break;
+            }
+        }
+    }
+
+    /// One scheduling round, admission then preemption, only the metrics while paused
+    async fn tick(&mut self) -> anyhow::Result<()> {
+        let mut conn = self.shared_resources.get_db_connection().await?;
+        let now = Utc::now().naive_utc();
+
+        let paused = *self.paused_rx.borrow();
+        if !paused {
+            self.admit_jobs(&mut conn, now).await?;
+            if self.preemption_enabled {
+                self.try_preempt(&mut conn, now).await?;
+            }
+        }
+        self.update_metrics(&mut conn).await?;
+        Ok(())
+    }
+
+    /// Refresh the job state gauges and the queue depth after a round
+    async fn update_metrics(&self, conn: &mut AsyncPgConnection) -> anyhow::Result<()> {
+        let counts = JobRepository::count_per_state(conn).await?;
+        let waiting: i64 = counts
+            .iter()
+            .filter(|(state, _)| matches!(state, JobStateEnum::Submitted | JobStateEnum::Queued))
+            .map(|(_, count)| count)
+            .sum();
+
+        let metrics = self.shared_resources.get_metrics();
+        metrics.set_jobs_by_state(&counts);
+        metrics.queue_depth.set(waiting);
+        Ok(())
+    }
+
+    /// Admit submitted jobs, highest aged priority first, within each users running quota
+    async fn admit_jobs(
+        &self,
+        conn: &mut AsyncPgConnection,
+        now: NaiveDateTime,
+    ) -> anyhow::Result<()> {
+        let mut submitted = JobRepository::list_by_state(conn, JobStateEnum::Submitted).await?;
+        if submitted.is_empty() {
+            return Ok(());
+        }
+        sort_by_priority(&mut submitted, now);
+
+        // free running slots per owner, filled lazily
+        let admitted: HashMap<i32, i64> = QuotaRepository::count_admitted_per_user(conn)
+            .await?
+            .into_iter()
+            .collect();
+        let mut slots: HashMap<i32, i64> = HashMap::new();
+
+        for job in submitted {
+            let free = match slots.get(&job.user_id) {
+                Some(free) => *free,
+                None => {
+                    let quota = QuotaRepository::find_effective(conn, job.user_id).await?;
+                    quota.free_running_slots(admitted.get(&job.user_id).copied().unwrap_or(0))
                 }
+            };
+            if free <= 0 {
+                slots.insert(job.user_id, 0);
+                continue;
+            }
+
+            // cancelled or deleted since it was listed
+            match JobRepository::mark_queued(conn, job.id).await {
+                Ok(_) => {}
+                Err(TransitionError::Illegal { .. } | TransitionError::NotFound(_)) => continue,
+                Err(e) => return Err(e.into()),
             }
+            slots.insert(job.user_id, free - 1);
+            let waited = (now - job.created_at).num_milliseconds().max(0) as f64 / 1000.0;
+            self.shared_resources
+                .get_metrics()
+                .scheduling_latency
+                .observe(waited);
+            Logger::log(
+                self.shared_resources.get_logger(),
+                LogLevelEnum::Info,
+                SystemModuleEnum::Scheduler,
+                LogActionEnum::JobSubmitted,
+                None,
+                Some(JobSubmittedPayload {
+                    job_id: job.id,
+                    from_module: SystemModuleEnum::Scheduler,
+                    to_module: SystemModuleEnum::Dispatcher,
+                }),
+                None,
+                None,
+            )
+            .await;
+        }
+        Ok(())
+    }
+
+    /// Evict one low priority running job when every worker that could run a long waiting,
+    /// much higher priority job is busy
+    async fn try_preempt(
+        &mut self,
+        conn: &mut AsyncPgConnection,
+        now: NaiveDateTime,
+    ) -> anyhow::Result<()> {
+        let queued = JobRepository::list_by_state(conn, JobStateEnum::Queued).await?;
+        // forget jobs that left the queue and requeues that are long enough ago
+        let queued_ids: HashSet<i32> = queued.iter().map(|j| j.id).collect();
+        self.preempted_for.retain(|id| queued_ids.contains(id));
+        self.recently_preempted
+            .retain(|_, at| (now - *at).num_seconds() < PREEMPTION_COOLDOWN_SECS);
+
+        // an idle worker of the owner claims the job without evicting anything
+        let idle_owners: HashSet<i32> =
+            WorkerStatusRepository::owners_by_status(conn, WorkerStatusEnum::Idle)
+                .await?
+                .into_iter()
+                .collect();
+        let candidates = preemption_candidates(
+            queued,
+            &self.preempted_for,
+            &self.recently_preempted,
+            &idle_owners,
+            now,
+        );
+        if candidates.is_empty() {
+            return Ok(());
+        }
+
+        let running = JobRepository::list_running_with_worker_owner(conn).await?;
+        let Some((candidate, victim)) = candidates
+            .iter()
+            .find_map(|c| pick_victim(c, &running, now).map(|v| (c, v)))
+        else {
+            return Ok(());
+        };
+
+        // the victim may have finished in the meantime, try again next round
+        match JobRepository::requeue_preempted(conn, victim.id).await {
+            Ok(_) => {}
+            Err(TransitionError::Illegal { .. } | TransitionError::NotFound(_)) => return Ok(()),
+            Err(e) => return Err(e.into()),
         }
+        self.preempted_for.insert(candidate.id);
+        self.recently_preempted.insert(victim.id, now);
+
+        let msg = format!(
+            "Job {} (effective priority {}) preempted for job {} (effective priority {})",
+            victim.id,
+            victim.effective_priority(now),
+            candidate.id,
+            candidate.effective_priority(now)
+        );
+        Logger::log(
+            self.shared_resources.get_logger(),
+            LogLevelEnum::Warning,
+            SystemModuleEnum::Scheduler,
+            LogActionEnum::JobPreempted,
+            None,
+            None,
+            None,
+            Some(msg),
+        )
+        .await;
+        Ok(())
+    }
+}
+
+/// Queued jobs that may evict a running job, highest aged priority first
+/// Skipped: jobs that already preempted one, jobs requeued by a preemption within
+/// `PREEMPTION_COOLDOWN_SECS`, jobs waiting less than `PREEMPTION_MIN_WAIT_SECS`
+/// and jobs whose owner has an idle worker
+fn preemption_candidates(
+    mut queued: Vec<Job>,
+    preempted_for: &HashSet<i32>,
+    recently_preempted: &HashMap<i32, NaiveDateTime>,
+    idle_owners: &HashSet<i32>,
+    now: NaiveDateTime,
+) -> Vec<Job> {
+    queued.retain(|j| {
+        !preempted_for.contains(&j.id)
+            && recently_preempted
+                .get(&j.id)
+                .is_none_or(|at| (now - *at).num_seconds() >= PREEMPTION_COOLDOWN_SECS)
+            && !idle_owners.contains(&j.user_id)
+            && (now - j.updated_at).num_seconds() >= PREEMPTION_MIN_WAIT_SECS
+    });
+    sort_by_priority(&mut queued, now);
+    queued
+}
+
+/// The running job `candidate` may evict: on a worker of the candidates owner, the lowest aged
+/// priority, most recently started on ties (least work lost), and `PREEMPTION_PRIORITY_GAP` below it
+/// # Arguments
+/// * `running` - running jobs with the owner of their worker
+fn pick_victim<'a>(
+    candidate: &Job,
+    running: &'a [(Job, i32)],
+    now: NaiveDateTime,
+) -> Option<&'a Job> {
+    let victim = running
+        .iter()
+        .filter(|(_, worker_owner)| *worker_owner == candidate.user_id)
+        .map(|(job, _)| job)
+        .min_by(|a, b| {
+            a.effective_priority(now)
+                .cmp(&b.effective_priority(now))
+                .then(b.updated_at.cmp(&a.updated_at))
+        })?;
+    (candidate.effective_priority(now) - victim.effective_priority(now) >= PREEMPTION_PRIORITY_GAP)
+        .then_some(victim)
+}
+
+/// Order jobs by aged priority, oldest first on ties
+fn sort_by_priority(jobs: &mut [Job], now: NaiveDateTime) {
+    jobs.sort_by(|a, b| {
+        b.effective_priority(now)
+            .cmp(&a.effective_priority(now))
+            .then(a.created_at.cmp(&b.created_at))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+    use common::enums::image_format::ImageFormatEnum;
+    use common::enums::output::OutputTypeEnum;
+    use common::enums::schedule::ScheduleTypeEnum;
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 5, 1)
+            .and_then(|d| d.and_hms_opt(12, 0, 0))
+            .unwrap_or_default()
+    }
+
+    /// A job of `user_id` created `age` and last moved `idle` seconds before `now()`
+    fn job(id: i32, user_id: i32, priority: i32, age: i64, idle: i64) -> Job {
+        Job {
+            id,
+            user_id,
+            job_name: format!("job-{id}"),
+            image_url: "https://example.com/image.tar".to_string(),
+            image_format: ImageFormatEnum::Tarball,
+            docker_flags: None,
+            output_type: OutputTypeEnum::Stdout,
+            output_paths: None,
+            schedule_type: ScheduleTypeEnum::Once,
+            cron_expression: None,
+            notes: None,
+            state: JobStateEnum::Queued,
+            error_message: None,
+            created_at: now() - Duration::seconds(age),
+            updated_at: now() - Duration::seconds(idle),
+            priority,
+        }
+    }
+
+    fn ids(jobs: &[Job]) -> Vec<i32> {
+        jobs.iter().map(|j| j.id).collect()
+    }
+
+    #[test]
+    fn victim_compared_on_aged_priority() {
+        // raw 10 against 0, but the running job waited 10 minutes before it started: aged 20
+        let candidate = job(1, 1, 10, 120, 120);
+        let running = vec![(job(2, 1, 0, 600, 0), 1)];
+        assert!(pick_victim(&candidate, &running, now()).is_none());
+
+        // the same priorities without the aging difference are far enough apart
+        let running = vec![(job(2, 1, 0, 120, 0), 1)];
+        assert_eq!(
+            pick_victim(&candidate, &running, now()).map(|j| j.id),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn victim_needs_the_priority_gap() {
+        let candidate = job(1, 1, 10, 0, 0);
+        let running = vec![(job(2, 1, 6, 0, 0), 1)];
+        assert!(pick_victim(&candidate, &running, now()).is_none());
+        let running = vec![(job(2, 1, 5, 0, 0), 1)];
+        assert_eq!(
+            pick_victim(&candidate, &running, now()).map(|j| j.id),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn victim_runs_on_a_worker_of_the_candidates_owner() {
+        let candidate = job(1, 1, 20, 0, 0);
+        // user 2's worker would never claim job 1, evicting from it gains nothing
+        let mut running = vec![(job(2, 1, 0, 0, 0), 2)];
+        assert!(pick_victim(&candidate, &running, now()).is_none());
+
+        running.push((job(3, 1, 5, 0, 0), 1));
+        assert_eq!(
+            pick_victim(&candidate, &running, now()).map(|j| j.id),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn victim_is_the_lowest_then_most_recently_started() {
+        let candidate = job(1, 1, 20, 0, 0);
+        let running = vec![
+            (job(2, 1, 3, 0, 300), 1),
+            (job(3, 1, 3, 0, 10), 1),
+            (job(4, 1, 8, 0, 5), 1),
+        ];
+        assert_eq!(
+            pick_victim(&candidate, &running, now()).map(|j| j.id),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn recently_preempted_jobs_are_no_candidates() {
+        let queued = || vec![job(1, 1, 10, 900, 120), job(2, 1, 5, 900, 120)];
+        let mut recent = HashMap::new();
+        recent.insert(1, now() - Duration::seconds(120));
+        let candidates =
+            preemption_candidates(queued(), &HashSet::new(), &recent, &HashSet::new(), now());
+        assert_eq!(ids(&candidates), vec![2]);
+
+        // past the cooldown it competes again
+        recent.insert(1, now() - Duration::seconds(PREEMPTION_COOLDOWN_SECS));
+        let candidates =
+            preemption_candidates(queued(), &HashSet::new(), &recent, &HashSet::new(), now());
+        assert_eq!(ids(&candidates), vec![1, 2]);
+    }
+
+    #[test]
+    fn candidates_skip_short_waits_earlier_preemptions_and_idle_owners() {
+        let queued = vec![
+            job(1, 1, 10, 0, 10),
+            job(2, 1, 10, 120, 120),
+            job(3, 2, 10, 120, 120),
+            job(4, 3, 50, 120, 120),
+        ];
+        let preempted_for = HashSet::from([2]);
+        let idle_owners = HashSet::from([3]);
+        let candidates =
+            preemption_candidates(queued, &preempted_for, &HashMap::new(), &idle_owners, now());
+        assert_eq!(ids(&candidates), vec![3]);
     }
 }
@@ -1,12 +1,69 @@
-use crate::core::shared_resources::SharedResources;
-use common::enums::system::CoreEvent;
+//! The task archive module, owns the retention of finished jobs
+//! Every slow pulse `Completed` and `Failed` jobs older than their retention are moved from `jobs`
+//! into `job_archives`, together with their results, metrics, assignments and log entries,
+//! one batch per pulse. Summaries stay listable through `/archive/jobs`, an archived job can be
+//! restored by its owner or an admin.
+//! With `JOB_ARCHIVE_PURGE_AFTER_DAYS` set, archive entries older than that are deleted for good.
+use std::env;
 use std::sync::Arc;
+
+use chrono::{Duration, Utc};
+use tokio::select;
 use tokio::sync::broadcast;
 
-#[allow(dead_code)]
+use crate::core::health::CoreModule;
+use crate::core::shared_resources::SharedResources;
+use crate::modules::Logger;
+use common::database::repositories::JobArchiveRepository;
+use common::enums::log::{LogActionEnum, LogLevelEnum};
+use common::enums::system::{CoreEvent, Pulse, SystemModuleEnum};
+
+/// Jobs moved per pulse, a backlog is worked off over the following pulses
+const ARCHIVE_BATCH: i64 = 500;
+/// Days a `Completed` job stays in `jobs` when `JOB_ARCHIVE_COMPLETED_AFTER_DAYS` is not set
+const DEFAULT_COMPLETED_AFTER_DAYS: i64 = 30;
+/// Days a `Failed` job stays in `jobs` when `JOB_ARCHIVE_FAILED_AFTER_DAYS` is not set
+const DEFAULT_FAILED_AFTER_DAYS: i64 = 90;
+
+/// How long finished jobs and their archive entries are kept
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivePolicy {
+    pub completed_after: Duration,
+    pub failed_after: Duration,
+    // None keeps archive entries forever
+    pub purge_after: Option<Duration>,
+}
+
+impl ArchivePolicy {
+    /// Read from `JOB_ARCHIVE_COMPLETED_AFTER_DAYS`, `JOB_ARCHIVE_FAILED_AFTER_DAYS`
+    /// and `JOB_ARCHIVE_PURGE_AFTER_DAYS`
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        // positive whole days, anything else falls back to the default
+        let days = |name: &str| {
+            var(name)
+                .and_then(|v| v.trim().parse::<i64>().ok())
+                .filter(|d| *d > 0)
+                .map(Duration::days)
+        };
+        ArchivePolicy {
+            completed_after: days("JOB_ARCHIVE_COMPLETED_AFTER_DAYS")
+                .unwrap_or(Duration::days(DEFAULT_COMPLETED_AFTER_DAYS)),
+            failed_after: days("JOB_ARCHIVE_FAILED_AFTER_DAYS")
+                .unwrap_or(Duration::days(DEFAULT_FAILED_AFTER_DAYS)),
+            purge_after: days("JOB_ARCHIVE_PURGE_AFTER_DAYS"),
+        }
+    }
+}
+
 pub struct TaskArchive {
     shared_resources: Arc<SharedResources>,
     core_event_rx: broadcast::Receiver<CoreEvent>,
+    pulse_rx: broadcast::Receiver<Pulse>,
+    policy: ArchivePolicy,
 }
 
 impl TaskArchive {
@@ -16,23 +73,110 @@ impl TaskArchive {
             core_event_rx: shared_resources
                 .get_service_channels()
                 .subscribe_to_core_event(),
+            pulse_rx: shared_resources.get_pulse_subscriptions().subscribe_slow(),
+            policy: ArchivePolicy::from_env(),
         }
     }
 
     pub async fn init(mut self) {
         loop {
-            match self.core_event_rx.recv().await {
-                Ok(CoreEvent::Startup) => println!("TaskArchive: Startup event received."),
-                Ok(CoreEvent::Restart) => println!("TaskArchive: Restart event received."),
-                Ok(CoreEvent::Shutdown) => {
-                    println!("TaskArchive: Shutdown event received. Stopping...");
-                    break;
-                }
-                Err(_) => {
-                    println!("TaskArchive: Channel closed. Exiting...");
-                    break;
+            select! {
+                ev = self.core_event_rx.recv() => match ev {
+                    Ok(CoreEvent::Startup) => println!("TaskArchive: Startup event received."),
+                    Ok(CoreEvent::Restart) => {
+                        // the supervisor builds a fresh instance
+                        println!("TaskArchive: Restart event received. Stopping for re-init...");
+                        break;
+                    }
+                    Ok(CoreEvent::Shutdown) => {
+                        println!("TaskArchive: Shutdown event received. Stopping...");
+                        break;
+                    }
+                    Err(_) => {
+                        println!("TaskArchive: Channel closed. Exiting...");
+                        break;
+                    }
+                },
+                Ok(pulse) = self.pulse_rx.recv() => {
+                    if matches!(pulse, Pulse::Slow) {
+                        self.shared_resources.get_health().pulse(CoreModule::TaskArchive);
+                        if let Err(e) = self.archive_round().await {
+                            eprintln!("TaskArchive: archive round failed: {e}");
+                        }
+                    }
                 }
             }
         }
     }
+
+    /// Archive one batch of finished jobs and purge expired archive entries
+    async fn archive_round(&self) -> anyhow::Result<()> {
+        let mut conn = self.shared_resources.get_db_connection().await?;
+        let now = Utc::now().naive_utc();
+
+        let archived = JobArchiveRepository::archive_finished(
+            &mut conn,
+            now - self.policy.completed_after,
+            now - self.policy.failed_after,
+            ARCHIVE_BATCH,
+        )
+        .await?;
+        if archived > 0 {
+            self.log(
+                LogLevelEnum::Info,
+                format!("{archived} finished jobs archived"),
+            )
+            .await;
+        }
+
+        if let Some(purge_after) = self.policy.purge_after {
+            let purged = JobArchiveRepository::purge(&mut conn, now - purge_after).await?;
+            if purged > 0 {
+                self.log(LogLevelEnum::Info, format!("{purged} archived jobs purged"))
+                    .await;
+            }
+        }
+        Ok(())
+    }
+
+    async fn log(&self, level: LogLevelEnum, msg: String) {
+        Logger::log(
+            self.shared_resources.get_logger(),
+            level,
+            SystemModuleEnum::TaskArchive,
+            LogActionEnum::Custom,
+            None,
+            None,
+            None,
+            Some(msg),
+        )
+        .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_falls_back_to_defaults() {
+        let policy = ArchivePolicy::from_vars(|name| match name {
+            "JOB_ARCHIVE_COMPLETED_AFTER_DAYS" => Some("7".into()),
+            "JOB_ARCHIVE_FAILED_AFTER_DAYS" => Some("-1".into()),
+            "JOB_ARCHIVE_PURGE_AFTER_DAYS" => Some("soon".into()),
+            _ => None,
+        });
+        assert_eq!(
+            policy,
+            ArchivePolicy {
+                completed_after: Duration::days(7),
+                failed_after: Duration::days(DEFAULT_FAILED_AFTER_DAYS),
+                purge_after: None,
+            }
+        );
+        assert_eq!(
+            ArchivePolicy::from_vars(|_| Some("365".into())).purge_after,
+            Some(Duration::days(365))
+        );
+    }
 }
@@ -0,0 +1,168 @@
+//! Audit trail, every POST, PATCH, PUT and DELETE is written to the append-only `audit_logs` table
+//!
+//! The fairing snapshots the addressed row before the handler runs and again after it,
+//! so each entry carries who called what, from where, and the row before and after the call.
+//! POST bodies (the created row) are taken from the response instead.
+//! Secrets are removed from every snapshot and `/login` never stores a body.
+use std::io::Cursor;
+
+use chrono::Utc;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Method;
+use rocket::serde::json::Value;
+use rocket::{Data, Request, Response};
+use rocket_db_pools::Connection;
+
+use common::database::models::audit::{audit_retention, audited_table, redact, NewAuditLog};
+use common::database::models::user::User;
+use common::database::repositories::AuditLogRepository;
+use common::rocket::DbConn;
+
+use crate::rocket_api::API_BASE;
+
+/// Characters of the bearer token stored with an entry
+const TOKEN_PREFIX_LEN: usize = 8;
+
+/// Audits mutating requests, see module docs
+pub struct AuditTrail;
+
+/// What is known about the request before the handler runs, kept in the request local cache
+#[derive(Default)]
+struct AuditContext {
+    user: Option<(i32, String)>,
+    resource: Option<String>,
+    resource_id: Option<i32>,
+    before: Option<Value>,
+}
+
+fn is_audited(method: Method) -> bool {
+    matches!(
+        method,
+        Method::Post | Method::Patch | Method::Put | Method::Delete
+    )
+}
+
+/// First path segment and the first numeric segment after it, `/jobs/12/failed` -> (jobs, 12)
+fn parse_target(req: &Request<'_>) -> (Option<String>, Option<i32>) {
+    let path = req.uri().path();
+    let path = path
+        .as_str()
+        .strip_prefix(API_BASE)
+        .unwrap_or(path.as_str());
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    let resource = segments.next().map(str::to_string);
+    let resource_id = segments.find_map(|s| s.parse::<i32>().ok());
+    (resource, resource_id)
+}
+
+fn token_prefix(req: &Request<'_>) -> Option<String> {
+    req.headers()
+        .get_one("Authorization")
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|token| token.trim().chars().take(TOKEN_PREFIX_LEN).collect())
+}
+
+#[rocket::async_trait]
+impl Fairing for AuditTrail {
+    fn info(&self) -> Info {
+        Info {
+            name: "Audit trail of mutating requests",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _data: &mut Data<'_>) {
+        if !is_audited(req.method()) {
+            return;
+        }
+        let (resource, resource_id) = parse_target(req);
+        let user = req
+            .guard::<User>()
+            .await
+            .succeeded()
+            .map(|u| (u.id, u.username));
+
+        let mut before = None;
+        if let (Some(resource), Some(id)) = (&resource, resource_id) {
+            if let Some(mut db) = req.guard::<Connection<DbConn>>().await.succeeded() {
+                before = AuditLogRepository::snapshot(&mut db, resource, id)
+                    .await
+                    .unwrap_or_else(|e| {
+                        eprintln!("[Audit] Snapshot of {resource}/{id} failed: {e}");
+                        None
+                    });
+            }
+        }
+
+        req.local_cache(|| AuditContext {
+            user,
+            resource,
+            resource_id,
+            before,
+        });
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if !is_audited(req.method()) {
+            return;
+        }
+        let ctx = req.local_cache(AuditContext::default);
+        let resource = ctx.resource.clone();
+        let mut resource_id = ctx.resource_id;
+        let audited = resource.as_deref().and_then(audited_table).is_some();
+        let Some(mut db) = req.guard::<Connection<DbConn>>().await.succeeded() else {
+            eprintln!(
+                "[Audit] No database connection, {} {} not audited",
+                req.method(),
+                req.uri()
+            );
+            return;
+        };
+
+        let after = match req.method() {
+            Method::Delete => None,
+            // created rows come back in the response, the body is put back untouched
+            Method::Post if audited => {
+                let body = res.body_mut().to_bytes().await.unwrap_or_default();
+                let created = serde_json::from_slice::<Value>(&body).ok().map(redact);
+                res.set_sized_body(body.len(), Cursor::new(body));
+                if resource_id.is_none() {
+                    resource_id = created
+                        .as_ref()
+                        .and_then(|v| v.get("id"))
+                        .and_then(Value::as_i64)
+                        .and_then(|id| i32::try_from(id).ok());
+                }
+                created
+            }
+            _ => match (&resource, resource_id) {
+                (Some(resource), Some(id)) => AuditLogRepository::snapshot(&mut db, resource, id)
+                    .await
+                    .unwrap_or(None),
+                _ => None,
+            },
+        };
+
+        let entry = NewAuditLog {
+            expires_at: (Utc::now() + audit_retention()).naive_utc(),
+            user_id: ctx.user.as_ref().map(|(id, _)| *id),
+            username: ctx.user.as_ref().map(|(_, name)| name.clone()),
+            token_prefix: token_prefix(req),
+            client_ip: req.client_ip().map(|ip| ip.to_string()),
+            method: req.method().as_str().to_string(),
+            route: req
+                .route()
+                .map(|r| r.uri.to_string())
+                .unwrap_or_else(|| "unmatched".to_string()),
+            path: req.uri().path().to_string(),
+            resource,
+            resource_id,
+            status: i32::from(res.status().code),
+            before: ctx.before.clone(),
+            after,
+        };
+        if let Err(e) = AuditLogRepository::create(&mut db, entry).await {
+            eprintln!("[Audit] Failed to write audit entry: {e}");
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! The one error type of the HTTP API and the envelope every failed request answers with
+//!
+//! Routes return `ApiResult<T>`, diesel errors convert with `?`: `NotFound` becomes a 404,
+//! unique violations a 409 `already_exists`, foreign key violations a 409 `conflict`.
+//! A job state change the transition table does not allow is a 409 `conflict` as well.
+//! Bodies failing `Validate` are a 400 `invalid_input` listing every invalid field in `details`.
+//! Unmatched routes, failed guards and undecodable bodies get the same envelope from `catch_all`.
+//!
+//! ```json
+//! { "error": "Job 7 not found", "code": "not_found" }
+//! ```
+use std::fmt;
+
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use rocket::http::{Header, Status};
+use rocket::response::{self, Responder};
+use rocket::serde::json::Json;
+use rocket::{catch, Request, Response};
+use serde::Serialize;
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use common::database::repositories::listing::ListQueryError;
+use common::database::repositories::TransitionError;
+use common::validation::ValidationErrors;
+
+/// Machine readable reason of an error, stable across releases, match on this and not the message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    InvalidInput,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    AlreadyExists,
+    Conflict,
+    QuotaExceeded,
+    Internal,
+}
+
+impl ErrorCode {
+    /// Code of a bare status, used by the catcher where no `ApiError` exists
+    fn for_status(status: Status) -> Self {
+        match status.code {
+            400 | 422 => ErrorCode::InvalidInput,
+            401 => ErrorCode::Unauthorized,
+            403 => ErrorCode::Forbidden,
+            404 => ErrorCode::NotFound,
+            409 => ErrorCode::Conflict,
+            429 => ErrorCode::QuotaExceeded,
+            _ => ErrorCode::Internal,
+        }
+    }
+}
+
+/// Error body returned next to every 4xx or 5xx status
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorBody {
+    // human readable, may change between releases
+    pub error: String,
+    pub code: ErrorCode,
+    // extra fields of some codes, e.g. the limit and retry hint of `quota_exceeded`
+    // or the invalid `fields` of `invalid_input`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub details: Option<Value>,
+}
+
+/// Everything a route can fail with
+#[derive(Debug)]
+pub enum ApiError {
+    BadRequest(String),
+    // body failed validation, one entry per field
+    Invalid(ValidationErrors),
+    Unauthorized(String),
+    Forbidden(String),
+    NotFound(String),
+    // unique constraint hit
+    AlreadyExists(String),
+    // referenced row missing or still referenced, illegal state transition
+    Conflict(String),
+    // answered with a `Retry-After` header
+    QuotaExceeded {
+        message: String,
+        details: Value,
+        retry_after_secs: i64,
+    },
+    // details are logged, the caller only sees a generic message
+    Internal(String),
+}
+
+pub type ApiResult<T> = Result<T, ApiError>;
+
+impl ApiError {
+    pub fn status(&self) -> Status {
+        match self {
+            ApiError::BadRequest(_) | ApiError::Invalid(_) => Status::BadRequest,
+            ApiError::Unauthorized(_) => Status::Unauthorized,
+            ApiError::Forbidden(_) => Status::Forbidden,
+            ApiError::NotFound(_) => Status::NotFound,
+            ApiError::AlreadyExists(_) | ApiError::Conflict(_) => Status::Conflict,
+            ApiError::QuotaExceeded { .. } => Status::TooManyRequests,
+            ApiError::Internal(_) => Status::InternalServerError,
+        }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ApiError::BadRequest(_) | ApiError::Invalid(_) => ErrorCode::InvalidInput,
+            ApiError::Unauthorized(_) => ErrorCode::Unauthorized,
+            ApiError::Forbidden(_) => ErrorCode::Forbidden,
+            ApiError::NotFound(_) => ErrorCode::NotFound,
+            ApiError::AlreadyExists(_) => ErrorCode::AlreadyExists,
+            ApiError::Conflict(_) => ErrorCode::Conflict,
+            ApiError::QuotaExceeded { .. } => ErrorCode::QuotaExceeded,
+            ApiError::Internal(_) => ErrorCode::Internal,
+        }
+    }
+
+    fn into_body(self) -> ErrorBody {
+        let code = self.code();
+        let (error, details) = match self {
+            ApiError::QuotaExceeded {
+                message, details, ..
+            } => (message, Some(details)),
+            ApiError::Invalid(errors) => (
+                format!("Invalid input: {}", errors),
+                serde_json::to_value(&errors).ok(),
+            ),
+            ApiError::Internal(_) => ("Internal server error".to_string(), None),
+            ApiError::BadRequest(m)
+            | ApiError::Unauthorized(m)
+            | ApiError::Forbidden(m)
+            | ApiError::NotFound(m)
+            | ApiError::AlreadyExists(m)
+            | ApiError::Conflict(m) => (m, None),
+        };
+        ErrorBody {
+            error,
+            code,
+            details,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::QuotaExceeded { message, .. } => write!(f, "{}", message),
+            ApiError::Invalid(errors) => write!(f, "Invalid input: {}", errors),
+            ApiError::BadRequest(m)
+            | ApiError::Unauthorized(m)
+            | ApiError::Forbidden(m)
+            | ApiError::NotFound(m)
+            | ApiError::AlreadyExists(m)
+            | ApiError::Conflict(m)
+            | ApiError::Internal(m) => write!(f, "{}", m),
+        }
+    }
+}
+
+impl From<DieselError> for ApiError {
+    fn from(e: DieselError) -> Self {
+        match e {
+            DieselError::NotFound => ApiError::NotFound("Record not found".to_string()),
+            DieselError::DatabaseError(kind, info) => match kind {
+                DatabaseErrorKind::UniqueViolation => {
+                    ApiError::AlreadyExists(info.message().to_string())
+                }
+                DatabaseErrorKind::ForeignKeyViolation => {
+                    ApiError::Conflict(info.message().to_string())
+                }
+                DatabaseErrorKind::CheckViolation | DatabaseErrorKind::NotNullViolation => {
+                    ApiError::BadRequest(info.message().to_string())
+                }
+                _ => ApiError::Internal(info.message().to_string()),
+            },
+            other => ApiError::Internal(other.to_string()),
+        }
+    }
+}
+
+impl From<ValidationErrors> for ApiError {
+    fn from(errors: ValidationErrors) -> Self {
+        ApiError::Invalid(errors)
+    }
+}
+
+impl From<TransitionError> for ApiError {
+    fn from(e: TransitionError) -> Self {
+        match e {
+            TransitionError::NotFound(_) => ApiError::NotFound(e.to_string()),
+            TransitionError::Illegal { .. } => ApiError::Conflict(e.to_string()),
+            TransitionError::Database(e) => ApiError::from(e),
+        }
+    }
+}
+
+impl From<ListQueryError> for ApiError {
+    fn from(e: ListQueryError) -> Self {
+        ApiError::BadRequest(e.to_string())
+    }
+}
+
+/// Turns a missing row into a 404 naming what was looked up
+pub trait OrNotFound<T> {
+    /// # Arguments
+    /// * `what` - e.g. `format!("Job {id}")`, answered as "Job 7 not found"
+    fn or_not_found(self, what: impl fmt::Display) -> ApiResult<T>;
+}
+
+impl<T> OrNotFound<T> for Option<T> {
+    fn or_not_found(self, what: impl fmt::Display) -> ApiResult<T> {
+        self.ok_or_else(|| ApiError::NotFound(format!("{} not found", what)))
+    }
+}
+
+impl<T> OrNotFound<T> for Result<T, DieselError> {
+    fn or_not_found(self, what: impl fmt::Display) -> ApiResult<T> {
+        match self {
+            Err(DieselError::NotFound) => Err(ApiError::NotFound(format!("{} not found", what))),
+            other => other.map_err(ApiError::from),
+        }
+    }
+}
+
+/// 204 if `rows` were deleted, 404 if there was nothing to delete
+pub fn deleted(rows: usize, what: impl fmt::Display) -> ApiResult<Status> {
+    if rows == 0 {
+        return Err(ApiError::NotFound(format!("{} not found", what)));
+    }
+    Ok(Status::NoContent)
+}
+
+impl<'r> Responder<'r, 'static> for ApiError {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let status = self.status();
+        let retry_after = match &self {
+            ApiError::QuotaExceeded {
+                retry_after_secs, ..
+            } => Some(*retry_after_secs),
+            _ => None,
+        };
+        if let ApiError::Internal(detail) = &self {
+            rocket::error!("{} {}: {}", req.method(), req.uri(), detail);
+        }
+
+        let mut res = Response::build_from(Json(self.into_body()).respond_to(req)?)
+            .status(status)
+            .finalize();
+        if let Some(secs) = retry_after {
+            res.set_header(Header::new("Retry-After", secs.to_string()));
+        }
+        Ok(res)
+    }
+}
+
+/// Envelope for errors raised outside the routes: unknown paths, failed guards, bad bodies
+#[catch(default)]
+pub fn catch_all(status: Status, _req: &Request<'_>) -> (Status, Json<ErrorBody>) {
+    let body = ErrorBody {
+        error: status.reason_lossy().to_string(),
+        code: ErrorCode::for_status(status),
+        details: None,
+    };
+    (status, Json(body))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use common::enums::job::JobStateEnum;
+
+    #[test]
+    fn diesel_errors_map_to_status_and_code() {
+        let missing = ApiError::from(DieselError::NotFound);
+        assert_eq!(missing.status(), Status::NotFound);
+        assert_eq!(missing.code(), ErrorCode::NotFound);
+
+        let rollback = ApiError::from(DieselError::RollbackTransaction);
+        assert_eq!(rollback.status(), Status::InternalServerError);
+        assert_eq!(rollback.into_body().error, "Internal server error");
+    }
+
+    #[test]
+    fn not_found_names_the_lookup() {
+        let err = Err::<(), _>(DieselError::NotFound)
+            .or_not_found("Job 7")
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Job 7 not found");
+        assert!(deleted(0, "Job 7").is_err());
+        assert_eq!(deleted(1, "Job 7").ok(), Some(Status::NoContent));
+    }
+
+    #[test]
+    fn invalid_fields_are_listed_in_details() {
+        let mut errors = ValidationErrors::default();
+        errors.add("cron_expression", "required when schedule_type is Cron");
+        let body = serde_json::to_value(ApiError::from(errors).into_body()).unwrap();
+        assert_eq!(body["code"], "invalid_input");
+        assert_eq!(body["details"]["fields"][0]["field"], "cron_expression");
+    }
+
+    #[test]
+    fn illegal_transitions_conflict() {
+        let err = ApiError::from(TransitionError::Illegal {
+            id: 7,
+            from: JobStateEnum::Completed,
+            to: JobStateEnum::Running,
+        });
+        assert_eq!(err.status(), Status::Conflict);
+        assert_eq!(
+            err.to_string(),
+            "Job 7 is Completed, it can't move to Running"
+        );
+        assert_eq!(
+            ApiError::from(TransitionError::NotFound(7)).status(),
+            Status::NotFound
+        );
+    }
+
+    #[test]
+    fn codes_serialize_in_snake_case() {
+        let body = ApiError::AlreadyExists("taken".into()).into_body();
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(json["code"], "already_exists");
+        assert!(json.get("details").is_none());
+    }
+}
@@ -1,13 +1,19 @@
 use rocket::fairing::{Fairing, Info, Kind};
-use rocket::http::Status;
-use rocket::response::status::Custom;
-use rocket::serde::json::{json, Value};
-use rocket::{Request, Response};
-use std::error::Error;
+use rocket::{Data, Request, Response};
+use std::sync::Arc;
+use std::time::Instant;
 
+use crate::core::Metrics;
+
+pub mod audit;
+pub mod error;
+pub mod openapi;
 pub mod rocket_server;
 pub mod routes;
 
+/// Every route of the API is mounted below this base, bump it for breaking changes
+pub const API_BASE: &str = "/api/v1";
+
 // Fairing for rocket
 
 #[rocket::options("/<_route_args..>")]
@@ -34,7 +40,39 @@ impl Fairing for Cors {
     }
 }
 
-pub fn server_error(e: Box<dyn Error>) -> Custom<Value> {
-    rocket::error!("{}", e);
-    Custom(Status::InternalServerError, json!("Error"))
+/// Fairing timing every request into the `http_request_duration_seconds` histogram,
+/// labelled by the route template (e.g. `/jobs/<id>`) to keep the label set small
+pub struct RequestTimer {
+    pub metrics: Arc<Metrics>,
+}
+
+/// Request start, kept in the request local cache between the two fairing callbacks
+struct RequestStart(Instant);
+
+#[rocket::async_trait]
+impl Fairing for RequestTimer {
+    fn info(&self) -> Info {
+        Info {
+            name: "Request latency metrics",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _data: &mut Data<'_>) {
+        req.local_cache(|| RequestStart(Instant::now()));
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        let start = req.local_cache(|| RequestStart(Instant::now()));
+        let route = req
+            .route()
+            .map(|r| r.uri.to_string())
+            .unwrap_or_else(|| "unmatched".to_string());
+        self.metrics.observe_request(
+            req.method().as_str(),
+            &route,
+            res.status().code,
+            start.0.elapsed(),
+        );
+    }
 }
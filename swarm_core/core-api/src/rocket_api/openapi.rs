@@ -0,0 +1,511 @@
+//! OpenAPI description of the HTTP API, assembled from the `#[utoipa::path]` annotations on the
+//! routes and the `ToSchema` derives on the models, so it can not drift from what is mounted.
+//! Every routes file exposes an `XxxApi` doc next to its `routes()`, `spec()` merges them.
+//! Paths are relative to the `API_BASE` server entry.
+//! Served as JSON on `GET /api/v1/openapi.json`, the `swarm-client` crate mirrors every operation in it.
+use std::sync::OnceLock;
+
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::OpenApi as OpenApiDoc;
+use utoipa::{Modify, OpenApi};
+
+use crate::rocket_api::routes::{
+    admin, archive, authorization, health, job, job_assignment, job_metric, job_result, log,
+    log_retention, openapi, prometheus, quota, template, user, webhook, worker, worker_status,
+};
+
+/// Name of the bearer scheme, routes without `security(())` require it
+pub const SESSION_SCHEME: &str = "session";
+
+/// Registers the session token as bearer scheme and requires it everywhere by default
+struct SessionAuth;
+
+impl Modify for SessionAuth {
+    fn modify(&self, doc: &mut OpenApiDoc) {
+        let components = doc.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            SESSION_SCHEME,
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .description(Some("Session token from `POST /login`"))
+                    .build(),
+            ),
+        );
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "SwarmSync Core API",
+        description = "Jobs, workers and their results, as served by core-api."
+    ),
+    servers((url = "/api/v1", description = "Current version of the API")),
+    security(("session" = [])),
+    modifiers(&SessionAuth),
+    tags(
+        (name = "auth", description = "Sessions"),
+        (name = "users", description = "Accounts"),
+        (name = "jobs", description = "Job submission, lookup and state transitions"),
+        (name = "assignments", description = "Jobs bound to workers"),
+        (name = "results", description = "Output of finished jobs"),
+        (name = "metrics", description = "Resource usage of finished jobs"),
+        (name = "archive", description = "Finished jobs moved out by the TaskArchive"),
+        (name = "templates", description = "Parameterised job blueprints"),
+        (name = "quotas", description = "Per user submission limits"),
+        (name = "workers", description = "Registered workers"),
+        (name = "worker-status", description = "Worker heartbeats and load"),
+        (name = "logs", description = "System log and its retention"),
+        (name = "webhooks", description = "Lifecycle event subscriptions"),
+        (name = "system", description = "Scraping, health and self description"),
+        (name = "admin", description = "Runtime control of core, for users in CORE_ADMIN_USERS"),
+    )
+)]
+struct ApiDoc;
+
+/// The full document, every routes file merged into `ApiDoc`
+pub fn spec() -> OpenApiDoc {
+    let mut doc = ApiDoc::openapi();
+    for part in [
+        authorization::AuthApi::openapi(),
+        user::UserApi::openapi(),
+        job::JobApi::openapi(),
+        job_assignment::JobAssignmentApi::openapi(),
+        job_result::JobResultApi::openapi(),
+        job_metric::JobMetricApi::openapi(),
+        archive::ArchiveApi::openapi(),
+        template::TemplateApi::openapi(),
+        quota::QuotaApi::openapi(),
+        worker::WorkerApi::openapi(),
+        worker_status::WorkerStatusApi::openapi(),
+        log::LogApi::openapi(),
+        log_retention::LogRetentionApi::openapi(),
+        webhook::WebhookApi::openapi(),
+        prometheus::PrometheusApi::openapi(),
+        openapi::OpenApiSpecApi::openapi(),
+        health::HealthApi::openapi(),
+        admin::AdminApi::openapi(),
+    ] {
+        doc.merge(part);
+    }
+    doc
+}
+
+/// `spec()` rendered once, the routes can not change while core runs
+pub fn spec_json() -> &'static str {
+    static SPEC: OnceLock<String> = OnceLock::new();
+    SPEC.get_or_init(|| spec().to_pretty_json().unwrap_or_else(|e| e.to_string()))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use serde_json::Value;
+    use utoipa::openapi::path::HttpMethod;
+
+    use super::*;
+    use crate::rocket_api::routes::all_routes;
+
+    fn method_name(method: &HttpMethod) -> &'static str {
+        match method {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Options => "OPTIONS",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Trace => "TRACE",
+        }
+    }
+
+    /// (method, path) of every documented operation, paths in OpenAPI form `/jobs/{id}`
+    fn documented() -> BTreeSet<(String, String)> {
+        let mut operations = BTreeSet::new();
+        for (path, item) in spec().paths.paths {
+            let methods = [
+                (HttpMethod::Get, &item.get),
+                (HttpMethod::Post, &item.post),
+                (HttpMethod::Put, &item.put),
+                (HttpMethod::Delete, &item.delete),
+                (HttpMethod::Head, &item.head),
+                (HttpMethod::Patch, &item.patch),
+            ];
+            for (method, operation) in methods {
+                if operation.is_some() {
+                    operations.insert((method_name(&method).to_string(), path.clone()));
+                }
+            }
+        }
+        operations
+    }
+
+    #[test]
+    fn every_mounted_route_is_documented() {
+        let mounted: BTreeSet<(String, String)> = all_routes()
+            .iter()
+            .map(|r| {
+                let path = r.uri.path().replace('<', "{").replace('>', "}");
+                (r.method.as_str().to_string(), path)
+            })
+            .collect();
+        assert_eq!(mounted, documented());
+    }
+
+    #[test]
+    fn server_is_the_mount_point() {
+        let servers = spec().servers.unwrap();
+        assert_eq!(servers[0].url, crate::rocket_api::API_BASE);
+        assert_eq!(swarm_client::API_BASE, crate::rocket_api::API_BASE);
+    }
+
+    #[test]
+    fn client_covers_every_operation() {
+        let client: BTreeSet<(String, String)> = swarm_client::ENDPOINTS
+            .iter()
+            .map(|e| (e.method.to_string(), e.path.to_string()))
+            .collect();
+        assert_eq!(client, documented());
+    }
+
+    #[test]
+    fn client_enums_match_schemas() {
+        use swarm_client::enums::*;
+
+        let doc = serde_json::to_value(spec()).unwrap();
+        let client: [(&str, &[&str]); 12] = [
+            ("ImageFormatEnum", ImageFormatEnum::VARIANTS),
+            ("JobStateEnum", JobStateEnum::VARIANTS),
+            ("OutputTypeEnum", OutputTypeEnum::VARIANTS),
+            ("ScheduleTypeEnum", ScheduleTypeEnum::VARIANTS),
+            ("TemplateParamKindEnum", TemplateParamKindEnum::VARIANTS),
+            ("WorkerStatusEnum", WorkerStatusEnum::VARIANTS),
+            ("OSEnum", OSEnum::VARIANTS),
+            ("LogLevelEnum", LogLevelEnum::VARIANTS),
+            ("LogActionEnum", LogActionEnum::VARIANTS),
+            ("SystemModuleEnum", SystemModuleEnum::VARIANTS),
+            ("WebhookEventEnum", WebhookEventEnum::VARIANTS),
+            ("DeliveryStatusEnum", DeliveryStatusEnum::VARIANTS),
+        ];
+        for (name, variants) in client {
+            let schema = &doc["components"]["schemas"][name]["enum"];
+            let documented: Vec<&str> = schema
+                .as_array()
+                .unwrap_or_else(|| panic!("{name} is not in the document"))
+                .iter()
+                .map(|v| v.as_str().unwrap())
+                .collect();
+            assert_eq!(documented, variants, "{name}");
+        }
+    }
+
+    /// A body with every property of `schema` set, `$ref`s and `allOf` parts resolved
+    fn sample(doc: &Value, schema: &Value) -> Value {
+        use serde_json::json;
+
+        if let Some(name) = schema["$ref"].as_str() {
+            let name = name.trim_start_matches("#/components/schemas/");
+            return sample(doc, &doc["components"]["schemas"][name]);
+        }
+        if let Some(parts) = schema["allOf"].as_array() {
+            let mut body = serde_json::Map::new();
+            for part in parts {
+                body.extend(sample(doc, part).as_object().unwrap().clone());
+            }
+            return Value::Object(body);
+        }
+        // nullable refs are `oneOf: [null, ref]`, a value is set so the field is not skipped
+        if let Some(variants) = schema["oneOf"].as_array() {
+            let variant = variants.iter().find(|v| v["type"] != "null").unwrap();
+            return sample(doc, variant);
+        }
+        if let Some(variants) = schema["enum"].as_array() {
+            return variants[0].clone();
+        }
+        let kind = match &schema["type"] {
+            Value::Array(kinds) => kinds.iter().find(|k| *k != "null").cloned(),
+            Value::Null => None,
+            kind => Some(kind.clone()),
+        };
+        match kind.as_ref().and_then(Value::as_str) {
+            // a timestamp is a valid string too, and some timestamps are documented as plain strings
+            Some("string") => json!("2025-01-01T00:00:00"),
+            Some("integer") => json!(1),
+            Some("number") => json!(1.0),
+            Some("boolean") => json!(true),
+            Some("array") => json!([sample(doc, &schema["items"])]),
+            _ => match schema["properties"].as_object() {
+                Some(properties) => properties
+                    .iter()
+                    .map(|(name, property)| (name.clone(), sample(doc, property)))
+                    .collect(),
+                // free form objects and untyped values, e.g. `dumps` or an archive's `data`
+                None => json!({}),
+            },
+        }
+    }
+
+    /// Decode `body` into a client model and encode it again, what comes back is the client's fields
+    fn roundtrip<T: serde::de::DeserializeOwned + serde::Serialize>(
+        body: Value,
+    ) -> serde_json::Result<Value> {
+        serde_json::to_value(serde_json::from_value::<T>(body)?)
+    }
+
+    #[test]
+    fn client_models_match_schemas() {
+        use swarm_client::models::*;
+
+        type Roundtrip = fn(Value) -> serde_json::Result<Value>;
+        let client: [(&str, Roundtrip); 65] = [
+            ("ArchivedJob", roundtrip::<ArchivedJob>),
+            (
+                "ClientConnectedPayload",
+                roundtrip::<ClientConnectedPayload>,
+            ),
+            ("ControlAck", roundtrip::<ControlAck>),
+            ("CoreState", roundtrip::<CoreState>),
+            ("CreateTemplateRequest", roundtrip::<CreateTemplateRequest>),
+            ("CreatedWebhook", roundtrip::<CreatedWebhook>),
+            ("Credentials", roundtrip::<Credentials>),
+            ("DependencyCheck", roundtrip::<DependencyCheck>),
+            ("ErrorBody", roundtrip::<ErrorBody>),
+            ("FailJobPayload", roundtrip::<FailJobPayload>),
+            ("FinishedAtPayload", roundtrip::<FinishedAtPayload>),
+            ("Health", roundtrip::<Health>),
+            ("Job", roundtrip::<Job>),
+            ("JobArchive", roundtrip::<JobArchive>),
+            ("JobAssignment", roundtrip::<JobAssignment>),
+            ("JobCompletedPayload", roundtrip::<JobCompletedPayload>),
+            ("JobMetric", roundtrip::<JobMetric>),
+            ("JobResult", roundtrip::<JobResult>),
+            ("JobSubmittedPayload", roundtrip::<JobSubmittedPayload>),
+            ("JobTemplate", roundtrip::<JobTemplate>),
+            ("LastErrorPayload", roundtrip::<LastErrorPayload>),
+            ("LastSeenPayload", roundtrip::<LastSeenPayload>),
+            ("Liveness", roundtrip::<Liveness>),
+            ("LoadAvgPayload", roundtrip::<LoadAvgPayload>),
+            ("LogEntry", roundtrip::<LogEntry>),
+            ("LogRetentionPolicy", roundtrip::<LogRetentionPolicy>),
+            ("ModuleHealth", roundtrip::<ModuleHealth>),
+            ("NewJob", roundtrip::<NewJob>),
+            ("NewJobAssignment", roundtrip::<NewJobAssignment>),
+            ("NewJobMetric", roundtrip::<NewJobMetric>),
+            ("NewJobResult", roundtrip::<NewJobResult>),
+            ("NewJobTemplate", roundtrip::<NewJobTemplate>),
+            ("NewLogRetentionPolicy", roundtrip::<NewLogRetentionPolicy>),
+            ("NewUserRequest", roundtrip::<NewUserRequest>),
+            ("NewWorker", roundtrip::<NewWorker>),
+            ("NewWorkerStatus", roundtrip::<NewWorkerStatus>),
+            ("Page_Job", roundtrip::<Page<Job>>),
+            ("Page_JobArchive", roundtrip::<Page<JobArchive>>),
+            ("Page_JobResult", roundtrip::<Page<JobResult>>),
+            ("Page_LogEntry", roundtrip::<Page<LogEntry>>),
+            ("Page_Worker", roundtrip::<Page<Worker>>),
+            ("QuotaStatus", roundtrip::<QuotaStatus>),
+            ("QuotaUsage", roundtrip::<QuotaUsage>),
+            ("Readiness", roundtrip::<Readiness>),
+            ("RunTemplateRequest", roundtrip::<RunTemplateRequest>),
+            ("SchedulerState", roundtrip::<SchedulerState>),
+            ("SessionToken", roundtrip::<SessionToken>),
+            ("StartedAtPayload", roundtrip::<StartedAtPayload>),
+            ("TemplateParameter", roundtrip::<TemplateParameter>),
+            ("TemplateParameterSpec", roundtrip::<TemplateParameterSpec>),
+            (
+                "TemplateWithParameters",
+                roundtrip::<TemplateWithParameters>,
+            ),
+            ("UpdateActiveJobId", roundtrip::<UpdateActiveJobId>),
+            ("UpdateFilesPayload", roundtrip::<UpdateFilesPayload>),
+            ("UpdateQuota", roundtrip::<UpdateQuota>),
+            ("UpdateStdoutPayload", roundtrip::<UpdateStdoutPayload>),
+            ("UpdateUserRequest", roundtrip::<UpdateUserRequest>),
+            ("UpdateWebhook", roundtrip::<UpdateWebhook>),
+            ("UptimePayload", roundtrip::<UptimePayload>),
+            ("UserQuota", roundtrip::<UserQuota>),
+            ("UserResponse", roundtrip::<UserResponse>),
+            ("Webhook", roundtrip::<Webhook>),
+            ("WebhookDelivery", roundtrip::<WebhookDelivery>),
+            ("WebhookRequest", roundtrip::<WebhookRequest>),
+            ("Worker", roundtrip::<Worker>),
+            ("WorkerStatus", roundtrip::<WorkerStatus>),
+        ];
+        // sent by core, ignored on the way in, the client leaves it out
+        let server_only = [("NewJob", "state")];
+
+        let doc = serde_json::to_value(spec()).unwrap();
+        let schemas = doc["components"]["schemas"].as_object().unwrap();
+        let mirrored: BTreeSet<&str> = client.iter().map(|(name, _)| *name).collect();
+        for (name, schema) in schemas {
+            // enums are compared by client_enums_match_schemas
+            if schema.get("enum").is_none() {
+                assert!(
+                    mirrored.contains(name.as_str()),
+                    "{name} has no client model"
+                );
+            }
+        }
+
+        for (name, roundtrip) in client {
+            let schema = schemas
+                .get(name)
+                .unwrap_or_else(|| panic!("{name} is not in the document"));
+            let body = sample(&doc, schema);
+            let documented: BTreeSet<&String> = body
+                .as_object()
+                .unwrap()
+                .keys()
+                .filter(|field| !server_only.contains(&(name, field.as_str())))
+                .collect();
+            let decoded = roundtrip(body.clone())
+                .unwrap_or_else(|e| panic!("{name}: the client can not decode {body}: {e}"));
+            let fields: BTreeSet<&String> = decoded.as_object().unwrap().keys().collect();
+            assert_eq!(fields, documented, "{name}");
+        }
+    }
+
+    /// Every combination of the values per field, each a full body built on `base`
+    fn combinations(base: &Value, values: &[(&str, Vec<Value>)]) -> Vec<Value> {
+        let mut bodies = vec![base.clone()];
+        for (field, options) in values {
+            bodies = bodies
+                .iter()
+                .flat_map(|body| {
+                    options.iter().map(move |option| {
+                        let mut body = body.clone();
+                        body[*field] = option.clone();
+                        body
+                    })
+                })
+                .collect();
+        }
+        bodies
+    }
+
+    #[test]
+    fn client_validation_matches_core() {
+        use common::validation::Validate;
+        use serde_json::json;
+
+        let long_name = "n".repeat(256);
+        let job = json!({
+            "user_id": 1,
+            "notes": null,
+        });
+        let job_values = [
+            (
+                "job_name",
+                vec![json!("nightly"), json!(" "), json!(long_name)],
+            ),
+            (
+                "image_url",
+                vec![
+                    json!("docker.io/library/alpine:3.20"),
+                    json!("Alpine"),
+                    json!("./alpine.tar"),
+                    json!("registry:5000/team/api@sha256:0123"),
+                    json!(""),
+                ],
+            ),
+            (
+                "image_format",
+                vec![json!("DockerRegistry"), json!("Tarball")],
+            ),
+            (
+                "docker_flags",
+                vec![json!(null), json!(["--rm"]), json!([""])],
+            ),
+            ("output_type", vec![json!("Stdout"), json!("Files")]),
+            (
+                "output_paths",
+                vec![json!(null), json!(["/out"]), json!([" "]), json!([])],
+            ),
+            ("schedule_type", vec![json!("Once"), json!("Cron")]),
+            (
+                "cron_expression",
+                vec![json!(null), json!("0 5 * * *"), json!("every day")],
+            ),
+        ];
+        let bodies = combinations(&job, &job_values);
+        assert_eq!(bodies.len(), 3 * 5 * 2 * 3 * 2 * 4 * 2 * 3);
+        for body in bodies {
+            let core =
+                serde_json::from_value::<common::database::models::job::NewJob>(body.clone())
+                    .unwrap()
+                    .validate()
+                    .err()
+                    .map(|e| e.to_string());
+            let client = serde_json::from_value::<swarm_client::models::NewJob>(body.clone())
+                .unwrap()
+                .validate()
+                .err()
+                .map(|e| e.to_string());
+            assert_eq!(core, client, "{body}");
+        }
+
+        let worker = json!({
+            "user_id": 1,
+            "ssh_key": "",
+            "os": "Linux",
+        });
+        let worker_values = [
+            ("label", vec![json!("w1"), json!(""), json!(long_name)]),
+            (
+                "ip_address",
+                vec![json!("10.0.0.7"), json!("::1"), json!("10.0.0"), json!("")],
+            ),
+            (
+                "hostname",
+                vec![json!("node-1.local"), json!("-node"), json!("")],
+            ),
+            ("ssh_user", vec![json!("root"), json!(" "), json!("ro ot")]),
+            ("docker_version", vec![json!("24.0.2"), json!("")]),
+            ("arch", vec![json!("x86_64"), json!("x86 64")]),
+            (
+                "tags",
+                vec![json!(null), json!(["gpu"]), json!(["gpu", " "])],
+            ),
+        ];
+        let bodies = combinations(&worker, &worker_values);
+        assert_eq!(bodies.len(), 3 * 4 * 3 * 3 * 2 * 2 * 3);
+        for body in bodies {
+            let core =
+                serde_json::from_value::<common::database::models::worker::NewWorker>(body.clone())
+                    .unwrap()
+                    .validate()
+                    .err()
+                    .map(|e| e.to_string());
+            let client = serde_json::from_value::<swarm_client::models::NewWorker>(body.clone())
+                .unwrap()
+                .validate()
+                .err()
+                .map(|e| e.to_string());
+            assert_eq!(core, client, "{body}");
+        }
+    }
+
+    #[test]
+    fn operation_ids_are_unique() {
+        let doc = spec();
+        let mut seen = BTreeSet::new();
+        for item in doc.paths.paths.values() {
+            for operation in [
+                &item.get,
+                &item.post,
+                &item.put,
+                &item.delete,
+                &item.head,
+                &item.patch,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                let id = operation.operation_id.clone().unwrap();
+                assert!(seen.insert(id.clone()), "duplicate operation id {id}");
+            }
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use crate::core::shared_resources::SharedResources;
+use crate::rocket_api::audit::AuditTrail;
+use crate::rocket_api::error::catch_all;
 use crate::rocket_api::routes;
-use crate::rocket_api::Cors;
+use crate::rocket_api::{Cors, RequestTimer, API_BASE};
 use common::enums::system::CoreEvent;
 use common::rocket::{CacheConn, DbConn};
-use rocket::{routes, Build, Rocket};
+use rocket::{catchers, routes, Build, Rocket};
 use rocket_db_pools::Database;
 use std::env;
 use std::sync::Arc;
@@ -17,6 +19,8 @@ pub async fn build_rocket(shared: Arc<SharedResources>) -> Rocket<Build> {
     let figment = rocket::Config::figment()
         .merge(("address", "0.0.0.0"))
         .merge(("port", 8000))
+        // ctrl-c is handled by core, Rocket stops on CoreEvent::Shutdown like every module
+        .merge(("shutdown.ctrlc", false))
         .merge(("databases.postgres.url", database_url.clone()))
         .merge(("databases.redis.url", redis_url.clone()));
 
@@ -27,11 +31,17 @@ pub async fn build_rocket(shared: Arc<SharedResources>) -> Rocket<Build> {
     // Attach all databases to the Rocket, aswell as cors
     rocket::custom(figment)
         .attach(Cors)
+        .attach(RequestTimer {
+            metrics: shared.get_metrics(),
+        })
         .attach(CacheConn::init())
         .attach(DbConn::init())
+        .attach(AuditTrail)
         .manage(shared)
-        .mount("/", routes::all_routes())
+        .mount(API_BASE, routes::all_routes())
+        // unversioned liveness probe of the consumer and worker
         .mount("/", routes![index])
+        .register("/", catchers![catch_all])
 }
 
 #[rocket::get("/")]
@@ -39,31 +49,35 @@ fn index() -> &'static str {
     "SwarmSync is live."
 }
 
-#[allow(clippy::unwrap_used)]
-pub async fn launch_rocket(shared: Arc<SharedResources>) {
+/// Serve the API until a shutdown, a failed ignition or launch is returned to the supervisor
+pub async fn launch_rocket(shared: Arc<SharedResources>) -> anyhow::Result<()> {
     let mut shutdown_rx = shared.get_service_channels().subscribe_to_core_event();
 
     let rocket = build_rocket(Arc::clone(&shared)).await;
 
-    let rocket = rocket.ignite().await.unwrap();
+    let rocket = rocket.ignite().await?;
     // Get Rocket's shutdown handle before launching
     let shutdown_handle = rocket.shutdown();
 
     // Spawn Rocket in a task
-    let rocket_handle = tokio::spawn(async move {
-        if let Err(e) = rocket.launch().await {
-            eprintln!("Rocket error: {:?}", e);
-        }
-    });
+    let mut rocket_handle = tokio::spawn(async move { rocket.launch().await.map(|_| ()) });
 
-    // Listen for CoreEvent::Shutdown
+    // Listen for CoreEvent::Shutdown, or Rocket stopping on its own
     loop {
-        match shutdown_rx.recv().await {
+        let event = tokio::select! {
+            launched = &mut rocket_handle => {
+                launched??;
+                anyhow::bail!("Rocket stopped without a shutdown");
+            }
+            event = shutdown_rx.recv() => event,
+        };
+        match event {
             Ok(CoreEvent::Shutdown) => {
                 println!("Rocket: Shutdown signal received.");
                 shutdown_handle.notify();
                 break;
             }
+            // routes hold no state of their own, the server keeps running
             Ok(CoreEvent::Restart) => {
                 println!("RocketServer: Restart event received.");
             }
@@ -79,5 +93,6 @@ pub async fn launch_rocket(shared: Arc<SharedResources>) {
         }
     }
 
-    let _ = rocket_handle.await;
+    rocket_handle.await??;
+    Ok(())
 }
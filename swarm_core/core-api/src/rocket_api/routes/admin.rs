@@ -0,0 +1,304 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use common::database::models::user::User;
+use common::enums::system::CoreEvent;
+use rocket::http::Status;
+use rocket::outcome::try_outcome;
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::response::status::Custom;
+use rocket::serde::json::{Json, Value};
+use rocket::{get, post, routes, Route, State};
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::core::health::{CoreModule, ModuleHealth};
+use crate::core::shared_resources::SharedResources;
+use crate::rocket_api::error::{ApiError, ApiResult, ErrorBody};
+use crate::services::ControlCommand;
+
+pub fn routes() -> Vec<Route> {
+    routes![
+        get_state,
+        shutdown,
+        restart,
+        restart_module,
+        pause_scheduler,
+        resume_scheduler
+    ]
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        get_state,
+        shutdown,
+        restart,
+        restart_module,
+        pause_scheduler,
+        resume_scheduler
+    ),
+    components(schemas(CoreState, ControlAck, SchedulerState))
+)]
+pub struct AdminApi;
+
+/* ===================== 🎛️ Admin API Overview =====================
+
+== 🔍 Inspection ==
+• GET     /admin/state                     → Module health and the state dumped by the modules → 200 OK (CoreState)
+  dumps: dispatcher (worker, status, last seen and pending heartbeat maps), logger (buffer depth)
+
+== 🔄 Lifecycle ==
+• POST    /admin/shutdown                  → Graceful shutdown of core, like ctrl-c     → 202 Accepted (ControlAck)
+• POST    /admin/restart                   → Restart every module (CoreEvent::Restart) → 202 Accepted (ControlAck)
+• POST    /admin/modules/:module/restart   → Restart one module, e.g. `task_archive`   → 202 Accepted (ControlAck)
+                                           → 400 Bad Request for rocket, it serves this API
+• POST    /admin/scheduler/pause           → Stop admitting and handing out jobs        → 200 OK (SchedulerState)
+• POST    /admin/scheduler/resume          → Admit and hand out jobs again              → 200 OK (SchedulerState)
+
+Only users listed in `CORE_ADMIN_USERS` (comma separated usernames) may call these,
+everyone else gets 403. Every command travels through `ServiceChannels`.
+Submissions are accepted while the scheduler is paused, claims answer null.
+
+======================================================================== */
+
+/// Modules answering `ControlCommand::DumpState`
+const DUMPING_MODULES: [CoreModule; 2] = [CoreModule::Dispatcher, CoreModule::Logger];
+/// How long `GET /admin/state` waits for the dumps, a busy module is left out after that
+const DUMP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Usernames in a comma separated list, blanks ignored
+fn admin_usernames(raw: &str) -> Vec<&str> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Whether `user` is listed in `CORE_ADMIN_USERS`, for routes open to the owner or an admin
+pub fn is_admin(user: &User) -> bool {
+    let admins = env::var("CORE_ADMIN_USERS").unwrap_or_default();
+    admin_usernames(&admins).contains(&user.username.as_str())
+}
+
+/// A logged in user listed in `CORE_ADMIN_USERS`, 401 without a session and 403 for everyone else
+pub struct Admin(pub User);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Admin {
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let user = try_outcome!(req.guard::<User>().await);
+        if is_admin(&user) {
+            Outcome::Success(Admin(user))
+        } else {
+            Outcome::Error((Status::Forbidden, ()))
+        }
+    }
+}
+
+/// Answer of `GET /admin/state`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CoreState {
+    pub scheduler_paused: bool,
+    pub modules: Vec<ModuleHealth>,
+    // module name -> its state, modules that did not answer within 2 seconds are missing
+    #[schema(value_type = Object)]
+    pub dumps: BTreeMap<CoreModule, Value>,
+}
+
+/// A lifecycle command was handed to core, it is carried out after the answer
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ControlAck {
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SchedulerState {
+    pub paused: bool,
+}
+
+fn accepted(message: String) -> Custom<Json<ControlAck>> {
+    Custom(Status::Accepted, Json(ControlAck { message }))
+}
+
+// ===== Inspection =====
+#[utoipa::path(
+    tag = "admin",
+    responses(
+        (status = 200, description = "Success", body = CoreState),
+        (status = 403, description = "Not an admin", body = ErrorBody),
+        (status = 500, description = "No module listens for control commands", body = ErrorBody),
+    ),
+)]
+#[get("/admin/state")]
+async fn get_state(
+    shared: &State<Arc<SharedResources>>,
+    _admin: Admin,
+) -> ApiResult<Json<CoreState>> {
+    let channels = shared.get_service_channels();
+    let (reply_tx, mut reply_rx) = mpsc::channel(DUMPING_MODULES.len());
+    if !channels.send_control(ControlCommand::DumpState(reply_tx)) {
+        return Err(ApiError::Internal(
+            "No module listens for control commands".to_string(),
+        ));
+    }
+
+    let mut dumps = BTreeMap::new();
+    let _ = timeout(DUMP_TIMEOUT, async {
+        while dumps.len() < DUMPING_MODULES.len() {
+            match reply_rx.recv().await {
+                Some(dump) => {
+                    dumps.insert(dump.module, dump.state);
+                }
+                None => break,
+            }
+        }
+    })
+    .await;
+
+    Ok(Json(CoreState {
+        scheduler_paused: channels.scheduler_paused(),
+        modules: shared.get_health().snapshot(Utc::now().naive_utc()),
+        dumps,
+    }))
+}
+
+// ===== Lifecycle =====
+#[utoipa::path(
+    tag = "admin",
+    responses(
+        (status = 202, description = "Shutdown started", body = ControlAck),
+        (status = 403, description = "Not an admin", body = ErrorBody),
+        (status = 500, description = "Core does not listen for a shutdown", body = ErrorBody),
+    ),
+)]
+#[post("/admin/shutdown")]
+async fn shutdown(
+    shared: &State<Arc<SharedResources>>,
+    admin: Admin,
+) -> ApiResult<Custom<Json<ControlAck>>> {
+    if !shared
+        .get_service_channels()
+        .send_control(ControlCommand::Shutdown)
+    {
+        return Err(ApiError::Internal(
+            "Core does not listen for a shutdown".to_string(),
+        ));
+    }
+    Ok(accepted(format!(
+        "Shutdown requested by {}",
+        admin.0.username
+    )))
+}
+
+#[utoipa::path(
+    tag = "admin",
+    responses(
+        (status = 202, description = "Every module restarts", body = ControlAck),
+        (status = 403, description = "Not an admin", body = ErrorBody),
+    ),
+)]
+#[post("/admin/restart")]
+async fn restart(
+    shared: &State<Arc<SharedResources>>,
+    admin: Admin,
+) -> ApiResult<Custom<Json<ControlAck>>> {
+    shared
+        .get_service_channels()
+        .send_event_to_all_services(CoreEvent::Restart)
+        .await;
+    Ok(accepted(format!(
+        "Restart of all modules requested by {}",
+        admin.0.username
+    )))
+}
+
+#[utoipa::path(
+    tag = "admin",
+    params(("module" = String, Path, description = "Module name, e.g. dispatcher or task_archive")),
+    responses(
+        (status = 202, description = "The module restarts", body = ControlAck),
+        (status = 400, description = "Rocket can not be restarted on its own", body = ErrorBody),
+        (status = 403, description = "Not an admin", body = ErrorBody),
+        (status = 404, description = "No such module", body = ErrorBody),
+    ),
+)]
+#[post("/admin/modules/<module>/restart")]
+async fn restart_module(
+    shared: &State<Arc<SharedResources>>,
+    module: &str,
+    admin: Admin,
+) -> ApiResult<Custom<Json<ControlAck>>> {
+    let module = CoreModule::from_name(module)
+        .ok_or_else(|| ApiError::NotFound(format!("Module {module}")))?;
+    if module == CoreModule::Rocket {
+        return Err(ApiError::BadRequest(
+            "Rocket serves this API and can not be restarted on its own, use /admin/restart"
+                .to_string(),
+        ));
+    }
+    if !shared
+        .get_service_channels()
+        .send_control(ControlCommand::RestartModule(module))
+    {
+        return Err(ApiError::Internal(format!(
+            "No supervisor listens for {}",
+            module.name()
+        )));
+    }
+    Ok(accepted(format!(
+        "Restart of {} requested by {}",
+        module.name(),
+        admin.0.username
+    )))
+}
+
+#[utoipa::path(
+    tag = "admin",
+    responses(
+        (status = 200, description = "Scheduler paused", body = SchedulerState),
+        (status = 403, description = "Not an admin", body = ErrorBody),
+    ),
+)]
+#[post("/admin/scheduler/pause")]
+async fn pause_scheduler(
+    shared: &State<Arc<SharedResources>>,
+    _admin: Admin,
+) -> Json<SchedulerState> {
+    shared.get_service_channels().set_scheduler_paused(true);
+    Json(SchedulerState { paused: true })
+}
+
+#[utoipa::path(
+    tag = "admin",
+    responses(
+        (status = 200, description = "Scheduler running", body = SchedulerState),
+        (status = 403, description = "Not an admin", body = ErrorBody),
+    ),
+)]
+#[post("/admin/scheduler/resume")]
+async fn resume_scheduler(
+    shared: &State<Arc<SharedResources>>,
+    _admin: Admin,
+) -> Json<SchedulerState> {
+    shared.get_service_channels().set_scheduler_paused(false);
+    Json(SchedulerState { paused: false })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn admin_list_ignores_blanks() {
+        assert_eq!(admin_usernames(" alice, bob ,,"), vec!["alice", "bob"]);
+        assert!(admin_usernames("").is_empty());
+    }
+}
@@ -0,0 +1,111 @@
+use crate::rocket_api::error::{ApiError, ApiResult, ErrorBody, OrNotFound};
+use crate::rocket_api::routes::admin::is_admin;
+use common::database::models::archive::{ArchivedJob, JobArchive};
+use common::database::models::job::Job;
+use common::database::models::user::User;
+use common::database::repositories::listing::{ListQuery, Page};
+use common::database::repositories::{JobArchiveRepository, ARCHIVE_LIST};
+use common::rocket::DbConn;
+use rocket::serde::json::Json;
+use rocket::{get, post, routes, Route};
+use rocket_db_pools::Connection;
+use utoipa::OpenApi;
+
+pub fn routes() -> Vec<Route> {
+    routes![list_archived_jobs, get_archived_job, restore_archived_job]
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(list_archived_jobs, get_archived_job, restore_archived_job))]
+pub struct ArchiveApi;
+
+/* ===================== 🗃️ Job Archive API Overview =====================
+
+== 📜 Listing ==
+• GET     /archive/jobs?filter&sort&limit&cursor → Filtered, sorted page of archived jobs → 200 OK (Page<JobArchive>)
+  filter fields: id, user, name, image, state, created, finished, archived
+
+== 🔍 Lookup ==
+• GET     /archive/jobs/:id            → Archived job with results, metrics, assignments, logs → 200 OK (ArchivedJob)
+
+== ♻️ Restore ==
+• POST    /archive/jobs/:id/restore    → Move the job back into /jobs (owner or admin) → 200 OK (Job)
+
+The TaskArchive moves finished jobs here once they are older than
+`JOB_ARCHIVE_COMPLETED_AFTER_DAYS` / `JOB_ARCHIVE_FAILED_AFTER_DAYS`.
+
+======================================================================== */
+
+// ===== Listing =====
+#[utoipa::path(
+    tag = "archive",
+    responses(
+        (status = 200, description = "Success", body = Page<JobArchive>),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[get("/archive/jobs?<filter>&<sort>&<limit>&<cursor>")]
+pub async fn list_archived_jobs(
+    mut db: Connection<DbConn>,
+    filter: Option<&str>,
+    sort: Option<&str>,
+    limit: Option<i64>,
+    cursor: Option<&str>,
+    _user: User,
+) -> ApiResult<Json<Page<JobArchive>>> {
+    let query = ListQuery::parse(&ARCHIVE_LIST, filter, sort, limit, cursor)?;
+    let rows = JobArchiveRepository::list(&mut db, &query).await?;
+    Ok(Json(query.paginate(rows)))
+}
+
+// ===== Lookup =====
+#[utoipa::path(
+    tag = "archive",
+    responses(
+        (status = 200, description = "Success", body = ArchivedJob),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+)]
+#[get("/archive/jobs/<id>")]
+pub async fn get_archived_job(
+    mut db: Connection<DbConn>,
+    id: i32,
+    _user: User,
+) -> ApiResult<Json<ArchivedJob>> {
+    JobArchiveRepository::find_by_id(&mut db, id)
+        .await
+        .or_not_found(format!("Archived job {}", id))
+        .map(Json)
+}
+
+// ===== Restore =====
+#[utoipa::path(
+    tag = "archive",
+    responses(
+        (status = 200, description = "Restored", body = Job),
+        (status = 403, description = "Caller is neither the owner nor an admin", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[post("/archive/jobs/<id>/restore")]
+pub async fn restore_archived_job(
+    mut db: Connection<DbConn>,
+    id: i32,
+    user: User,
+) -> ApiResult<Json<Job>> {
+    let archived = JobArchiveRepository::find_by_id(&mut db, id)
+        .await
+        .or_not_found(format!("Archived job {}", id))?;
+    if archived.summary.user_id != user.id && !is_admin(&user) {
+        return Err(ApiError::Forbidden(
+            "Only the owner or an admin can restore a job".to_string(),
+        ));
+    }
+
+    JobArchiveRepository::restore(&mut db, id)
+        .await
+        .or_not_found(format!("Archived job {}", id))
+        .map(Json)
+}
@@ -1,40 +1,59 @@
-use crate::rocket_api::server_error;
+use crate::rocket_api::error::{ApiError, ApiResult, ErrorBody, OrNotFound};
 use common::auth::{authorize_user, Credentials};
 use common::database::models::user::User;
 use common::database::repositories::UserRepository;
 use common::rocket::{CacheConn, DbConn};
-use rocket::http::Status;
-use rocket::response::status::Custom;
-use rocket::serde::json::{json, Json, Value};
-use rocket::{routes, Route};
+use rocket::serde::json::Json;
+use rocket::{post, routes, Route};
 use rocket_db_pools::deadpool_redis::redis::AsyncCommands;
 use rocket_db_pools::Connection;
+use serde::Serialize;
+use utoipa::{OpenApi, ToSchema};
 
 pub fn routes() -> Vec<Route> {
     routes![login]
 }
 
+#[derive(OpenApi)]
+#[openapi(paths(login))]
+pub struct AuthApi;
+
+/// Answer of a successful login
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SessionToken {
+    // send as `Authorization: Bearer <token>`, valid for 3 hours
+    pub token: String,
+}
+
 // Login route, used to get a session token and login a user account
-#[rocket::post("/login", format = "json", data = "<credentials>")]
+#[utoipa::path(
+    tag = "auth",
+    request_body = Credentials,
+    responses(
+        (status = 200, description = "Session token, send it as `Authorization: Bearer <token>`", body = SessionToken),
+        (status = 401, description = "Wrong credentials", body = ErrorBody),
+        (status = 404, description = "Unknown user", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+    security(()),
+)]
+#[post("/login", format = "json", data = "<credentials>")]
 pub async fn login(
     mut db: Connection<DbConn>,
     mut cache: Connection<CacheConn>,
     credentials: Json<Credentials>,
-) -> Result<Value, Custom<Value>> {
+) -> ApiResult<Json<SessionToken>> {
     let user: User = UserRepository::find_by_username(&mut db, &credentials.username)
-        .await
-        .map_err(|e| server_error(e.into()))?
-        .ok_or_else(|| Custom(Status::NotFound, json!("User not found")))?;
+        .await?
+        .or_not_found("User")?;
 
     let session_id = authorize_user(&user, credentials.into_inner())
-        .map_err(|_| Custom(Status::Unauthorized, json!("Wrong credentials")))?;
+        .map_err(|_| ApiError::Unauthorized("Wrong credentials".to_string()))?;
 
     cache
         .set_ex::<String, i32, ()>(format!("sessions/{}", session_id), user.id, 3 * 60 * 60)
         .await
-        .map_err(|e| server_error(e.into()))?;
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
 
-    Ok(json!({
-        "token": session_id,
-    }))
+    Ok(Json(SessionToken { token: session_id }))
 }
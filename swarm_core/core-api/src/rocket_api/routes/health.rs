@@ -0,0 +1,229 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use common::database::repositories::BackupRepository;
+use common::rocket::{CacheConn, DbConn};
+use diesel_async::RunQueryDsl;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{get, routes, Route, State};
+use rocket_db_pools::deadpool_redis::redis;
+use rocket_db_pools::Connection;
+use serde::Serialize;
+use tokio::time::timeout;
+use utoipa::{OpenApi, ToSchema};
+
+use crate::core::health::ModuleHealth;
+use crate::core::schema::expected_version;
+use crate::core::shared_resources::SharedResources;
+use crate::rocket_api::error::{ApiResult, ErrorBody};
+
+pub fn routes() -> Vec<Route> {
+    routes![health, live, ready]
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(health, live, ready),
+    components(schemas(Health, Liveness, Readiness, DependencyCheck))
+)]
+pub struct HealthApi;
+
+/* ===================== 🩺 Health API Overview =====================
+
+• GET     /health                      → Schema version of the database and of this build → 200 OK (Health)
+• GET     /health/live                 → Every module task running and not stalled → 200 OK / 503 (Liveness)
+• GET     /health/ready                → Live, and Postgres, Redis, UDP listener and schema usable → 200 OK / 503 (Readiness)
+
+Unauthenticated like /metrics. Core does not start on a schema it was not built for,
+`schema_up_to_date` only turns false when migrations ran against the database afterwards.
+Restart core when /health/live answers 503, e.g. after the dispatcher task died;
+take it out of rotation while /health/ready answers 503.
+The Postgres and Redis checks give up after 2 seconds each, a hanging dependency reads as down.
+
+======================================================================== */
+
+/// How long `GET /health/ready` waits for Postgres and for Redis before reporting them down
+const READY_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Answer of `GET /health`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Health {
+    // latest migration applied to the database, None on an empty database
+    pub schema_version: Option<String>,
+    // latest migration embedded in this build of core-api
+    pub expected_schema_version: String,
+    pub schema_up_to_date: bool,
+}
+
+/// Answer of `GET /health/live`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Liveness {
+    pub live: bool,
+    pub modules: Vec<ModuleHealth>,
+}
+
+/// One dependency checked by `GET /health/ready`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DependencyCheck {
+    // postgres, redis, udp_listener or schema
+    pub name: String,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+impl DependencyCheck {
+    fn new(name: &str, result: Result<(), String>) -> Self {
+        DependencyCheck {
+            name: name.to_string(),
+            ok: result.is_ok(),
+            error: result.err(),
+        }
+    }
+}
+
+/// Answer of `GET /health/ready`
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Readiness {
+    pub ready: bool,
+    pub dependencies: Vec<DependencyCheck>,
+    pub modules: Vec<ModuleHealth>,
+}
+
+fn status_of(ok: bool) -> Status {
+    if ok {
+        Status::Ok
+    } else {
+        Status::ServiceUnavailable
+    }
+}
+
+#[utoipa::path(
+    tag = "system",
+    responses(
+        (status = 200, description = "Success", body = Health),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+    security(()),
+)]
+#[get("/health")]
+pub async fn health(mut db: Connection<DbConn>) -> ApiResult<Json<Health>> {
+    let schema_version = BackupRepository::schema_version(&mut db).await?;
+    let expected_schema_version = expected_version();
+    Ok(Json(Health {
+        schema_up_to_date: schema_version.as_deref() == Some(expected_schema_version.as_str()),
+        schema_version,
+        expected_schema_version,
+    }))
+}
+
+#[utoipa::path(
+    tag = "system",
+    responses(
+        (status = 200, description = "Every module is running", body = Liveness),
+        (status = 503, description = "A module stopped, failed or stalled", body = Liveness),
+    ),
+    security(()),
+)]
+#[get("/health/live")]
+pub fn live(shared: &State<Arc<SharedResources>>) -> (Status, Json<Liveness>) {
+    let modules = shared.get_health().snapshot(Utc::now().naive_utc());
+    let live = modules.iter().all(ModuleHealth::is_alive);
+    (status_of(live), Json(Liveness { live, modules }))
+}
+
+#[utoipa::path(
+    tag = "system",
+    responses(
+        (status = 200, description = "Ready for traffic", body = Readiness),
+        (status = 503, description = "A dependency or module is down, or a check timed out", body = Readiness),
+    ),
+    security(()),
+)]
+#[get("/health/ready")]
+pub async fn ready(
+    shared: &State<Arc<SharedResources>>,
+    cache: &State<CacheConn>,
+) -> (Status, Json<Readiness>) {
+    let health = shared.get_health();
+
+    // the shared pool, what the modules write through
+    let postgres_check = async {
+        match shared.get_db_connection().await {
+            Ok(mut conn) => {
+                let ping = diesel::sql_query("SELECT 1")
+                    .execute(&mut conn)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string());
+                let schema = match BackupRepository::schema_version(&mut conn).await {
+                    Ok(Some(v)) if v == expected_version() => Ok(()),
+                    Ok(v) => Err(format!(
+                        "schema at {}, expected {}",
+                        v.unwrap_or_else(|| "none".to_string()),
+                        expected_version()
+                    )),
+                    Err(e) => Err(e.to_string()),
+                };
+                (ping, schema)
+            }
+            Err(e) => (
+                Err(format!("{e:#}")),
+                Err("Postgres unavailable".to_string()),
+            ),
+        }
+    };
+    let (postgres, schema) = match timeout(READY_CHECK_TIMEOUT, postgres_check).await {
+        Ok(checks) => checks,
+        Err(_) => (
+            Err(format!(
+                "no answer within {}s",
+                READY_CHECK_TIMEOUT.as_secs()
+            )),
+            Err("Postgres unavailable".to_string()),
+        ),
+    };
+
+    let redis_check = async {
+        match cache.get().await {
+            Ok(mut conn) => redis::cmd("PING")
+                .query_async::<_, String>(&mut *conn)
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        }
+    };
+    let redis = timeout(READY_CHECK_TIMEOUT, redis_check)
+        .await
+        .unwrap_or_else(|_| {
+            Err(format!(
+                "no answer within {}s",
+                READY_CHECK_TIMEOUT.as_secs()
+            ))
+        });
+
+    let udp = if health.udp_bound() {
+        Ok(())
+    } else {
+        Err("dispatcher has not bound its UDP socket".to_string())
+    };
+
+    let dependencies = vec![
+        DependencyCheck::new("postgres", postgres),
+        DependencyCheck::new("redis", redis),
+        DependencyCheck::new("udp_listener", udp),
+        DependencyCheck::new("schema", schema),
+    ];
+    let modules = health.snapshot(Utc::now().naive_utc());
+    let ready = dependencies.iter().all(|d| d.ok) && modules.iter().all(ModuleHealth::is_alive);
+    (
+        status_of(ready),
+        Json(Readiness {
+            ready,
+            dependencies,
+            modules,
+        }),
+    )
+}
@@ -1,19 +1,28 @@
 use common::database::models::job::{Job, NewJob};
 use common::database::models::user::User;
-use common::database::repositories::JobRepository;
+use common::database::repositories::listing::{ListQuery, Page};
+use common::database::repositories::{JobRepository, QuotaRepository, JOB_LIST};
+use common::enums::job::JobStateEnum;
 use common::rocket::DbConn;
+use common::validation::Validate;
 
 use rocket::http::Status;
 use rocket::response::status::Custom;
 use rocket::serde::json::{json, Json, Value};
 use rocket::{delete, get, patch, post, routes, Route};
 
+use crate::rocket_api::error::{deleted, ApiError, ApiResult, ErrorBody, OrNotFound};
 use chrono::{NaiveDateTime, Utc};
+use diesel_async::{AsyncConnection, AsyncPgConnection};
 use rocket_db_pools::Connection;
+use serde::Deserialize;
+use utoipa::{OpenApi, ToSchema};
 
 pub fn routes() -> Vec<Route> {
     routes![
         create_job,
+        list_jobs,
+        rerun_job,
         get_job,
         update_job,
         delete_job,
@@ -35,14 +44,52 @@ pub fn routes() -> Vec<Route> {
         list_jobs_with_no_assignment,
     ]
 }
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    create_job,
+    rerun_job,
+    list_jobs,
+    get_job,
+    update_job,
+    delete_job,
+    search_jobs,
+    find_job_by_name,
+    list_jobs_by_admin,
+    list_jobs_by_state,
+    get_recent_jobs,
+    get_failed_jobs,
+    mark_job_running,
+    mark_job_succeeded,
+    mark_job_failed,
+    list_scheduled_jobs,
+    list_due_cron_jobs,
+    list_ready_jobs,
+    get_admin_job_counts,
+    get_active_jobs_for_worker,
+    get_jobs_assigned_to_worker,
+    list_jobs_with_no_assignment
+))]
+pub struct JobApi;
 /*
 ===================== 🚀 Job API Overview =====================
 
 == 🛠️ CRUD ==
 • POST   /jobs                     -> Creates a new job (NewJob)           → 201 Created (Job)
+                                                                          → 400 Bad Request (invalid fields, see common::validation)
+                                                                          → 429 Too Many Requests (quota hit, Retry-After)
+  user_id must be the caller, the job always starts Submitted
 • GET    /jobs/:id                 -> Fetch job by ID                      → 200 OK (Job)
 • PATCH  /jobs/:id                 -> Update job by ID (Job)               → 200 OK (Job)
+                                                                          → 409 Conflict (state change not in the transition table)
 • DELETE /jobs/:id                -> Delete job by ID                     → 204 No Content
+• POST   /jobs/:id/rerun          -> Clone job into a new submission       → 201 Created (Job)
+
+== 📜 Listing ==
+• GET    /jobs?filter&sort&limit&cursor    -> Filtered, sorted page of jobs      → 200 OK (Page<Job>)
+                                                                          → 400 Bad Request (bad filter, sort or cursor)
+  filter fields: id, state, user, worker, name, priority, created, updated
+  e.g. ?filter=state:Queued|Running,created>=2025-05-01,name~backup&sort=-priority
 
 == 🔍 Lookup & Search ==
 • GET    /jobs/search?user_id&query         -> Fuzzy match jobs by name         → 200 OK (Vec<Job>)
@@ -53,9 +100,12 @@ pub fn routes() -> Vec<Route> {
 • GET    /jobs/failed?limit                 -> Recently failed jobs             → 200 OK (Vec<Job>)
 
 == 🔄 State Transitions ==
-• PATCH  /jobs/:id/running        -> Mark job as running                   → 200 OK (Job)
-• PATCH  /jobs/:id/succeeded      -> Mark job as succeeded                 → 200 OK (Job)
+• PATCH  /jobs/:id/running        -> Mark job as running (from Queued)     → 200 OK (Job)
+• PATCH  /jobs/:id/succeeded      -> Mark job as succeeded (from Running)  → 200 OK (Job)
 • PATCH  /jobs/:id/failed         -> Mark job as failed (with message)     → 200 OK (Job)
+                                                                          → 409 Conflict (illegal transition, e.g. Completed → Running)
+Transitions follow JobStateEnum::next_states and are checked in the UPDATE itself (WHERE state = expected).
+Creation and every transition publish a lifecycle event, picked up by the Notifier for webhooks.
 
 == ⏱️ Scheduling & Readiness ==
 • GET    /jobs/scheduled                   -> All jobs with a schedule          → 200 OK (Vec<Job>)
@@ -74,102 +124,217 @@ pub fn routes() -> Vec<Route> {
 */
 
 // ======= CRUD =======
+/// Insert a job after validating it and checking the owners quota, shared by every route that
+/// creates jobs. The job always starts `Submitted`, whatever the body said
+/// The check and the insert run in one transaction holding the owners submission lock,
+/// so concurrent submissions are counted one after the other
+/// `JobRepository::create` records `JobSubmitted` with the job
+pub async fn submit_with_quota(c: &mut AsyncPgConnection, mut new_job: NewJob) -> ApiResult<Job> {
+    new_job.validate()?;
+    new_job.state = JobStateEnum::Submitted;
+
+    c.transaction::<_, ApiError, _>(|tx| {
+        Box::pin(async move {
+            QuotaRepository::lock_submissions(tx, new_job.user_id).await?;
+            // Reject before insert if the owner is over quota
+            let quota = QuotaRepository::find_effective(tx, new_job.user_id).await?;
+            let usage = QuotaRepository::usage_for_user(tx, new_job.user_id).await?;
+            if let Err(exceeded) = quota.check_submission(&usage, Utc::now().naive_utc()) {
+                return Err(ApiError::QuotaExceeded {
+                    message: exceeded.to_string(),
+                    details: json!({
+                        "quota": exceeded.quota,
+                        "limit": exceeded.limit,
+                        "current": exceeded.current,
+                        "retry_after_secs": exceeded.retry_after_secs,
+                    }),
+                    retry_after_secs: exceeded.retry_after_secs,
+                });
+            }
+
+            Ok(JobRepository::create(tx, new_job).await?)
+        })
+    })
+    .await
+}
+
+#[utoipa::path(
+    tag = "jobs",
+    request_body = NewJob,
+    responses(
+        (status = 201, description = "Created", body = Job),
+        (status = 400, description = "Invalid fields, listed in `details.fields`", body = ErrorBody),
+        (status = 403, description = "`user_id` is not the caller", body = ErrorBody),
+        (status = 429, description = "Quota exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[post("/jobs", format = "json", data = "<new_job>")]
 pub async fn create_job(
     mut db: Connection<DbConn>,
     new_job: Json<NewJob>,
-    _user: User,
-) -> Result<Custom<Json<Job>>, Custom<Json<serde_json::Value>>> {
-    JobRepository::create(&mut db, new_job.into_inner())
-        .await
-        .map(|job| Custom(Status::Created, Json(job)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+    user: User,
+) -> ApiResult<Custom<Json<Job>>> {
+    if new_job.user_id != user.id {
+        return Err(ApiError::Forbidden(
+            "Jobs can only be submitted for the caller".to_string(),
+        ));
+    }
+    let job = submit_with_quota(&mut db, new_job.into_inner()).await?;
+    Ok(Custom(Status::Created, Json(job)))
 }
 
-#[get("/jobs/<id>")]
-pub async fn get_job(
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 201, description = "Created", body = Job),
+        (status = 403, description = "Caller does not own the resource", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 429, description = "Quota exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[post("/jobs/<id>/rerun")]
+pub async fn rerun_job(
     mut db: Connection<DbConn>,
     id: i32,
+    user: User,
+) -> ApiResult<Custom<Json<Job>>> {
+    let job = JobRepository::find_by_id(&mut db, id)
+        .await
+        .or_not_found(format!("Job {}", id))?;
+    if job.user_id != user.id {
+        return Err(ApiError::Forbidden(
+            "Only the owner can re-run a job".to_string(),
+        ));
+    }
+
+    let job = submit_with_quota(&mut db, NewJob::from(job)).await?;
+    Ok(Custom(Status::Created, Json(job)))
+}
+
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Page<Job>),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[get("/jobs?<filter>&<sort>&<limit>&<cursor>")]
+pub async fn list_jobs(
+    mut db: Connection<DbConn>,
+    filter: Option<&str>,
+    sort: Option<&str>,
+    limit: Option<i64>,
+    cursor: Option<&str>,
     _user: User,
-) -> Result<Json<Job>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Json<Page<Job>>> {
+    let query = ListQuery::parse(&JOB_LIST, filter, sort, limit, cursor)?;
+    let rows = JobRepository::list(&mut db, &query).await?;
+    Ok(Json(query.paginate(rows)))
+}
+
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Job),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+)]
+#[get("/jobs/<id>")]
+pub async fn get_job(mut db: Connection<DbConn>, id: i32, _user: User) -> ApiResult<Json<Job>> {
     JobRepository::find_by_id(&mut db, id)
         .await
+        .or_not_found(format!("Job {}", id))
         .map(Json)
-        .map_err(|e| Custom(Status::NotFound, Json(json!({ "error": e.to_string() }))))
 }
 
+#[utoipa::path(
+    tag = "jobs",
+    request_body = Job,
+    responses(
+        (status = 200, description = "Success", body = Job),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 409, description = "Changed state is not a legal transition", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[patch("/jobs/<id>", format = "json", data = "<job>")]
 pub async fn update_job(
     mut db: Connection<DbConn>,
     id: i32,
     job: Json<Job>,
     _user: User,
-) -> Result<Json<Job>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Json<Job>> {
     JobRepository::update(&mut db, id, job.into_inner())
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[delete("/jobs/<id>")]
-pub async fn delete_job(
-    mut db: Connection<DbConn>,
-    id: i32,
-    _user: User,
-) -> Result<Status, Custom<Json<serde_json::Value>>> {
-    JobRepository::delete(&mut db, id)
-        .await
-        .map(|_| Status::NoContent)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+pub async fn delete_job(mut db: Connection<DbConn>, id: i32, _user: User) -> ApiResult<Status> {
+    let rows = JobRepository::delete(&mut db, id).await?;
+    deleted(rows, format!("Job {}", id))
 }
 
 // ======== Lookup & Search ========
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Vec<Job>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/jobs/search?<user_id>&<query>")]
 pub async fn search_jobs(
     mut db: Connection<DbConn>,
     user_id: i32,
     query: &str,
     _user: User,
-) -> Result<Json<Vec<Job>>, Custom<Value>> {
+) -> ApiResult<Json<Vec<Job>>> {
     JobRepository::search_by_job_name(&mut db, user_id, query)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Vec<Job>),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+)]
 #[get("/jobs/name/<user_id>?<name>")]
 pub async fn find_job_by_name(
     mut db: Connection<DbConn>,
     user_id: i32,
     name: &str,
     _user: User,
-) -> Result<Json<Vec<Job>>, Custom<Value>> {
+) -> ApiResult<Json<Vec<Job>>> {
     JobRepository::search_by_job_name(&mut db, user_id, name)
         .await
         .map(Json)
-        .map_err(|e| Custom(Status::NotFound, json!({ "error": e.to_string() })))
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Vec<Job>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/jobs/by_admin?<user_id>&<limit>&<offset>")]
 pub async fn list_jobs_by_admin(
     mut db: Connection<DbConn>,
@@ -177,229 +342,276 @@ pub async fn list_jobs_by_admin(
     limit: Option<i64>,
     offset: Option<i64>,
     _user: User,
-) -> Result<Json<Vec<Job>>, Custom<Value>> {
+) -> ApiResult<Json<Vec<Job>>> {
     let limit = limit.unwrap_or(50);
     let offset = offset.unwrap_or(0);
 
     JobRepository::list_by_admin(&mut db, user_id, limit, offset)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Vec<Job>),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+    ),
+)]
 #[get("/jobs/state/<state>")]
 pub async fn list_jobs_by_state(
     mut db: Connection<DbConn>,
     state: String,
     _user: User,
-) -> Result<Json<Vec<Job>>, Custom<Value>> {
+) -> ApiResult<Json<Vec<Job>>> {
     let enum_state = state
         .parse()
-        .map_err(|_| Custom(Status::BadRequest, json!({ "error": "Invalid job state" })))?;
+        .map_err(|_| ApiError::BadRequest("Invalid job state".to_string()))?;
     JobRepository::list_by_state(&mut db, enum_state)
         .await
         .map(Json)
-        .map_err(|e| Custom(Status::BadRequest, json!({ "error": e.to_string() })))
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Vec<Job>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/jobs/recent?<limit>")]
 pub async fn get_recent_jobs(
     mut db: Connection<DbConn>,
     limit: Option<i64>,
     _user: User,
-) -> Result<Json<Vec<Job>>, Custom<Value>> {
+) -> ApiResult<Json<Vec<Job>>> {
     let limit = limit.unwrap_or(10);
     JobRepository::get_recent_jobs(&mut db, limit)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Vec<Job>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/jobs/failed?<limit>")]
 pub async fn get_failed_jobs(
     mut db: Connection<DbConn>,
     limit: Option<i64>,
     _user: User,
-) -> Result<Json<Vec<Job>>, Custom<Value>> {
+) -> ApiResult<Json<Vec<Job>>> {
     let limit = limit.unwrap_or(10);
     JobRepository::get_failed_jobs(&mut db, limit)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
 // ====== State Transition =======
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Job),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 409, description = "Illegal state transition", body = ErrorBody),
+    ),
+)]
 #[patch("/jobs/<id>/running")]
 pub async fn mark_job_running(
     mut db: Connection<DbConn>,
     id: i32,
     _user: User,
-) -> Result<Json<Job>, Custom<Value>> {
-    JobRepository::mark_running(&mut db, id)
-        .await
-        .map(Json)
-        .map_err(|e| Custom(Status::Conflict, json!({ "error": e.to_string() })))
+) -> ApiResult<Json<Job>> {
+    let job = JobRepository::mark_running(&mut db, id).await?;
+    Ok(Json(job))
 }
 
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Job),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 409, description = "Illegal state transition", body = ErrorBody),
+    ),
+)]
 #[patch("/jobs/<id>/succeeded")]
 pub async fn mark_job_succeeded(
     mut db: Connection<DbConn>,
     id: i32,
     _user: User,
-) -> Result<Json<Job>, Custom<Value>> {
-    JobRepository::mark_succeeded(&mut db, id)
-        .await
-        .map(Json)
-        .map_err(|e| Custom(Status::Conflict, json!({ "error": e.to_string() })))
+) -> ApiResult<Json<Job>> {
+    let job = JobRepository::mark_succeeded(&mut db, id).await?;
+    Ok(Json(job))
+}
+
+/// Body of `PATCH /jobs/<id>/failed`, a missing message is stored as "Unspecified error"
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FailJobPayload {
+    pub message: Option<String>,
 }
 
+#[utoipa::path(
+    tag = "jobs",
+    request_body = FailJobPayload,
+    responses(
+        (status = 200, description = "Success", body = Job),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 409, description = "Illegal state transition", body = ErrorBody),
+    ),
+)]
 #[patch("/jobs/<id>/failed", format = "json", data = "<body>")]
 pub async fn mark_job_failed(
     mut db: Connection<DbConn>,
     id: i32,
     body: Json<Value>,
     _user: User,
-) -> Result<Json<Job>, Custom<Value>> {
+) -> ApiResult<Json<Job>> {
     let message = body
         .get("message")
         .and_then(|v| v.as_str())
         .unwrap_or("Unspecified error")
         .to_string();
 
-    JobRepository::mark_failed(&mut db, id, &message)
-        .await
-        .map(Json)
-        .map_err(|e| Custom(Status::Conflict, json!({ "error": e.to_string() })))
+    let job = JobRepository::mark_failed(&mut db, id, &message).await?;
+    Ok(Json(job))
 }
 
 // =========0 Schedulihng and readyness ==========
 
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Vec<Job>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/jobs/scheduled")]
 pub async fn list_scheduled_jobs(
     mut db: Connection<DbConn>,
     _user: User,
-) -> Result<Json<Vec<Job>>, Custom<Value>> {
+) -> ApiResult<Json<Vec<Job>>> {
     JobRepository::list_scheduled_jobs(&mut db)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Vec<Job>),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+    ),
+)]
 #[get("/jobs/cron_due?<date>&<time>")]
 pub async fn list_due_cron_jobs(
     mut db: Connection<DbConn>,
     date: String,
     time: String,
     _user: User,
-) -> Result<Json<Vec<Job>>, Custom<Value>> {
+) -> ApiResult<Json<Vec<Job>>> {
     let current_time: NaiveDateTime =
         NaiveDateTime::parse_from_str(&date, &time).unwrap_or(Utc::now().naive_utc());
     JobRepository::list_due_cron_jobs(&mut db, current_time)
         .await
         .map(Json)
-        .map_err(|e| Custom(Status::BadRequest, json!({ "error": e.to_string() })))
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Vec<Job>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/jobs/one-time-ready")]
-pub async fn list_ready_jobs(
-    mut db: Connection<DbConn>,
-    _user: User,
-) -> Result<Json<Vec<Job>>, Custom<Value>> {
+pub async fn list_ready_jobs(mut db: Connection<DbConn>, _user: User) -> ApiResult<Json<Vec<Job>>> {
     JobRepository::list_one_time_jobs_ready(&mut db)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
 // ======= Aggregation And Stats =========
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Vec<(i32, i64)>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/jobs/stats/admins")]
 pub async fn get_admin_job_counts(
     mut db: Connection<DbConn>,
     _user: User,
-) -> Result<Json<Vec<(i32, i64)>>, Custom<Value>> {
+) -> ApiResult<Json<Vec<(i32, i64)>>> {
     JobRepository::get_job_counts_per_admin(&mut db)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
 // ====== Assignment-related ===========
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Vec<Job>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/jobs/active/<worker_id>")]
 pub async fn get_active_jobs_for_worker(
     mut db: Connection<DbConn>,
     worker_id: i32,
     _user: User,
-) -> Result<Json<Vec<Job>>, Custom<Value>> {
+) -> ApiResult<Json<Vec<Job>>> {
     JobRepository::get_active_jobs_for_worker(&mut db, worker_id)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Vec<Job>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/jobs/assigned/<worker_id>")]
 pub async fn get_jobs_assigned_to_worker(
     mut db: Connection<DbConn>,
     worker_id: i32,
     _user: User,
-) -> Result<Json<Vec<Job>>, Custom<Value>> {
+) -> ApiResult<Json<Vec<Job>>> {
     JobRepository::find_jobs_assigned_to_worker(&mut db, worker_id)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "jobs",
+    responses(
+        (status = 200, description = "Success", body = Vec<Job>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/jobs/unassigned")]
 pub async fn list_jobs_with_no_assignment(
     mut db: Connection<DbConn>,
     _user: User,
-) -> Result<Json<Vec<Job>>, Custom<Value>> {
+) -> ApiResult<Json<Vec<Job>>> {
     JobRepository::list_jobs_with_no_assignment(&mut db)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
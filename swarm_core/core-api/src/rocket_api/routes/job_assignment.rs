@@ -1,17 +1,24 @@
+use std::sync::Arc;
+
+use crate::core::shared_resources::SharedResources;
+use crate::rocket_api::error::{deleted, ApiError, ApiResult, ErrorBody, OrNotFound};
 use crate::utils::parsing;
 use common::database::models::job::{JobAssignment, NewJobAssignment};
 use common::database::models::user::User;
-use common::database::repositories::JobAssignmentRepository;
+use common::database::repositories::{JobAssignmentRepository, JobRepository, WorkerRepository};
 use common::rocket::DbConn;
 use rocket::http::Status;
 use rocket::response::status::Custom;
-use rocket::serde::json::{json, Json, Value};
-use rocket::{delete, get, patch, post, routes, Route};
+use rocket::serde::json::{Json, Value};
+use rocket::{delete, get, patch, post, routes, Route, State};
 use rocket_db_pools::Connection;
+use serde::Deserialize;
+use utoipa::{OpenApi, ToSchema};
 
 pub fn routes() -> Vec<Route> {
     routes![
         create_assignment,
+        claim_job,
         get_assignment_by_id,
         delete_assignment,
         get_assignments_by_job_id,
@@ -24,12 +31,35 @@ pub fn routes() -> Vec<Route> {
     ]
 }
 
+#[derive(OpenApi)]
+#[openapi(paths(
+    create_assignment,
+    claim_job,
+    get_assignment_by_id,
+    delete_assignment,
+    lookup_assignment,
+    get_assignments_by_job_id,
+    get_assignments_by_worker_id,
+    get_assignments_for_worker_in_range,
+    get_active_assignments,
+    update_started_at,
+    update_finished_at
+))]
+pub struct JobAssignmentApi;
+
 /* ===================== ⚙️ JobAssignment API Overview =====================
 
 == 🛠️ CRUD ==
 • POST   /assignments                              → Create new assignment (NewJobAssignment) → 201 Created (JobAssignment)
 • GET    /assignments/:id                          → Fetch assignment by ID                 → 200 OK (JobAssignment)
 • DELETE /assignments/:id                          → Delete assignment by ID                → 204 No Content
+  Creating an assignment only records it, the job state is left alone
+
+== 🎯 Claiming ==
+• POST   /assignments/claim/:worker_id             → Claim the next queued job of the owner → 200 OK (Option<JobAssignment>)
+                                                  → 403 Forbidden (worker of another user)
+  Moves the job Queued → Running and assigns it in one transaction, FOR UPDATE SKIP LOCKED,
+  so concurrent claims never get the same job. null if nothing is queued or the scheduler is paused.
 
 == 🔍 Lookup & Search ==
 • GET    /assignments/by_job/:job_id               → Assignments by Job ID                  → 200 OK (Vec<JobAssignment>)
@@ -46,106 +76,160 @@ pub fn routes() -> Vec<Route> {
 ======================================================================== */
 
 // ========== CRUD =========
+#[utoipa::path(
+    tag = "assignments",
+    request_body = NewJobAssignment,
+    responses(
+        (status = 201, description = "Created", body = JobAssignment),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[post("/assignments", format = "json", data = "<new_assignment>")]
 async fn create_assignment(
     mut db: Connection<DbConn>,
     new_assignment: Json<NewJobAssignment>,
     _user: User,
-) -> Result<Custom<Json<JobAssignment>>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Custom<Json<JobAssignment>>> {
     JobAssignmentRepository::create(&mut db, new_assignment.into_inner())
         .await
         .map(|job| Custom(Status::Created, Json(job)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "assignments",
+    responses(
+        (status = 200, description = "Assignment of the claimed job, null if nothing is queued or the scheduler is paused", body = Option<JobAssignment>),
+        (status = 403, description = "Worker of another user", body = ErrorBody),
+        (status = 404, description = "Worker not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[post("/assignments/claim/<worker_id>")]
+async fn claim_job(
+    mut db: Connection<DbConn>,
+    shared: &State<Arc<SharedResources>>,
+    worker_id: i32,
+    user: User,
+) -> ApiResult<Json<Option<JobAssignment>>> {
+    let worker = WorkerRepository::find_by_id(&mut db, worker_id)
+        .await
+        .or_not_found(format!("Worker {}", worker_id))?;
+    if worker.user_id != user.id {
+        return Err(ApiError::Forbidden(
+            "Jobs can only be claimed for the callers workers".to_string(),
+        ));
+    }
+    // paused on the admin API, queued jobs wait like the submitted ones
+    if shared.get_service_channels().scheduler_paused() {
+        return Ok(Json(None));
+    }
+
+    let claimed = JobRepository::claim_next(&mut db, worker_id).await?;
+    Ok(Json(claimed.map(|(_, assignment)| assignment)))
+}
+
+#[utoipa::path(
+    tag = "assignments",
+    responses(
+        (status = 200, description = "Success", body = JobAssignment),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+)]
 #[get("/assignments/<id>")]
 async fn get_assignment_by_id(
     mut db: Connection<DbConn>,
     id: i32,
     _user: User,
-) -> Result<Json<JobAssignment>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Json<JobAssignment>> {
     JobAssignmentRepository::find_by_id(&mut db, id)
         .await
+        .or_not_found(format!("Assignment {}", id))
         .map(Json)
-        .map_err(|e| Custom(Status::NotFound, Json(json!({ "error": e.to_string() }))))
 }
 
+#[utoipa::path(
+    tag = "assignments",
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[delete("/assignments/<id>")]
-async fn delete_assignment(
-    mut db: Connection<DbConn>,
-    id: i32,
-    _user: User,
-) -> Result<Status, Custom<Json<serde_json::Value>>> {
-    JobAssignmentRepository::delete(&mut db, id)
-        .await
-        .map(|_| Status::NoContent)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string() })),
-            )
-        })
+async fn delete_assignment(mut db: Connection<DbConn>, id: i32, _user: User) -> ApiResult<Status> {
+    let rows = JobAssignmentRepository::delete(&mut db, id).await?;
+    deleted(rows, format!("Assignment {}", id))
 }
 
 // ========== Lookup & Search =======
 
+#[utoipa::path(
+    tag = "assignments",
+    responses(
+        (status = 200, description = "Success", body = Option<JobAssignment>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/assignments/lookup/<job_id>/<worker_id>")]
 async fn lookup_assignment(
     mut db: Connection<DbConn>,
     job_id: i32,
     worker_id: i32,
     _user: User,
-) -> Result<Json<Option<JobAssignment>>, Custom<serde_json::Value>> {
+) -> ApiResult<Json<Option<JobAssignment>>> {
     JobAssignmentRepository::find_assignment_by_job_and_worker(&mut db, job_id, worker_id)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "assignments",
+    responses(
+        (status = 200, description = "Success", body = Vec<JobAssignment>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/assignments/by_job/<job_id>")]
 async fn get_assignments_by_job_id(
     mut db: Connection<DbConn>,
     job_id: i32,
     _user: User,
-) -> Result<Json<Vec<JobAssignment>>, Custom<serde_json::Value>> {
+) -> ApiResult<Json<Vec<JobAssignment>>> {
     JobAssignmentRepository::find_by_job_id(&mut db, job_id)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "assignments",
+    responses(
+        (status = 200, description = "Success", body = Vec<JobAssignment>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/assignments/by_worker/<worker_id>")]
 async fn get_assignments_by_worker_id(
     mut db: Connection<DbConn>,
     worker_id: i32,
     _user: User,
-) -> Result<Json<Vec<JobAssignment>>, Custom<serde_json::Value>> {
+) -> ApiResult<Json<Vec<JobAssignment>>> {
     JobAssignmentRepository::find_by_worker_id(&mut db, worker_id)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "assignments",
+    responses(
+        (status = 200, description = "Success", body = Vec<JobAssignment>),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/assignments/by_worker/range?<worker_id>&<start>&<end>")]
 async fn get_assignments_for_worker_in_range(
     mut db: Connection<DbConn>,
@@ -153,95 +237,110 @@ async fn get_assignments_for_worker_in_range(
     start: &str,
     end: &str,
     _user: User,
-) -> Result<Json<Vec<JobAssignment>>, Custom<Json<Value>>> {
+) -> ApiResult<Json<Vec<JobAssignment>>> {
     let start = parsing::parse_naive_datetime(start)
-        .map_err(|_| Custom(Status::BadRequest, Json(json!({"error":"invalid start"}))))?;
+        .map_err(|_| ApiError::BadRequest("invalid start".to_string()))?;
     let end = parsing::parse_naive_datetime(end)
-        .map_err(|_| Custom(Status::BadRequest, Json(json!({"error":"invalid end"}))))?;
+        .map_err(|_| ApiError::BadRequest("invalid end".to_string()))?;
 
     JobAssignmentRepository::find_assignments_for_worker_in_time_range(
         &mut db, worker_id, start, end,
     )
     .await
     .map(Json)
-    .map_err(|e| {
-        Custom(
-            Status::InternalServerError,
-            Json(json!({ "error": e.to_string() })),
-        )
-    })
+    .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "assignments",
+    responses(
+        (status = 200, description = "Success", body = Vec<JobAssignment>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/assignments/active")]
 async fn get_active_assignments(
     mut db: Connection<DbConn>,
     _user: User,
-) -> Result<Json<Vec<JobAssignment>>, Custom<serde_json::Value>> {
+) -> ApiResult<Json<Vec<JobAssignment>>> {
     JobAssignmentRepository::list_active_assignments(&mut db)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
 // ========== Updates ==========
 
+/// Body of `PATCH /assignments/<id>/started`, any format `parse_naive_datetime` accepts
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct StartedAtPayload {
+    pub started_at: String,
+}
+
+#[utoipa::path(
+    tag = "assignments",
+    request_body = StartedAtPayload,
+    responses(
+        (status = 200, description = "Success", body = JobAssignment),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[patch("/assignments/<id>/started", format = "json", data = "<started_at>")]
 async fn update_started_at(
     mut db: Connection<DbConn>,
     id: i32,
     started_at: Json<Value>,
     _user: User,
-) -> Result<Json<JobAssignment>, Custom<serde_json::Value>> {
+) -> ApiResult<Json<JobAssignment>> {
     if let Some(started_at_str) = started_at.get("started_at").and_then(Value::as_str) {
-        let started_at = parsing::parse_naive_datetime(started_at_str)
-            .map_err(|e| Custom(Status::BadRequest, json!({ "error": e })))?;
+        let started_at =
+            parsing::parse_naive_datetime(started_at_str).map_err(ApiError::BadRequest)?;
 
         JobAssignmentRepository::update_started_at(&mut db, id, started_at)
             .await
             .map(Json)
-            .map_err(|e| {
-                Custom(
-                    Status::InternalServerError,
-                    json!({ "error": e.to_string() }),
-                )
-            })
+            .map_err(ApiError::from)
     } else {
-        Err(Custom(
-            Status::BadRequest,
-            json!({ "error": "Missing or invalid 'started_at' field" }),
+        Err(ApiError::BadRequest(
+            "Missing or invalid 'started_at' field".to_string(),
         ))
     }
 }
 
+/// Body of `PATCH /assignments/<id>/finished`, any format `parse_naive_datetime` accepts
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct FinishedAtPayload {
+    pub finished_at: String,
+}
+
+#[utoipa::path(
+    tag = "assignments",
+    request_body = FinishedAtPayload,
+    responses(
+        (status = 200, description = "Success", body = JobAssignment),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[patch("/assignments/<id>/finished", format = "json", data = "<finished_at>")]
 async fn update_finished_at(
     mut db: Connection<DbConn>,
     id: i32,
     finished_at: Json<Value>,
     _user: User,
-) -> Result<Json<JobAssignment>, Custom<serde_json::Value>> {
+) -> ApiResult<Json<JobAssignment>> {
     if let Some(finished_at_str) = finished_at.get("finished_at").and_then(Value::as_str) {
-        let finished_at = parsing::parse_naive_datetime(finished_at_str)
-            .map_err(|e| Custom(Status::BadRequest, json!({ "error": e })))?;
+        let finished_at =
+            parsing::parse_naive_datetime(finished_at_str).map_err(ApiError::BadRequest)?;
 
         JobAssignmentRepository::update_finished_at(&mut db, id, finished_at)
             .await
             .map(Json)
-            .map_err(|e| {
-                Custom(
-                    Status::InternalServerError,
-                    json!({ "error": e.to_string() }),
-                )
-            })
+            .map_err(ApiError::from)
     } else {
-        Err(Custom(
-            Status::BadRequest,
-            json!({ "error": "Missing or invalid 'finished_at' field" }),
+        Err(ApiError::BadRequest(
+            "Missing or invalid 'finished_at' field".to_string(),
         ))
     }
 }
@@ -2,12 +2,14 @@ use common::database::models::job::{JobMetric, NewJobMetric};
 use common::database::models::user::User;
 use common::database::repositories::JobMetricRepository;
 
+use crate::rocket_api::error::{deleted, ApiError, ApiResult, ErrorBody, OrNotFound};
 use common::rocket::DbConn;
 use rocket::http::Status;
 use rocket::response::status::Custom;
-use rocket::serde::json::{json, Json, Value};
+use rocket::serde::json::Json;
 use rocket::{delete, get, post, routes, Route};
 use rocket_db_pools::Connection;
+use utoipa::OpenApi;
 
 pub fn routes() -> Vec<Route> {
     routes![
@@ -20,6 +22,17 @@ pub fn routes() -> Vec<Route> {
     ]
 }
 
+#[derive(OpenApi)]
+#[openapi(paths(
+    create_metric,
+    get_metric,
+    delete_job,
+    get_metrics_by_job_id,
+    get_metrics_by_worker_id,
+    get_most_recent_for_job
+))]
+pub struct JobMetricApi;
+
 /* ===================== ⚙️ JobMetric API Overview =====================
 
 == 🛠️ CRUD ==
@@ -35,100 +48,114 @@ pub fn routes() -> Vec<Route> {
 ======================================================================== */
 
 // ===== CRUD =======
+#[utoipa::path(
+    tag = "metrics",
+    request_body = NewJobMetric,
+    responses(
+        (status = 201, description = "Created", body = JobMetric),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[post("/metrics", format = "json", data = "<new_metric>")]
 pub async fn create_metric(
     mut db: Connection<DbConn>,
     new_metric: Json<NewJobMetric>,
     _user: User,
-) -> Result<Custom<Json<JobMetric>>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Custom<Json<JobMetric>>> {
     JobMetricRepository::create(&mut db, new_metric.into_inner())
         .await
         .map(|metric| Custom(Status::Created, Json(metric)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "metrics",
+    responses(
+        (status = 200, description = "Success", body = JobMetric),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+)]
 #[get("/metrics/<id>")]
 pub async fn get_metric(
     mut db: Connection<DbConn>,
     id: i32,
     _user: User,
-) -> Result<Json<JobMetric>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Json<JobMetric>> {
     JobMetricRepository::find_by_id(&mut db, id)
         .await
+        .or_not_found(format!("Metric {}", id))
         .map(Json)
-        .map_err(|e| Custom(Status::NotFound, Json(json!({ "error": e.to_string() }))))
 }
 
+#[utoipa::path(
+    tag = "metrics",
+    operation_id = "delete_metric",
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[delete("/metrics/<id>")]
-pub async fn delete_job(
-    mut db: Connection<DbConn>,
-    id: i32,
-    _user: User,
-) -> Result<Status, Custom<Json<serde_json::Value>>> {
-    JobMetricRepository::delete(&mut db, id)
-        .await
-        .map(|_| Status::NoContent)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+pub async fn delete_job(mut db: Connection<DbConn>, id: i32, _user: User) -> ApiResult<Status> {
+    let rows = JobMetricRepository::delete(&mut db, id).await?;
+    deleted(rows, format!("Metric {}", id))
 }
 
 // ===== Lookup & Search =====
+#[utoipa::path(
+    tag = "metrics",
+    responses(
+        (status = 200, description = "Success", body = JobMetric),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/metrics/by_job/<job_id>")]
 pub async fn get_metrics_by_job_id(
     mut db: Connection<DbConn>,
     job_id: i32,
     _user: User,
-) -> Result<Json<JobMetric>, Custom<Value>> {
+) -> ApiResult<Json<JobMetric>> {
     JobMetricRepository::find_by_job_id(&mut db, job_id)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "metrics",
+    responses(
+        (status = 200, description = "Success", body = Vec<JobMetric>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/metrics/by_worker/<worker_id>")]
 pub async fn get_metrics_by_worker_id(
     mut db: Connection<DbConn>,
     worker_id: i32,
     _user: User,
-) -> Result<Json<Vec<JobMetric>>, Custom<Value>> {
+) -> ApiResult<Json<Vec<JobMetric>>> {
     JobMetricRepository::find_by_worker_id(&mut db, worker_id)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "metrics",
+    responses(
+        (status = 200, description = "Success", body = Option<JobMetric>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/metrics/recent/<job_id>")]
 pub async fn get_most_recent_for_job(
     mut db: Connection<DbConn>,
     job_id: i32,
     _user: User,
-) -> Result<Json<Option<JobMetric>>, Custom<Value>> {
+) -> ApiResult<Json<Option<JobMetric>>> {
     JobMetricRepository::get_most_recent_for_job(&mut db, job_id)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                json!({ "error": e.to_string() }),
-            )
-        })
+        .map_err(ApiError::from)
 }
@@ -1,17 +1,21 @@
+use crate::rocket_api::error::{deleted, ApiError, ApiResult, ErrorBody, OrNotFound};
 use common::database::models::job::{JobResult, NewJobResult};
 use common::database::models::user::User;
-use common::database::repositories::JobResultRepository;
+use common::database::repositories::listing::{ListQuery, Page};
+use common::database::repositories::{JobResultRepository, RESULT_LIST};
 use common::rocket::DbConn;
 use rocket::http::Status;
 use rocket::response::status::Custom;
-use rocket::serde::json::{json, Json};
+use rocket::serde::json::Json;
 use rocket::{delete, get, patch, post, routes, Route};
 use rocket_db_pools::Connection;
 use serde::Deserialize;
+use utoipa::{OpenApi, ToSchema};
 
 pub fn routes() -> Vec<Route> {
     routes![
         create_result,
+        list_results,
         get_result,
         delete_result,
         get_results_by_job_id,
@@ -22,6 +26,20 @@ pub fn routes() -> Vec<Route> {
     ]
 }
 
+#[derive(OpenApi)]
+#[openapi(paths(
+    create_result,
+    list_results,
+    get_result,
+    delete_result,
+    get_results_by_job_id,
+    list_results_for_job,
+    get_most_recent_result_for_job,
+    update_stdout,
+    update_files
+))]
+pub struct JobResultApi;
+
 /* ===================== ⚙️ JobResult API Overview =====================
 
 == 🛠️ CRUD ==
@@ -29,6 +47,10 @@ pub fn routes() -> Vec<Route> {
 • GET     /results/:id                 → Fetch result by ID                     → 200 OK (JobResult)
 • DELETE  /results/:id                 → Delete result by ID                    → 204 No Content
 
+== 📜 Listing ==
+• GET     /results?filter&sort&limit&cursor → Filtered, sorted page of results  → 200 OK (Page<JobResult>)
+  filter fields: id, job, user, state, name, worker (all of the job), created (saved_at)
+
 == 🔍 Lookup & Search ==
 • GET     /results/job/:job_id         → Results by Job ID                      → 200 OK (Vec<JobResult>)
 • GET     /results/list/:job_id        → List results for Job                   → 200 OK (Vec<JobResult>)
@@ -41,162 +63,195 @@ pub fn routes() -> Vec<Route> {
 ======================================================================== */
 
 // ===== CRUD =====
+#[utoipa::path(
+    tag = "results",
+    request_body = NewJobResult,
+    responses(
+        (status = 201, description = "Created", body = JobResult),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[post("/results", format = "json", data = "<new_result>")]
 pub async fn create_result(
     mut db: Connection<DbConn>,
     new_result: Json<NewJobResult>,
     _user: User,
-) -> Result<Custom<Json<JobResult>>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Custom<Json<JobResult>>> {
     JobResultRepository::create(&mut db, new_result.into_inner())
         .await
         .map(|res| Custom(Status::Created, Json(res)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+        .map_err(ApiError::from)
+}
+
+#[utoipa::path(
+    tag = "results",
+    responses(
+        (status = 200, description = "Success", body = Page<JobResult>),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[get("/results?<filter>&<sort>&<limit>&<cursor>")]
+pub async fn list_results(
+    mut db: Connection<DbConn>,
+    filter: Option<&str>,
+    sort: Option<&str>,
+    limit: Option<i64>,
+    cursor: Option<&str>,
+    _user: User,
+) -> ApiResult<Json<Page<JobResult>>> {
+    let query = ListQuery::parse(&RESULT_LIST, filter, sort, limit, cursor)?;
+    let rows = JobResultRepository::list(&mut db, &query).await?;
+    Ok(Json(query.paginate(rows)))
 }
 
+#[utoipa::path(
+    tag = "results",
+    responses(
+        (status = 200, description = "Success", body = JobResult),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+)]
 #[get("/results/<id>")]
 pub async fn get_result(
     mut db: Connection<DbConn>,
     id: i32,
     _user: User,
-) -> Result<Json<JobResult>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Json<JobResult>> {
     JobResultRepository::find_by_id(&mut db, id)
         .await
+        .or_not_found(format!("Result {}", id))
         .map(Json)
-        .map_err(|e| Custom(Status::NotFound, Json(json!({ "error": e.to_string() }))))
 }
 
+#[utoipa::path(
+    tag = "results",
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[delete("/results/<id>")]
-pub async fn delete_result(
-    mut db: Connection<DbConn>,
-    id: i32,
-    _user: User,
-) -> Result<Status, Custom<Json<serde_json::Value>>> {
-    JobResultRepository::delete(&mut db, id)
-        .await
-        .map(|_| Status::NoContent)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+pub async fn delete_result(mut db: Connection<DbConn>, id: i32, _user: User) -> ApiResult<Status> {
+    let rows = JobResultRepository::delete(&mut db, id).await?;
+    deleted(rows, format!("Result {}", id))
 }
 
 // ===== Lookup & Search =====
+#[utoipa::path(
+    tag = "results",
+    responses(
+        (status = 200, description = "Success", body = Vec<JobResult>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/results/job/<job_id>")]
 pub async fn get_results_by_job_id(
     mut db: Connection<DbConn>,
     job_id: i32,
     _user: User,
-) -> Result<Json<Vec<JobResult>>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Json<Vec<JobResult>>> {
     JobResultRepository::find_by_job_id(&mut db, job_id)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "results",
+    responses(
+        (status = 200, description = "Success", body = Vec<JobResult>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/results/list/<job_id>")]
 pub async fn list_results_for_job(
     mut db: Connection<DbConn>,
     job_id: i32,
     _user: User,
-) -> Result<Json<Vec<JobResult>>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Json<Vec<JobResult>>> {
     JobResultRepository::list_results_for_job(&mut db, job_id)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "results",
+    responses(
+        (status = 200, description = "Success", body = Option<JobResult>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/results/recent/<job_id>")]
 pub async fn get_most_recent_result_for_job(
     mut db: Connection<DbConn>,
     job_id: i32,
     _user: User,
-) -> Result<Json<Option<JobResult>>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Json<Option<JobResult>>> {
     JobResultRepository::get_most_recent_for_job(&mut db, job_id)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
 // ===== Field Updates =====
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateStdoutPayload {
     pub stdout: Option<String>,
 }
 
+#[utoipa::path(
+    tag = "results",
+    request_body = UpdateStdoutPayload,
+    responses(
+        (status = 200, description = "Success", body = JobResult),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[patch("/results/<id>/stdout", format = "json", data = "<payload>")]
 pub async fn update_stdout(
     mut db: Connection<DbConn>,
     id: i32,
     payload: Json<serde_json::Value>,
     _user: User,
-) -> Result<Json<JobResult>, Custom<Json<serde_json::Value>>> {
-    let extracted: UpdateStdoutPayload =
-        serde_json::from_value(payload.into_inner()).map_err(|e| {
-            Custom(
-                Status::UnprocessableEntity,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })?;
+) -> ApiResult<Json<JobResult>> {
+    let extracted: UpdateStdoutPayload = serde_json::from_value(payload.into_inner())
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
 
     JobResultRepository::update_stdout(&mut db, id, extracted.stdout)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateFilesPayload {
     pub files: Option<Vec<String>>,
 }
+#[utoipa::path(
+    tag = "results",
+    request_body = UpdateFilesPayload,
+    responses(
+        (status = 200, description = "Success", body = JobResult),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[patch("/results/<id>/files", format = "json", data = "<payload>")]
 pub async fn update_files(
     mut db: Connection<DbConn>,
     id: i32,
     payload: Json<serde_json::Value>,
     _user: User,
-) -> Result<Json<JobResult>, Custom<Json<serde_json::Value>>> {
-    let extracted: UpdateFilesPayload =
-        serde_json::from_value(payload.into_inner()).map_err(|e| {
-            Custom(
-                Status::UnprocessableEntity,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })?;
+) -> ApiResult<Json<JobResult>> {
+    let extracted: UpdateFilesPayload = serde_json::from_value(payload.into_inner())
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
 
     JobResultRepository::update_files(&mut db, id, extracted.files)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+        .map_err(ApiError::from)
 }
@@ -0,0 +1,55 @@
+use crate::rocket_api::error::{ApiResult, ErrorBody};
+use common::database::models::log::LogEntry;
+use common::database::models::user::User;
+use common::database::repositories::listing::{ListQuery, Page};
+use common::database::repositories::{LogEntryRepository, LOG_LIST};
+use common::rocket::DbConn;
+use rocket::serde::json::Json;
+use rocket::{get, routes, Route};
+use rocket_db_pools::Connection;
+use utoipa::OpenApi;
+
+pub fn routes() -> Vec<Route> {
+    routes![list_logs]
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(list_logs))]
+pub struct LogApi;
+
+/* ===================== 📜 Log API Overview =====================
+
+== 🔍 Listing ==
+• GET     /logs?filter&sort&limit&cursor → Filtered, sorted page of log entries  → 200 OK (Page<LogEntry>)
+                                                                           → 400 Bad Request (bad filter, sort or cursor)
+  filter fields: id, level, module, action, user (connecting client), job, created
+  e.g. ?filter=level:Error|Warning,module:Dispatcher,created>=2025-05-01&limit=100
+
+Same grammar as GET /jobs, /workers and /results, see `common::database::repositories::listing`.
+Retention policies live under /logs/retention.
+
+======================================================================== */
+
+#[utoipa::path(
+    tag = "logs",
+    responses(
+        (status = 200, description = "Success", body = Page<LogEntry>),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[get("/logs?<filter>&<sort>&<limit>&<cursor>")]
+pub async fn list_logs(
+    mut db: Connection<DbConn>,
+    filter: Option<&str>,
+    sort: Option<&str>,
+    limit: Option<i64>,
+    cursor: Option<&str>,
+    _user: User,
+) -> ApiResult<Json<Page<LogEntry>>> {
+    let query = ListQuery::parse(&LOG_LIST, filter, sort, limit, cursor)?;
+    let rows = LogEntryRepository::list(&mut db, &query).await?;
+    Ok(Json(
+        query.paginate(rows.into_iter().map(LogEntry::from).collect()),
+    ))
+}
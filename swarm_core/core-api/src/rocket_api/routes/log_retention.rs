@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use common::database::models::retention::{LogRetentionPolicy, NewLogRetentionPolicy};
+use common::database::models::user::User;
+use common::database::repositories::LogRetentionRepository;
+use common::rocket::DbConn;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, put, routes, Route, State};
+use rocket_db_pools::Connection;
+
+use crate::core::shared_resources::SharedResources;
+use crate::rocket_api::error::{deleted, ApiError, ApiResult, ErrorBody};
+use crate::rocket_api::routes::admin::Admin;
+use utoipa::OpenApi;
+
+pub fn routes() -> Vec<Route> {
+    routes![list_policies, set_policy, delete_policy]
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(list_policies, set_policy, delete_policy))]
+pub struct LogRetentionApi;
+
+/* ===================== 🗄️ Log Retention API Overview =====================
+
+== 🛠️ CRUD ==
+• GET     /logs/retention              → All retention policies                 → 200 OK (Vec<LogRetentionPolicy>)
+• PUT     /logs/retention              → Create or replace a policy             → 200 OK (LogRetentionPolicy)
+• DELETE  /logs/retention/:id          → Drop a policy                          → 204 No Content
+
+A policy sets `retention_minutes` for entries matching `level`, `module` and `action`,
+a missing field matches everything. The most specific match wins (action > module > level),
+levels without any match keep the built-in defaults. Changes apply to new log entries right away.
+Every user can list the policies, only admins (`CORE_ADMIN_USERS`) set or drop one, others get 403.
+
+======================================================================== */
+
+#[utoipa::path(
+    tag = "logs",
+    responses(
+        (status = 200, description = "Success", body = Vec<LogRetentionPolicy>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[get("/logs/retention")]
+pub async fn list_policies(
+    mut db: Connection<DbConn>,
+    _user: User,
+) -> ApiResult<Json<Vec<LogRetentionPolicy>>> {
+    LogRetentionRepository::list_all(&mut db)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+#[utoipa::path(
+    tag = "logs",
+    request_body = NewLogRetentionPolicy,
+    responses(
+        (status = 200, description = "Success", body = LogRetentionPolicy),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 403, description = "Not an admin", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[put("/logs/retention", format = "json", data = "<policy>")]
+pub async fn set_policy(
+    mut db: Connection<DbConn>,
+    shared: &State<Arc<SharedResources>>,
+    policy: Json<NewLogRetentionPolicy>,
+    _admin: Admin,
+) -> ApiResult<Json<LogRetentionPolicy>> {
+    let policy = policy.into_inner();
+    if policy.retention_minutes <= 0 {
+        return Err(ApiError::BadRequest(
+            "retention_minutes must be positive".to_string(),
+        ));
+    }
+    let saved = LogRetentionRepository::upsert(&mut db, policy).await?;
+    shared.get_logger().refresh_retention().await;
+    Ok(Json(saved))
+}
+
+#[utoipa::path(
+    tag = "logs",
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 403, description = "Not an admin", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[delete("/logs/retention/<id>")]
+pub async fn delete_policy(
+    mut db: Connection<DbConn>,
+    shared: &State<Arc<SharedResources>>,
+    id: i32,
+    _admin: Admin,
+) -> ApiResult<Status> {
+    let rows = LogRetentionRepository::delete(&mut db, id).await?;
+    let status = deleted(rows, format!("Retention policy {}", id))?;
+    shared.get_logger().refresh_retention().await;
+    Ok(status)
+}
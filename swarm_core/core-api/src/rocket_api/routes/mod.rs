@@ -1,9 +1,19 @@
+pub mod admin;
+pub mod archive;
 pub mod authorization;
+pub mod health;
 pub mod job;
 pub mod job_assignment;
 pub mod job_metric;
 pub mod job_result;
+pub mod log;
+pub mod log_retention;
+pub mod openapi;
+pub mod prometheus;
+pub mod quota;
+pub mod template;
 pub mod user;
+pub mod webhook;
 pub mod worker;
 pub mod worker_status;
 
@@ -12,10 +22,20 @@ use rocket::Route;
 pub fn all_routes() -> Vec<Route> {
     [
         job::routes(),
+        archive::routes(),
         job_assignment::routes(),
         job_metric::routes(),
         job_result::routes(),
+        log::routes(),
+        log_retention::routes(),
+        openapi::routes(),
+        health::routes(),
+        admin::routes(),
+        prometheus::routes(),
+        quota::routes(),
+        template::routes(),
         user::routes(),
+        webhook::routes(),
         worker::routes(),
         worker_status::routes(),
         authorization::routes(),
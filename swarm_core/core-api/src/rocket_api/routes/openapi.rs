@@ -0,0 +1,34 @@
+use rocket::http::ContentType;
+use rocket::{get, routes, Route};
+use utoipa::OpenApi;
+
+use crate::rocket_api::openapi::spec_json;
+
+pub fn routes() -> Vec<Route> {
+    routes![openapi_json]
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(openapi_json))]
+pub struct OpenApiSpecApi;
+
+/* ===================== 📖 OpenAPI Overview =====================
+
+• GET     /openapi.json                → OpenAPI 3.1 document of every route    → 200 OK (application/json)
+
+Unauthenticated, served below /api/v1 like every other route, generated from the route annotations and model schemas at startup.
+Typed Rust access goes through the `swarm-client` crate, which covers every operation listed here.
+
+======================================================================== */
+
+#[utoipa::path(
+    tag = "system",
+    responses(
+        (status = 200, description = "This document", content_type = "application/json"),
+    ),
+    security(()),
+)]
+#[get("/openapi.json")]
+pub fn openapi_json() -> (ContentType, &'static str) {
+    (ContentType::JSON, spec_json())
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use rocket::http::ContentType;
+use rocket::{get, routes, Route, State};
+
+use crate::core::shared_resources::SharedResources;
+use crate::rocket_api::error::{ApiError, ApiResult, ErrorBody};
+use utoipa::OpenApi;
+
+pub fn routes() -> Vec<Route> {
+    routes![metrics]
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(metrics))]
+pub struct PrometheusApi;
+
+/* ===================== 📈 Metrics API Overview =====================
+
+• GET     /metrics                     → Prometheus text exposition             → 200 OK (text/plain; version=0.0.4)
+
+Unauthenticated so Prometheus can scrape it, keep core's port off the public network.
+Series (all prefixed `swarmsync_`):
+  jobs{state}, workers{status}, dispatcher_heartbeats_received_total, dispatcher_heartbeats_dropped_total,
+  scheduler_queue_depth, scheduler_latency_seconds, logger_buffer_entries, logger_flush_failures_total,
+  logger_dropped_entries_total, logger_spilled_entries,
+  http_request_duration_seconds{method,route,status}, notifier_webhook_deliveries_total{outcome},
+  db_pool_connections{state}, db_pool_max_connections (read from the shared pool on every scrape)
+
+======================================================================== */
+
+#[utoipa::path(
+    tag = "system",
+    responses(
+        (status = 200, description = "Prometheus text exposition", body = String, content_type = "text/plain; version=0.0.4"),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+    security(()),
+)]
+#[get("/metrics")]
+pub fn metrics(shared: &State<Arc<SharedResources>>) -> ApiResult<(ContentType, String)> {
+    let metrics = shared.get_metrics();
+    metrics.set_db_pool(&shared.get_db_pool());
+    let body = metrics
+        .render()
+        .map_err(|e| ApiError::Internal(e.to_string()))?;
+    let content_type = ContentType::new("text", "plain").with_params(("version", "0.0.4"));
+    Ok((content_type, body))
+}
@@ -0,0 +1,124 @@
+use crate::rocket_api::error::{ApiError, ApiResult, ErrorBody};
+use crate::rocket_api::routes::admin::{is_admin, Admin};
+use common::database::models::quota::{NewUserQuota, QuotaStatus, UserQuota};
+use common::database::models::user::User;
+use common::database::repositories::QuotaRepository;
+use common::rocket::DbConn;
+use rocket::http::Status;
+use rocket::serde::json::Json;
+use rocket::{delete, get, put, routes, Route};
+use rocket_db_pools::Connection;
+use serde::Deserialize;
+use utoipa::{OpenApi, ToSchema};
+
+pub fn routes() -> Vec<Route> {
+    routes![get_quota_status, set_quota, reset_quota]
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(get_quota_status, set_quota, reset_quota))]
+pub struct QuotaApi;
+
+/* ===================== 🚦 Quota API Overview =====================
+
+== 🛠️ CRUD ==
+• GET     /quotas/:user_id             → Quota in effect + current usage        → 200 OK (QuotaStatus)
+• PUT     /quotas/:user_id             → Set quota for user (UpdateQuota)       → 200 OK (UserQuota)
+• DELETE  /quotas/:user_id             → Drop quota, user falls back to default → 204 No Content
+
+Reading is open to the user and admins, setting and resetting to admins (`CORE_ADMIN_USERS`) only.
+
+======================================================================== */
+
+/// Limits sent when setting a quota, user comes from the path
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateQuota {
+    pub max_queued_jobs: i32,
+    pub max_running_jobs: i32,
+    pub max_submissions_per_minute: i32,
+}
+
+#[utoipa::path(
+    tag = "quotas",
+    responses(
+        (status = 200, description = "Success", body = QuotaStatus),
+        (status = 403, description = "Quota of another user", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[get("/quotas/<user_id>")]
+pub async fn get_quota_status(
+    mut db: Connection<DbConn>,
+    user_id: i32,
+    user: User,
+) -> ApiResult<Json<QuotaStatus>> {
+    if user_id != user.id && !is_admin(&user) {
+        return Err(ApiError::Forbidden(
+            "Quotas can only be read by their user or an admin".to_string(),
+        ));
+    }
+    let quota = QuotaRepository::find_effective(&mut db, user_id).await?;
+    let usage = QuotaRepository::usage_for_user(&mut db, user_id).await?;
+    Ok(Json(QuotaStatus { quota, usage }))
+}
+
+#[utoipa::path(
+    tag = "quotas",
+    request_body = UpdateQuota,
+    responses(
+        (status = 200, description = "Success", body = UserQuota),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 403, description = "Not an admin", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[put("/quotas/<user_id>", format = "json", data = "<update>")]
+pub async fn set_quota(
+    mut db: Connection<DbConn>,
+    user_id: i32,
+    update: Json<UpdateQuota>,
+    _admin: Admin,
+) -> ApiResult<Json<UserQuota>> {
+    let update = update.into_inner();
+    if update.max_queued_jobs < 0
+        || update.max_running_jobs < 0
+        || update.max_submissions_per_minute < 0
+    {
+        return Err(ApiError::BadRequest(
+            "Quota limits can not be negative".to_string(),
+        ));
+    }
+
+    QuotaRepository::upsert(
+        &mut db,
+        NewUserQuota {
+            user_id,
+            max_queued_jobs: update.max_queued_jobs,
+            max_running_jobs: update.max_running_jobs,
+            max_submissions_per_minute: update.max_submissions_per_minute,
+        },
+    )
+    .await
+    .map(Json)
+    .map_err(ApiError::from)
+}
+
+#[utoipa::path(
+    tag = "quotas",
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 403, description = "Not an admin", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[delete("/quotas/<user_id>")]
+pub async fn reset_quota(
+    mut db: Connection<DbConn>,
+    user_id: i32,
+    _admin: Admin,
+) -> ApiResult<Status> {
+    QuotaRepository::delete_by_user_id(&mut db, user_id)
+        .await
+        .map(|_| Status::NoContent)
+        .map_err(ApiError::from)
+}
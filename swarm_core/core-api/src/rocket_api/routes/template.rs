@@ -0,0 +1,208 @@
+use common::database::models::job::Job;
+use common::database::models::template::{
+    CreateTemplateRequest, RunTemplateRequest, TemplateWithParameters,
+};
+use common::database::models::user::User;
+use common::database::repositories::TemplateRepository;
+use common::rocket::DbConn;
+use rocket::http::Status;
+use rocket::response::status::Custom;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post, routes, Route};
+use rocket_db_pools::Connection;
+
+use crate::rocket_api::error::{deleted, ApiError, ApiResult, ErrorBody, OrNotFound};
+use crate::rocket_api::routes::job::submit_with_quota;
+use utoipa::OpenApi;
+
+pub fn routes() -> Vec<Route> {
+    routes![
+        create_template,
+        get_template,
+        list_templates_for_user,
+        delete_template,
+        run_template
+    ]
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    create_template,
+    get_template,
+    delete_template,
+    list_templates_for_user,
+    run_template
+))]
+pub struct TemplateApi;
+
+/* ===================== 📐 Template API Overview =====================
+
+== 🛠️ CRUD ==
+• POST    /templates                   → Create template (CreateTemplateRequest) → 201 Created (TemplateWithParameters)
+• GET     /templates/:id               → Fetch template with parameters          → 200 OK (TemplateWithParameters)
+• DELETE  /templates/:id               → Delete template                         → 204 No Content
+
+== 🔍 Lookup & Search ==
+• GET     /templates/user/:user_id     → Templates owned by user                 → 200 OK (Vec<TemplateWithParameters>)
+
+== 🚀 Run ==
+• POST    /templates/:id/run           → Render + submit job (RunTemplateRequest) → 201 Created (Job)
+                                                                                 → 429 Too Many Requests (quota hit)
+
+Templates belong to their user: creating one for, or reading, deleting and running
+someone else's answers 403.
+
+======================================================================== */
+
+// ===== CRUD =====
+#[utoipa::path(
+    tag = "templates",
+    request_body = CreateTemplateRequest,
+    responses(
+        (status = 201, description = "Created", body = TemplateWithParameters),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 403, description = "`user_id` is not the caller", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[post("/templates", format = "json", data = "<request>")]
+pub async fn create_template(
+    mut db: Connection<DbConn>,
+    request: Json<CreateTemplateRequest>,
+    user: User,
+) -> ApiResult<Custom<Json<TemplateWithParameters>>> {
+    let CreateTemplateRequest {
+        template,
+        parameters,
+    } = request.into_inner();
+    if template.user_id != user.id {
+        return Err(ApiError::Forbidden(
+            "Templates can only be created for the caller".to_string(),
+        ));
+    }
+
+    // Reject bad parameter definitions before anything is stored
+    for param in &parameters {
+        param
+            .validate()
+            .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    }
+    // template id is filled in by the repository
+    let parameters = parameters.into_iter().map(|p| p.into_new(0)).collect();
+
+    TemplateRepository::create(&mut db, template, parameters)
+        .await
+        .map(|t| Custom(Status::Created, Json(t)))
+        .map_err(ApiError::from)
+}
+
+#[utoipa::path(
+    tag = "templates",
+    responses(
+        (status = 200, description = "Success", body = TemplateWithParameters),
+        (status = 403, description = "Caller does not own the resource", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+)]
+#[get("/templates/<id>")]
+pub async fn get_template(
+    mut db: Connection<DbConn>,
+    id: i32,
+    user: User,
+) -> ApiResult<Json<TemplateWithParameters>> {
+    let template = TemplateRepository::find_by_id(&mut db, id)
+        .await
+        .or_not_found(format!("Template {}", id))?;
+    if template.template.user_id != user.id {
+        return Err(ApiError::Forbidden(
+            "Only the owner can read a template".to_string(),
+        ));
+    }
+    Ok(Json(template))
+}
+
+#[utoipa::path(
+    tag = "templates",
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 403, description = "Caller does not own the resource", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[delete("/templates/<id>")]
+pub async fn delete_template(mut db: Connection<DbConn>, id: i32, user: User) -> ApiResult<Status> {
+    let template = TemplateRepository::find_by_id(&mut db, id)
+        .await
+        .or_not_found(format!("Template {}", id))?;
+    if template.template.user_id != user.id {
+        return Err(ApiError::Forbidden(
+            "Only the owner can delete a template".to_string(),
+        ));
+    }
+    let rows = TemplateRepository::delete(&mut db, id).await?;
+    deleted(rows, format!("Template {}", id))
+}
+
+// ===== Lookup =====
+#[utoipa::path(
+    tag = "templates",
+    responses(
+        (status = 200, description = "Success", body = Vec<TemplateWithParameters>),
+        (status = 403, description = "Templates of another user", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[get("/templates/user/<user_id>")]
+pub async fn list_templates_for_user(
+    mut db: Connection<DbConn>,
+    user_id: i32,
+    user: User,
+) -> ApiResult<Json<Vec<TemplateWithParameters>>> {
+    if user_id != user.id {
+        return Err(ApiError::Forbidden(
+            "Templates can only be listed by their user".to_string(),
+        ));
+    }
+    TemplateRepository::list_by_user(&mut db, user_id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+// ===== Run =====
+#[utoipa::path(
+    tag = "templates",
+    request_body = RunTemplateRequest,
+    responses(
+        (status = 201, description = "Created", body = Job),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 403, description = "Caller does not own the resource", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 429, description = "Quota exceeded, retry after `Retry-After` seconds", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[post("/templates/<id>/run", format = "json", data = "<run>")]
+pub async fn run_template(
+    mut db: Connection<DbConn>,
+    id: i32,
+    run: Json<RunTemplateRequest>,
+    user: User,
+) -> ApiResult<Custom<Json<Job>>> {
+    let template = TemplateRepository::find_by_id(&mut db, id)
+        .await
+        .or_not_found(format!("Template {}", id))?;
+    if template.template.user_id != user.id {
+        return Err(ApiError::Forbidden(
+            "Only the owner can run a template".to_string(),
+        ));
+    }
+
+    let new_job = template
+        .render(run.into_inner(), user.id)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    let job = submit_with_quota(&mut db, new_job).await?;
+    Ok(Custom(Status::Created, Json(job)))
+}
@@ -1,3 +1,4 @@
+use crate::rocket_api::error::{deleted, ApiError, ApiResult, ErrorBody, OrNotFound};
 use common::auth;
 use common::database::models::user::{
     NewUser, NewUserRequest, UpdateUserRequest, User, UserResponse,
@@ -6,9 +7,14 @@ use common::database::repositories::UserRepository;
 use common::rocket::DbConn;
 use rocket::http::Status;
 use rocket::response::status::{Custom, NoContent};
-use rocket::serde::json::{json, Json, Value};
+use rocket::serde::json::Json;
 use rocket::{delete, get, head, post, put, routes, Route};
 use rocket_db_pools::Connection;
+use utoipa::OpenApi;
+
+// the consumer shows this message as-is when registration fails
+const PASSWORD_RULES: &str =
+    "Password must be 8–128 characters long and contain both letters and digits.";
 
 pub fn routes() -> Vec<Route> {
     routes![
@@ -28,6 +34,24 @@ pub fn routes() -> Vec<Route> {
     ]
 }
 
+#[derive(OpenApi)]
+#[openapi(paths(
+    get_user_by_id,
+    create_user,
+    delete_user,
+    update_user,
+    find_user_by_email,
+    find_user_by_username,
+    search_by_username,
+    search_by_email,
+    list_users,
+    exists_user_by_email,
+    exists_user_by_username,
+    users_with_jobs,
+    user_job_counts
+))]
+pub struct UserApi;
+
 /* ===================== ⚙️ User API Overview =====================
 
 == 🛠️ CRUD ==
@@ -58,43 +82,48 @@ pub fn routes() -> Vec<Route> {
 ======================================================================== */
 
 // === CRUD ===
+#[utoipa::path(
+    tag = "users",
+    responses(
+        (status = 200, description = "Success", body = UserResponse),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+)]
 #[get("/users/<id>")]
 pub async fn get_user_by_id(
     mut conn: Connection<DbConn>,
     id: i32,
     _user: User,
-) -> Result<Json<User>, Custom<Json<Value>>> {
+) -> ApiResult<Json<User>> {
     UserRepository::find_by_id(&mut conn, id)
         .await
+        .or_not_found(format!("User {}", id))
         .map(Json)
-        .map_err(|e| Custom(Status::NotFound, Json(json!({"error": e.to_string()}))))
 }
 
+#[utoipa::path(
+    tag = "users",
+    request_body = NewUserRequest,
+    responses(
+        (status = 201, description = "Created", body = UserResponse),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+    security(()),
+)]
 #[post("/users", format = "json", data = "<new_user_req>")]
 pub async fn create_user(
     mut conn: Connection<DbConn>,
     new_user_req: Json<NewUserRequest>,
-) -> Result<Custom<Json<UserResponse>>, Custom<Json<Value>>> {
+) -> ApiResult<Custom<Json<UserResponse>>> {
     // Validate password
     if !auth::is_password_valid(&new_user_req.password) {
-        return Err(Custom(
-            Status::BadRequest,
-            Json(json!({
-                "error": "Password must be 8–128 characters long and contain both letters and digits."
-            })),
-        ));
+        return Err(ApiError::BadRequest(PASSWORD_RULES.to_string()));
     }
 
     // Hash password
-    let hashed = match auth::hash_password(new_user_req.password.clone()) {
-        Ok(p) => p,
-        Err(_) => {
-            return Err(Custom(
-                Status::InternalServerError,
-                Json(json!({"error": "Password hashing failed"})),
-            ))
-        }
-    };
+    let hashed = auth::hash_password(new_user_req.password.clone())
+        .map_err(|_| ApiError::Internal("Password hashing failed".to_string()))?;
 
     // Construct NewUser properly
     let new_user = NewUser {
@@ -109,63 +138,50 @@ pub async fn create_user(
             let resp: UserResponse = u.into();
             Custom(Status::Created, Json(resp))
         })
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "users",
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[delete("/users/<id>")]
-pub async fn delete_user(
-    mut conn: Connection<DbConn>,
-    id: i32,
-    _user: User,
-) -> Result<NoContent, Custom<Json<Value>>> {
-    UserRepository::delete(&mut conn, id)
-        .await
-        .map(|_| NoContent)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+pub async fn delete_user(mut conn: Connection<DbConn>, id: i32, _user: User) -> ApiResult<Status> {
+    let rows = UserRepository::delete(&mut conn, id).await?;
+    deleted(rows, format!("User {}", id))
 }
 
+#[utoipa::path(
+    tag = "users",
+    request_body = UpdateUserRequest,
+    responses(
+        (status = 200, description = "Success", body = UserResponse),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[put("/users/<id>", format = "json", data = "<update_req>")]
 pub async fn update_user(
     id: i32,
     update_req: Json<UpdateUserRequest>,
     mut conn: Connection<DbConn>,
-) -> Result<Json<UserResponse>, Custom<Json<Value>>> {
-    let user = match UserRepository::find_by_id(&mut conn, id).await {
-        Ok(u) => u,
-        Err(_) => {
-            return Err(Custom(
-                Status::NotFound,
-                Json(json!({"error": "User not found"})),
-            ))
-        }
-    };
+) -> ApiResult<Json<UserResponse>> {
+    let user = UserRepository::find_by_id(&mut conn, id)
+        .await
+        .or_not_found(format!("User {}", id))?;
 
     // Hash password if provided
     let password_hash = if let Some(pwd) = &update_req.password {
         if !auth::is_password_valid(pwd) {
-            return Err(Custom(
-                Status::BadRequest,
-                Json(json!({
-                    "error": "Password must be 8–128 characters long and contain both letters and digits."
-                })),
-            ));
+            return Err(ApiError::BadRequest(PASSWORD_RULES.to_string()));
         }
-        auth::hash_password(pwd.clone().to_string()).map_err(|_| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": "Password hashing failed"})),
-            )
-        })?
+        auth::hash_password(pwd.clone().to_string())
+            .map_err(|_| ApiError::Internal("Password hashing failed".to_string()))?
     } else {
         user.password_hash.clone() // preserve old one
     };
@@ -178,118 +194,123 @@ pub async fn update_user(
         created_at: user.created_at,
     };
 
-    let result = UserRepository::update(&mut conn, user.id, updated)
-        .await
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })?;
+    let result = UserRepository::update(&mut conn, user.id, updated).await?;
 
     Ok(Json(result.into()))
 }
 
 // === Lookup ===
+#[utoipa::path(
+    tag = "users",
+    responses(
+        (status = 200, description = "Success", body = UserResponse),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/users/email/<email>")]
 pub async fn find_user_by_email(
     mut conn: Connection<DbConn>,
     email: String,
     _user: User,
-) -> Result<Json<User>, Custom<Json<Value>>> {
-    let user = UserRepository::find_by_email(&mut conn, &email)
-        .await
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })?;
-
-    match user {
-        Some(user) => Ok(Json(user)),
-        None => Err(Custom(
-            Status::NotFound,
-            Json(json!({"error": "User not found"})),
-        )),
-    }
+) -> ApiResult<Json<User>> {
+    UserRepository::find_by_email(&mut conn, &email)
+        .await?
+        .or_not_found(format!("User with email {}", email))
+        .map(Json)
 }
 
+#[utoipa::path(
+    tag = "users",
+    responses(
+        (status = 200, description = "Success", body = Option<UserResponse>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/users/username/<username>")]
 pub async fn find_user_by_username(
     mut conn: Connection<DbConn>,
     username: String,
     _user: User,
-) -> Result<Json<Option<User>>, Custom<Json<Value>>> {
+) -> ApiResult<Json<Option<User>>> {
     UserRepository::find_by_username(&mut conn, &username)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
 // === Search ===
+#[utoipa::path(
+    tag = "users",
+    responses(
+        (status = 200, description = "Success", body = Vec<UserResponse>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/users/search/username?<q>")]
 pub async fn search_by_username(
     mut conn: Connection<DbConn>,
     q: String,
     _user: User,
-) -> Result<Custom<Json<Vec<User>>>, Custom<Json<Value>>> {
+) -> ApiResult<Custom<Json<Vec<User>>>> {
     UserRepository::search_by_username(&mut conn, &q)
         .await
         .map(|data| Custom(Status::Ok, Json(data)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "users",
+    responses(
+        (status = 200, description = "Success", body = Vec<UserResponse>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/users/search/email?<q>")]
 pub async fn search_by_email(
     mut conn: Connection<DbConn>,
     q: String,
     _user: User,
-) -> Result<Custom<Json<Vec<User>>>, Custom<Json<Value>>> {
+) -> ApiResult<Custom<Json<Vec<User>>>> {
     UserRepository::search_by_email(&mut conn, &q)
         .await
         .map(|data| Custom(Status::Ok, Json(data)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
 // === Listing ===
+#[utoipa::path(
+    tag = "users",
+    responses(
+        (status = 200, description = "Success", body = Vec<UserResponse>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/users?<page>&<limit>")]
 pub async fn list_users(
     mut conn: Connection<DbConn>,
     page: Option<u32>,
     limit: Option<u32>,
     _user: User,
-) -> Result<Custom<Json<Vec<User>>>, Custom<Json<Value>>> {
+) -> ApiResult<Custom<Json<Vec<User>>>> {
     let limit = limit.unwrap_or(50);
     let offset = page.unwrap_or(0) * limit;
 
     UserRepository::list_all(&mut conn, limit as i64, offset as i64)
         .await
         .map(|data| Custom(Status::Ok, Json(data)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
 // === Existence Checks ===
+#[utoipa::path(
+    tag = "users",
+    responses(
+        (status = 204, description = "A user with this email exists"),
+        (status = 404, description = "No such user"),
+        (status = 500, description = "Database error"),
+    ),
+)]
 #[head("/users/exists/email/<email>")]
 pub async fn exists_user_by_email(
     mut conn: Connection<DbConn>,
@@ -303,6 +324,14 @@ pub async fn exists_user_by_email(
     }
 }
 
+#[utoipa::path(
+    tag = "users",
+    responses(
+        (status = 204, description = "A user with this username exists"),
+        (status = 404, description = "No such user"),
+        (status = 500, description = "Database error"),
+    ),
+)]
 #[head("/users/exists/username/<username>")]
 pub async fn exists_user_by_username(
     mut conn: Connection<DbConn>,
@@ -317,34 +346,38 @@ pub async fn exists_user_by_username(
 }
 
 // === Relational & Aggregation ===
+#[utoipa::path(
+    tag = "users",
+    responses(
+        (status = 200, description = "Success", body = Vec<UserResponse>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/users/with-jobs")]
 pub async fn users_with_jobs(
     mut conn: Connection<DbConn>,
     _user: User,
-) -> Result<Custom<Json<Vec<User>>>, Custom<Json<Value>>> {
+) -> ApiResult<Custom<Json<Vec<User>>>> {
     UserRepository::find_users_with_jobs(&mut conn)
         .await
         .map(|data| Custom(Status::Ok, Json(data)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "users",
+    responses(
+        (status = 200, description = "Success", body = Vec<(UserResponse, i64)>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/users/job-counts")]
 pub async fn user_job_counts(
     mut conn: Connection<DbConn>,
     _user: User,
-) -> Result<Custom<Json<Vec<(User, i64)>>>, Custom<Json<Value>>> {
+) -> ApiResult<Custom<Json<Vec<(User, i64)>>>> {
     UserRepository::get_user_with_job_counts(&mut conn)
         .await
         .map(|data| Custom(Status::Ok, Json(data)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
@@ -0,0 +1,265 @@
+use crate::rocket_api::error::{deleted, ApiError, ApiResult, ErrorBody, OrNotFound};
+use chrono::Utc;
+use common::database::models::user::User;
+use common::database::models::webhook::{
+    NewWebhook, UpdateWebhook, Webhook, WebhookDelivery, WebhookRequest,
+};
+use common::database::repositories::{WebhookDeliveryRepository, WebhookRepository};
+use common::enums::webhook::WebhookEventEnum;
+use common::rocket::DbConn;
+use diesel_async::AsyncPgConnection;
+use rocket::http::Status;
+use rocket::response::status::Custom;
+use rocket::serde::json::Json;
+use rocket::{delete, get, patch, post, routes, Route};
+use rocket_db_pools::Connection;
+use serde::Serialize;
+use utoipa::{OpenApi, ToSchema};
+
+pub fn routes() -> Vec<Route> {
+    routes![
+        list_webhooks,
+        create_webhook,
+        update_webhook,
+        delete_webhook,
+        list_deliveries,
+        redeliver
+    ]
+}
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    list_webhooks,
+    create_webhook,
+    update_webhook,
+    delete_webhook,
+    list_deliveries,
+    redeliver
+))]
+pub struct WebhookApi;
+
+/* ===================== 🔔 Webhook API Overview =====================
+
+== 🛠️ CRUD (callers own webhooks) ==
+• GET     /webhooks                    → Callers webhooks                        → 200 OK (Vec<Webhook>)
+• POST    /webhooks                    → Register a webhook (WebhookRequest)     → 201 Created (Webhook + secret)
+• PATCH   /webhooks/:id                → Change url, events or active flag       → 200 OK (Webhook)
+• DELETE  /webhooks/:id                → Drop a webhook and its delivery log     → 204 No Content
+
+== 📬 Delivery log ==
+• GET     /webhooks/:id/deliveries?limit&offset          → Deliveries, newest first   → 200 OK (Vec<WebhookDelivery>)
+• POST    /webhooks/:id/deliveries/:delivery_id/redeliver → Send again from scratch    → 200 OK (WebhookDelivery)
+
+Events: JobSubmitted, JobRunning, JobCompleted, JobFailed, JobRequeued, WorkerUnreachable, only
+for the callers own jobs and workers. Each POST carries `X-Swarm-Event`, `X-Swarm-Delivery`,
+`X-Swarm-Timestamp` and `X-Swarm-Signature: sha256=<hex HMAC-SHA256 of "<timestamp>.<body>">`.
+The secret is only returned on creation, a missing secret is generated.
+
+======================================================================== */
+
+/// Response of a registration, the only one that carries the secret
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedWebhook {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+    pub secret: String,
+}
+
+/// Shortest secret accepted from callers
+const MIN_SECRET_LEN: usize = 16;
+
+fn bad_request(msg: &str) -> ApiError {
+    ApiError::BadRequest(msg.to_string())
+}
+
+fn validate(url: Option<&str>, events: Option<&[WebhookEventEnum]>) -> ApiResult<()> {
+    if let Some(url) = url {
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(bad_request("url must start with http:// or https://"));
+        }
+    }
+    if events.is_some_and(|e| e.is_empty()) {
+        return Err(bad_request("subscribe to at least one event"));
+    }
+    Ok(())
+}
+
+/// Load a webhook owned by the caller
+async fn owned_webhook(c: &mut AsyncPgConnection, id: i32, user: &User) -> ApiResult<Webhook> {
+    let webhook = WebhookRepository::find_by_id(c, id)
+        .await
+        .or_not_found(format!("Webhook {}", id))?;
+    if webhook.user_id != user.id {
+        return Err(ApiError::Forbidden(
+            "Only the owner can manage a webhook".to_string(),
+        ));
+    }
+    Ok(webhook)
+}
+
+#[utoipa::path(
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Success", body = Vec<Webhook>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[get("/webhooks")]
+pub async fn list_webhooks(
+    mut db: Connection<DbConn>,
+    user: User,
+) -> ApiResult<Json<Vec<Webhook>>> {
+    WebhookRepository::list_for_user(&mut db, user.id)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+#[utoipa::path(
+    tag = "webhooks",
+    request_body = WebhookRequest,
+    responses(
+        (status = 201, description = "Webhook registered, the only response carrying its secret", body = CreatedWebhook),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[post("/webhooks", format = "json", data = "<request>")]
+pub async fn create_webhook(
+    mut db: Connection<DbConn>,
+    request: Json<WebhookRequest>,
+    user: User,
+) -> ApiResult<Custom<Json<CreatedWebhook>>> {
+    let request = request.into_inner();
+    validate(Some(&request.url), Some(&request.events))?;
+    let secret = match request.secret {
+        Some(secret) if secret.len() < MIN_SECRET_LEN => {
+            return Err(bad_request(&format!(
+                "secret must be at least {} characters",
+                MIN_SECRET_LEN
+            )))
+        }
+        Some(secret) => secret,
+        None => Webhook::generate_secret(),
+    };
+
+    let webhook = WebhookRepository::create(
+        &mut db,
+        NewWebhook {
+            user_id: user.id,
+            url: request.url,
+            secret: secret.clone(),
+            events: request.events,
+        },
+    )
+    .await?;
+
+    Ok(Custom(
+        Status::Created,
+        Json(CreatedWebhook { webhook, secret }),
+    ))
+}
+
+#[utoipa::path(
+    tag = "webhooks",
+    request_body = UpdateWebhook,
+    responses(
+        (status = 200, description = "Success", body = Webhook),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 403, description = "Caller does not own the resource", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[patch("/webhooks/<id>", format = "json", data = "<changes>")]
+pub async fn update_webhook(
+    mut db: Connection<DbConn>,
+    id: i32,
+    changes: Json<UpdateWebhook>,
+    user: User,
+) -> ApiResult<Json<Webhook>> {
+    let changes = changes.into_inner();
+    validate(changes.url.as_deref(), changes.events.as_deref())?;
+    owned_webhook(&mut db, id, &user).await?;
+    WebhookRepository::update(&mut db, id, changes)
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
+
+#[utoipa::path(
+    tag = "webhooks",
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 403, description = "Caller does not own the resource", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[delete("/webhooks/<id>")]
+pub async fn delete_webhook(mut db: Connection<DbConn>, id: i32, user: User) -> ApiResult<Status> {
+    owned_webhook(&mut db, id, &user).await?;
+    let rows = WebhookRepository::delete(&mut db, id).await?;
+    deleted(rows, format!("Webhook {}", id))
+}
+
+#[utoipa::path(
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Success", body = Vec<WebhookDelivery>),
+        (status = 403, description = "Caller does not own the resource", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[get("/webhooks/<id>/deliveries?<limit>&<offset>")]
+pub async fn list_deliveries(
+    mut db: Connection<DbConn>,
+    id: i32,
+    limit: Option<i64>,
+    offset: Option<i64>,
+    user: User,
+) -> ApiResult<Json<Vec<WebhookDelivery>>> {
+    owned_webhook(&mut db, id, &user).await?;
+    WebhookDeliveryRepository::list_for_webhook(
+        &mut db,
+        id,
+        limit.unwrap_or(50).clamp(1, 500),
+        offset.unwrap_or(0).max(0),
+    )
+    .await
+    .map(Json)
+    .map_err(ApiError::from)
+}
+
+#[utoipa::path(
+    tag = "webhooks",
+    responses(
+        (status = 200, description = "Success", body = WebhookDelivery),
+        (status = 403, description = "Caller does not own the resource", body = ErrorBody),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[post("/webhooks/<id>/deliveries/<delivery_id>/redeliver")]
+pub async fn redeliver(
+    mut db: Connection<DbConn>,
+    id: i32,
+    delivery_id: i32,
+    user: User,
+) -> ApiResult<Json<WebhookDelivery>> {
+    owned_webhook(&mut db, id, &user).await?;
+    let delivery = WebhookDeliveryRepository::find_by_id(&mut db, delivery_id)
+        .await
+        .or_not_found(format!("Delivery {}", delivery_id))?;
+    if delivery.webhook_id != id {
+        return Err(ApiError::NotFound(format!(
+            "Delivery {} of webhook {} not found",
+            delivery_id, id
+        )));
+    }
+    WebhookDeliveryRepository::redeliver(&mut db, delivery.id, Utc::now().naive_utc())
+        .await
+        .map(Json)
+        .map_err(ApiError::from)
+}
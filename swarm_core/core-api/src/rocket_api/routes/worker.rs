@@ -1,19 +1,25 @@
 use crate::utils::parsing;
 use common::database::models::user::User;
 use common::database::models::worker::{NewWorker, Worker};
-use common::database::repositories::WorkerRepository;
+use common::database::repositories::listing::{ListQuery, Page};
+use common::database::repositories::{WorkerRepository, WORKER_LIST};
 use common::rocket::DbConn;
+use common::validation::Validate;
 
+use crate::rocket_api::error::{deleted, ApiError, ApiResult, ErrorBody, OrNotFound};
 use rocket::http::Status;
-use rocket::response::status::{Custom, NoContent};
-use rocket::serde::json::{json, Json, Value};
+use rocket::response::status::Custom;
+use rocket::serde::json::{Json, Value};
 use rocket::{delete, get, patch, post, put, routes, Route};
 use rocket_db_pools::Connection;
+use serde::Deserialize;
+use utoipa::{OpenApi, ToSchema};
 
 // === Mount routes ===
 pub fn routes() -> Vec<Route> {
     routes![
         create_worker,
+        list_workers,
         get_worker_by_id,
         delete_worker,
         get_workers_by_admin,
@@ -25,14 +31,34 @@ pub fn routes() -> Vec<Route> {
     ]
 }
 
+#[derive(OpenApi)]
+#[openapi(paths(
+    create_worker,
+    update_worker,
+    list_workers,
+    get_worker_by_id,
+    delete_worker,
+    get_workers_by_admin,
+    find_worker_by_label,
+    find_worker_by_ip,
+    list_workers_by_admin,
+    update_last_seen
+))]
+pub struct WorkerApi;
+
 /* ===================== ⚙️ Worker API Overview =====================
 
 == 🛠️ CRUD ==
 • POST    /workers                                      → Create new worker (NewWorker)      → 201 Created (Worker)
+                                                                                             → 400 Bad Request (invalid ip, hostname, ...)
 • GET     /workers/:id                                  → Fetch worker by ID                 → 200 OK (Worker)
 • DELETE  /workers/:id                                  → Delete worker by ID                → 204 No Content
 • PATCH   /workers/:id                                  → Update worker by ID                → 200 OK (Worker)
 
+== 📜 Listing ==
+• GET     /workers?filter&sort&limit&cursor             → Filtered, sorted page of workers   → 200 OK (Page<Worker>)
+  filter fields: id, user, name, hostname, status (latest reported), created
+
 == 🔍 Lookup & Search ==
 • GET     /workers/admin/:admin_id                      → Workers by Admin ID                → 200 OK (Vec<Worker>)
 • GET     /workers/label/:label                         → Find worker by label               → 200 OK (Option<Worker>)
@@ -45,122 +71,180 @@ pub fn routes() -> Vec<Route> {
 ======================================================================== */
 
 // ===== CRUD =====
+#[utoipa::path(
+    tag = "workers",
+    request_body = NewWorker,
+    responses(
+        (status = 201, description = "Created", body = Worker),
+        (status = 400, description = "Invalid fields, listed in `details.fields`", body = ErrorBody),
+        (status = 403, description = "`user_id` is not the caller", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[post("/workers", format = "json", data = "<new_worker>")]
 pub async fn create_worker(
     mut conn: Connection<DbConn>,
     new_worker: Json<NewWorker>,
-    _user: User,
-) -> Result<Custom<Json<Worker>>, Custom<Json<Value>>> {
+    user: User,
+) -> ApiResult<Custom<Json<Worker>>> {
+    if new_worker.user_id != user.id {
+        return Err(ApiError::Forbidden(
+            "Workers can only be registered for the caller".to_string(),
+        ));
+    }
+    new_worker.validate()?;
+
     WorkerRepository::create(&mut conn, new_worker.into_inner())
         .await
         .map(|w| Custom(Status::Created, Json(w)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "workers",
+    request_body = Worker,
+    responses(
+        (status = 200, description = "Success", body = Worker),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[patch("/workers/<id>", format = "json", data = "<worker>")]
 pub async fn update_worker(
     mut db: Connection<DbConn>,
     id: i32,
     worker: Json<Worker>,
     _user: User,
-) -> Result<Json<Worker>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Json<Worker>> {
     WorkerRepository::update(&mut db, id, worker.into_inner())
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+        .map_err(ApiError::from)
+}
+
+#[utoipa::path(
+    tag = "workers",
+    responses(
+        (status = 200, description = "Success", body = Page<Worker>),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
+#[get("/workers?<filter>&<sort>&<limit>&<cursor>")]
+pub async fn list_workers(
+    mut db: Connection<DbConn>,
+    filter: Option<&str>,
+    sort: Option<&str>,
+    limit: Option<i64>,
+    cursor: Option<&str>,
+    _user: User,
+) -> ApiResult<Json<Page<Worker>>> {
+    let query = ListQuery::parse(&WORKER_LIST, filter, sort, limit, cursor)?;
+    let rows = WorkerRepository::list(&mut db, &query).await?;
+    Ok(Json(query.paginate(rows)))
 }
 
+#[utoipa::path(
+    tag = "workers",
+    responses(
+        (status = 200, description = "Success", body = Worker),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+)]
 #[get("/workers/<id>")]
 pub async fn get_worker_by_id(
     mut conn: Connection<DbConn>,
     id: i32,
     _user: User,
-) -> Result<Json<Worker>, Custom<Json<Value>>> {
+) -> ApiResult<Json<Worker>> {
     WorkerRepository::find_by_id(&mut conn, id)
         .await
+        .or_not_found(format!("Worker {}", id))
         .map(Json)
-        .map_err(|e| Custom(Status::NotFound, Json(json!({"error": e.to_string()}))))
 }
 
+#[utoipa::path(
+    tag = "workers",
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[delete("/workers/<id>")]
 pub async fn delete_worker(
     mut conn: Connection<DbConn>,
     id: i32,
     _user: User,
-) -> Result<NoContent, Custom<Json<Value>>> {
-    WorkerRepository::delete_worker(&mut conn, id)
-        .await
-        .map(|_| NoContent)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+) -> ApiResult<Status> {
+    let rows = WorkerRepository::delete_worker(&mut conn, id).await?;
+    deleted(rows, format!("Worker {}", id))
 }
 
 // ===== Lookup & Search =====
+#[utoipa::path(
+    tag = "workers",
+    responses(
+        (status = 200, description = "Success", body = Vec<Worker>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/workers/admin/<admin_id>")]
 pub async fn get_workers_by_admin(
     mut conn: Connection<DbConn>,
     admin_id: i32,
     _user: User,
-) -> Result<Custom<Json<Vec<Worker>>>, Custom<Json<Value>>> {
+) -> ApiResult<Custom<Json<Vec<Worker>>>> {
     WorkerRepository::find_by_admin_id(&mut conn, admin_id)
         .await
         .map(|data| Custom(Status::Ok, Json(data)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "workers",
+    responses(
+        (status = 200, description = "Success", body = Option<Worker>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/workers/label/<label>")]
 pub async fn find_worker_by_label(
     mut conn: Connection<DbConn>,
     label: String,
     _user: User,
-) -> Result<Json<Option<Worker>>, Custom<Json<Value>>> {
+) -> ApiResult<Json<Option<Worker>>> {
     WorkerRepository::find_by_label(&mut conn, &label)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "workers",
+    responses(
+        (status = 200, description = "Success", body = Option<Worker>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/workers/ip/<ip_address>")]
 pub async fn find_worker_by_ip(
     mut conn: Connection<DbConn>,
     ip_address: String,
     _user: User,
-) -> Result<Json<Option<Worker>>, Custom<Json<Value>>> {
+) -> ApiResult<Json<Option<Worker>>> {
     WorkerRepository::find_by_ip_address(&mut conn, &ip_address)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "workers",
+    responses(
+        (status = 200, description = "Success", body = Vec<Worker>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/workers/admin/<admin_id>/list?<page>&<limit>")]
 pub async fn list_workers_by_admin(
     mut conn: Connection<DbConn>,
@@ -168,46 +252,49 @@ pub async fn list_workers_by_admin(
     page: Option<u32>,
     limit: Option<u32>,
     _user: User,
-) -> Result<Custom<Json<Vec<Worker>>>, Custom<Json<Value>>> {
+) -> ApiResult<Custom<Json<Vec<Worker>>>> {
     let limit = limit.unwrap_or(50);
     let offset = page.unwrap_or(0) as i64 * limit as i64;
     WorkerRepository::list_workers_by_admin(&mut conn, admin_id, limit as i64, offset)
         .await
         .map(|data| Custom(Status::Ok, Json(data)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
 // ===== State Update =====
+/// Body of `PUT /workers/<id>/last-seen`, any format `parse_naive_datetime` accepts
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LastSeenPayload {
+    pub last_seen_at: String,
+}
+
+#[utoipa::path(
+    tag = "workers",
+    request_body = LastSeenPayload,
+    responses(
+        (status = 200, description = "Success", body = Worker),
+        (status = 400, description = "Invalid input", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[put("/workers/<id>/last-seen", format = "json", data = "<last_seen>")]
 pub async fn update_last_seen(
     mut conn: Connection<DbConn>,
     id: i32,
     last_seen: Json<Value>,
     _user: User,
-) -> Result<Custom<Json<Worker>>, Custom<Json<Value>>> {
+) -> ApiResult<Custom<Json<Worker>>> {
     if let Some(last_seen_str) = last_seen.get("last_seen_at").and_then(Value::as_str) {
         // Use the parse_naive_datetime function for flexible date parsing
-        let parsed = parsing::parse_naive_datetime(last_seen_str)
-            .map_err(|e| Custom(Status::BadRequest, Json(json!({ "error": e }))))?;
+        let parsed = parsing::parse_naive_datetime(last_seen_str).map_err(ApiError::BadRequest)?;
 
         WorkerRepository::update_last_seen_at(&mut conn, id, parsed)
             .await
             .map(|w| Custom(Status::Ok, Json(w)))
-            .map_err(|e| {
-                Custom(
-                    Status::InternalServerError,
-                    Json(json!({ "error": e.to_string() })),
-                )
-            })
+            .map_err(ApiError::from)
     } else {
-        Err(Custom(
-            Status::BadRequest,
-            Json(json!({ "error": "Missing or invalid 'last_seen_at'" })),
+        Err(ApiError::BadRequest(
+            "Missing or invalid 'last_seen_at'".to_string(),
         ))
     }
 }
@@ -1,13 +1,15 @@
+use crate::rocket_api::error::{deleted, ApiError, ApiResult, ErrorBody, OrNotFound};
 use common::database::models::user::User;
 use common::database::models::worker::{NewWorkerStatus, WorkerStatus};
 use common::database::repositories::WorkerStatusRepository;
 use common::enums::workers::WorkerStatusEnum;
 use common::rocket::DbConn;
 use rocket::http::Status;
-use rocket::response::status::{Custom, NoContent};
-use rocket::serde::json::{json, Json, Value};
+use rocket::response::status::Custom;
+use rocket::serde::json::{Json, Value};
 use rocket::{delete, get, post, put, routes, Route};
 use rocket_db_pools::Connection;
+use utoipa::{OpenApi, ToSchema};
 
 // === Mount routes ===
 pub fn routes() -> Vec<Route> {
@@ -25,6 +27,21 @@ pub fn routes() -> Vec<Route> {
     ]
 }
 
+#[derive(OpenApi)]
+#[openapi(paths(
+    create_worker_status,
+    get_worker_status_by_id,
+    delete_worker_status,
+    get_status_by_worker_id,
+    update_status,
+    update_last_heartbeat,
+    update_active_job_id,
+    update_uptime,
+    update_load_avg,
+    update_last_error
+))]
+pub struct WorkerStatusApi;
+
 /* ===================== ⚙️ WorkerStatus API Overview =====================
 
 == 🛠️ CRUD ==
@@ -49,159 +66,203 @@ use chrono::Utc;
 use serde::Deserialize;
 
 // ===== CRUD =====
+#[utoipa::path(
+    tag = "worker-status",
+    request_body = NewWorkerStatus,
+    responses(
+        (status = 201, description = "Created", body = WorkerStatus),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[post("/worker-status", format = "json", data = "<new_status>")]
 pub async fn create_worker_status(
     mut conn: Connection<DbConn>,
     mut new_status: Json<NewWorkerStatus>,
     _user: User,
-) -> Result<Custom<Json<WorkerStatus>>, Custom<Json<Value>>> {
+) -> ApiResult<Custom<Json<WorkerStatus>>> {
     if new_status.last_heartbeat.is_none() {
         new_status.last_heartbeat = Some(Utc::now().naive_utc());
     }
     WorkerStatusRepository::create(&mut conn, new_status.into_inner())
         .await
         .map(|ws| Custom(Status::Created, Json(ws)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "worker-status",
+    responses(
+        (status = 200, description = "Success", body = WorkerStatus),
+        (status = 404, description = "Not found", body = ErrorBody),
+    ),
+)]
 #[get("/worker-status/<id>")]
 pub async fn get_worker_status_by_id(
     mut conn: Connection<DbConn>,
     id: i32,
     _user: User,
-) -> Result<Json<WorkerStatus>, Custom<Json<Value>>> {
+) -> ApiResult<Json<WorkerStatus>> {
     WorkerStatusRepository::find_by_id(&mut conn, id)
         .await
+        .or_not_found(format!("Worker status {}", id))
         .map(Json)
-        .map_err(|e| Custom(Status::NotFound, Json(json!({"error": e.to_string()}))))
 }
 
+#[utoipa::path(
+    tag = "worker-status",
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 404, description = "Not found", body = ErrorBody),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[delete("/worker-status/<id>")]
 pub async fn delete_worker_status(
     mut conn: Connection<DbConn>,
     id: i32,
     _user: User,
-) -> Result<NoContent, Custom<Json<Value>>> {
-    WorkerStatusRepository::delete_worker_status(&mut conn, id)
-        .await
-        .map(|_| NoContent)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+) -> ApiResult<Status> {
+    let rows = WorkerStatusRepository::delete_worker_status(&mut conn, id).await?;
+    deleted(rows, format!("Worker status {}", id))
 }
 
 // ===== Lookup =====
+#[utoipa::path(
+    tag = "worker-status",
+    responses(
+        (status = 200, description = "Success", body = Option<WorkerStatus>),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[get("/worker-status/worker/<worker_id>")]
 pub async fn get_status_by_worker_id(
     mut conn: Connection<DbConn>,
     worker_id: i32,
     _user: User,
-) -> Result<Json<Option<WorkerStatus>>, Custom<Json<Value>>> {
+) -> ApiResult<Json<Option<WorkerStatus>>> {
     WorkerStatusRepository::find_by_worker_id(&mut conn, worker_id)
         .await
         .map(Json)
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
 // ===== State Updates =====
+#[utoipa::path(
+    tag = "worker-status",
+    request_body = WorkerStatusEnum,
+    responses(
+        (status = 200, description = "Success", body = WorkerStatus),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[put("/worker-status/<id>/status", format = "json", data = "<status>")]
 pub async fn update_status(
     mut conn: Connection<DbConn>,
     id: i32,
     status: Json<WorkerStatusEnum>,
     _user: User,
-) -> Result<Custom<Json<WorkerStatus>>, Custom<Json<Value>>> {
+) -> ApiResult<Custom<Json<WorkerStatus>>> {
     WorkerStatusRepository::update_status(&mut conn, id, status.into_inner())
         .await
         .map(|ws| Custom(Status::Ok, Json(ws)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+#[utoipa::path(
+    tag = "worker-status",
+    responses(
+        (status = 200, description = "Success", body = WorkerStatus),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[put("/worker-status/<id>/last-heartbeat")]
 pub async fn update_last_heartbeat(
     mut conn: Connection<DbConn>,
     id: i32,
     _user: User,
-) -> Result<Custom<Json<WorkerStatus>>, Custom<Json<Value>>> {
+) -> ApiResult<Custom<Json<WorkerStatus>>> {
     WorkerStatusRepository::update_last_heartbeat(&mut conn, id)
         .await
         .map(|ws| Custom(Status::Ok, Json(ws)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({"error": e.to_string()})),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UpdateActiveJobId {
     active_job_id: Option<i32>,
 }
 
+#[utoipa::path(
+    tag = "worker-status",
+    request_body = UpdateActiveJobId,
+    responses(
+        (status = 200, description = "Success", body = WorkerStatus),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[put("/worker-status/<id>/active-job-id", format = "json", data = "<data>")]
 pub async fn update_active_job_id(
     mut conn: Connection<DbConn>,
     id: i32,
     data: Json<UpdateActiveJobId>,
     _user: User,
-) -> Result<Custom<Json<WorkerStatus>>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Custom<Json<WorkerStatus>>> {
     WorkerStatusRepository::update_active_job_id(&mut conn, id, data.active_job_id)
         .await
         .map(|ws| Custom(Status::Ok, Json(ws)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+        .map_err(ApiError::from)
+}
+
+/// Body of `PUT /worker-status/<id>/uptime`, seconds since the worker started
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UptimePayload {
+    pub uptime: Option<i32>,
 }
 
+#[utoipa::path(
+    tag = "worker-status",
+    request_body = UptimePayload,
+    responses(
+        (status = 200, description = "Success", body = WorkerStatus),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[put("/worker-status/<id>/uptime", format = "json", data = "<data>")]
 pub async fn update_uptime(
     mut conn: Connection<DbConn>,
     id: i32,
     data: Json<serde_json::Value>,
     _user: User,
-) -> Result<Custom<Json<WorkerStatus>>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Custom<Json<WorkerStatus>>> {
     let uptime = data.get("uptime").and_then(Value::as_i64).map(|v| v as i32);
 
     WorkerStatusRepository::update_uptime(&mut conn, id, uptime)
         .await
         .map(|ws| Custom(Status::Ok, Json(ws)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+        .map_err(ApiError::from)
 }
 
+/// Body of `PUT /worker-status/<id>/load-avg`, the 1, 5 and 15 minute load averages
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LoadAvgPayload {
+    pub load_avg: Option<Vec<f32>>,
+}
+
+#[utoipa::path(
+    tag = "worker-status",
+    request_body = LoadAvgPayload,
+    responses(
+        (status = 200, description = "Success", body = WorkerStatus),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[put("/worker-status/<id>/load-avg", format = "json", data = "<data>")]
 pub async fn update_load_avg(
     mut conn: Connection<DbConn>,
     id: i32,
     data: Json<serde_json::Value>,
     _user: User,
-) -> Result<Custom<Json<WorkerStatus>>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Custom<Json<WorkerStatus>>> {
     let load_avg = data.get("load_avg").and_then(Value::as_array).map(|arr| {
         arr.iter()
             .filter_map(Value::as_f64)
@@ -212,21 +273,30 @@ pub async fn update_load_avg(
     WorkerStatusRepository::update_load_avg(&mut conn, id, load_avg)
         .await
         .map(|ws| Custom(Status::Ok, Json(ws)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+        .map_err(ApiError::from)
+}
+
+/// Body of `PUT /worker-status/<id>/last-error`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LastErrorPayload {
+    pub last_error: Option<String>,
 }
 
+#[utoipa::path(
+    tag = "worker-status",
+    request_body = LastErrorPayload,
+    responses(
+        (status = 200, description = "Success", body = WorkerStatus),
+        (status = 500, description = "Database error", body = ErrorBody),
+    ),
+)]
 #[put("/worker-status/<id>/last-error", format = "json", data = "<data>")]
 pub async fn update_last_error(
     mut conn: Connection<DbConn>,
     id: i32,
     data: Json<serde_json::Value>,
     _user: User,
-) -> Result<Custom<Json<WorkerStatus>>, Custom<Json<serde_json::Value>>> {
+) -> ApiResult<Custom<Json<WorkerStatus>>> {
     let last_error = data
         .get("last_error")
         .and_then(Value::as_str)
@@ -235,10 +305,5 @@ pub async fn update_last_error(
     WorkerStatusRepository::update_last_error(&mut conn, id, last_error)
         .await
         .map(|ws| Custom(Status::Ok, Json(ws)))
-        .map_err(|e| {
-            Custom(
-                Status::InternalServerError,
-                Json(json!({ "error": e.to_string() })),
-            )
-        })
+        .map_err(ApiError::from)
 }
@@ -1,2 +1,4 @@
 pub mod service_channels;
-pub use service_channels::{ServiceChannels, ServiceWiring};
+pub use service_channels::{
+    ControlCommand, LifecycleEvent, ModuleDump, ServiceChannels, ServiceWiring,
+};
@@ -1,6 +1,16 @@
+use common::database::models::worker::Worker;
 use common::enums::system::CoreEvent;
+use common::enums::webhook::WebhookEventEnum;
+use serde_json::{json, Value};
 use std::collections::HashMap;
-use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio::sync::{broadcast, mpsc, watch, RwLock};
+
+use crate::core::health::CoreModule;
+
+/// Lifecycle events waiting for the Notifier before the oldest are dropped
+const LIFECYCLE_EVENT_CAPACITY: usize = 1024;
+/// Control commands not yet seen by a slow subscriber before the oldest are dropped
+const CONTROL_CAPACITY: usize = 16;
 
 /// Enum for different one-to-one channel types.
 #[derive(Hash, Eq, PartialEq, Debug, Clone)]
@@ -30,18 +40,78 @@ impl NotificationEvent {
     }
 }
 
+/// A worker changed state, published by the module that noticed
+/// Job events do not travel here, `JobRepository` records them with the transition
+#[derive(Debug, Clone)]
+pub struct LifecycleEvent {
+    pub event: WebhookEventEnum,
+    // owner of the worker, only the owners webhooks are called
+    pub user_id: i32,
+    // the worker after the change
+    pub data: Value,
+}
+
+impl LifecycleEvent {
+    /// A worker stopped sending heartbeats
+    pub fn worker_unreachable(worker: &Worker) -> Self {
+        LifecycleEvent {
+            event: WebhookEventEnum::WorkerUnreachable,
+            user_id: worker.user_id,
+            data: json!({
+                "id": worker.id,
+                "label": worker.label,
+                "hostname": worker.hostname,
+                "ip_address": worker.ip_address,
+            }),
+        }
+    }
+}
+
+/// Runtime control of core, sent by the admin routes
+#[derive(Debug, Clone)]
+pub enum ControlCommand {
+    /// Graceful shutdown of core, handled by main like ctrl-c
+    Shutdown,
+    /// Stop one module and build it again, handled by its supervisor
+    RestartModule(CoreModule),
+    /// Modules with inspectable state answer with a `ModuleDump` on the enclosed sender
+    DumpState(mpsc::Sender<ModuleDump>),
+}
+
+/// Internal state of one module, the answer to `ControlCommand::DumpState`
+#[derive(Debug, Clone)]
+pub struct ModuleDump {
+    pub module: CoreModule,
+    pub state: Value,
+}
+
 /// Global service channels for system-wide events.
 /// - `core_event_tx` broadcasts events to all subscribers.
+/// - `lifecycle_event_tx` broadcasts job and worker state changes, consumed by the Notifier.
+/// - `control_tx` broadcasts admin commands to main, the supervisors and the modules.
+/// - `scheduler_paused_tx` holds whether the Scheduler may admit jobs, it outlives Scheduler restarts.
 /// - `corebridge_to_main_tx` and `corebridge_to_main_rx` form a one-to-one channel.
 #[doc = include_str!("../../../docs/core/services/service_channels.md")]
 pub struct ServiceChannels {
     pub core_event_tx: broadcast::Sender<CoreEvent>,
+    pub lifecycle_event_tx: broadcast::Sender<LifecycleEvent>,
+    pub control_tx: broadcast::Sender<ControlCommand>,
+    pub scheduler_paused_tx: watch::Sender<bool>,
 }
 
 impl ServiceChannels {
     pub fn new() -> Self {
         let (core_event_tx, _) = broadcast::channel::<CoreEvent>(16);
-        ServiceChannels { core_event_tx }
+        let (lifecycle_event_tx, _) =
+            broadcast::channel::<LifecycleEvent>(LIFECYCLE_EVENT_CAPACITY);
+        let (control_tx, _) = broadcast::channel::<ControlCommand>(CONTROL_CAPACITY);
+        let (scheduler_paused_tx, _) = watch::channel(false);
+        ServiceChannels {
+            core_event_tx,
+            lifecycle_event_tx,
+            control_tx,
+            scheduler_paused_tx,
+        }
     }
 
     /// Broadcasts an event to all subscribers.
@@ -53,6 +123,40 @@ impl ServiceChannels {
     pub fn subscribe_to_core_event(&self) -> broadcast::Receiver<CoreEvent> {
         self.core_event_tx.subscribe()
     }
+
+    /// Publishes a job or worker state change, dropped when nobody listens.
+    pub fn publish_lifecycle_event(&self, event: LifecycleEvent) {
+        let _ = self.lifecycle_event_tx.send(event);
+    }
+
+    /// Returns a new subscription to job and worker state changes.
+    pub fn subscribe_to_lifecycle_events(&self) -> broadcast::Receiver<LifecycleEvent> {
+        self.lifecycle_event_tx.subscribe()
+    }
+
+    /// Broadcasts a control command, false when nobody listens.
+    pub fn send_control(&self, command: ControlCommand) -> bool {
+        self.control_tx.send(command).is_ok()
+    }
+
+    /// Returns a new subscription to control commands.
+    pub fn subscribe_to_control(&self) -> broadcast::Receiver<ControlCommand> {
+        self.control_tx.subscribe()
+    }
+
+    /// Pause or resume the Scheduler, submissions are accepted either way.
+    pub fn set_scheduler_paused(&self, paused: bool) {
+        self.scheduler_paused_tx.send_replace(paused);
+    }
+
+    pub fn scheduler_paused(&self) -> bool {
+        *self.scheduler_paused_tx.borrow()
+    }
+
+    /// Returns a receiver of the Scheduler pause flag, starting at its current value.
+    pub fn subscribe_scheduler_paused(&self) -> watch::Receiver<bool> {
+        self.scheduler_paused_tx.subscribe()
+    }
 }
 
 impl Default for ServiceChannels {
@@ -0,0 +1,114 @@
+use anyhow::Context;
+use chrono::Duration;
+use diesel_async::{AsyncConnection, AsyncPgConnection};
+use reqwest::StatusCode;
+
+use common::database::models::log::NewDBLogEntry;
+use common::database::repositories::{JobArchiveRepository, LogEntryRepository};
+use common::enums::log::{LogActionEnum, LogLevelEnum};
+use common::enums::system::SystemModuleEnum;
+use swarm_client::models::Job;
+use swarm_client::SwarmClient;
+
+pub mod common_test;
+
+/// A connection of its own, pooled ones would outlive the runtime of the test that opened them
+async fn db() -> anyhow::Result<AsyncPgConnection> {
+    let url = std::env::var("DATABASE_URL").context("DATABASE_URL of the core under test")?;
+    Ok(AsyncPgConnection::establish(&url).await?)
+}
+
+/// A failed job of the caller with a result and a log entry naming it
+async fn failed_job_with_log(client: &SwarmClient, user_id: i32) -> anyhow::Result<(Job, i32)> {
+    let job = client.create_job(&common_test::new_job(user_id)).await?;
+    common_test::assign_result_to_job(client, job.id).await?;
+    let job = common_test::mark_job_failed(client, job.id).await?;
+
+    let mut db = db().await?;
+    let log = LogEntryRepository::create(
+        &mut db,
+        NewDBLogEntry {
+            created_at: None,
+            level: LogLevelEnum::Info,
+            module: SystemModuleEnum::Scheduler,
+            action: LogActionEnum::JobSubmitted,
+            expires_at: common_test::get_ndt_now() + Duration::days(1),
+            client_connected_ip: None,
+            client_connected_username: None,
+            job_submitted_job_id: Some(job.id),
+            job_submitted_from_module: Some(SystemModuleEnum::Scheduler),
+            job_submitted_to_module: Some(SystemModuleEnum::Dispatcher),
+            job_completed_job_id: None,
+            job_completed_success: None,
+            custom_msg: None,
+        },
+    )
+    .await?;
+    Ok((job, log.id))
+}
+
+/// Run archive passes like the TaskArchive does until `job` is archived,
+/// finished jobs of other tests older than it are archived along the way
+async fn archive(job: &Job) -> anyhow::Result<()> {
+    let mut db = db().await?;
+    let cutoff = job.updated_at + Duration::seconds(1);
+    while JobArchiveRepository::archive_finished(&mut db, cutoff, cutoff, 500).await? == 500 {}
+    Ok(())
+}
+
+#[cfg(test)]
+mod archive_api_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_archived_job_is_restored_with_its_logs() -> anyhow::Result<()> {
+        let (client, user) = common_test::build_client_with_logged_in_admin().await?;
+        let (job, log_id) = failed_job_with_log(&client, user.id).await?;
+
+        archive(&job).await?;
+        let err = client.get_job(job.id).await.unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::NOT_FOUND));
+        let archived = client.get_archived_job(job.id).await?;
+        assert_eq!(archived.summary.result_count, 1);
+        assert_eq!(archived.data["results"].as_array().unwrap().len(), 1);
+        // the delete cascaded to the log entry, the snapshot still has it
+        let mut db = db().await?;
+        assert!(LogEntryRepository::find_by_id(&mut db, log_id)
+            .await
+            .is_err());
+        assert_eq!(archived.data["logs"][0]["id"], log_id);
+
+        let restored = client.restore_archived_job(job.id).await?;
+        assert_eq!(restored.id, job.id);
+        assert_eq!(restored.job_name, job.job_name);
+        assert_eq!(client.results_for_job(job.id).await?.len(), 1);
+        let log = LogEntryRepository::find_by_id(&mut db, log_id).await?;
+        assert_eq!(log.job_submitted_job_id, Some(job.id));
+        let err = client.get_archived_job(job.id).await.unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::NOT_FOUND));
+
+        common_test::delete_job_via_api(&client, job.id).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_only_the_owner_or_an_admin_restores() -> anyhow::Result<()> {
+        let (owner, owner_user) = common_test::build_client_with_logged_in_admin().await?;
+        let (other, other_user) = common_test::build_client_with_logged_in_admin().await?;
+        let (job, _) = failed_job_with_log(&owner, owner_user.id).await?;
+        archive(&job).await?;
+
+        let err = other.restore_archived_job(job.id).await.unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+
+        let admin = common_test::build_client_with_core_admin().await?;
+        let restored = admin.restore_archived_job(job.id).await?;
+        assert_eq!(restored.user_id, owner_user.id);
+
+        common_test::delete_job_via_api(&owner, job.id).await?;
+        common_test::delete_user_via_api(&other, other_user.id).await?;
+        common_test::delete_user_via_api(&owner, owner_user.id).await?;
+        Ok(())
+    }
+}
@@ -1,15 +1,16 @@
-use anyhow::{anyhow, ensure, Context};
+use anyhow::Context;
 use chrono::{NaiveDateTime, Utc};
-use diesel_async::AsyncPgConnection;
-use reqwest::{header, Client, ClientBuilder, StatusCode};
-use serde_json::json;
 use uuid::Uuid;
 
-use common::commands;
-use common::database::models::job::{Job, JobAssignment, JobMetric, JobResult};
-use common::database::models::user::UserResponse;
-use common::database::models::worker::{Worker, WorkerStatus};
-use common::database::repositories::{JobAssignmentRepository, JobRepository};
+use swarm_client::enums::{
+    ImageFormatEnum, JobStateEnum, OSEnum, OutputTypeEnum, ScheduleTypeEnum, WorkerStatusEnum,
+};
+use swarm_client::models::{
+    Credentials, Job, JobAssignment, JobMetric, JobResult, NewJob, NewJobAssignment, NewJobMetric,
+    NewJobResult, NewUserRequest, NewWorker, NewWorkerStatus, SessionToken, UserResponse, Worker,
+    WorkerStatus,
+};
+use swarm_client::SwarmClient;
 
 // ===== UTILITIES =====
 pub fn get_ndt_now() -> NaiveDateTime {
@@ -21,8 +22,9 @@ pub const APP_HOST: &str = "http://localhost:8000";
 pub const TEST_PASSWORD: &str = "ADMINPASSWORD123";
 
 // ========== HTTP Client Setup ==========
-pub fn http_client() -> Client {
-    Client::new()
+/// Anonymous client, enough to create users and log in
+pub fn http_client() -> SwarmClient {
+    SwarmClient::new(APP_HOST).expect("APP_HOST is a valid base url")
 }
 
 // ========== User Utilities ==========
@@ -33,55 +35,35 @@ pub fn generate_unique_username() -> String {
 
 /// Create a user via the HTTP API
 pub async fn create_user_via_api(
-    client: &Client,
+    client: &SwarmClient,
     username: &str,
-) -> anyhow::Result<UserResponse, anyhow::Error> {
-    let email = format!("{}@example.com", username);
-    let res = client
-        .post(&format!("{}/users", APP_HOST))
-        .json(&json!({
-            "username": username,
-            "email": email,
-            "password": TEST_PASSWORD
-        }))
-        .send()
+) -> anyhow::Result<UserResponse> {
+    let request = NewUserRequest {
+        username: username.to_string(),
+        email: format!("{}@example.com", username),
+        password: TEST_PASSWORD.to_string(),
+    };
+    client
+        .create_user(&request)
         .await
-        .context("Failed to send create-user request")?;
-
-    assert!(
-        res.status().is_success(),
-        "User creation failed (status={}): {:?}",
-        res.status(),
-        res.text().await.unwrap_or_default()
-    );
-
-    let user_resp = res
-        .json::<UserResponse>()
-        .await
-        .context("Failed to deserialize User from create response")?;
-
-    Ok(user_resp)
+        .context("Failed to create user")
 }
 
-/// Attempt login via HTTP API, returning the response
+/// Attempt login via HTTP API
 pub async fn login_user(
-    client: &Client,
+    client: &SwarmClient,
     username: &str,
     password: &str,
-) -> anyhow::Result<reqwest::Response, anyhow::Error> {
-    let resp = client
-        .post(&format!("{}/login", APP_HOST))
-        .json(&json!({ "username": username, "password": password }))
-        .send()
-        .await
-        .context("Login request failed")?;
-
-    Ok(resp)
+) -> swarm_client::Result<SessionToken> {
+    let credentials = Credentials {
+        username: username.to_string(),
+        password: password.to_string(),
+    };
+    client.login(&credentials).await
 }
 
 /// Returns a logged-in client and the corresponding user
-pub async fn build_client_with_logged_in_admin(
-) -> anyhow::Result<(Client, UserResponse), anyhow::Error> {
+pub async fn build_client_with_logged_in_admin() -> anyhow::Result<(SwarmClient, UserResponse)> {
     let client = http_client();
 
     let username = generate_unique_username();
@@ -89,49 +71,41 @@ pub async fn build_client_with_logged_in_admin(
         .await
         .context("Failed to create user with api")?;
 
-    let login_resp = login_user(&client, &username, TEST_PASSWORD).await?;
-
-    ensure!(login_resp.status() == StatusCode::OK,);
+    let session = login_user(&client, &username, TEST_PASSWORD)
+        .await
+        .context("Login failed")?;
 
-    let token = login_resp.json::<serde_json::Value>().await.unwrap()["token"]
-        .as_str()
-        .ok_or_else(|| anyhow!("could not extract login token"))?
-        .to_string();
+    Ok((client.authenticated(&session.token)?, user))
+}
 
-    let mut headers = header::HeaderMap::new();
-    headers.insert(
-        header::AUTHORIZATION,
-        header::HeaderValue::from_str(&format!("Bearer {}", token)).context("invalid tokens")?,
-    );
+/// Username the tests of admin-only routes log in with, core must list it in `CORE_ADMIN_USERS`
+pub fn core_admin_username() -> String {
+    std::env::var("TEST_CORE_ADMIN").unwrap_or_else(|_| "test_core_admin".to_string())
+}
 
-    let authorized_client = ClientBuilder::new().default_headers(headers).build()?;
+/// Logged-in client of the core admin, the user is created on first use and kept afterwards
+pub async fn build_client_with_core_admin() -> anyhow::Result<SwarmClient> {
+    let client = http_client();
+    let username = core_admin_username();
+    // already there after the first run
+    let _ = create_user_via_api(&client, &username).await;
 
-    Ok((authorized_client, user))
+    let session = login_user(&client, &username, TEST_PASSWORD)
+        .await
+        .context("Login of the core admin failed")?;
+    Ok(client.authenticated(&session.token)?)
 }
 
 /// Delete user via API by ID
-pub async fn delete_user_via_api(client: &Client, id: i32) -> anyhow::Result<(), anyhow::Error> {
-    let res = client
-        .delete(&format!("{}/users/{}", APP_HOST, id))
-        .send()
+pub async fn delete_user_via_api(client: &SwarmClient, id: i32) -> anyhow::Result<()> {
+    client
+        .delete_user(id)
         .await
-        .context("Delete-user request failed")?;
-
-    assert!(
-        res.status().is_success() || res.status() == StatusCode::NO_CONTENT,
-        "Delete-user failed: status={} body={}",
-        res.status(),
-        res.text().await.unwrap_or_default()
-    );
-
-    Ok(())
+        .context("Delete-user request failed")
 }
 
 /// Delete multiple users
-pub async fn delete_users_via_api(
-    client: &Client,
-    user_ids: &[i32],
-) -> anyhow::Result<(), anyhow::Error> {
+pub async fn delete_users_via_api(client: &SwarmClient, user_ids: &[i32]) -> anyhow::Result<()> {
     for &id in user_ids {
         delete_user_via_api(client, id)
             .await
@@ -147,39 +121,36 @@ pub fn generate_unique_job_name() -> String {
     format!("test_job_{}", Uuid::new_v4())
 }
 
+/// One-time alpine job owned by `user_id`
+pub fn new_job(user_id: i32) -> NewJob {
+    NewJob {
+        user_id,
+        job_name: generate_unique_job_name(),
+        image_url: "docker.io/library/alpine:latest".to_string(),
+        image_format: ImageFormatEnum::DockerRegistry,
+        docker_flags: None,
+        output_type: OutputTypeEnum::Stdout,
+        output_paths: None,
+        schedule_type: ScheduleTypeEnum::Once,
+        cron_expression: None,
+        notes: None,
+        priority: 0,
+    }
+}
+
 /// Create `n` jobs and return client, user, jobs, job_ids
 pub async fn build_client_and_user_with_n_jobs(
     n: usize,
-) -> anyhow::Result<(Client, UserResponse, Vec<Job>, Vec<i32>), anyhow::Error> {
+) -> anyhow::Result<(SwarmClient, UserResponse, Vec<Job>, Vec<i32>)> {
     let (client, user) = build_client_with_logged_in_admin().await?;
     let mut jobs = Vec::with_capacity(n);
     let mut job_ids = Vec::with_capacity(n);
 
     for _ in 0..n {
-        let payload = json!({
-            "user_id": user.id,
-            "job_name": generate_unique_job_name(),
-            "image_url": "docker.io/library/alpine:latest",
-            "image_format": "DockerRegistry",
-            "docker_flags": null,
-            "output_type": "Stdout",
-            "output_paths": null,
-            "schedule_type": "Once",
-            "cron_expression": null,
-            "notes": null,
-            "state": "Queued"
-        });
-
-        let resp = client
-            .post(format!("{}/jobs", APP_HOST))
-            .json(&payload)
-            .send()
+        let job = client
+            .create_job(&new_job(user.id))
             .await
             .context("Job creation request failed")?;
-
-        assert_eq!(resp.status(), StatusCode::CREATED);
-
-        let job: Job = resp.json().await.expect("Failed to parse created job");
         job_ids.push(job.id);
         jobs.push(job);
     }
@@ -188,412 +159,176 @@ pub async fn build_client_and_user_with_n_jobs(
 }
 
 /// Delete a job by ID
-pub async fn delete_job_via_api(client: &Client, job_id: i32) -> anyhow::Result<(), anyhow::Error> {
-    let resp = client
-        .delete(&format!("{}/jobs/{}", APP_HOST, job_id))
-        .send()
+pub async fn delete_job_via_api(client: &SwarmClient, job_id: i32) -> anyhow::Result<()> {
+    client
+        .delete_job(job_id)
         .await
-        .context("Failed to delete job")?;
-
-    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
-
-    Ok(())
+        .context("Failed to delete job")
 }
 
 /// Delete multiple jobs
-pub async fn delete_jobs_via_api(
-    client: &Client,
-    job_ids: &[i32],
-) -> anyhow::Result<(), anyhow::Error> {
+pub async fn delete_jobs_via_api(client: &SwarmClient, job_ids: &[i32]) -> anyhow::Result<()> {
     for &id in job_ids {
         delete_job_via_api(client, id).await?;
     }
     Ok(())
 }
 
-/// Mark a job as running, use pg connection and bypass rocket routes
-/// # Arguments
-/// * `job_id` - The ID of the job to mark as running
-/// * `client` - The client, must be logged in
-/// # Returns
-/// * `anyhow::Result<(), anyhow::Error>`
-/// # Example
-/// ```
-/// let client = build_client_with_logged_in_admin().await;
-/// mark_job_running(&client, 1).await;
-/// ```
-pub async fn mark_job_running(client: &Client, job_id: i32) -> anyhow::Result<(), anyhow::Error> {
-    //get job
-    let mut conn: AsyncPgConnection = commands::load_db_connection()
+/// Stores `state` on the job through `PATCH /jobs/<id>`, keeping every other field
+/// Core only accepts legal transitions, e.g. Submitted → Queued but not Submitted → Running
+pub async fn set_job_state(
+    client: &SwarmClient,
+    job_id: i32,
+    state: JobStateEnum,
+) -> anyhow::Result<Job> {
+    let mut job = client
+        .get_job(job_id)
         .await
-        .context("Failed to load db connection")?;
+        .context("Could not find job through id")?;
+    job.state = state;
+    job.updated_at = get_ndt_now();
 
-    let job: Job = JobRepository::find_by_id(&mut conn, job_id)
-        .await
-        .context("Could not find job through id in db")?;
-
-    let updated_payload = serde_json::json!({
-        "id": job_id,
-        "user_id": job.user_id,
-        "job_name": job.job_name,
-        "image_url": job.image_url,
-        "image_format": job.image_format,
-        "docker_flags": job.docker_flags,
-        "output_type": job.output_type,
-        "output_paths": job.output_paths,
-        "schedule_type": job.schedule_type,
-        "cron_expression": job.cron_expression,
-        "notes": job.notes,
-        "state": "Running",
-        "created_at": job.created_at,
-        "updated_at": Utc::now().naive_utc(),
-    });
-
-    let _ = client
-        .patch(format!("{}/jobs/{job_id}", APP_HOST))
-        .json(&updated_payload)
-        .send()
+    client
+        .update_job(job_id, &job)
         .await
-        .context("Failed to mark job complete")?;
-
-    Ok(())
+        .with_context(|| format!("Failed to mark job {}", state))
 }
 
-/// Mark a job as failed through direct postgres API call bypassing rocket routes
-/// # Arguments
-/// * `job_id` - The ID of the job
-/// * `client` - The client, must be logged in
-/// # Returns
-/// * `anyhow::Result<(), anyhow::Error>`
-/// # Example
-/// ```
-/// let client = build_client_with_logged_in_admin().await;
-/// mark_job_failed(&client, 1).await;
-/// ```
-pub async fn mark_job_failed(client: &Client, job_id: i32) -> anyhow::Result<(), anyhow::Error> {
-    //get job
-    let mut conn: AsyncPgConnection = commands::load_db_connection()
-        .await
-        .context("Failed to load db connection")?;
-    let job: Job = JobRepository::find_by_id(&mut conn, job_id)
-        .await
-        .context("Could not find job through id in db")?;
-
-    let updated_payload = serde_json::json!({
-        "id": job_id,
-        "user_id": job.user_id,
-        "job_name": job.job_name,
-        "image_url": job.image_url,
-        "image_format": job.image_format,
-        "docker_flags": job.docker_flags,
-        "output_type": job.output_type,
-        "output_paths": job.output_paths,
-        "schedule_type": job.schedule_type,
-        "cron_expression": job.cron_expression,
-        "notes": job.notes,
-        "state": "Failed",
-        "created_at": job.created_at,
-        "updated_at": Utc::now().naive_utc(),
-    });
-
-    let _ = client
-        .patch(format!("{}/jobs/{job_id}", APP_HOST))
-        .json(&updated_payload)
-        .send()
+/// Queue a submitted job without waiting for the Scheduler, then mark it running
+pub async fn mark_job_running(client: &SwarmClient, job_id: i32) -> anyhow::Result<Job> {
+    set_job_state(client, job_id, JobStateEnum::Queued).await?;
+    client
+        .mark_job_running(job_id)
         .await
-        .context("Failed to mark job complete")?;
+        .context("Failed to mark job running")
+}
 
-    Ok(())
+/// Mark a job as failed
+pub async fn mark_job_failed(client: &SwarmClient, job_id: i32) -> anyhow::Result<Job> {
+    set_job_state(client, job_id, JobStateEnum::Failed).await
 }
 
-/// Mark an assignment as started via the API, bypassing rocket routes
-/// # Arguments
-/// * `client` - The client, must be logged in
-/// * `assignment_id` - The ID of the assignment
-/// * `started_at` - The time the assignment was started
-/// # Returns
-/// * `anyhow::Result<(), anyhow::Error>`
-/// # Example
-/// ```
-/// let client = build_client_with_logged_in_admin().await;
-/// let dt = Utc::now().naive_utc();
-/// mark_assignment_started_via_api(&client, 1, dt).await;
-/// ```
+/// Mark an assignment as started via the API
 pub async fn mark_assignment_started_via_api(
-    client: &Client,
+    client: &SwarmClient,
     assignment_id: i32,
     started_at: NaiveDateTime,
-) -> anyhow::Result<(), anyhow::Error> {
-    // Fetch the assignment details
-    let mut conn: AsyncPgConnection = commands::load_db_connection()
-        .await
-        .context("Failed to load db connection")?;
-    let assignment: JobAssignment = JobAssignmentRepository::find_by_id(&mut conn, assignment_id)
-        .await
-        .context("Could not find assignment by id")?;
-
-    // Construct the payload to update the assignment
-    let updated_payload = json!({
-        "id": assignment.id,
-        "job_id": assignment.job_id,
-        "worker_id": assignment.worker_id,
-        "assigned_at": assignment.assigned_at,
-        "started_at": started_at.to_string(),
-        "finished_at": assignment.finished_at,
-    });
-
-    // Send the PATCH request
-    let resp = client
-        .patch(&format!(
-            "{}/assignments/{}/started",
-            APP_HOST, assignment_id
-        ))
-        .json(&updated_payload)
-        .send()
+) -> anyhow::Result<JobAssignment> {
+    client
+        .mark_assignment_started(assignment_id, started_at)
         .await
-        .context("Failed to mark assignment started")?;
-
-    // Assert that the request was successful
-    assert_eq!(resp.status(), StatusCode::OK);
-
-    Ok(())
+        .context("Failed to mark assignment started")
 }
 
 /// Mark a job assignment as finished via API
 pub async fn mark_assignment_finished_via_api(
-    client: &Client,
+    client: &SwarmClient,
     assignment_id: i32,
     finished_at: NaiveDateTime,
-) -> anyhow::Result<(), anyhow::Error> {
-    // Fetch the assignment details
-    let mut conn: AsyncPgConnection = commands::load_db_connection()
-        .await
-        .context("Failed to load db connection")?;
-    let assignment: JobAssignment = JobAssignmentRepository::find_by_id(&mut conn, assignment_id)
-        .await
-        .context("Could not find assignment by id")?;
-
-    // Construct the payload to update the assignment
-    let updated_payload = json!({
-        "id": assignment.id,
-        "job_id": assignment.job_id,
-        "worker_id": assignment.worker_id,
-        "assigned_at": assignment.assigned_at,
-        "started_at": assignment.started_at,
-        "finished_at": finished_at.to_string(), // Adding the finished_at value
-    });
-
-    // Send the PATCH request
-    let resp = client
-        .patch(&format!(
-            "{}/assignments/{}/finished",
-            APP_HOST, assignment_id
-        ))
-        .json(&updated_payload)
-        .send()
+) -> anyhow::Result<JobAssignment> {
+    client
+        .mark_assignment_finished(assignment_id, finished_at)
         .await
-        .context("Failed to mark assignment finished")?;
-
-    // Assert that the request was successful
-    assert_eq!(resp.status(), StatusCode::OK);
-
-    Ok(())
+        .context("Failed to mark assignment finished")
 }
 
 // Metrics
 pub async fn create_metric_via_api(
-    client: &Client,
+    client: &SwarmClient,
     job_id: i32,
     worker_id: i32,
     duration_sec: i32,
     cpu_usage_pct: f32,
     mem_usage_mb: f32,
     exit_code: i32,
-) -> anyhow::Result<JobMetric, anyhow::Error> {
-    let payload = json!({
-        "job_id": job_id,
-        "worker_id": worker_id,
-        "duration_sec": duration_sec,
-        "cpu_usage_pct": cpu_usage_pct,
-        "mem_usage_mb": mem_usage_mb,
-        "exit_code": exit_code
-    });
-
-    let res = client
-        .post(&format!("{}/metrics", APP_HOST))
-        .json(&payload)
-        .send()
+) -> anyhow::Result<JobMetric> {
+    let metric = NewJobMetric {
+        job_id,
+        worker_id,
+        duration_sec: Some(duration_sec),
+        cpu_usage_pct: Some(cpu_usage_pct),
+        mem_usage_mb: Some(mem_usage_mb),
+        exit_code: Some(exit_code),
+    };
+    client
+        .create_metric(&metric)
         .await
-        .context("Failed to send create-metric request")?;
-
-    assert!(
-        res.status().is_success(),
-        "Metric creation failed (status={}): {:?}",
-        res.status(),
-        res.text().await.unwrap_or_default()
-    );
-
-    let job_metric = res
-        .json::<JobMetric>()
-        .await
-        .context("Failed to deserialize JobMetric from create response")?;
-
-    Ok(job_metric)
+        .context("Failed to create metric")
 }
 
 // JOb result
-pub async fn assign_result_to_job(
-    client: &Client,
-    job_id: i32,
-) -> anyhow::Result<JobResult, anyhow::Error> {
-    let payload = json!({
-        "job_id": job_id,
-        "stdout": Some("Execution finished successfully."),
-        "files": vec![
-            "result.log".to_string(),
-            "output.json".to_string()
-        ]
-    });
-
-    let res = client
-        .post(&format!("{}/results", APP_HOST))
-        .json(&payload)
-        .send()
+pub async fn assign_result_to_job(client: &SwarmClient, job_id: i32) -> anyhow::Result<JobResult> {
+    let result = NewJobResult {
+        job_id,
+        stdout: Some("Execution finished successfully.".to_string()),
+        files: Some(vec![
+            Some("result.log".to_string()),
+            Some("output.json".to_string()),
+        ]),
+    };
+    client
+        .create_result(&result)
         .await
-        .context("Failed to send POST /results")?;
-
-    assert!(
-        res.status().is_success(),
-        "JobResult creation failed (status={}): {:?}",
-        res.status(),
-        res.text().await.unwrap_or_default()
-    );
-
-    let job_result = res
-        .json::<JobResult>()
-        .await
-        .context("Failed to deserialize JobResult")?;
-
-    Ok(job_result)
+        .context("Failed to create job result")
 }
 
 // ======== Worker Utilities ==========
-pub async fn create_worker_via_api(
-    client: &Client,
-    user_id: i32,
-) -> anyhow::Result<Worker, anyhow::Error> {
-    let label = format!("worker-{}", Uuid::new_v4());
-    let res = client
-        .post(&format!("{}/workers", APP_HOST))
-        .json(&json!({
-            "user_id": user_id,
-            "label": label,
-            "ip_address": "127.0.0.1",
-            "hostname": "test-host",
-            "ssh_user": "test-user",
-            "ssh_key": "ssh-rsa AAA...",
-            "docker_version": "24.0.2",
-            "arch": "x86_64",
-            "os": "Linux",
-            "tags": ["test", "integration"]
-        }))
-        .send()
+pub async fn create_worker_via_api(client: &SwarmClient, user_id: i32) -> anyhow::Result<Worker> {
+    let worker = NewWorker {
+        user_id,
+        label: format!("worker-{}", Uuid::new_v4()),
+        ip_address: "127.0.0.1".to_string(),
+        hostname: "test-host".to_string(),
+        ssh_user: "test-user".to_string(),
+        ssh_key: "ssh-rsa AAA...".to_string(),
+        docker_version: "24.0.2".to_string(),
+        arch: "x86_64".to_string(),
+        os: OSEnum::Linux,
+        tags: Some(vec![
+            Some("test".to_string()),
+            Some("integration".to_string()),
+        ]),
+    };
+    client
+        .create_worker(&worker)
         .await
-        .context("Failed to send create-worker request")?;
-
-    assert!(
-        res.status().is_success(),
-        "Worker creation failed (status={}): {:?}",
-        res.status(),
-        res.text().await.unwrap_or_default()
-    );
-
-    let worker = res
-        .json::<Worker>()
-        .await
-        .context("Failed to deserialize Worker from create response")?;
-
-    Ok(worker)
+        .context("Failed to create worker")
 }
 
 pub async fn assign_job_to_worker(
-    client: &Client,
+    client: &SwarmClient,
     job_id: i32,
     worker_id: i32,
-) -> anyhow::Result<JobAssignment, anyhow::Error> {
-    let res = client
-        .post(&format!("{}/assignments", APP_HOST))
-        .json(&json!({
-            "job_id": job_id,
-            "worker_id": worker_id
-        }))
-        .send()
+) -> anyhow::Result<JobAssignment> {
+    client
+        .create_assignment(&NewJobAssignment { job_id, worker_id })
         .await
-        .context("Failed to send create-assignment request")?;
-
-    assert!(
-        res.status().is_success(),
-        "Job assignment failed (status={}): {:?}",
-        res.status(),
-        res.text().await.unwrap_or_default()
-    );
-
-    let job_assignment = res
-        .json::<JobAssignment>()
-        .await
-        .context("Failed to deserialize JobAssignment from response")?;
-
-    Ok(job_assignment)
+        .context("Failed to assign job to worker")
 }
 
-pub async fn delete_worker_via_api(
-    client: &Client,
-    worker_id: i32,
-) -> anyhow::Result<(), anyhow::Error> {
-    let res = client
-        .delete(&format!("{}/workers/{}", APP_HOST, worker_id))
-        .send()
+pub async fn delete_worker_via_api(client: &SwarmClient, worker_id: i32) -> anyhow::Result<()> {
+    client
+        .delete_worker(worker_id)
         .await
-        .context("Failed to send DELETE /workers/:id request")?;
-
-    assert!(
-        res.status().is_success(),
-        "Failed to delete worker (status={}): {:?}",
-        res.status(),
-        res.text().await.unwrap_or_default()
-    );
-
-    Ok(())
+        .context("Failed to delete worker")
 }
 
 pub async fn create_worker_status_via_api(
-    client: &Client,
+    client: &SwarmClient,
     worker_id: i32,
     job_id: Option<i32>,
-) -> anyhow::Result<WorkerStatus, anyhow::Error> {
-    let status_data = json!({
-        "worker_id": worker_id,
-        "status": "Idle",
-        "last_heartbeat": null,
-        "active_job_id": job_id,
-        "uptime_sec": 3600,
-        "load_avg": [Some(0.5), Some(0.3), Some(0.1)],
-        "last_error": "No errors"
-    });
-
-    let res = client
-        .post(&format!("{}/worker-status", APP_HOST))
-        .json(&status_data)
-        .send()
+) -> anyhow::Result<WorkerStatus> {
+    let status = NewWorkerStatus {
+        worker_id,
+        status: WorkerStatusEnum::Idle,
+        last_heartbeat: None,
+        active_job_id: job_id,
+        uptime_sec: Some(3600),
+        load_avg: Some(vec![Some(0.5), Some(0.3), Some(0.1)]),
+        last_error: Some("No errors".to_string()),
+    };
+    client
+        .create_worker_status(&status)
         .await
-        .context("Failed to send request to create worker status")?;
-
-    ensure!(res.status() == StatusCode::CREATED,);
-
-    let worker_status = res
-        .json::<WorkerStatus>()
-        .await
-        .context("Failed to parse created WorkerStatus")?;
-
-    Ok(worker_status)
+        .context("Failed to create worker status")
 }
@@ -1,456 +1,256 @@
-use rocket::serde::json::json;
+use swarm_client::enums::{ImageFormatEnum, JobStateEnum};
 
-use common::enums::job::JobStateEnum;
 pub mod common_test;
 
 #[cfg(test)]
 mod job_api_tests {
-    use chrono::Utc;
-    use common::database::models::job::Job;
-    use reqwest::StatusCode;
-
-    use crate::common_test::APP_HOST;
-
     use super::*;
 
     #[tokio::test]
-    async fn test_create_job() {
+    async fn test_create_job() -> anyhow::Result<()> {
         // Build the authorized client with a logged-in admin user
-        let (client, user) = common_test::build_client_with_logged_in_admin().await;
-
-        // Build JSON payload directly
-        let payload_json = json!({
-            "user_id": user.id,
-            "job_name": common_test::generate_unique_job_name(),
-            "image_url": "docker.io/library/alpine:latest",
-            "image_format": "DockerRegistry",
-            "docker_flags": null,
-            "output_type": "Stdout",
-            "output_paths": null,
-            "schedule_type": "Once",
-            "cron_expression": null,
-            "notes": null,
-            "state": "Queued"
-        });
-
-        // Send the request
-        let response = client
-            .post(format!("{}/jobs", common_test::APP_HOST))
-            .json(&payload_json)
-            .send()
-            .await
-            .expect("Failed to send create job request");
-
-        assert_eq!(response.status(), StatusCode::CREATED);
-        let body = response.text().await.expect("Failed to read response body");
+        let (client, user) = common_test::build_client_with_logged_in_admin().await?;
 
-        let created_job: Job = serde_json::from_str(&body).unwrap_or_else(|e| {
-            panic!(
-                "Failed to parse Job from response JSON:\nError: {}\nBody:\n{}",
-                e, body
-            )
-        });
-
-        // Extract values from original JSON to assert against
-        let expected_job_name = payload_json["job_name"].as_str().unwrap();
-        let expected_image_url = payload_json["image_url"].as_str().unwrap();
+        let payload = common_test::new_job(user.id);
+        let created_job = client.create_job(&payload).await?;
 
         assert_eq!(created_job.user_id, user.id);
-        assert_eq!(created_job.job_name, expected_job_name);
-        assert_eq!(created_job.image_url, expected_image_url);
-        assert_eq!(created_job.image_format.to_string(), "DockerRegistry");
-        assert_eq!(created_job.state.to_string(), "Queued");
-
-        common_test::delete_jobs_via_api(&client, &vec![created_job.id]).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        assert_eq!(created_job.job_name, payload.job_name);
+        assert_eq!(created_job.image_url, payload.image_url);
+        assert_eq!(created_job.image_format, ImageFormatEnum::DockerRegistry);
+        // core decides the initial state, the Scheduler admits it later
+        assert_eq!(created_job.state, JobStateEnum::Submitted);
+
+        common_test::delete_jobs_via_api(&client, &[created_job.id]).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_job_by_id() {
-        let (client, user, jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(3).await;
+    async fn test_get_job_by_id() -> anyhow::Result<()> {
+        let (client, user, jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(3).await?;
         let job_id = job_ids[1];
 
-        let response = client
-            .get(format!("{}/jobs/{job_id}", common_test::APP_HOST))
-            .send()
-            .await
-            .expect("Failed to send GET /jobs/:id");
-
-        assert_eq!(response.status(), 200);
-
-        let job: Job = response.json().await.expect("Invalid JobPayload response");
+        let job = client.get_job(job_id).await?;
         assert_eq!(job.id, job_id);
         assert_eq!(job.job_name, jobs[1].job_name);
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_update_job() {
-        let (client, user, jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(1).await;
+    async fn test_update_job() -> anyhow::Result<()> {
+        let (client, user, jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(1).await?;
         let job_id = job_ids[0];
 
         let new_job_name = common_test::generate_unique_job_name();
         let job_notes = "Hello this job is cpu bound";
 
-        let updated_payload = serde_json::json!({
-            "id": job_id,
-            "user_id": user.id,
-            "job_name": new_job_name,
-            "image_url": "docker.io/library/alpine:latest",
-            "image_format": "DockerRegistry",
-            "docker_flags": null,
-            "output_type": "Stdout",
-            "output_paths": null,
-            "schedule_type": "Once",
-            "cron_expression": null,
-            "notes": job_notes,
-            "state": "Running",
-            "created_at": jobs[0].created_at,
-            "updated_at": Utc::now().naive_utc(),
-        });
-
-        let response = client
-            .patch(format!("{}/jobs/{job_id}", common_test::APP_HOST))
-            .json(&updated_payload)
-            .send()
-            .await
-            .expect("Failed to send PATCH /jobs/:id");
-
-        assert_eq!(response.status(), 200);
+        let mut job = jobs[0].clone();
+        job.job_name = new_job_name.clone();
+        job.notes = Some(job_notes.to_string());
+        job.state = JobStateEnum::Running;
+        job.updated_at = common_test::get_ndt_now();
 
-        let updated_job: Job = response.json().await.expect("Invalid updated JobPayload");
+        let updated_job = client.update_job(job_id, &job).await?;
         assert_eq!(updated_job.state, JobStateEnum::Running);
         assert_eq!(updated_job.job_name, new_job_name);
-        assert_eq!(updated_job.notes.unwrap(), job_notes);
+        assert_eq!(updated_job.notes.as_deref(), Some(job_notes));
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_delete_job() {
+    async fn test_delete_job() -> anyhow::Result<()> {
         let (client, user, _jobs, job_ids) =
-            common_test::build_client_and_user_with_n_jobs(1).await;
+            common_test::build_client_and_user_with_n_jobs(1).await?;
         let job_id = job_ids[0];
 
-        let delete_response = client
-            .delete(format!("{}/jobs/{job_id}", common_test::APP_HOST))
-            .send()
-            .await
-            .expect("Failed to send DELETE /jobs/:id");
-
-        assert_eq!(delete_response.status(), 204);
-
-        let get_response = client
-            .get(format!("{}/jobs/{job_id}", common_test::APP_HOST))
-            .send()
-            .await
-            .expect("Failed to send GET /jobs/:id after delete");
+        client.delete_job(job_id).await?;
 
-        assert_eq!(get_response.status(), 404);
+        let err = client.get_job(job_id).await.unwrap_err();
+        assert!(err.is_not_found());
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     // 🔍 Lookup & Search Endpoints
 
     #[tokio::test]
-    async fn test_search_jobs() {
-        let (client, user, jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(5).await;
+    async fn test_search_jobs() -> anyhow::Result<()> {
+        let (client, user, jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(5).await?;
         let keyword = &jobs[2].job_name[0..3]; // partial match
 
-        let response = client
-            .get(format!(
-                "{}/jobs/search?user_id={}&query={}",
-                APP_HOST, user.id, keyword
-            ))
-            .send()
-            .await
-            .expect("Failed to send search request");
-
-        assert_eq!(response.status(), 200);
-        let matches: Vec<Job> = response.json().await.expect("Invalid search result");
+        let matches = client.search_jobs(user.id, keyword).await?;
         assert!(matches.iter().any(|job| job.job_name == jobs[2].job_name));
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_job_by_name() {
-        let (client, user, jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(3).await;
+    async fn test_get_job_by_name() -> anyhow::Result<()> {
+        let (client, user, jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(3).await?;
         let exact_name = &jobs[0].job_name;
 
-        let response = client
-            .get(format!(
-                "{}/jobs/name/{}?name={}",
-                APP_HOST, user.id, exact_name
-            ))
-            .send()
-            .await
-            .expect("Failed to send exact name request");
-
-        assert_eq!(response.status(), 200);
-        let matches: Vec<Job> = response.json().await.expect("Invalid name lookup");
+        let matches = client.find_jobs_by_name(user.id, exact_name).await?;
         assert!(matches.iter().any(|job| job.job_name == *exact_name));
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_jobs_by_admin() {
+    async fn test_get_jobs_by_admin() -> anyhow::Result<()> {
         let (client, user, _jobs, job_ids) =
-            common_test::build_client_and_user_with_n_jobs(4).await;
-
-        let response = client
-            .get(format!(
-                "{}/jobs/by_admin?user_id={}&limit=10&offset=0",
-                APP_HOST, user.id
-            ))
-            .send()
-            .await
-            .expect("Failed to send by_admin request");
+            common_test::build_client_and_user_with_n_jobs(4).await?;
 
-        assert_eq!(response.status(), 200);
-        let found: Vec<Job> = response.json().await.expect("Invalid by_admin response");
+        let found = client
+            .list_jobs_by_admin(user.id, Some(10), Some(0))
+            .await?;
         assert!(found.len() >= 4);
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_jobs_by_state() {
-        let (client, user, jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(2).await;
+    async fn test_get_jobs_by_state() -> anyhow::Result<()> {
+        let (client, user, _jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(2).await?;
         let job_id = job_ids[0];
 
         // Manually mark one job as Failed
-        let updated_payload = serde_json::json!({
-            "id": job_id,
-            "user_id": user.id,
-            "job_name": jobs[0].job_name,
-            "image_url": "docker.io/library/alpine:latest",
-            "image_format": "DockerRegistry",
-            "docker_flags": null,
-            "output_type": "Stdout",
-            "output_paths": null,
-            "schedule_type": "Once",
-            "cron_expression": null,
-            "notes": null,
-            "state": "Failed",
-            "created_at": jobs[0].created_at,
-            "updated_at": Utc::now().naive_utc(),
-        });
-
-        let response = client
-            .patch(format!("{}/jobs/{job_id}", common_test::APP_HOST))
-            .json(&updated_payload)
-            .send()
-            .await
-            .expect("Failed to send PATCH /jobs/:id");
-        assert_eq!(response.status(), 200);
-
-        let response = client
-            .get(format!("{}/jobs/state/Failed", APP_HOST))
-            .send()
-            .await
-            .expect("Failed to fetch by state");
+        common_test::mark_job_failed(&client, job_id).await?;
 
-        assert_eq!(response.status(), 200);
-        let found: Vec<Job> = response.json().await.expect("Invalid response by state");
+        let found = client.list_jobs_by_state(JobStateEnum::Failed).await?;
         assert!(found
             .iter()
             .any(|job| job.id == job_id && job.state == JobStateEnum::Failed));
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_recent_jobs() {
+    async fn test_get_recent_jobs() -> anyhow::Result<()> {
         let (client, user, _jobs, job_ids) =
-            common_test::build_client_and_user_with_n_jobs(3).await;
+            common_test::build_client_and_user_with_n_jobs(3).await?;
 
-        let response = client
-            .get(format!("{}/jobs/recent?limit=5", APP_HOST))
-            .send()
-            .await
-            .expect("Failed to get recent jobs");
-
-        assert_eq!(response.status(), 200);
-        let recent: Vec<Job> = response.json().await.expect("Invalid recent jobs response");
+        let recent = client.recent_jobs(Some(5)).await?;
         assert!(!recent.is_empty());
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_failed_jobs() {
-        let (client, user, jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(2).await;
+    async fn test_get_failed_jobs() -> anyhow::Result<()> {
+        let (client, user, _jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(2).await?;
         let job_id = job_ids[1];
 
         // Manually mark one job as Failed
-        let updated_payload = serde_json::json!({
-            "id": job_id,
-            "user_id": user.id,
-            "job_name": jobs[0].job_name,
-            "image_url": "docker.io/library/alpine:latest",
-            "image_format": "DockerRegistry",
-            "docker_flags": null,
-            "output_type": "Stdout",
-            "output_paths": null,
-            "schedule_type": "Once",
-            "cron_expression": null,
-            "notes": null,
-            "state": "Failed",
-            "created_at": jobs[0].created_at,
-            "updated_at": Utc::now().naive_utc(),
-        });
-
-        let response = client
-            .patch(format!("{}/jobs/{job_id}", common_test::APP_HOST))
-            .json(&updated_payload)
-            .send()
-            .await
-            .expect("Failed to send PATCH /jobs/:id");
-        assert_eq!(response.status(), 200);
+        common_test::mark_job_failed(&client, job_id).await?;
 
-        let response = client
-            .get(format!("{}/jobs/failed?limit=10", APP_HOST))
-            .send()
-            .await
-            .expect("Failed to get failed jobs");
-
-        assert_eq!(response.status(), 200);
-        let failed_jobs: Vec<Job> = response.json().await.expect("Invalid failed jobs response");
+        let failed_jobs = client.failed_jobs(Some(10)).await?;
         assert!(!failed_jobs.is_empty());
         assert!(failed_jobs
             .iter()
             .any(|job| job.id == job_id && job.state == JobStateEnum::Failed));
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     // 🔄 State Transitions
 
     #[tokio::test]
-    async fn test_mark_job_running() {
-        let (client, user, jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(1).await;
-        let job_id = job_ids[0];
-        let job = &jobs[0];
-
-        let updated_payload = serde_json::json!({
-            "id": job_id,
-            "user_id": user.id,
-            "job_name": job.job_name,
-            "image_url": job.image_url,
-            "image_format": job.image_format,
-            "docker_flags": job.docker_flags,
-            "output_type": job.output_type,
-            "output_paths": job.output_paths,
-            "schedule_type": job.schedule_type,
-            "cron_expression": job.cron_expression,
-            "notes": job.notes,
-            "state": "Running",
-            "created_at": job.created_at,
-            "updated_at": Utc::now().naive_utc(),
-        });
-
-        let response = client
-            .patch(format!("{}/jobs/{job_id}/running", APP_HOST))
-            .json(&updated_payload)
-            .send()
-            .await
-            .expect("Failed to mark job running");
+    async fn test_mark_job_running() -> anyhow::Result<()> {
+        let (client, user, _jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(1).await?;
 
-        assert_eq!(response.status(), 200);
-        let job: Job = response.json().await.expect("Invalid response");
+        common_test::set_job_state(&client, job_ids[0], JobStateEnum::Queued).await?;
+        let job = client.mark_job_running(job_ids[0]).await?;
         assert_eq!(job.state, JobStateEnum::Running);
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_mark_job_succeeded() {
-        let (client, user, jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(1).await;
-        let job_id = job_ids[0];
-        let job = &jobs[0];
-
-        let updated_payload = serde_json::json!({
-            "id": job_id,
-            "user_id": user.id,
-            "job_name": job.job_name,
-            "image_url": job.image_url,
-            "image_format": job.image_format,
-            "docker_flags": job.docker_flags,
-            "output_type": job.output_type,
-            "output_paths": job.output_paths,
-            "schedule_type": job.schedule_type,
-            "cron_expression": job.cron_expression,
-            "notes": job.notes,
-            "state": "Completed",
-            "created_at": job.created_at,
-            "updated_at": Utc::now().naive_utc(),
-        });
-
-        let response = client
-            .patch(format!("{}/jobs/{job_id}", APP_HOST))
-            .json(&updated_payload)
-            .send()
-            .await
-            .expect("Failed to mark job complete");
+    async fn test_mark_job_succeeded() -> anyhow::Result<()> {
+        let (client, user, _jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(1).await?;
 
-        assert_eq!(response.status(), 200);
-        let job: Job = response.json().await.expect("Invalid response");
+        common_test::mark_job_running(&client, job_ids[0]).await?;
+        let job = client.mark_job_succeeded(job_ids[0]).await?;
         assert_eq!(job.state, JobStateEnum::Completed);
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_mark_job_failed() {
-        let (client, user, jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(1).await;
-        let job_id = job_ids[0];
-        let job = &jobs[0];
-
-        let updated_payload = serde_json::json!({
-            "id": job_id,
-            "user_id": user.id,
-            "job_name": job.job_name,
-            "image_url": job.image_url,
-            "image_format": job.image_format,
-            "docker_flags": job.docker_flags,
-            "output_type": job.output_type,
-            "output_paths": job.output_paths,
-            "schedule_type": job.schedule_type,
-            "cron_expression": job.cron_expression,
-            "notes": "Example failure reason",
-            "state": "Failed",
-            "created_at": job.created_at,
-            "updated_at": Utc::now().naive_utc(),
-        });
-
-        let response = client
-            .patch(format!("{}/jobs/{job_id}/failed", APP_HOST))
-            .json(&updated_payload)
-            .send()
+    async fn test_illegal_transitions_conflict() -> anyhow::Result<()> {
+        let (client, user, _jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(1).await?;
+        let id = job_ids[0];
+
+        // Submitted can't skip the queue
+        let err = client.mark_job_running(id).await.unwrap_err();
+        assert!(err.is_conflict(), "{err}");
+
+        common_test::mark_job_running(&client, id).await?;
+        client.mark_job_succeeded(id).await?;
+
+        // Completed is final, neither a transition nor an update may reopen it
+        let err = client.mark_job_running(id).await.unwrap_err();
+        assert!(err.is_conflict(), "{err}");
+        let err = client.mark_job_failed(id, None).await.unwrap_err();
+        assert!(err.is_conflict(), "{err}");
+        let err = common_test::set_job_state(&client, id, JobStateEnum::Queued)
             .await
-            .expect("Failed to mark job failed");
+            .unwrap_err();
+        let cause = err.downcast_ref::<swarm_client::ClientError>();
+        assert!(cause.is_some_and(|e| e.is_conflict()), "{err:#}");
+        assert_eq!(client.get_job(id).await?.state, JobStateEnum::Completed);
+
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_mark_job_failed() -> anyhow::Result<()> {
+        let (client, user, _jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(1).await?;
 
-        assert_eq!(response.status(), 200);
-        let job: Job = response.json().await.expect("Invalid response");
+        let job = client
+            .mark_job_failed(job_ids[0], Some("Example failure reason"))
+            .await?;
         assert_eq!(job.state, JobStateEnum::Failed);
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     // ⏱️ Scheduling & Readiness
@@ -462,179 +262,95 @@ mod job_api_tests {
     }
 
     #[tokio::test]
-    async fn test_get_ready_one_time_jobs() {
-        let (client, user, jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(2).await;
-
-        //mark both jobs ready
-        let job_id = job_ids[0];
-        let job = &jobs[0];
-        let updated_payload = serde_json::json!({
-            "id": job_id,
-            "user_id": user.id,
-            "job_name": job.job_name,
-            "image_url": job.image_url,
-            "image_format": job.image_format,
-            "docker_flags": job.docker_flags,
-            "output_type": job.output_type,
-            "output_paths": job.output_paths,
-            "schedule_type": "Once",
-            "cron_expression": job.cron_expression,
-            "notes": "Example failure reason",
-            "state": "Queued",
-            "created_at": job.created_at,
-            "updated_at": Utc::now().naive_utc(),
-        });
-
-        let response = client
-            .patch(format!("{}/jobs/{job_id}", APP_HOST))
-            .json(&updated_payload)
-            .send()
-            .await
-            .expect("Failed to mark job failed");
-
-        // Second job
-        let job_id = job_ids[1];
-        let job = &jobs[1];
-        let updated_payload = serde_json::json!({
-            "id": job_id,
-            "user_id": user.id,
-            "job_name": job.job_name,
-            "image_url": job.image_url,
-            "image_format": job.image_format,
-            "docker_flags": job.docker_flags,
-            "output_type": job.output_type,
-            "output_paths": job.output_paths,
-            "schedule_type": "Once",
-            "cron_expression": job.cron_expression,
-            "notes": "Example failure reason",
-            "state": "Queued",
-            "created_at": job.created_at,
-            "updated_at": Utc::now().naive_utc(),
-        });
-
-        let response = client
-            .patch(format!("{}/jobs/{job_id}", APP_HOST))
-            .json(&updated_payload)
-            .send()
-            .await
-            .expect("Failed to mark job failed");
-
-        let response = client
-            .get(format!("{}/jobs/one-time-ready", APP_HOST))
-            .send()
-            .await
-            .expect("Failed to get ready jobs");
+    async fn test_get_ready_one_time_jobs() -> anyhow::Result<()> {
+        let (client, user, _jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(2).await?;
 
-        assert_eq!(response.status(), 200);
-        let ready_jobs: Vec<Job> = response.json().await.expect("Invalid ready jobs response");
+        // Both jobs are one-time jobs, queue them without waiting for the Scheduler
+        for &id in &job_ids {
+            common_test::set_job_state(&client, id, JobStateEnum::Queued).await?;
+        }
+        let ready_jobs = client.ready_jobs().await?;
         assert!(ready_jobs.len() >= 2);
         assert!(!ready_jobs.is_empty());
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     // 📊 Aggregation & Stats
 
     #[tokio::test]
-    async fn test_get_job_stats_by_admin() {
-        let (client, user, jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(3).await;
-
-        let response = client
-            .get(format!("{}/jobs/stats/admins", common_test::APP_HOST))
-            .send()
-            .await
-            .expect("Failed to GET job stats");
+    async fn test_get_job_stats_by_admin() -> anyhow::Result<()> {
+        let (client, user, _jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(3).await?;
 
-        assert_eq!(response.status(), 200);
-        let stats: Vec<(i32, i64)> = response.json().await.expect("Invalid stats format");
+        let stats = client.admin_job_counts().await?;
 
         // Ensure our admin's ID is present and job count matches
         let entry = stats.iter().find(|(admin_id, _)| *admin_id == user.id);
-        assert!(entry.is_some());
-        assert_eq!(entry.unwrap().1, 3);
+        assert_eq!(entry.map(|(_, count)| *count), Some(3));
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     // 🤝 Assignment & Worker Routing
 
     #[tokio::test]
-    async fn test_get_active_jobs_for_worker() {
-        let (client, user, jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(2).await;
-        let worker = common_test::create_worker_via_api(&client, user.id).await;
-
-        common_test::assign_job_to_worker(&client, job_ids[0], worker.id).await;
-        common_test::mark_job_running(&client, job_ids[0]).await;
-
-        let response = client
-            .get(format!(
-                "{}/jobs/active/{}",
-                common_test::APP_HOST,
-                worker.id
-            ))
-            .send()
-            .await
-            .expect("Failed to GET active jobs for worker");
+    async fn test_get_active_jobs_for_worker() -> anyhow::Result<()> {
+        let (client, user, _jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(2).await?;
+        let worker = common_test::create_worker_via_api(&client, user.id).await?;
 
-        assert_eq!(response.status(), 200);
-        let jobs: Vec<Job> = response.json().await.expect("Invalid jobs response");
+        common_test::assign_job_to_worker(&client, job_ids[0], worker.id).await?;
+        common_test::mark_job_running(&client, job_ids[0]).await?;
+
+        let jobs = client.active_jobs_for_worker(worker.id).await?;
         assert!(jobs
             .iter()
             .any(|j| j.id == job_ids[0] && j.state == JobStateEnum::Running));
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_worker_via_api(&client, worker.id).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_worker_via_api(&client, worker.id).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_assigned_jobs_for_worker() {
-        let (client, user, jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(2).await;
-        let worker = common_test::create_worker_via_api(&client, user.id).await;
-
-        common_test::assign_job_to_worker(&client, job_ids[1], worker.id).await;
-
-        let response = client
-            .get(format!(
-                "{}/jobs/assigned/{}",
-                common_test::APP_HOST,
-                worker.id
-            ))
-            .send()
-            .await
-            .expect("Failed to GET assigned jobs for worker");
+    async fn test_get_assigned_jobs_for_worker() -> anyhow::Result<()> {
+        let (client, user, _jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(2).await?;
+        let worker = common_test::create_worker_via_api(&client, user.id).await?;
+
+        common_test::assign_job_to_worker(&client, job_ids[1], worker.id).await?;
 
-        assert_eq!(response.status(), 200);
-        let jobs: Vec<Job> = response.json().await.expect("Invalid jobs response");
+        let jobs = client.jobs_assigned_to_worker(worker.id).await?;
         assert!(jobs.iter().any(|j| j.id == job_ids[1]));
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_worker_via_api(&client, worker.id).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_worker_via_api(&client, worker.id).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_unassigned_jobs() {
-        let (client, user, jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(2).await;
-        let worker = common_test::create_worker_via_api(&client, user.id).await;
+    async fn test_get_unassigned_jobs() -> anyhow::Result<()> {
+        let (client, user, _jobs, job_ids) =
+            common_test::build_client_and_user_with_n_jobs(2).await?;
+        let worker = common_test::create_worker_via_api(&client, user.id).await?;
 
         // Assign one job, leave the other unassigned
-        common_test::assign_job_to_worker(&client, job_ids[0], worker.id).await;
-
-        let response = client
-            .get(format!("{}/jobs/unassigned", common_test::APP_HOST))
-            .send()
-            .await
-            .expect("Failed to GET unassigned jobs");
+        common_test::assign_job_to_worker(&client, job_ids[0], worker.id).await?;
 
-        assert_eq!(response.status(), 200);
-        let jobs: Vec<Job> = response.json().await.expect("Invalid jobs response");
+        let jobs = client.unassigned_jobs().await?;
         assert!(!jobs.is_empty());
 
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_worker_via_api(&client, worker.id).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_worker_via_api(&client, worker.id).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 }
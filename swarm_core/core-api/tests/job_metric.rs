@@ -5,88 +5,55 @@ mod job_metric_api_tests {
     use crate::common_test::{
         assign_job_to_worker, build_client_and_user_with_n_jobs, create_metric_via_api,
         create_worker_via_api, delete_job_via_api, delete_jobs_via_api, delete_user_via_api,
-        delete_worker_via_api, APP_HOST,
+        delete_worker_via_api,
     };
-    use common::database::models::job::JobMetric;
-    use rocket::serde::json::json;
+    use swarm_client::models::NewJobMetric;
     use tokio::time::{sleep, Duration};
 
+    fn exit_only(job_id: i32, worker_id: i32, exit_code: i32) -> NewJobMetric {
+        NewJobMetric {
+            job_id,
+            worker_id,
+            duration_sec: None,
+            cpu_usage_pct: None,
+            mem_usage_mb: None,
+            exit_code: Some(exit_code),
+        }
+    }
+
     #[tokio::test]
-    async fn test_create_job_metric() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await;
+    async fn test_create_job_metric() -> anyhow::Result<()> {
+        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await?;
         let job = &jobs[0];
-        let worker = create_worker_via_api(&client, user.id).await;
-        assign_job_to_worker(&client, job.id, worker.id).await;
-
-        let payload = json!({
-            "job_id": job.id,
-            "worker_id": worker.id,
-            "duration_sec": 42,
-            "cpu_usage_pct": 63.5,
-            "mem_usage_mb": 128.0,
-            "exit_code": 0
-        });
+        let worker = create_worker_via_api(&client, user.id).await?;
+        assign_job_to_worker(&client, job.id, worker.id).await?;
 
-        let url = format!("{}/metrics", APP_HOST);
-        let res = client.post(&url).json(&payload).send().await.unwrap();
-        assert_eq!(res.status().as_u16(), 201);
-
-        let metric: JobMetric = res.json().await.unwrap();
+        let metric = create_metric_via_api(&client, job.id, worker.id, 42, 63.5, 128.0, 0).await?;
         assert_eq!(metric.job_id, job.id);
         assert_eq!(metric.worker_id, worker.id);
         assert_eq!(metric.duration_sec, Some(42));
         assert_eq!(metric.exit_code, Some(0));
 
-        delete_job_via_api(&client, job.id).await;
-        delete_worker_via_api(&client, worker.id).await;
-        delete_user_via_api(&client, user.id).await;
+        delete_job_via_api(&client, job.id).await?;
+        delete_worker_via_api(&client, worker.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_metric_upsert_updates_existing_row() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await;
+    async fn test_metric_upsert_updates_existing_row() -> anyhow::Result<()> {
+        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await?;
         let job = &jobs[0];
-        let worker = create_worker_via_api(&client, user.id).await;
-        assign_job_to_worker(&client, job.id, worker.id).await;
+        let worker = create_worker_via_api(&client, user.id).await?;
+        assign_job_to_worker(&client, job.id, worker.id).await?;
 
         // First metric creation
-        let payload_1 = json!({
-            "job_id": job.id,
-            "worker_id": worker.id,
-            "duration_sec": 10,
-            "cpu_usage_pct": 50.0,
-            "mem_usage_mb": 100.0,
-            "exit_code": 0
-        });
-
-        let create_url = format!("{}/metrics", APP_HOST);
-        let res_1 = client
-            .post(&create_url)
-            .json(&payload_1)
-            .send()
-            .await
-            .unwrap();
-        assert_eq!(res_1.status().as_u16(), 201);
-        let metric_1: JobMetric = res_1.json().await.unwrap();
+        let metric_1 =
+            create_metric_via_api(&client, job.id, worker.id, 10, 50.0, 100.0, 0).await?;
 
         // Second metric creation for the same (job_id, worker_id) — should overwrite
-        let payload_2 = json!({
-            "job_id": job.id,
-            "worker_id": worker.id,
-            "duration_sec": 99,
-            "cpu_usage_pct": 88.8,
-            "mem_usage_mb": 256.0,
-            "exit_code": 137
-        });
-
-        let res_2 = client
-            .post(&create_url)
-            .json(&payload_2)
-            .send()
-            .await
-            .unwrap();
-        assert_eq!(res_2.status().as_u16(), 201);
-        let metric_2: JobMetric = res_2.json().await.unwrap();
+        let metric_2 =
+            create_metric_via_api(&client, job.id, worker.id, 99, 88.8, 256.0, 137).await?;
 
         // Should be the same row ID (i.e., it was updated)
         assert_eq!(metric_1.id, metric_2.id);
@@ -97,164 +64,112 @@ mod job_metric_api_tests {
         assert_eq!(metric_2.exit_code, Some(137));
 
         // Clean up
-        delete_job_via_api(&client, job.id).await;
-        delete_worker_via_api(&client, worker.id).await;
-        delete_user_via_api(&client, user.id).await;
+        delete_job_via_api(&client, job.id).await?;
+        delete_worker_via_api(&client, worker.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_job_metric_by_id() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await;
+    async fn test_get_job_metric_by_id() -> anyhow::Result<()> {
+        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await?;
         let job = &jobs[0];
-        let worker = create_worker_via_api(&client, user.id).await;
-        assign_job_to_worker(&client, job.id, worker.id).await;
-
-        let create_url = format!("{}/metrics", APP_HOST);
-        let payload = json!({
-            "job_id": job.id,
-            "worker_id": worker.id,
-            "duration_sec": 55,
-            "exit_code": 1
-        });
+        let worker = create_worker_via_api(&client, user.id).await?;
+        assign_job_to_worker(&client, job.id, worker.id).await?;
 
-        let created: JobMetric = client
-            .post(&create_url)
-            .json(&payload)
-            .send()
-            .await
-            .unwrap()
-            .json()
-            .await
-            .unwrap();
-        let get_url = format!("{}/metrics/{}", APP_HOST, created.id);
-        let fetched: JobMetric = client
-            .get(&get_url)
-            .send()
-            .await
-            .unwrap()
-            .json()
-            .await
-            .unwrap();
+        let mut payload = exit_only(job.id, worker.id, 1);
+        payload.duration_sec = Some(55);
+        let created = client.create_metric(&payload).await?;
+        let fetched = client.get_metric(created.id).await?;
 
         assert_eq!(fetched.id, created.id);
         assert_eq!(fetched.exit_code, Some(1));
 
-        delete_job_via_api(&client, job.id).await;
-        delete_worker_via_api(&client, worker.id).await;
-        delete_user_via_api(&client, user.id).await;
+        delete_job_via_api(&client, job.id).await?;
+        delete_worker_via_api(&client, worker.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_delete_job_metric() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await;
+    async fn test_delete_job_metric() -> anyhow::Result<()> {
+        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await?;
         let job = &jobs[0];
-        let worker = create_worker_via_api(&client, user.id).await;
-        assign_job_to_worker(&client, job.id, worker.id).await;
-
-        let payload = json!({
-            "job_id": job.id,
-            "worker_id": worker.id,
-            "exit_code": 0
-        });
-
-        let res = client
-            .post(&format!("{}/metrics", APP_HOST))
-            .json(&payload)
-            .send()
-            .await
-            .unwrap();
-        let metric: JobMetric = res.json().await.unwrap();
-
-        let delete_url = format!("{}/metrics/{}", APP_HOST, metric.id);
-        let del_res = client.delete(&delete_url).send().await.unwrap();
-        assert_eq!(del_res.status().as_u16(), 204);
-
-        delete_job_via_api(&client, job.id).await;
-        delete_worker_via_api(&client, worker.id).await;
-        delete_user_via_api(&client, user.id).await;
+        let worker = create_worker_via_api(&client, user.id).await?;
+        assign_job_to_worker(&client, job.id, worker.id).await?;
+
+        let metric = client
+            .create_metric(&exit_only(job.id, worker.id, 0))
+            .await?;
+        client.delete_metric(metric.id).await?;
+
+        delete_job_via_api(&client, job.id).await?;
+        delete_worker_via_api(&client, worker.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_metrics_by_job_id() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await;
+    async fn test_get_metrics_by_job_id() -> anyhow::Result<()> {
+        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await?;
         let job = &jobs[0];
-        let worker = create_worker_via_api(&client, user.id).await;
-        assign_job_to_worker(&client, job.id, worker.id).await;
-        create_metric_via_api(&client, job.id, worker.id, 4, 4.0, 4.0, 4).await;
+        let worker = create_worker_via_api(&client, user.id).await?;
+        assign_job_to_worker(&client, job.id, worker.id).await?;
+        create_metric_via_api(&client, job.id, worker.id, 4, 4.0, 4.0, 4).await?;
 
-        let res = client
-            .get(&format!("{}/metrics/by_job/{}", APP_HOST, job.id))
-            .send()
-            .await
-            .unwrap();
-        let metrics: JobMetric = res.json().await.unwrap();
+        let metric = client.metric_for_job(job.id).await?;
+        assert_eq!(metric.job_id, job.id);
 
-        delete_job_via_api(&client, job.id).await;
-        delete_worker_via_api(&client, worker.id).await;
-        delete_user_via_api(&client, user.id).await;
+        delete_job_via_api(&client, job.id).await?;
+        delete_worker_via_api(&client, worker.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_metrics_by_worker_id() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(2).await;
-        let job1 = &jobs[0];
-        let job2 = &jobs[1];
-        let worker = create_worker_via_api(&client, user.id).await;
+    async fn test_get_metrics_by_worker_id() -> anyhow::Result<()> {
+        let (client, user, jobs, job_ids) = build_client_and_user_with_n_jobs(2).await?;
+        let worker = create_worker_via_api(&client, user.id).await?;
 
         //worker has 2 jobs assigned now
-        assign_job_to_worker(&client, job1.id, worker.id).await;
-        assign_job_to_worker(&client, job2.id, worker.id).await;
+        for job in &jobs {
+            assign_job_to_worker(&client, job.id, worker.id).await?;
+        }
 
         //now create metrics for these 2 jobs
+        for job in &jobs {
+            create_metric_via_api(&client, job.id, worker.id, 4, 4.0, 4.0, 4).await?;
+        }
 
-        create_metric_via_api(&client, job1.id, worker.id, 4, 4.0, 4.0, 4).await;
-        create_metric_via_api(&client, job2.id, worker.id, 4, 4.0, 4.0, 4).await;
-
-        let res = client
-            .get(&format!("{}/metrics/by_worker/{}", APP_HOST, worker.id))
-            .send()
-            .await
-            .unwrap();
-        let metrics: Vec<JobMetric> = res.json().await.unwrap();
+        let metrics = client.metrics_for_worker(worker.id).await?;
         assert!(metrics.len() >= 2); // Can be more if other tests run in parallel
 
-        delete_jobs_via_api(&client, &vec![job1.id, job2.id]).await;
-        delete_worker_via_api(&client, worker.id).await;
-        delete_user_via_api(&client, user.id).await;
+        delete_jobs_via_api(&client, &job_ids).await?;
+        delete_worker_via_api(&client, worker.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_most_recent_metric_for_job() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await;
+    async fn test_get_most_recent_metric_for_job() -> anyhow::Result<()> {
+        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await?;
         let job = &jobs[0];
-        let worker = create_worker_via_api(&client, user.id).await;
-        assign_job_to_worker(&client, job.id, worker.id).await;
+        let worker = create_worker_via_api(&client, user.id).await?;
+        assign_job_to_worker(&client, job.id, worker.id).await?;
 
         for _ in 0..3 {
-            let payload = json!({
-                "job_id": job.id,
-                "worker_id": worker.id,
-                "exit_code": 0
-            });
             client
-                .post(&format!("{}/metrics", APP_HOST))
-                .json(&payload)
-                .send()
-                .await
-                .unwrap();
+                .create_metric(&exit_only(job.id, worker.id, 0))
+                .await?;
             sleep(Duration::from_millis(10)).await;
         }
 
-        let res = client
-            .get(&format!("{}/metrics/recent/{}", APP_HOST, job.id))
-            .send()
-            .await
-            .unwrap();
-        let recent: Option<JobMetric> = res.json().await.unwrap();
+        let recent = client.recent_metric_for_job(job.id).await?;
         assert!(recent.is_some());
 
-        delete_job_via_api(&client, job.id).await;
-        delete_worker_via_api(&client, worker.id).await;
-        delete_user_via_api(&client, user.id).await;
+        delete_job_via_api(&client, job.id).await?;
+        delete_worker_via_api(&client, worker.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 }
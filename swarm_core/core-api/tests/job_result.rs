@@ -4,159 +4,122 @@ pub mod common_test;
 mod job_result_api_tests {
     use crate::common_test::{
         assign_result_to_job, build_client_and_user_with_n_jobs, delete_job_via_api,
-        delete_user_via_api, APP_HOST,
+        delete_user_via_api,
     };
-    use common::database::models::job::JobResult;
-    use rocket::serde::json::json;
 
     #[tokio::test]
-    async fn test_create_job_result() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await;
+    async fn test_create_job_result() -> anyhow::Result<()> {
+        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await?;
         let job = &jobs[0];
-        let result = assign_result_to_job(&client, job.id).await;
+        let result = assign_result_to_job(&client, job.id).await?;
         assert_eq!(result.job_id, job.id);
-        delete_job_via_api(&client, job.id).await;
-        delete_user_via_api(&client, user.id).await;
+        delete_job_via_api(&client, job.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_job_result_by_id() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await;
+    async fn test_get_job_result_by_id() -> anyhow::Result<()> {
+        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await?;
         let job = &jobs[0];
-        let result = assign_result_to_job(&client, job.id).await;
+        let result = assign_result_to_job(&client, job.id).await?;
 
-        let res = client
-            .get(&format!("{}/results/{}", APP_HOST, result.id))
-            .send()
-            .await
-            .unwrap();
-        assert!(res.status().is_success());
-
-        let fetched = res.json::<JobResult>().await.unwrap();
+        let fetched = client.get_result(result.id).await?;
         assert_eq!(fetched.id, result.id);
 
-        delete_job_via_api(&client, job.id).await;
-        delete_user_via_api(&client, user.id).await;
+        delete_job_via_api(&client, job.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_delete_job_result() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await;
+    async fn test_delete_job_result() -> anyhow::Result<()> {
+        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await?;
         let job = &jobs[0];
-        let result = assign_result_to_job(&client, job.id).await;
+        let result = assign_result_to_job(&client, job.id).await?;
 
-        let res = client
-            .delete(&format!("{}/results/{}", APP_HOST, result.id))
-            .send()
-            .await
-            .unwrap();
-        assert!(res.status().is_success());
+        client.delete_result(result.id).await?;
 
-        delete_job_via_api(&client, job.id).await;
-        delete_user_via_api(&client, user.id).await;
+        delete_job_via_api(&client, job.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_results_by_job_id() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await;
+    async fn test_get_results_by_job_id() -> anyhow::Result<()> {
+        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await?;
         let job = &jobs[0];
-        assign_result_to_job(&client, job.id).await;
-
-        let res = client
-            .get(&format!("{}/results/job/{}", APP_HOST, job.id))
-            .send()
-            .await
-            .unwrap();
-        assert!(res.status().is_success());
+        assign_result_to_job(&client, job.id).await?;
 
-        let results: Vec<JobResult> = res.json().await.unwrap();
+        let results = client.results_for_job(job.id).await?;
         assert!(!results.is_empty());
 
-        delete_job_via_api(&client, job.id).await;
-        delete_user_via_api(&client, user.id).await;
+        delete_job_via_api(&client, job.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_list_results_for_job() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await;
+    async fn test_list_results_for_job() -> anyhow::Result<()> {
+        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await?;
         let job = &jobs[0];
-        assign_result_to_job(&client, job.id).await;
+        assign_result_to_job(&client, job.id).await?;
 
-        let res = client
-            .get(&format!("{}/results/list/{}", APP_HOST, job.id))
-            .send()
-            .await
-            .unwrap();
-        assert!(res.status().is_success());
-
-        let list: Vec<JobResult> = res.json().await.unwrap();
+        let list = client.list_results_for_job(job.id).await?;
         assert!(!list.is_empty());
 
-        delete_job_via_api(&client, job.id).await;
-        delete_user_via_api(&client, user.id).await;
+        delete_job_via_api(&client, job.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_most_recent_result_for_job() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await;
+    async fn test_get_most_recent_result_for_job() -> anyhow::Result<()> {
+        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await?;
         let job = &jobs[0];
-        assign_result_to_job(&client, job.id).await;
-
-        let res = client
-            .get(&format!("{}/results/recent/{}", APP_HOST, job.id))
-            .send()
-            .await
-            .unwrap();
-        assert!(res.status().is_success());
+        assign_result_to_job(&client, job.id).await?;
 
-        let result: Option<JobResult> = res.json().await.unwrap();
+        let result = client.recent_result_for_job(job.id).await?;
         assert!(result.is_some());
 
-        delete_job_via_api(&client, job.id).await;
-        delete_user_via_api(&client, user.id).await;
+        delete_job_via_api(&client, job.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_update_stdout_field() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await;
+    async fn test_update_stdout_field() -> anyhow::Result<()> {
+        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await?;
         let job = &jobs[0];
-        let result = assign_result_to_job(&client, job.id).await;
+        let result = assign_result_to_job(&client, job.id).await?;
 
         let new_stdout = "Updated output logs";
-        let res = client
-            .patch(&format!("{}/results/{}/stdout", APP_HOST, result.id))
-            .json(&json!({ "stdout": new_stdout }))
-            .send()
-            .await
-            .unwrap();
-        assert!(res.status().is_success());
-
-        let updated = res.json::<JobResult>().await.unwrap();
+        let updated = client
+            .update_result_stdout(result.id, Some(new_stdout))
+            .await?;
         assert_eq!(updated.stdout.as_deref(), Some(new_stdout));
 
-        delete_job_via_api(&client, job.id).await;
-        delete_user_via_api(&client, user.id).await;
+        delete_job_via_api(&client, job.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_update_files_field() {
-        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await;
+    async fn test_update_files_field() -> anyhow::Result<()> {
+        let (client, user, jobs, _) = build_client_and_user_with_n_jobs(1).await?;
         let job = &jobs[0];
-        let result = assign_result_to_job(&client, job.id).await;
-
-        let new_files = vec![Some("final-output.csv".to_string())];
-        let res = client
-            .patch(&format!("{}/results/{}/files", APP_HOST, result.id))
-            .json(&json!({ "files": new_files }))
-            .send()
-            .await
-            .unwrap();
-        assert!(res.status().is_success());
-
-        let updated = res.json::<JobResult>().await.unwrap();
-        assert_eq!(updated.files.unwrap(), new_files);
-
-        delete_job_via_api(&client, job.id).await;
-        delete_user_via_api(&client, user.id).await;
+        let result = assign_result_to_job(&client, job.id).await?;
+
+        let updated = client
+            .update_result_files(result.id, Some(vec!["final-output.csv".to_string()]))
+            .await?;
+        assert_eq!(
+            updated.files,
+            Some(vec![Some("final-output.csv".to_string())])
+        );
+
+        delete_job_via_api(&client, job.id).await?;
+        delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 }
@@ -0,0 +1,33 @@
+use reqwest::StatusCode;
+
+use swarm_client::enums::{LogActionEnum, LogLevelEnum, SystemModuleEnum};
+use swarm_client::models::NewLogRetentionPolicy;
+
+pub mod common_test;
+
+#[tokio::test]
+async fn test_retention_changes_need_an_admin() -> anyhow::Result<()> {
+    let (client, user) = common_test::build_client_with_logged_in_admin().await?;
+    let policy = NewLogRetentionPolicy {
+        level: Some(LogLevelEnum::Info),
+        module: Some(SystemModuleEnum::Scheduler),
+        action: Some(LogActionEnum::JobPreempted),
+        retention_minutes: 60,
+    };
+
+    // everyone may read the policies, nobody but an admin change them
+    client.log_retention_policies().await?;
+    let err = client.set_log_retention(&policy).await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+
+    let admin = common_test::build_client_with_core_admin().await?;
+    let saved = admin.set_log_retention(&policy).await?;
+    assert_eq!(saved.retention_minutes, 60);
+
+    let err = client.delete_log_retention(saved.id).await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+    admin.delete_log_retention(saved.id).await?;
+
+    common_test::delete_user_via_api(&client, user.id).await?;
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use reqwest::StatusCode;
+
+use common::database::models::quota::DEFAULT_MAX_QUEUED_JOBS;
+use swarm_client::models::UpdateQuota;
+use swarm_client::ClientError;
+
+pub mod common_test;
+
+#[tokio::test]
+async fn test_quota_defaults_and_usage() -> anyhow::Result<()> {
+    let (client, user, _jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(2).await?;
+
+    let status = client.quota_status(user.id).await?;
+    assert_eq!(status.quota.max_queued_jobs, DEFAULT_MAX_QUEUED_JOBS);
+    assert_eq!(status.usage.submissions_last_minute, 2);
+
+    common_test::delete_jobs_via_api(&client, &job_ids).await?;
+    common_test::delete_user_via_api(&client, user.id).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_quota_changes_need_an_admin() -> anyhow::Result<()> {
+    let (client, user) = common_test::build_client_with_logged_in_admin().await?;
+    let (other, other_user) = common_test::build_client_with_logged_in_admin().await?;
+    let limits = UpdateQuota {
+        max_queued_jobs: 1000,
+        max_running_jobs: 1000,
+        max_submissions_per_minute: 1000,
+    };
+
+    // own quota can be read but not raised
+    let err = client.set_quota(user.id, &limits).await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+    let err = client.reset_quota(user.id).await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+    // nor can the quota of someone else be read
+    let err = other.quota_status(user.id).await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+
+    let admin = common_test::build_client_with_core_admin().await?;
+    admin.quota_status(user.id).await?;
+    admin.set_quota(user.id, &limits).await?;
+    admin.reset_quota(user.id).await?;
+
+    common_test::delete_user_via_api(&other, other_user.id).await?;
+    common_test::delete_user_via_api(&client, user.id).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_submission_rate_limit() -> anyhow::Result<()> {
+    let (client, user, _jobs, job_ids) = common_test::build_client_and_user_with_n_jobs(1).await?;
+    let admin = common_test::build_client_with_core_admin().await?;
+
+    // 1) Allow a single submission per minute, already used by the job above
+    let limits = UpdateQuota {
+        max_queued_jobs: 10,
+        max_running_jobs: 1,
+        max_submissions_per_minute: 1,
+    };
+    admin.set_quota(user.id, &limits).await?;
+
+    // 2) Next submission is rejected with a retry hint
+    let resp = client
+        .http()
+        .post(client.url(&["jobs"]))
+        .json(&common_test::new_job(user.id))
+        .send()
+        .await?;
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(resp.headers().contains_key("Retry-After"));
+
+    let err = client
+        .create_job(&common_test::new_job(user.id))
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::TOO_MANY_REQUESTS));
+    assert_eq!(err.code(), Some("quota_exceeded"));
+    assert!(err.retry_after_secs().unwrap_or(0) > 0);
+    let ClientError::Api { body, .. } = err else {
+        panic!("expected an api error");
+    };
+    assert_eq!(body["details"]["quota"], "max_submissions_per_minute");
+
+    // 3) Reset back to defaults
+    admin.reset_quota(user.id).await?;
+
+    common_test::delete_jobs_via_api(&client, &job_ids).await?;
+    common_test::delete_user_via_api(&client, user.id).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_submissions_respect_the_rate_limit() -> anyhow::Result<()> {
+    let (client, user) = common_test::build_client_with_logged_in_admin().await?;
+    let admin = common_test::build_client_with_core_admin().await?;
+    let limits = UpdateQuota {
+        max_queued_jobs: 10,
+        max_running_jobs: 1,
+        max_submissions_per_minute: 3,
+    };
+    admin.set_quota(user.id, &limits).await?;
+
+    // all at once, each submission is counted after the one before it
+    let submissions = (0..8).map(|_| {
+        let client = client.clone();
+        let new_job = common_test::new_job(user.id);
+        async move { client.create_job(&new_job).await }
+    });
+    let results = futures::future::join_all(submissions).await;
+    let job_ids: Vec<i32> = results
+        .iter()
+        .filter_map(|r| r.as_ref().ok().map(|job| job.id))
+        .collect();
+    assert_eq!(job_ids.len(), 3);
+    for err in results.iter().filter_map(|r| r.as_ref().err()) {
+        assert_eq!(err.status(), Some(StatusCode::TOO_MANY_REQUESTS));
+    }
+
+    admin.reset_quota(user.id).await?;
+    common_test::delete_jobs_via_api(&client, &job_ids).await?;
+    common_test::delete_user_via_api(&client, user.id).await?;
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use reqwest::StatusCode;
+
+use swarm_client::enums::{
+    ImageFormatEnum, OutputTypeEnum, ScheduleTypeEnum, TemplateParamKindEnum,
+};
+use swarm_client::models::{
+    CreateTemplateRequest, NewJobTemplate, RunTemplateRequest, TemplateParameterSpec,
+};
+
+pub mod common_test;
+
+fn overrides(pairs: &[(&str, &str)]) -> RunTemplateRequest {
+    RunTemplateRequest {
+        job_name: None,
+        overrides: pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<_, _>>(),
+    }
+}
+
+fn new_template(user_id: i32) -> CreateTemplateRequest {
+    CreateTemplateRequest {
+        template: NewJobTemplate {
+            user_id,
+            name: common_test::generate_unique_job_name(),
+            image_url: "docker.io/library/alpine:latest".to_string(),
+            image_format: ImageFormatEnum::DockerRegistry,
+            docker_flags: None,
+            output_type: OutputTypeEnum::Stdout,
+            output_paths: None,
+            schedule_type: ScheduleTypeEnum::Once,
+            cron_expression: None,
+            notes: None,
+            priority: 0,
+        },
+        parameters: vec![],
+    }
+}
+
+#[tokio::test]
+async fn test_run_template_with_overrides() -> anyhow::Result<()> {
+    let (client, user) = common_test::build_client_with_logged_in_admin().await?;
+
+    // 1) Template with a required tag and an optional env variable
+    let request = CreateTemplateRequest {
+        template: NewJobTemplate {
+            user_id: user.id,
+            name: common_test::generate_unique_job_name(),
+            image_url: "docker.io/library/alpine:latest".to_string(),
+            image_format: ImageFormatEnum::DockerRegistry,
+            docker_flags: None,
+            output_type: OutputTypeEnum::Stdout,
+            output_paths: None,
+            schedule_type: ScheduleTypeEnum::Once,
+            cron_expression: None,
+            notes: None,
+            priority: 0,
+        },
+        parameters: vec![
+            TemplateParameterSpec {
+                name: "tag".to_string(),
+                kind: TemplateParamKindEnum::ImageTag,
+                default_value: Some("3.19".to_string()),
+                required: true,
+            },
+            TemplateParameterSpec {
+                name: "MODE".to_string(),
+                kind: TemplateParamKindEnum::Env,
+                default_value: None,
+                required: false,
+            },
+        ],
+    };
+    let template = client.create_template(&request).await?;
+    assert_eq!(template.parameters.len(), 2);
+
+    // 2) Run with an override
+    let job = client
+        .run_template(
+            template.template.id,
+            &overrides(&[("tag", "3.20"), ("MODE", "fast")]),
+        )
+        .await?;
+    assert_eq!(job.image_url, "docker.io/library/alpine:3.20");
+    assert_eq!(job.docker_flags, Some(vec![Some("--env=MODE=fast".into())]));
+
+    // 3) Unknown parameters are rejected
+    let err = client
+        .run_template(template.template.id, &overrides(&[("nope", "x")]))
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::BAD_REQUEST));
+
+    // 4) Re-run clones the job
+    let rerun = client.rerun_job(job.id).await?;
+    assert_ne!(rerun.id, job.id);
+    assert_eq!(rerun.image_url, job.image_url);
+
+    common_test::delete_jobs_via_api(&client, &[job.id, rerun.id]).await?;
+    client.delete_template(template.template.id).await?;
+    common_test::delete_user_via_api(&client, user.id).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_templates_of_other_users_are_forbidden() -> anyhow::Result<()> {
+    let (client, user) = common_test::build_client_with_logged_in_admin().await?;
+    let (other, other_user) = common_test::build_client_with_logged_in_admin().await?;
+
+    let template = client.create_template(&new_template(user.id)).await?;
+    let id = template.template.id;
+
+    // no creating for, reading, listing, deleting or running someone else's templates
+    let err = other.create_template(&new_template(user.id)).await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+    let err = other.get_template(id).await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+    let err = other.templates_for_user(user.id).await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+    let err = other.delete_template(id).await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+    let err = other.run_template(id, &overrides(&[])).await.unwrap_err();
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN));
+
+    // the owner still can
+    assert_eq!(client.get_template(id).await?.template.id, id);
+    assert_eq!(client.templates_for_user(user.id).await?.len(), 1);
+    client.delete_template(id).await?;
+
+    common_test::delete_user_via_api(&other, other_user.id).await?;
+    common_test::delete_user_via_api(&client, user.id).await?;
+    Ok(())
+}
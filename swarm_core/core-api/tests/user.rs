@@ -1,53 +1,31 @@
 use reqwest::StatusCode;
-use rocket::serde::json::json;
-use tokio;
 
-use common::database::models::user::UserResponse;
+use swarm_client::models::UpdateUserRequest;
 
 pub mod common_test;
 
 #[cfg(test)]
 mod user_api_tests {
-    use crate::common_test::APP_HOST;
-
     use super::*;
 
     #[tokio::test]
-    async fn test_create_user() {
-        let (client, admin_user) = common_test::build_client_with_logged_in_admin().await;
+    async fn test_create_user() -> anyhow::Result<()> {
+        let (client, admin_user) = common_test::build_client_with_logged_in_admin().await?;
 
-        let resp = client
-            .get(format!("{}/users/{}", APP_HOST, admin_user.id))
-            .send()
-            .await
-            .expect("Request failed");
-        assert_eq!(resp.status(), StatusCode::OK);
+        client.get_user(admin_user.id).await?;
 
         // Clean up by deleting the user
-        common_test::delete_user_via_api(&client, admin_user.id).await;
+        common_test::delete_user_via_api(&client, admin_user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_get_user_by_id() {
-        // Use the helper function to create an authenticated user and get the token
-        let (client, admin_user) = common_test::build_client_with_logged_in_admin().await;
-
-        // Make an authenticated GET request to fetch the user by ID
-        let resp = client
-            .get(&format!(
-                "{}/users/{}",
-                common_test::APP_HOST,
-                admin_user.id
-            ))
-            .send()
-            .await
-            .unwrap();
-
-        // Assert that the status code is OK
-        assert_eq!(resp.status(), StatusCode::OK);
-
-        // Deserialize the response body into the `User` struct
-        let fetched: UserResponse = resp.json().await.unwrap();
+    async fn test_get_user_by_id() -> anyhow::Result<()> {
+        // Use the helper function to create an authenticated user
+        let (client, admin_user) = common_test::build_client_with_logged_in_admin().await?;
+
+        // Fetch the user by ID with the authenticated client
+        let fetched = client.get_user(admin_user.id).await?;
 
         // Assert that the fetched user matches the admin user that was created
         assert_eq!(fetched.id, admin_user.id);
@@ -55,286 +33,175 @@ mod user_api_tests {
         assert_eq!(fetched.email, admin_user.email);
 
         // Clean up by deleting the user
-        common_test::delete_user_via_api(&client, admin_user.id).await;
-    }
-
-    use serde::Serialize;
-
-    #[derive(Serialize)]
-    struct UpdateUserRequest {
-        username: String,
-        email: String,
-        password: Option<String>,
+        common_test::delete_user_via_api(&client, admin_user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_update_user() {
-        let (client, user_resp) = common_test::build_client_with_logged_in_admin().await;
+    async fn test_update_user() -> anyhow::Result<()> {
+        let (client, user_resp) = common_test::build_client_with_logged_in_admin().await?;
 
         // Define new values
         let new_password = "MySecurePass123";
         let new_email = format!("updated_{}@example.com", user_resp.id);
 
-        // Build JSON payload directly
-        let update_payload = json!({
-            "username": user_resp.username,
-            "email": new_email,
-            "password": new_password
-        });
-
-        // Send PUT request
-        let resp = client
-            .put(format!("{}/users/{}", APP_HOST, user_resp.id))
-            .json(&update_payload)
-            .send()
-            .await
-            .expect("Request failed");
-
-        assert_eq!(resp.status(), StatusCode::OK);
-
-        let updated: UserResponse = resp.json().await.unwrap();
+        let update = UpdateUserRequest {
+            username: user_resp.username.clone(),
+            email: new_email.clone(),
+            password: Some(new_password.to_string()),
+        };
+        let updated = client.update_user(user_resp.id, &update).await?;
         assert_eq!(updated.id, user_resp.id);
         assert_eq!(updated.email, new_email);
 
         // Attempt login with new password
-        let login_resp = common_test::login_user(&client, &user_resp.username, new_password).await;
-        assert_eq!(
-            login_resp.status(),
-            StatusCode::OK,
-            "Login with new password failed"
-        );
+        let login = common_test::login_user(&client, &user_resp.username, new_password).await;
+        assert!(login.is_ok(), "Login with new password failed");
 
         // Attempt login with old password
-        let old_pass_login_resp =
+        let old_pass_login =
             common_test::login_user(&client, &user_resp.username, common_test::TEST_PASSWORD).await;
         assert_eq!(
-            old_pass_login_resp.status(),
-            StatusCode::UNAUTHORIZED,
+            old_pass_login.err().and_then(|e| e.status()),
+            Some(StatusCode::UNAUTHORIZED),
             "Old password still works"
         );
 
-        common_test::delete_user_via_api(&client, user_resp.id).await;
+        common_test::delete_user_via_api(&client, user_resp.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_delete_user() {
-        let (client, user) = common_test::build_client_with_logged_in_admin().await;
-        let _ = common_test::delete_user_via_api(&client, user.id).await;
-        // delete_user_via_api should return status or panic on error
+    async fn test_delete_user() -> anyhow::Result<()> {
+        let (client, user) = common_test::build_client_with_logged_in_admin().await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+
         // verify deletion by attempting to fetch
-        let (client1, user1) = common_test::build_client_with_logged_in_admin().await;
-        let check = client1
-            .get(format!("{}/users/{}", APP_HOST, user.id))
-            .send()
-            .await
-            .expect("Request failed");
-
-        assert_eq!(check.status(), StatusCode::NOT_FOUND);
-        common_test::delete_user_via_api(&client1, user1.id).await;
+        let (client1, user1) = common_test::build_client_with_logged_in_admin().await?;
+        let err = client1.get_user(user.id).await.unwrap_err();
+        assert!(err.is_not_found());
+
+        common_test::delete_user_via_api(&client1, user1.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_find_user_by_email() {
-        let (client, user) = common_test::build_client_with_logged_in_admin().await;
-        let resp = client
-            .get(format!("{}/users/email/{}", APP_HOST, user.email))
-            .send()
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), StatusCode::OK);
-        let found: Option<UserResponse> = resp.json().await.unwrap();
-        assert!(found.is_some());
-        let u = found.unwrap();
-        assert_eq!(u.id, user.id);
-        common_test::delete_user_via_api(&client, user.id).await;
+    async fn test_find_user_by_email() -> anyhow::Result<()> {
+        let (client, user) = common_test::build_client_with_logged_in_admin().await?;
+        let found = client.find_user_by_email(&user.email).await?;
+        assert_eq!(found.id, user.id);
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_find_user_by_username() {
-        let (client, user) = common_test::build_client_with_logged_in_admin().await;
-        let resp = client
-            .get(format!("{}/users/username/{}", APP_HOST, user.username))
-            .send()
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), StatusCode::OK);
-        let found: Option<UserResponse> = resp.json().await.unwrap();
-        assert!(found.is_some());
-        let u = found.unwrap();
-        assert_eq!(u.username, user.username);
-        common_test::delete_user_via_api(&client, user.id).await;
+    async fn test_find_user_by_username() -> anyhow::Result<()> {
+        let (client, user) = common_test::build_client_with_logged_in_admin().await?;
+        let found = client
+            .find_user_by_username(&user.username)
+            .await?
+            .expect("user should exist");
+        assert_eq!(found.username, user.username);
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_search_users_by_username() {
-        let (_client, user1) = common_test::build_client_with_logged_in_admin().await;
+    async fn test_search_users_by_username() -> anyhow::Result<()> {
+        let (_client, user1) = common_test::build_client_with_logged_in_admin().await?;
         // create a second user for search
-        let (client, user2) = common_test::build_client_with_logged_in_admin().await;
+        let (client, user2) = common_test::build_client_with_logged_in_admin().await?;
 
         let query = &user1.username[..3];
-        let resp = client
-            .get(format!("{}/users/search/username?q={}", APP_HOST, query))
-            .send()
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), StatusCode::OK);
-        let list: Vec<UserResponse> = resp.json().await.unwrap();
+        let list = client.search_users_by_username(query).await?;
         assert!(list.iter().any(|u| u.id == user1.id));
-        common_test::delete_user_via_api(&client, user1.id).await;
-        common_test::delete_user_via_api(&client, user2.id).await;
+
+        common_test::delete_users_via_api(&client, &[user1.id, user2.id]).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_search_users_by_email() {
-        let (_client, user1) = common_test::build_client_with_logged_in_admin().await;
-        let (client, user2) = common_test::build_client_with_logged_in_admin().await;
-        let query = "example.com";
-        let resp = client
-            .get(format!("{}/users/search/email?q={}", APP_HOST, query))
-            .send()
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), StatusCode::OK);
-        let list: Vec<UserResponse> = resp.json().await.unwrap();
+    async fn test_search_users_by_email() -> anyhow::Result<()> {
+        let (_client, user1) = common_test::build_client_with_logged_in_admin().await?;
+        let (client, user2) = common_test::build_client_with_logged_in_admin().await?;
+
+        let list = client.search_users_by_email("example.com").await?;
         assert!(list.len() >= 2);
-        common_test::delete_user_via_api(&client, user1.id).await;
-        common_test::delete_user_via_api(&client, user2.id).await;
+
+        common_test::delete_users_via_api(&client, &[user1.id, user2.id]).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_list_users_paginated() {
-        let (_client, u1) = common_test::build_client_with_logged_in_admin().await;
-        let (_client, u2) = common_test::build_client_with_logged_in_admin().await;
-        let (client, u3) = common_test::build_client_with_logged_in_admin().await;
-
-        let resp = client
-            .get(format!("{}/users?page=1&limit=2", APP_HOST))
-            .send()
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), StatusCode::OK);
-        let page1: Vec<UserResponse> = resp.json().await.unwrap();
-        assert_eq!(page1.len(), 2);
+    async fn test_list_users_paginated() -> anyhow::Result<()> {
+        let (_client, u1) = common_test::build_client_with_logged_in_admin().await?;
+        let (_client, u2) = common_test::build_client_with_logged_in_admin().await?;
+        let (client, u3) = common_test::build_client_with_logged_in_admin().await?;
 
-        let resp2 = client
-            .get(format!("{}/users?page=2&limit=2", APP_HOST))
-            .send()
-            .await
-            .unwrap();
+        let page1 = client.list_users(Some(1), Some(2)).await?;
+        assert_eq!(page1.len(), 2);
 
-        let page2: Vec<UserResponse> = resp2.json().await.unwrap();
-        println!("Page 2 users: {:?}", page2);
-        assert!(page2.len() > 0); // Check that page2 has users
+        let page2 = client.list_users(Some(2), Some(2)).await?;
+        assert!(!page2.is_empty());
 
-        common_test::delete_user_via_api(&client, u1.id).await;
-        common_test::delete_user_via_api(&client, u2.id).await;
-        common_test::delete_user_via_api(&client, u3.id).await;
+        common_test::delete_users_via_api(&client, &[u1.id, u2.id, u3.id]).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_exists_user_by_email() {
-        let (client, user) = common_test::build_client_with_logged_in_admin().await;
-        let resp = client
-            .head(format!("{}/users/exists/email/{}", APP_HOST, user.email))
-            .send()
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
-
-        let resp2 = client
-            .head(format!(
-                "{}/users/exists/email/nonexistent@example.com",
-                APP_HOST
-            ))
-            .send()
-            .await
-            .unwrap();
-        assert_eq!(resp2.status(), StatusCode::NOT_FOUND);
-
-        common_test::delete_user_via_api(&client, user.id).await;
+    async fn test_exists_user_by_email() -> anyhow::Result<()> {
+        let (client, user) = common_test::build_client_with_logged_in_admin().await?;
+
+        assert!(client.user_email_exists(&user.email).await?);
+        assert!(!client.user_email_exists("nonexistent@example.com").await?);
+
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_exists_user_by_username() {
-        let (client, user) = common_test::build_client_with_logged_in_admin().await;
-        let resp = client
-            .head(format!(
-                "{}/users/exists/username/{}",
-                APP_HOST, user.username
-            ))
-            .send()
-            .await
-            .unwrap();
-        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
-
-        let resp2 = client
-            .head(format!(
-                "{}/users/exists/username/nonexistent_user",
-                APP_HOST
-            ))
-            .send()
-            .await
-            .unwrap();
-        assert_eq!(resp2.status(), StatusCode::NOT_FOUND);
-
-        common_test::delete_user_via_api(&client, user.id).await;
+    async fn test_exists_user_by_username() -> anyhow::Result<()> {
+        let (client, user) = common_test::build_client_with_logged_in_admin().await?;
+
+        assert!(client.username_exists(&user.username).await?);
+        assert!(!client.username_exists("nonexistent_user").await?);
+
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_users_with_jobs() {
+    async fn test_users_with_jobs() -> anyhow::Result<()> {
         // Create client, user, and jobs
         let (client, user, _jobs, job_ids) =
-            common_test::build_client_and_user_with_n_jobs(3).await;
-
-        // Call /users/with-jobs endpoint
-        let resp = client
-            .get(format!("{}/users/with-jobs", APP_HOST))
-            .send()
-            .await
-            .expect("Request to /users/with-jobs failed");
-
-        assert_eq!(resp.status(), StatusCode::OK);
-
-        let users: Vec<UserResponse> = resp.json().await.expect("Failed to parse user list");
-        let found = users.iter().any(|u| u.id == user.id);
+            common_test::build_client_and_user_with_n_jobs(3).await?;
 
+        let users = client.users_with_jobs().await?;
         assert!(
-            found,
+            users.iter().any(|u| u.id == user.id),
             "Expected user with ID {} to be present in /users/with-jobs response",
             user.id
         );
 
         // Cleanup: delete jobs, then user
-        common_test::delete_jobs_via_api(&client, &job_ids).await;
-        common_test::delete_user_via_api(&client, user.id).await;
+        common_test::delete_jobs_via_api(&client, &job_ids).await?;
+        common_test::delete_user_via_api(&client, user.id).await?;
+        Ok(())
     }
 
     #[tokio::test]
-    async fn test_user_job_counts() {
+    async fn test_user_job_counts() -> anyhow::Result<()> {
         // Create 3 users with 1, 2, and 3 jobs respectively
-        let (client1, user1, _, jobs1) = common_test::build_client_and_user_with_n_jobs(1).await;
-        let (client2, user2, _, jobs2) = common_test::build_client_and_user_with_n_jobs(2).await;
-        let (client3, user3, _, jobs3) = common_test::build_client_and_user_with_n_jobs(3).await;
+        let (client1, user1, _, jobs1) = common_test::build_client_and_user_with_n_jobs(1).await?;
+        let (client2, user2, _, jobs2) = common_test::build_client_and_user_with_n_jobs(2).await?;
+        let (client3, user3, _, jobs3) = common_test::build_client_and_user_with_n_jobs(3).await?;
 
         // Use any client to call the aggregation endpoint
-        let resp = client1
-            .get(format!("{}/users/job-counts", APP_HOST))
-            .send()
-            .await
-            .expect("Request to /users/job-counts failed");
-
-        assert_eq!(resp.status(), StatusCode::OK);
-
-        let result: Vec<(serde_json::Value, i64)> = resp
-            .json()
-            .await
-            .expect("Failed to parse job count response");
-
-        // Helper to extract user + count
+        let result = client1.user_job_counts().await?;
         let get_count_for_id = |id: i32| -> Option<i64> {
             result
                 .iter()
-                .find(|(user, _count)| user["id"] == id)
+                .find(|(user, _count)| user.id == id)
                 .map(|(_user, count)| *count)
         };
 
@@ -356,13 +223,14 @@ mod user_api_tests {
         );
 
         // Cleanup jobs first
-        common_test::delete_jobs_via_api(&client1, &jobs1).await;
-        common_test::delete_jobs_via_api(&client2, &jobs2).await;
-        common_test::delete_jobs_via_api(&client3, &jobs3).await;
+        common_test::delete_jobs_via_api(&client1, &jobs1).await?;
+        common_test::delete_jobs_via_api(&client2, &jobs2).await?;
+        common_test::delete_jobs_via_api(&client3, &jobs3).await?;
 
         // Then users
-        common_test::delete_user_via_api(&client1, user1.id).await;
-        common_test::delete_user_via_api(&client2, user2.id).await;
-        common_test::delete_user_via_api(&client3, user3.id).await;
+        common_test::delete_user_via_api(&client1, user1.id).await?;
+        common_test::delete_user_via_api(&client2, user2.id).await?;
+        common_test::delete_user_via_api(&client3, user3.id).await?;
+        Ok(())
     }
 }
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_jobs_user_created_at;
+DROP INDEX IF EXISTS idx_jobs_user_state;
+
+DROP TABLE IF EXISTS user_quotas;
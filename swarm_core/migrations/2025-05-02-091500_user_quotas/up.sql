@@ -0,0 +1,13 @@
+-- Your SQL goes here
+CREATE TABLE user_quotas (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL UNIQUE REFERENCES users(id) ON DELETE CASCADE,
+    max_queued_jobs INTEGER NOT NULL,  -- Submitted + Queued jobs a user may hold at once
+    max_running_jobs INTEGER NOT NULL,  -- Jobs the Scheduler lets run concurrently
+    max_submissions_per_minute INTEGER NOT NULL,  -- Sliding one minute window on POST /jobs
+    updated_at TIMESTAMP NOT NULL DEFAULT now()
+);
+
+-- Quota checks count a user's jobs by state and by creation time
+CREATE INDEX idx_jobs_user_state ON jobs (user_id, state);
+CREATE INDEX idx_jobs_user_created_at ON jobs (user_id, created_at);
@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_jobs_state_priority;
+
+ALTER TABLE jobs DROP COLUMN IF EXISTS priority;
@@ -0,0 +1,5 @@
+-- Your SQL goes here
+-- Higher value runs first, the Scheduler ages waiting jobs on top of this
+ALTER TABLE jobs ADD COLUMN priority INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_jobs_state_priority ON jobs (state, priority DESC);
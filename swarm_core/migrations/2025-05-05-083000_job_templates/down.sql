@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS job_template_parameters;
+DROP TABLE IF EXISTS job_templates;
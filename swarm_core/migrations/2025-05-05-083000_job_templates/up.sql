@@ -0,0 +1,28 @@
+-- Your SQL goes here
+CREATE TABLE job_templates (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    image_url TEXT NOT NULL,  -- May hold `{{param}}` placeholders for Text parameters
+    image_format varchar(64) NOT NULL,
+    docker_flags TEXT[],
+    output_type varchar(64) NOT NULL,
+    output_paths TEXT[],
+    schedule_type varchar(64) NOT NULL,
+    cron_expression TEXT,
+    notes TEXT,
+    priority INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP NOT NULL DEFAULT now(),
+    UNIQUE (user_id, name)
+);
+
+CREATE TABLE job_template_parameters (
+    id SERIAL PRIMARY KEY,
+    template_id INTEGER NOT NULL REFERENCES job_templates(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    kind varchar(64) NOT NULL,  -- "ImageTag", "Env" or "Text"
+    default_value TEXT,
+    required BOOLEAN NOT NULL DEFAULT TRUE,
+    UNIQUE (template_id, name)
+);
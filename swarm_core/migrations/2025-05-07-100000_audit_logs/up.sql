@@ -0,0 +1,46 @@
+-- Your SQL goes here
+-- Audit trail of every mutating API call, append-only
+CREATE TABLE audit_logs (
+    id SERIAL PRIMARY KEY,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    expires_at TIMESTAMP NOT NULL,
+
+    -- who, no FK so the trail survives deleted users
+    user_id INTEGER,
+    username TEXT,
+    token_prefix VARCHAR(16),
+    client_ip TEXT,
+
+    -- what
+    method VARCHAR(8) NOT NULL,
+    route TEXT NOT NULL,
+    path TEXT NOT NULL,
+    resource VARCHAR(64),
+    resource_id INTEGER,
+    status INTEGER NOT NULL,
+
+    -- row snapshots, secrets stripped
+    before JSONB,
+    after JSONB
+);
+
+CREATE INDEX idx_audit_logs_created_at ON audit_logs (created_at);
+CREATE INDEX idx_audit_logs_username ON audit_logs (username);
+CREATE INDEX idx_audit_logs_resource ON audit_logs (resource, resource_id);
+
+-- Entries can never be changed, and only removed once they expired
+CREATE FUNCTION audit_logs_append_only() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'UPDATE' THEN
+        RAISE EXCEPTION 'audit_logs is append-only';
+    END IF;
+    IF OLD.expires_at > now() THEN
+        RAISE EXCEPTION 'audit log % has not expired yet', OLD.id;
+    END IF;
+    RETURN OLD;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_logs_append_only
+    BEFORE UPDATE OR DELETE ON audit_logs
+    FOR EACH ROW EXECUTE FUNCTION audit_logs_append_only();
@@ -0,0 +1,20 @@
+-- Your SQL goes here
+-- How long log entries are kept, NULL matches every level, module or action
+-- The most specific matching policy wins: action before module before level
+CREATE TABLE log_retention_policies (
+    id SERIAL PRIMARY KEY,
+    level VARCHAR(64),
+    module VARCHAR(64),
+    action VARCHAR(64),
+    retention_minutes BIGINT NOT NULL CHECK (retention_minutes > 0),
+    updated_at TIMESTAMP NOT NULL DEFAULT now(),
+    UNIQUE NULLS NOT DISTINCT (level, module, action)
+);
+
+-- Same expiry the Logger used to hard-code
+INSERT INTO log_retention_policies (level, retention_minutes) VALUES
+    ('Info', 5),
+    ('Success', 1440),
+    ('Warning', 4320),
+    ('Error', 10080),
+    ('Fatal', 10080);
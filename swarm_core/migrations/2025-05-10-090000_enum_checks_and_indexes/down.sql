@@ -0,0 +1,32 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_logs_expires_at;
+DROP INDEX IF EXISTS idx_job_assignments_job_id;
+DROP INDEX IF EXISTS idx_job_assignments_worker_id;
+
+ALTER TABLE webhook_deliveries
+    DROP CONSTRAINT IF EXISTS webhook_deliveries_status_check,
+    DROP CONSTRAINT IF EXISTS webhook_deliveries_event_check;
+ALTER TABLE webhooks DROP CONSTRAINT IF EXISTS webhooks_events_check;
+ALTER TABLE webhooks RENAME CONSTRAINT webhooks_events_not_null_check TO webhooks_events_check;
+ALTER TABLE log_retention_policies
+    DROP CONSTRAINT IF EXISTS log_retention_policies_action_check,
+    DROP CONSTRAINT IF EXISTS log_retention_policies_module_check,
+    DROP CONSTRAINT IF EXISTS log_retention_policies_level_check;
+ALTER TABLE logs
+    DROP CONSTRAINT IF EXISTS logs_to_module_check,
+    DROP CONSTRAINT IF EXISTS logs_from_module_check,
+    DROP CONSTRAINT IF EXISTS logs_action_check,
+    DROP CONSTRAINT IF EXISTS logs_module_check,
+    DROP CONSTRAINT IF EXISTS logs_level_check;
+ALTER TABLE worker_status DROP CONSTRAINT IF EXISTS worker_status_status_check;
+ALTER TABLE workers DROP CONSTRAINT IF EXISTS workers_os_check;
+ALTER TABLE job_template_parameters DROP CONSTRAINT IF EXISTS job_template_parameters_kind_check;
+ALTER TABLE job_templates
+    DROP CONSTRAINT IF EXISTS job_templates_schedule_type_check,
+    DROP CONSTRAINT IF EXISTS job_templates_output_type_check,
+    DROP CONSTRAINT IF EXISTS job_templates_image_format_check;
+ALTER TABLE jobs
+    DROP CONSTRAINT IF EXISTS jobs_schedule_type_check,
+    DROP CONSTRAINT IF EXISTS jobs_output_type_check,
+    DROP CONSTRAINT IF EXISTS jobs_image_format_check,
+    DROP CONSTRAINT IF EXISTS jobs_state_check;
@@ -0,0 +1,50 @@
+-- Your SQL goes here
+-- Enum columns stay text, these checks only allow the variant names of the matching enums in
+-- common::enums. Adding a variant means a new migration that replaces the constraint.
+
+ALTER TABLE jobs
+    ADD CONSTRAINT jobs_state_check CHECK (state IN ('Submitted', 'Queued', 'Running', 'Completed', 'Failed')),
+    ADD CONSTRAINT jobs_image_format_check CHECK (image_format IN ('Tarball', 'DockerRegistry')),
+    ADD CONSTRAINT jobs_output_type_check CHECK (output_type IN ('Stdout', 'Files')),
+    ADD CONSTRAINT jobs_schedule_type_check CHECK (schedule_type IN ('Once', 'Cron'));
+
+ALTER TABLE job_templates
+    ADD CONSTRAINT job_templates_image_format_check CHECK (image_format IN ('Tarball', 'DockerRegistry')),
+    ADD CONSTRAINT job_templates_output_type_check CHECK (output_type IN ('Stdout', 'Files')),
+    ADD CONSTRAINT job_templates_schedule_type_check CHECK (schedule_type IN ('Once', 'Cron'));
+
+ALTER TABLE job_template_parameters
+    ADD CONSTRAINT job_template_parameters_kind_check CHECK (kind IN ('ImageTag', 'Env', 'Text'));
+
+ALTER TABLE workers
+    ADD CONSTRAINT workers_os_check CHECK (os IN ('Linux', 'Windows', 'MacOSEnum', 'Any'));
+
+ALTER TABLE worker_status
+    ADD CONSTRAINT worker_status_status_check CHECK (status IN ('Idle', 'Busy', 'Offline', 'Unreachable'));
+
+ALTER TABLE logs
+    ADD CONSTRAINT logs_level_check CHECK (level IN ('Info', 'Success', 'Warning', 'Error', 'Fatal')),
+    ADD CONSTRAINT logs_module_check CHECK (module IN ('Dispatcher', 'Harvester', 'Hibernator', 'Receiver', 'Scheduler', 'TaskArchive', 'Notifier')),
+    ADD CONSTRAINT logs_action_check CHECK (action IN ('ClientConnected', 'JobSubmitted', 'JobCompleted', 'JobPreempted', 'SystemStarted', 'SystemShutdown', 'Custom')),
+    ADD CONSTRAINT logs_from_module_check CHECK (job_submitted_from_module IN ('Dispatcher', 'Harvester', 'Hibernator', 'Receiver', 'Scheduler', 'TaskArchive', 'Notifier')),
+    ADD CONSTRAINT logs_to_module_check CHECK (job_submitted_to_module IN ('Dispatcher', 'Harvester', 'Hibernator', 'Receiver', 'Scheduler', 'TaskArchive', 'Notifier'));
+
+-- NULL matches every level, module or action
+ALTER TABLE log_retention_policies
+    ADD CONSTRAINT log_retention_policies_level_check CHECK (level IN ('Info', 'Success', 'Warning', 'Error', 'Fatal')),
+    ADD CONSTRAINT log_retention_policies_module_check CHECK (module IN ('Dispatcher', 'Harvester', 'Hibernator', 'Receiver', 'Scheduler', 'TaskArchive', 'Notifier')),
+    ADD CONSTRAINT log_retention_policies_action_check CHECK (action IN ('ClientConnected', 'JobSubmitted', 'JobCompleted', 'JobPreempted', 'SystemStarted', 'SystemShutdown', 'Custom'));
+
+-- the inline NULL check of `events` got Postgres' default name, free it for the check of the names
+ALTER TABLE webhooks RENAME CONSTRAINT webhooks_events_check TO webhooks_events_not_null_check;
+ALTER TABLE webhooks
+    ADD CONSTRAINT webhooks_events_check CHECK (events <@ ARRAY['JobSubmitted', 'JobRunning', 'JobCompleted', 'JobFailed', 'WorkerUnreachable']::text[]);
+
+ALTER TABLE webhook_deliveries
+    ADD CONSTRAINT webhook_deliveries_event_check CHECK (event IN ('JobSubmitted', 'JobRunning', 'JobCompleted', 'JobFailed', 'WorkerUnreachable')),
+    ADD CONSTRAINT webhook_deliveries_status_check CHECK (status IN ('Pending', 'Delivered', 'Failed'));
+
+-- jobs.state and jobs.user_id lead idx_jobs_state_priority and idx_jobs_user_state already
+CREATE INDEX idx_job_assignments_worker_id ON job_assignments (worker_id);
+CREATE INDEX idx_job_assignments_job_id ON job_assignments (job_id, assigned_at DESC);
+CREATE INDEX idx_logs_expires_at ON logs (expires_at);
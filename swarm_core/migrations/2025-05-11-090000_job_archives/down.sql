@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_jobs_state_updated_at;
+DROP TABLE IF EXISTS job_archives;
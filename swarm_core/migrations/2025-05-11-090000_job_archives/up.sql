@@ -0,0 +1,23 @@
+-- Your SQL goes here
+-- Finished jobs moved out of `jobs` by the TaskArchive, one row per job
+-- The summary columns stay queryable, `data` holds everything needed to restore the job:
+-- {"job": <jobs row>, "results": [...], "metrics": [...], "assignments": [...]}
+CREATE TABLE job_archives (
+    id INTEGER PRIMARY KEY, -- id the job had, kept on restore
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    job_name TEXT NOT NULL,
+    image_url TEXT NOT NULL,
+    state VARCHAR(64) NOT NULL,
+    error_message TEXT,
+    created_at TIMESTAMP NOT NULL,
+    finished_at TIMESTAMP NOT NULL, -- updated_at of the job when it was archived
+    result_count INTEGER NOT NULL DEFAULT 0,
+    archived_at TIMESTAMP NOT NULL DEFAULT now(),
+    data JSONB NOT NULL,
+    CONSTRAINT job_archives_state_check CHECK (state IN ('Completed', 'Failed'))
+);
+
+CREATE INDEX idx_job_archives_user_id ON job_archives (user_id, finished_at DESC);
+CREATE INDEX idx_job_archives_archived_at ON job_archives (archived_at);
+-- the TaskArchive picks finished jobs by age
+CREATE INDEX idx_jobs_state_updated_at ON jobs (state, updated_at);
@@ -0,0 +1,19 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM logs
+WHERE module = 'Supervisor'
+   OR job_submitted_from_module = 'Supervisor'
+   OR job_submitted_to_module = 'Supervisor';
+DELETE FROM log_retention_policies WHERE module = 'Supervisor';
+
+ALTER TABLE logs
+    DROP CONSTRAINT logs_module_check,
+    DROP CONSTRAINT logs_from_module_check,
+    DROP CONSTRAINT logs_to_module_check;
+ALTER TABLE logs
+    ADD CONSTRAINT logs_module_check CHECK (module IN ('Dispatcher', 'Harvester', 'Hibernator', 'Receiver', 'Scheduler', 'TaskArchive', 'Notifier')),
+    ADD CONSTRAINT logs_from_module_check CHECK (job_submitted_from_module IN ('Dispatcher', 'Harvester', 'Hibernator', 'Receiver', 'Scheduler', 'TaskArchive', 'Notifier')),
+    ADD CONSTRAINT logs_to_module_check CHECK (job_submitted_to_module IN ('Dispatcher', 'Harvester', 'Hibernator', 'Receiver', 'Scheduler', 'TaskArchive', 'Notifier'));
+
+ALTER TABLE log_retention_policies DROP CONSTRAINT log_retention_policies_module_check;
+ALTER TABLE log_retention_policies
+    ADD CONSTRAINT log_retention_policies_module_check CHECK (module IN ('Dispatcher', 'Harvester', 'Hibernator', 'Receiver', 'Scheduler', 'TaskArchive', 'Notifier'));
@@ -0,0 +1,15 @@
+-- Your SQL goes here
+-- The Supervisor logs failed and restarted modules under a module of its own
+
+ALTER TABLE logs
+    DROP CONSTRAINT logs_module_check,
+    DROP CONSTRAINT logs_from_module_check,
+    DROP CONSTRAINT logs_to_module_check;
+ALTER TABLE logs
+    ADD CONSTRAINT logs_module_check CHECK (module IN ('Dispatcher', 'Harvester', 'Hibernator', 'Receiver', 'Scheduler', 'TaskArchive', 'Notifier', 'Supervisor')),
+    ADD CONSTRAINT logs_from_module_check CHECK (job_submitted_from_module IN ('Dispatcher', 'Harvester', 'Hibernator', 'Receiver', 'Scheduler', 'TaskArchive', 'Notifier', 'Supervisor')),
+    ADD CONSTRAINT logs_to_module_check CHECK (job_submitted_to_module IN ('Dispatcher', 'Harvester', 'Hibernator', 'Receiver', 'Scheduler', 'TaskArchive', 'Notifier', 'Supervisor'));
+
+ALTER TABLE log_retention_policies DROP CONSTRAINT log_retention_policies_module_check;
+ALTER TABLE log_retention_policies
+    ADD CONSTRAINT log_retention_policies_module_check CHECK (module IN ('Dispatcher', 'Harvester', 'Hibernator', 'Receiver', 'Scheduler', 'TaskArchive', 'Notifier', 'Supervisor'));
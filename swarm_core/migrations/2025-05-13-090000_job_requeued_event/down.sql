@@ -0,0 +1,11 @@
+-- This file should undo anything in `up.sql`
+DELETE FROM webhook_deliveries WHERE event = 'JobRequeued';
+UPDATE webhooks SET events = array_remove(events, 'JobRequeued');
+
+ALTER TABLE webhooks DROP CONSTRAINT webhooks_events_check;
+ALTER TABLE webhooks
+    ADD CONSTRAINT webhooks_events_check CHECK (events <@ ARRAY['JobSubmitted', 'JobRunning', 'JobCompleted', 'JobFailed', 'WorkerUnreachable']::text[]);
+
+ALTER TABLE webhook_deliveries DROP CONSTRAINT webhook_deliveries_event_check;
+ALTER TABLE webhook_deliveries
+    ADD CONSTRAINT webhook_deliveries_event_check CHECK (event IN ('JobSubmitted', 'JobRunning', 'JobCompleted', 'JobFailed', 'WorkerUnreachable'));
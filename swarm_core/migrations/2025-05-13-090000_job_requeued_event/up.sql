@@ -0,0 +1,10 @@
+-- Your SQL goes here
+-- Webhooks can subscribe to jobs put back in the queue, e.g. after a preemption
+
+ALTER TABLE webhooks DROP CONSTRAINT webhooks_events_check;
+ALTER TABLE webhooks
+    ADD CONSTRAINT webhooks_events_check CHECK (events <@ ARRAY['JobSubmitted', 'JobRunning', 'JobCompleted', 'JobFailed', 'JobRequeued', 'WorkerUnreachable']::text[]);
+
+ALTER TABLE webhook_deliveries DROP CONSTRAINT webhook_deliveries_event_check;
+ALTER TABLE webhook_deliveries
+    ADD CONSTRAINT webhook_deliveries_event_check CHECK (event IN ('JobSubmitted', 'JobRunning', 'JobCompleted', 'JobFailed', 'JobRequeued', 'WorkerUnreachable'));
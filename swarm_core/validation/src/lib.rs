@@ -0,0 +1,324 @@
+//! Field level rules for the request bodies core validates before anything is stored
+//!
+//! The one copy of the rules: `common::validation` runs them in the routes, `swarm-client` for
+//! callers that check before submitting. Both map their own `NewJob` / `NewWorker` onto
+//! `JobFields` / `WorkerFields`, so the two can not drift apart on a rule or a message.
+//! Every problem is collected instead of stopping at the first, so a client can fix a body in one go.
+use std::fmt;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Longest accepted job name or worker label
+pub const MAX_NAME_LEN: usize = 255;
+
+/// One invalid field of a request body
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct FieldError {
+    // name of the field in the JSON body, e.g. `cron_expression`
+    pub field: String,
+    pub message: String,
+}
+
+/// Every invalid field of a body, never empty when returned as an error,
+/// also the `details` of an `invalid_input` answer
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ValidationErrors {
+    pub fields: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        self.fields.push(FieldError {
+            field: field.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// Ok if nothing was added
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.fields.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|e| format!("{}: {}", e.field, e.message))
+            .collect();
+        write!(f, "{}", fields.join("; "))
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+/// The fields of a `NewJob` the rules look at, the enums reduced to the variant that matters
+pub struct JobFields<'a> {
+    pub job_name: &'a str,
+    pub image_url: &'a str,
+    // image_format is DockerRegistry, the url then has to be an image reference
+    pub from_registry: bool,
+    pub docker_flags: &'a Option<Vec<Option<String>>>,
+    // output_type is Files, otherwise Stdout
+    pub output_files: bool,
+    pub output_paths: &'a Option<Vec<Option<String>>>,
+    // schedule_type is Cron, otherwise Once
+    pub cron_schedule: bool,
+    pub cron_expression: Option<&'a str>,
+}
+
+/// The fields of a `NewWorker` the rules look at
+pub struct WorkerFields<'a> {
+    pub label: &'a str,
+    pub ip_address: &'a str,
+    pub hostname: &'a str,
+    pub ssh_user: &'a str,
+    pub docker_version: &'a str,
+    pub arch: &'a str,
+    pub tags: &'a Option<Vec<Option<String>>>,
+}
+
+/// The checks `POST /jobs` runs
+pub fn validate_job(job: &JobFields) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::default();
+
+    check_name(&mut errors, "job_name", job.job_name);
+    if job.image_url.trim().is_empty() {
+        errors.add("image_url", "must not be empty");
+    } else if job.image_url.chars().any(char::is_whitespace) {
+        errors.add("image_url", "must not contain whitespace");
+    } else if job.from_registry && !is_image_reference(job.image_url) {
+        errors.add(
+            "image_url",
+            "not a valid image reference, expected [registry/]name[:tag][@digest]",
+        );
+    }
+    if flatten(job.docker_flags).any(|f| f.trim().is_empty()) {
+        errors.add("docker_flags", "flags must not be empty");
+    }
+
+    let paths: Vec<&String> = flatten(job.output_paths).collect();
+    if job.output_files {
+        if paths.is_empty() {
+            errors.add("output_paths", "at least one path is required for Files");
+        } else if paths.iter().any(|p| p.trim().is_empty()) {
+            errors.add("output_paths", "paths must not be empty");
+        }
+    } else if !paths.is_empty() {
+        errors.add("output_paths", "only allowed when output_type is Files");
+    }
+
+    match (job.cron_schedule, job.cron_expression) {
+        (true, None) => errors.add("cron_expression", "required when schedule_type is Cron"),
+        (true, Some(expr)) => {
+            if let Err(e) = parse_cron(expr) {
+                errors.add("cron_expression", format!("invalid expression: {}", e));
+            }
+        }
+        (false, Some(_)) => {
+            errors.add("cron_expression", "only allowed when schedule_type is Cron")
+        }
+        (false, None) => {}
+    }
+
+    errors.into_result()
+}
+
+/// The checks `POST /workers` runs
+pub fn validate_worker(worker: &WorkerFields) -> Result<(), ValidationErrors> {
+    let mut errors = ValidationErrors::default();
+
+    check_name(&mut errors, "label", worker.label);
+    if IpAddr::from_str(worker.ip_address).is_err() {
+        errors.add("ip_address", "not an IPv4 or IPv6 address");
+    }
+    if !is_hostname(worker.hostname) {
+        errors.add("hostname", "not a valid hostname");
+    }
+    for (field, value) in [
+        ("ssh_user", worker.ssh_user),
+        ("docker_version", worker.docker_version),
+        ("arch", worker.arch),
+    ] {
+        if value.trim().is_empty() {
+            errors.add(field, "must not be empty");
+        } else if value.chars().any(char::is_whitespace) {
+            errors.add(field, "must not contain whitespace");
+        }
+    }
+    if flatten(worker.tags).any(|t| t.trim().is_empty()) {
+        errors.add("tags", "tags must not be empty");
+    }
+
+    errors.into_result()
+}
+
+fn check_name(errors: &mut ValidationErrors, field: &str, value: &str) {
+    if value.trim().is_empty() {
+        errors.add(field, "must not be empty");
+    } else if value.chars().count() > MAX_NAME_LEN {
+        errors.add(field, format!("at most {} characters", MAX_NAME_LEN));
+    }
+}
+
+// postgres arrays arrive as `Option<Vec<Option<String>>>`, nulls carry nothing
+fn flatten(values: &Option<Vec<Option<String>>>) -> impl Iterator<Item = &String> {
+    values.iter().flatten().flatten()
+}
+
+/// Parse a cron expression, the usual 5 field form gets a leading seconds field
+pub fn parse_cron(expr: &str) -> Result<cron::Schedule, cron::error::Error> {
+    let expr = expr.trim();
+    if expr.split_whitespace().count() == 5 {
+        cron::Schedule::from_str(&format!("0 {}", expr))
+    } else {
+        cron::Schedule::from_str(expr)
+    }
+}
+
+/// Docker tag grammar: `[A-Za-z0-9_][A-Za-z0-9_.-]{0,127}`
+pub fn is_image_tag(tag: &str) -> bool {
+    let mut chars = tag.chars();
+    let first_ok = chars
+        .next()
+        .map(|c| c.is_ascii_alphanumeric() || c == '_')
+        .unwrap_or(false);
+    first_ok
+        && tag.len() <= 128
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// Docker image reference, `[registry[:port]/]path[:tag][@algo:hex]`
+pub fn is_image_reference(reference: &str) -> bool {
+    let (name, digest) = match reference.split_once('@') {
+        Some((name, digest)) => (name, Some(digest)),
+        None => (reference, None),
+    };
+    if let Some(digest) = digest {
+        let valid = digest.split_once(':').is_some_and(|(algo, hex)| {
+            !algo.is_empty()
+                && algo.chars().all(|c| c.is_ascii_alphanumeric())
+                && hex.len() >= 32
+                && hex.chars().all(|c| c.is_ascii_hexdigit())
+        });
+        if !valid {
+            return false;
+        }
+    }
+
+    // a colon after the last slash is a tag, before it is a registry port
+    let name_start = name.rfind('/').map(|i| i + 1).unwrap_or(0);
+    let (repository, tag) = match name[name_start..].find(':') {
+        Some(i) => (&name[..name_start + i], Some(&name[name_start + i + 1..])),
+        None => (name, None),
+    };
+    if tag.is_some_and(|t| !is_image_tag(t)) {
+        return false;
+    }
+
+    let mut components: Vec<&str> = repository.split('/').collect();
+    // the first component is a registry if it looks like a host
+    if components.len() > 1 && (components[0].contains(['.', ':']) || components[0] == "localhost")
+    {
+        let registry = components.remove(0);
+        let (host, port) = match registry.split_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (registry, None),
+        };
+        if !is_hostname(host) || port.is_some_and(|p| p.parse::<u16>().is_err()) {
+            return false;
+        }
+    }
+    !components.is_empty() && components.iter().all(|c| is_path_component(c))
+}
+
+// lowercase alphanumerics separated by single `.`, `_`, `__` or `-` runs
+fn is_path_component(component: &str) -> bool {
+    let bytes = component.as_bytes();
+    let is_alnum = |b: &u8| b.is_ascii_lowercase() || b.is_ascii_digit();
+    match (bytes.first(), bytes.last()) {
+        (Some(first), Some(last)) if is_alnum(first) && is_alnum(last) => {}
+        _ => return false,
+    }
+    bytes
+        .iter()
+        .all(|b| is_alnum(b) || matches!(b, b'.' | b'_' | b'-'))
+        && !component.contains("..")
+        && !component.contains("___")
+}
+
+/// RFC 1123 hostname, dot separated labels of letters, digits and inner hyphens
+pub fn is_hostname(host: &str) -> bool {
+    !host.is_empty()
+        && host.len() <= 253
+        && host.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn image_references() {
+        for ok in [
+            "alpine",
+            "alpine:3.20",
+            "library/alpine",
+            "registry:5000/team/api:v2",
+            "localhost/app",
+            "ghcr.io/org/app@sha256:0123456789abcdef0123456789abcdef",
+        ] {
+            assert!(is_image_reference(ok), "{ok}");
+        }
+        for bad in [
+            "",
+            "Alpine",
+            "alpine:",
+            "alpine:bad tag",
+            "/alpine",
+            "a..b",
+            "app@md5",
+        ] {
+            assert!(!is_image_reference(bad), "{bad}");
+        }
+    }
+
+    #[test]
+    fn hostnames() {
+        for ok in ["node-1", "node-1.local", "10.0.0.7", "a"] {
+            assert!(is_hostname(ok), "{ok}");
+        }
+        for bad in [
+            "",
+            "-node",
+            "node-",
+            "node..local",
+            "no_de",
+            &"a".repeat(64),
+        ] {
+            assert!(!is_hostname(bad), "{bad}");
+        }
+    }
+
+    #[test]
+    fn five_field_cron_gets_seconds() {
+        assert!(parse_cron("0 5 * * *").is_ok());
+        assert!(parse_cron("0 0 5 * * *").is_ok());
+        assert!(parse_cron("every day").is_err());
+    }
+}
@@ -1,92 +1,66 @@
 use anyhow::{anyhow, bail, Context, Result};
-use reqwest::{Client, StatusCode};
-use serde_json::json;
+use reqwest::StatusCode;
+use swarm_client::models::{Credentials, NewUserRequest, UpdateUserRequest};
+use swarm_client::SwarmClient;
 
-use super::model::{Job, JobResult, UserResponse, WorkerStatusEnum};
-use super::net::{self, http_with_rocket_port, Session};
+use super::model::{
+    ImageFormatEnum, Job, JobResult, NewJob, OutputTypeEnum, ScheduleTypeEnum, UserResponse,
+    WorkerStatusEnum,
+};
+use super::net::Session;
 
 /// Register a new user via `/users`
 pub async fn register_user(
-    client: &Client,
-    base_url: &str,
+    client: &SwarmClient,
     username: &str,
     email: &str,
     password: &str,
 ) -> anyhow::Result<UserResponse> {
-    let http_base = http_with_rocket_port(base_url)?;
-    let url = format!("{http_base}/users");
-    println!("DEBUG register_user → {}", url); // <‑‑👀
-
-    let res = client
-        .post(&url)
-        .json(&json!({ "username": username, "email": email, "password": password }))
-        .send()
-        .await
-        .with_context(|| format!("POST {url} failed"))?; // <‑‑ context includes URL
+    println!("DEBUG register_user → {}", client.url(&["users"])); // <‑‑👀
 
-    if !res.status().is_success() {
-        let status = res.status();
-        let body = res.text().await.unwrap_or_default();
-        anyhow::bail!("register_user {url} → {status}: {body}");
-    }
-    Ok(res.json().await.context("bad JSON in /users response")?)
+    let request = NewUserRequest {
+        username: username.to_string(),
+        email: email.to_string(),
+        password: password.to_string(),
+    };
+    client
+        .create_user(&request)
+        .await
+        .with_context(|| format!("register_user {} failed", client.url(&["users"])))
 }
 
 pub async fn login_user(
-    client: &Client,
-    base_url: &str,
+    client: &SwarmClient,
     username: &str,
     password: &str,
-) -> anyhow::Result<(String, UserResponse)> {
-    let http_base = http_with_rocket_port(base_url)?;
-    let login_url = format!("{http_base}/login");
-    println!("DEBUG login_user → {}", login_url); // <‑‑👀
-
-    let res = client
-        .post(&login_url)
-        .json(&json!({ "username": username, "password": password }))
-        .send()
-        .await
-        .with_context(|| format!("POST {login_url} failed"))?;
-
-    if res.status() == StatusCode::UNAUTHORIZED {
-        anyhow::bail!("login_user {login_url} → 401 UNAUTHORIZED");
-    }
-    if !res.status().is_success() {
-        let status = res.status();
-        let body = res.text().await.unwrap_or_default();
-        anyhow::bail!("login_user {login_url} → {status}: {body}");
-    }
+) -> anyhow::Result<(SwarmClient, UserResponse)> {
+    println!("DEBUG login_user → {}", client.url(&["login"])); // <‑‑👀
 
-    let token = res
-        .json::<serde_json::Value>()
-        .await
-        .context("malformed JSON in /login response")?["token"]
-        .as_str()
-        .ok_or_else(|| anyhow!("no token in /login response"))?
-        .to_string();
-
-    let authed = net::build_authed_client(&token)?;
-    let user_url = format!("{http_base}/users/username/{username}");
-    println!("DEBUG fetch_user → {}", user_url); // <‑‑👀
-
-    let res = authed
-        .get(&user_url)
-        .send()
-        .await
-        .with_context(|| format!("GET {user_url} failed"))?;
+    let credentials = Credentials {
+        username: username.to_string(),
+        password: password.to_string(),
+    };
+    let session = match client.login(&credentials).await {
+        Ok(session) => session,
+        Err(err) if err.status() == Some(StatusCode::UNAUTHORIZED) => {
+            bail!("login_user {} → 401 UNAUTHORIZED", client.url(&["login"]))
+        }
+        Err(err) => return Err(err).context("login_user failed"),
+    };
 
-    if !res.status().is_success() {
-        anyhow::bail!("fetch_user {user_url} → {}", res.status());
-    }
+    let authed = client.authenticated(&session.token)?;
+    println!(
+        "DEBUG fetch_user → {}",
+        client.url(&["users", "username", username])
+    ); // <‑‑👀
 
-    let user_opt: Option<UserResponse> = res
-        .json()
+    let user = authed
+        .find_user_by_username(username)
         .await
-        .context("bad JSON in /users/username response")?;
-    let user = user_opt.ok_or_else(|| anyhow!("user not found"))?;
+        .context("fetch_user failed")?
+        .ok_or_else(|| anyhow!("user not found"))?;
 
-    Ok((token, user))
+    Ok((authed, user))
 }
 
 /// PUT /users/<id>  ➜ update username / email / password
@@ -96,29 +70,17 @@ pub async fn update_user(
     new_email: &str,
     new_password: Option<&str>,
 ) -> anyhow::Result<UserResponse> {
-    let mut payload = json!({
-        "username": new_username,
-        "email": new_email,
-    });
-    if let Some(pw) = new_password {
-        payload["password"] = json!(pw);
-    }
+    let update = UpdateUserRequest {
+        username: new_username.to_string(),
+        email: new_email.to_string(),
+        password: new_password.map(String::from),
+    };
 
-    let http_base = http_with_rocket_port(&session.app_host)?;
-    let res = session
+    session
         .client
-        .put(format!("{}/users/{}", http_base, session.user.id))
-        .json(&payload)
-        .send()
-        .await?;
-
-    if !res.status().is_success() {
-        let status = res.status();
-        let body = res.text().await.unwrap_or_default();
-        anyhow::bail!("Update failed (status={}): {}", status, body);
-    }
-
-    Ok(res.json().await?)
+        .update_user(session.user.id, &update)
+        .await
+        .context("Update failed")
 }
 
 /// Submit a new job via `POST /jobs`
@@ -126,115 +88,65 @@ pub async fn submit_job(
     session: &Session,
     job_name: &str,
     image_url: &str,
-    image_format: &str,
-    output_type: &str,
+    image_format: ImageFormatEnum,
+    output_type: OutputTypeEnum,
     output_paths: Option<Vec<String>>,
-    schedule_type: &str,
+    schedule_type: ScheduleTypeEnum,
     cron_expression: Option<&str>,
 ) -> anyhow::Result<Job> {
-    let initial_state = if schedule_type == "Cron" {
-        "Submitted"
-    } else {
-        "Queued"
+    let job = NewJob {
+        user_id: session.user.id,
+        job_name: job_name.to_string(),
+        image_url: image_url.to_string(),
+        image_format,
+        docker_flags: None,
+        output_type,
+        output_paths: output_paths.map(|paths| paths.into_iter().map(Some).collect()),
+        schedule_type,
+        cron_expression: cron_expression.map(String::from),
+        notes: None,
+        priority: 0,
     };
 
-    let payload = json!({
-        "user_id": session.user.id,
-        "job_name": job_name,
-        "image_url": image_url,
-        "image_format": image_format,
-        "docker_flags": null,
-        "output_type": output_type,
-        "output_paths": output_paths,
-        "schedule_type": schedule_type,
-        "cron_expression": cron_expression,
-        "notes": null,
-        "state": initial_state
-    });
-
-    let http_base = http_with_rocket_port(&session.app_host)?;
-    let res = session
+    session
         .client
-        .post(format!("{}/jobs", http_base))
-        .json(&payload)
-        .send()
-        .await?;
-
-    if res.status() != StatusCode::CREATED {
-        let status = res.status();
-        let body = res.text().await.unwrap_or_default();
-        bail!("Job submission failed (status={}): {}", status, body);
-    }
-
-    Ok(res.json().await?)
+        .create_job(&job)
+        .await
+        .context("Job submission failed")
 }
 
 /// List all jobs for the current user
 pub async fn list_jobs(session: &Session) -> anyhow::Result<Vec<Job>> {
-    let http_base = http_with_rocket_port(&session.app_host)?;
-    let url = format!("{}/jobs/by_admin?user_id={}", http_base, session.user.id);
-    let res = session.client.get(url).send().await?;
-    if !res.status().is_success() {
-        bail!("Failed to list jobs (status={})", res.status());
-    }
-    Ok(res.json().await?)
+    session
+        .client
+        .list_jobs_by_admin(session.user.id, None, None)
+        .await
+        .context("Failed to list jobs")
 }
 
 /// Filter finished jobs (not Submitted / Queued / Running)
 pub async fn get_finished_jobs(session: &Session) -> anyhow::Result<Vec<Job>> {
     let jobs = list_jobs(session).await?;
-    Ok(jobs
-        .into_iter()
-        .filter(|j| {
-            let s = j.state.as_str();
-            s != "Submitted" && s != "Queued" && s != "Running"
-        })
-        .collect())
+    Ok(jobs.into_iter().filter(|j| j.state.is_finished()).collect())
 }
 
 /// Fetch `/results/job/<id>`
 pub async fn get_results_for_job(session: &Session, job_id: i32) -> anyhow::Result<Vec<JobResult>> {
-    let http_base = http_with_rocket_port(&session.app_host)?;
-    let url = format!("{}/results/job/{}", http_base, job_id);
-    let res = session.client.get(url).send().await?;
-    if res.status() == StatusCode::NOT_FOUND {
-        return Ok(vec![]); // none yet
+    match session.client.results_for_job(job_id).await {
+        Ok(results) => Ok(results),
+        Err(err) if err.is_not_found() => Ok(vec![]), // none yet
+        Err(err) => Err(err).with_context(|| format!("Failed to fetch results for job {}", job_id)),
     }
-    if !res.status().is_success() {
-        bail!(
-            "Failed to fetch results for job {} (status={})",
-            job_id,
-            res.status()
-        );
-    }
-    Ok(res.json().await?)
 }
 
 /// Fetch workerStatusEnum from worker_id
-
 pub async fn get_worker_status(session: &Session, worker_id: i32) -> Result<WorkerStatusEnum> {
-    let http_base = http_with_rocket_port(&session.app_host)?;
-    let url = format!("{}/worker-status/worker/{}", http_base, worker_id);
-
-    let resp = session
+    let status = session
         .client
-        .get(&url)
-        .send()
-        .await
-        .context("failed to send GET to worker-status endpoint")?;
-
-    if resp.status() == StatusCode::NOT_FOUND {
-        bail!("worker {} not found (404)", worker_id);
-    }
-
-    let resp = resp
-        .error_for_status()
-        .with_context(|| format!("error status fetching worker {}", worker_id))?;
-
-    let status = resp
-        .json::<WorkerStatusEnum>()
+        .status_for_worker(worker_id)
         .await
-        .context("failed to parse WorkerStatusEnum from JSON")?;
+        .with_context(|| format!("error status fetching worker {}", worker_id))?
+        .ok_or_else(|| anyhow!("worker {} has no status yet", worker_id))?;
 
-    Ok(status)
+    Ok(status.status)
 }
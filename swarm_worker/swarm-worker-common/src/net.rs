@@ -1,23 +1,19 @@
 use crate::model::UserResponse;
 use anyhow::{anyhow, Context, Result};
-use reqwest::{self, header, Client, Url};
+use reqwest::{self, Url};
+use swarm_client::SwarmClient;
 
 #[derive(Clone)]
 pub struct Session {
-    pub client: Client,
+    // typed core client, authenticated with the session token
+    pub client: SwarmClient,
     pub user: UserResponse,
-    pub app_host: String,
 }
 
-/// Builds authenticated client from token, token fetched by logging in
-pub fn build_authed_client(token: &str) -> anyhow::Result<Client> {
-    let mut headers = header::HeaderMap::new();
-    headers.insert(
-        header::AUTHORIZATION,
-        header::HeaderValue::from_str(&format!("Bearer {}", token))?,
-    );
-
-    Ok(Client::builder().default_headers(headers).build()?)
+/// Anonymous core client for “core”, “http://core” or “http://core:8080”
+pub fn core_client(base: &str) -> Result<SwarmClient> {
+    let http_base = http_with_rocket_port(base)?;
+    Ok(SwarmClient::new(&http_base)?)
 }
 
 /// Simple GET / to ensure the server is up.
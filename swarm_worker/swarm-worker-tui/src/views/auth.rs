@@ -1,7 +1,8 @@
+use swarm_client::SwarmClient;
 use swarm_worker_common::commands;
 use swarm_worker_common::config::{config_file_path, CoreConfig};
 use swarm_worker_common::model::UserResponse;
-use swarm_worker_common::net::{build_authed_client, Session};
+use swarm_worker_common::net::{core_client, Session};
 
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use std::fs;
@@ -41,16 +42,16 @@ pub async fn auth_flow(base_url: &str) -> anyhow::Result<Session> {
     let password: String = Input::new().with_prompt("Password").interact_text()?;
 
     // ── 5. Execute flow ───────────────────────────────────────────
-    let client = reqwest::Client::new();
-    let (token, user): (String, UserResponse) = if mode == 0 {
+    let client = core_client(base_url)?;
+    let (authed_client, user): (SwarmClient, UserResponse) = if mode == 0 {
         // Login
-        commands::login_user(&client, base_url, &username, &password).await?
+        commands::login_user(&client, &username, &password).await?
     } else {
         // Register
         let email: String = Input::new().with_prompt("Email").interact_text()?;
-        let user = commands::register_user(&client, base_url, &username, &email, &password).await?;
-        let (token, _) = commands::login_user(&client, base_url, &username, &password).await?;
-        (token, user)
+        let user = commands::register_user(&client, &username, &email, &password).await?;
+        let (authed_client, _) = commands::login_user(&client, &username, &password).await?;
+        (authed_client, user)
     };
 
     // ── 6. Save username back to config ───────────────────────────
@@ -67,10 +68,8 @@ pub async fn auth_flow(base_url: &str) -> anyhow::Result<Session> {
     fs::write(config_file_path(), serde_json::to_string_pretty(&cfg)?)?;
 
     // ── 7. Build authed session object ────────────────────────────
-    let authed_client = build_authed_client(&token)?;
     Ok(Session {
         client: authed_client,
         user,
-        app_host: base_url.to_string(),
     })
 }